@@ -0,0 +1,82 @@
+/*
+ * C declarations for the almagest `ffi` feature, kept in step with
+ * src/ffi.rs. Link against the static library built with
+ *
+ *     cargo rustc --release --features ffi --crate-type staticlib
+ *
+ * Quantities are SI (meters, m/s, seconds) and angles are radians.
+ * Every function returns an AlmagestStatus and writes its result
+ * through the last argument, which may not be null.
+ */
+
+#ifndef ALMAGEST_H
+#define ALMAGEST_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Zero on success, otherwise the kind of error */
+typedef enum AlmagestStatus {
+    ALMAGEST_OK = 0,
+    ALMAGEST_INVALID_ECCENTRICITY = 1,
+    ALMAGEST_NO_CONVERGENCE = 2,
+    ALMAGEST_COPLANARITY = 3,
+    ALMAGEST_EPOCH_OUT_OF_RANGE = 4,
+    ALMAGEST_PARSE = 5,
+    ALMAGEST_INVALID_INPUT = 6,
+    ALMAGEST_NULL_POINTER = 7
+} AlmagestStatus;
+
+/* Position in meters and velocity in m/s */
+typedef struct AlmagestState {
+    double position[3];
+    double velocity[3];
+} AlmagestState;
+
+/* Classical elements: semi-major axis in meters, angles in radians */
+typedef struct AlmagestElements {
+    double semi_major_axis;
+    double eccentricity;
+    double inclination;
+    double raan;
+    double argument_of_periapsis;
+    double true_anomaly;
+} AlmagestElements;
+
+/* A state at an epoch in seconds past J2000 */
+typedef struct AlmagestEpochState {
+    double epoch;
+    AlmagestState state;
+} AlmagestEpochState;
+
+/* Eccentric anomaly for a mean anomaly */
+AlmagestStatus almagest_solve_kepler(double mean_anomaly, double eccentricity,
+                                     double *ecc_anomaly);
+
+/* Elements from a state */
+AlmagestStatus almagest_rv2coe(AlmagestState state, double mu, AlmagestElements *elements);
+
+/* A state from elements */
+AlmagestStatus almagest_coe2rv(AlmagestElements elements, double mu, AlmagestState *state);
+
+/* Elements dt seconds later on the two-body orbit */
+AlmagestStatus almagest_propagate_elements(AlmagestElements elements, double dt, double mu,
+                                           AlmagestElements *propagated);
+
+/* Earth-fixed state from an inertial one */
+AlmagestStatus almagest_gcrf_to_itrf(AlmagestEpochState inertial, AlmagestEpochState *fixed);
+
+/* Inertial state from an Earth-fixed one */
+AlmagestStatus almagest_itrf_to_gcrf(AlmagestEpochState fixed, AlmagestEpochState *inertial);
+
+/* TEME state since_epoch seconds after a two-line element set; the
+ * lines are NUL-terminated */
+AlmagestStatus almagest_sgp4(const char *line1, const char *line2, double since_epoch,
+                             AlmagestState *state);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ALMAGEST_H */
@@ -0,0 +1,449 @@
+//! Aerobraking: lowering apoapsis by dipping periapsis into an
+//! atmosphere.
+//!
+//! Each pass is treated as an impulsive loss of speed at periapsis,
+//! found by integrating the drag of an exponential atmosphere across a
+//! pass, with the density expanded to second order in time about
+//! periapsis: Δv = ρₚ vₚ² √(2π H / r̈ₚ) / 2β, where β = m / (C_D A).
+//! Periapsis is held fixed from pass to pass, as walk-in and walk-out
+//! maneuvers would keep it in practice.
+
+use libm::{exp, log, sqrt};
+
+use crate::entry::MARS_RADIUS;
+use crate::error::AlmagestError;
+use crate::groundtrack::EARTH_RADIUS;
+use crate::utils::{GravParam, Meters, PI, Real, Seconds, TAU};
+
+/// An atmosphere whose density falls off exponentially with altitude
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExponentialAtmosphere {
+    pub body_radius: Meters,
+    pub reference_altitude: Meters,
+    /// Density at the reference altitude, in kg/m³
+    pub reference_density: Real,
+    pub scale_height: Meters,
+}
+
+impl ExponentialAtmosphere {
+    /// Mars around aerobraking altitudes, fitted near 110 km
+    pub fn mars() -> Self {
+        ExponentialAtmosphere {
+            body_radius: MARS_RADIUS,
+            reference_altitude: Meters(110_000.0),
+            reference_density: 4.0e-8,
+            scale_height: Meters(7_500.0),
+        }
+    }
+
+    /// The Earth's 100–110 km band of Vallado's Table 8-4
+    pub fn earth() -> Self {
+        ExponentialAtmosphere {
+            body_radius: EARTH_RADIUS,
+            reference_altitude: Meters(100_000.0),
+            reference_density: 5.297e-7,
+            scale_height: Meters(5_877.0),
+        }
+    }
+
+    /// Density at a distance from the body's center, in kg/m³
+    pub fn density(&self, radius: Meters) -> Real {
+        let altitude = radius - self.body_radius;
+        self.reference_density
+            * exp(-(altitude - self.reference_altitude).value() / self.scale_height.value())
+    }
+}
+
+/// Air density about a body
+pub trait Atmosphere {
+    /// Density at a distance from the body's center, in kg/m³
+    fn density(&self, radius: Meters) -> Real;
+
+    /// Distance over which the density falls by a factor of e, at a
+    /// distance from the body's center; by default from the density
+    /// 10 m either side
+    fn scale_height(&self, radius: Meters) -> Meters {
+        let h = Meters(10.0);
+        let ratio = self.density(radius - h) / self.density(radius + h);
+        h * 2.0 / log(ratio)
+    }
+}
+
+impl<A: Atmosphere> Atmosphere for &A {
+    fn density(&self, radius: Meters) -> Real {
+        (*self).density(radius)
+    }
+
+    fn scale_height(&self, radius: Meters) -> Meters {
+        (*self).scale_height(radius)
+    }
+}
+
+impl Atmosphere for ExponentialAtmosphere {
+    fn density(&self, radius: Meters) -> Real {
+        ExponentialAtmosphere::density(self, radius)
+    }
+
+    fn scale_height(&self, _radius: Meters) -> Meters {
+        self.scale_height
+    }
+}
+
+// Vallado's Table 8-4: base altitude and scale height in km, base
+// density in kg/m³
+const EARTH_BANDS: [(Real, Real, Real); 28] = [
+    (0.0, 1.225, 7.249),
+    (25.0, 3.899e-2, 6.349),
+    (30.0, 1.774e-2, 6.682),
+    (40.0, 3.972e-3, 7.554),
+    (50.0, 1.057e-3, 8.382),
+    (60.0, 3.206e-4, 7.714),
+    (70.0, 8.770e-5, 6.549),
+    (80.0, 1.905e-5, 5.799),
+    (90.0, 3.396e-6, 5.382),
+    (100.0, 5.297e-7, 5.877),
+    (110.0, 9.661e-8, 7.263),
+    (120.0, 2.438e-8, 9.473),
+    (130.0, 8.484e-9, 12.636),
+    (140.0, 3.845e-9, 16.149),
+    (150.0, 2.070e-9, 22.523),
+    (180.0, 5.464e-10, 29.740),
+    (200.0, 2.789e-10, 37.105),
+    (250.0, 7.248e-11, 45.546),
+    (300.0, 2.418e-11, 53.628),
+    (350.0, 9.518e-12, 53.298),
+    (400.0, 3.725e-12, 58.515),
+    (450.0, 1.585e-12, 60.828),
+    (500.0, 6.967e-13, 63.822),
+    (600.0, 1.454e-13, 71.835),
+    (700.0, 3.614e-14, 88.667),
+    (800.0, 1.170e-14, 124.64),
+    (900.0, 5.245e-15, 181.05),
+    (1000.0, 3.019e-15, 268.00),
+];
+
+/// The Earth's atmosphere from the ground to 1000 km, piecewise
+/// exponential in bands (Vallado, Table 8-4). Above 1000 km the top
+/// band is extended.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EarthAtmosphere;
+
+impl Atmosphere for EarthAtmosphere {
+    fn density(&self, radius: Meters) -> Real {
+        let altitude = (radius - EARTH_RADIUS).value() / 1000.0;
+        let (base, density, scale) = EARTH_BANDS
+            .iter()
+            .rev()
+            .find(|band| band.0 <= altitude)
+            .copied()
+            .unwrap_or(EARTH_BANDS[0]);
+        density * exp(-(altitude - base) / scale)
+    }
+}
+
+/// Speed lost on one pass through periapsis of an orbit with the
+/// given apsides, for a ballistic coefficient in kg/m²
+pub fn pass_delta_v<A: Atmosphere>(
+    atmosphere: &A,
+    periapsis: Meters,
+    apoapsis: Meters,
+    ballistic_coefficient: Real,
+    mu: GravParam,
+) -> Real {
+    let (rp, ra) = (periapsis.value(), apoapsis.value());
+    let e = (ra - rp) / (ra + rp);
+    let vp2 = mu.value() * (1.0 + e) / rp;
+    // Radial acceleration at periapsis, which sets how long the pass
+    // lingers at the bottom of the atmosphere
+    let curvature = vp2 * e / ((1.0 + e) * rp);
+    let exposure = sqrt(2.0 * PI * atmosphere.scale_height(periapsis).value() / curvature);
+    atmosphere.density(periapsis) * vp2 * exposure / (2.0 * ballistic_coefficient)
+}
+
+/// One pass of an aerobraking campaign
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AerobrakingPass {
+    /// Pass number, from 1
+    pub number: usize,
+    /// Time of the pass since the first
+    pub time: Seconds,
+    pub delta_v: Real,
+    pub apoapsis_before: Meters,
+    pub apoapsis_after: Meters,
+    /// Period of the orbit after the pass
+    pub period: Seconds,
+}
+
+/// Totals over an aerobraking campaign
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CampaignSummary {
+    pub passes: usize,
+    /// Time from the first pass to the last
+    pub duration: Seconds,
+    /// Speed taken out by drag, which would otherwise be propellant
+    pub delta_v: Real,
+}
+
+// Most passes a campaign summary will follow; Mars Global Surveyor
+// took under 900
+const MAX_PASSES: usize = 100_000;
+
+/// An aerobraking campaign at a fixed periapsis
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aerobraking<A = ExponentialAtmosphere> {
+    pub atmosphere: A,
+    pub periapsis: Meters,
+    /// m / (C_D A), in kg/m²
+    pub ballistic_coefficient: Real,
+    pub mu: GravParam,
+}
+
+impl<A: Atmosphere + Copy> Aerobraking<A> {
+    /// Passes from an orbit with `apoapsis` until it is at or below
+    /// `target`; the last pass is the one that gets there. They stop
+    /// early once a pass no longer lowers apoapsis.
+    pub fn passes(&self, apoapsis: Meters, target: Meters) -> AerobrakingPasses<A> {
+        AerobrakingPasses {
+            campaign: *self,
+            apoapsis,
+            target,
+            number: 0,
+            time: Seconds::ZERO,
+        }
+    }
+
+    /// Pass count, duration and drag delta-v to bring `apoapsis` down
+    /// to `target`; an error if the atmosphere is too thin at
+    /// periapsis to get there within 100 000 passes
+    pub fn summary(
+        &self,
+        apoapsis: Meters,
+        target: Meters,
+    ) -> Result<CampaignSummary, AlmagestError> {
+        let mut summary = CampaignSummary::default();
+        let mut reached = apoapsis <= target;
+        for pass in self.passes(apoapsis, target).take(MAX_PASSES) {
+            summary = CampaignSummary {
+                passes: pass.number,
+                duration: pass.time,
+                delta_v: summary.delta_v + pass.delta_v,
+            };
+            reached = pass.apoapsis_after <= target;
+        }
+        if !reached {
+            return Err(AlmagestError::NoConvergence(
+                "Drag cannot bring apoapsis down to the target",
+            ));
+        }
+        Ok(summary)
+    }
+
+    fn period(&self, apoapsis: Meters) -> Seconds {
+        let a = ((self.periapsis + apoapsis) / 2.0).value();
+        Seconds(TAU * sqrt(a * a * a / self.mu.value()))
+    }
+}
+
+/// Iterator over the passes of an aerobraking campaign, created by
+/// [`Aerobraking::passes`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AerobrakingPasses<A = ExponentialAtmosphere> {
+    campaign: Aerobraking<A>,
+    apoapsis: Meters,
+    target: Meters,
+    number: usize,
+    time: Seconds,
+}
+
+impl<A: Atmosphere + Copy> Iterator for AerobrakingPasses<A> {
+    type Item = AerobrakingPass;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let campaign = &self.campaign;
+        if self.apoapsis <= self.target || self.apoapsis <= campaign.periapsis {
+            return None;
+        }
+        let delta_v = pass_delta_v(
+            &campaign.atmosphere,
+            campaign.periapsis,
+            self.apoapsis,
+            campaign.ballistic_coefficient,
+            campaign.mu,
+        );
+        // New apoapsis from vis-viva after the loss at periapsis
+        let (mu, rp) = (campaign.mu.value(), campaign.periapsis.value());
+        let ra = self.apoapsis.value();
+        let vp = sqrt(2.0 * mu * ra / (rp * (rp + ra))) - delta_v;
+        let a = 1.0 / (2.0 / rp - vp * vp / mu);
+        let apoapsis = Meters((2.0 * a - rp).max(rp));
+        if apoapsis >= self.apoapsis {
+            return None;
+        }
+
+        if self.number > 0 {
+            self.time = self.time + campaign.period(self.apoapsis);
+        }
+        self.number += 1;
+        let pass = AerobrakingPass {
+            number: self.number,
+            time: self.time,
+            delta_v,
+            apoapsis_before: self.apoapsis,
+            apoapsis_after: apoapsis,
+            period: campaign.period(apoapsis),
+        };
+        self.apoapsis = apoapsis;
+        Some(pass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::entry::MU_MARS;
+    use approx::assert_relative_eq;
+    use libm::cos;
+
+    // A Mars Global Surveyor-like orbiter
+    fn campaign() -> Aerobraking {
+        Aerobraking {
+            atmosphere: ExponentialAtmosphere::mars(),
+            periapsis: MARS_RADIUS + Meters(110_000.0),
+            ballistic_coefficient: 20.0,
+            mu: MU_MARS,
+        }
+    }
+
+    #[test]
+    fn pass_matches_integrated_drag() {
+        let aerobraking = campaign();
+        let (rp, ra) = (aerobraking.periapsis.value(), MARS_RADIUS.value() + 5.0e7);
+        let mu = MU_MARS.value();
+        let a = (rp + ra) / 2.0;
+        let e = (ra - rp) / (ra + rp);
+        let p = a * (1.0 - e * e);
+        let h = sqrt(mu * p);
+        // Integrate drag deceleration over true anomaly, dt = r² / h dν
+        let mut delta_v = 0.0;
+        let steps = 20_000;
+        let span = 0.6;
+        for i in 0..steps {
+            let nu = -span + 2.0 * span * (i as Real + 0.5) / steps as Real;
+            let r = p / (1.0 + e * cos(nu));
+            let v2 = mu * (2.0 / r - 1.0 / a);
+            let density = aerobraking.atmosphere.density(Meters(r));
+            delta_v += density * v2 / (2.0 * 20.0) * r * r / h * (2.0 * span / steps as Real);
+        }
+        let estimate = pass_delta_v(
+            &aerobraking.atmosphere,
+            aerobraking.periapsis,
+            Meters(ra),
+            20.0,
+            MU_MARS,
+        );
+        assert_relative_eq!(estimate, delta_v, max_relative = 0.02);
+    }
+
+    #[test]
+    fn earth_bands() {
+        let at = |km: Real| EarthAtmosphere.density(EARTH_RADIUS + Meters(km * 1000.0));
+        let band = ExponentialAtmosphere::earth();
+        assert_relative_eq!(
+            at(105.0),
+            band.density(EARTH_RADIUS + Meters(105_000.0)),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(at(400.0), 3.725e-12, max_relative = 1e-12);
+        // Nearly continuous across band edges
+        assert_relative_eq!(at(399.999), at(400.0), max_relative = 0.05);
+        assert!(at(1_500.0) < at(1_000.0));
+        assert_relative_eq!(
+            at(-1.0),
+            1.225 * libm::exp(1.0 / 7.249),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn campaign_walks_apoapsis_down() {
+        let aerobraking = campaign();
+        let start = MARS_RADIUS + Meters(20_000_000.0);
+        let target = MARS_RADIUS + Meters(2_000_000.0);
+        let mut previous: Option<AerobrakingPass> = None;
+        for pass in aerobraking.passes(start, target) {
+            assert!(pass.apoapsis_after < pass.apoapsis_before);
+            if let Some(previous) = previous {
+                assert_eq!(pass.apoapsis_before, previous.apoapsis_after);
+                assert_relative_eq!(
+                    (pass.time - previous.time).value(),
+                    previous.period.value(),
+                    max_relative = 1e-12
+                );
+                assert!(previous.apoapsis_after > target);
+            }
+            previous = Some(pass);
+        }
+        let last = previous.unwrap();
+        assert!(last.apoapsis_after <= target);
+
+        let summary = aerobraking.summary(start, target).unwrap();
+        assert_eq!(summary.passes, last.number);
+        assert_eq!(summary.duration, last.time);
+        // Drag does the work of the equivalent apoapsis-lowering burns
+        let vp = |ra: Real| {
+            let rp = aerobraking.periapsis.value();
+            sqrt(2.0 * MU_MARS.value() * ra / (rp * (rp + ra)))
+        };
+        assert_relative_eq!(
+            summary.delta_v,
+            vp(start.value()) - vp(last.apoapsis_after.value()),
+            max_relative = 1e-9
+        );
+        assert!(summary.passes > 10);
+    }
+
+    #[test]
+    fn thin_air_ends_the_campaign() {
+        // Hundreds of kilometers up, a pass takes off nothing
+        let high = Aerobraking {
+            periapsis: MARS_RADIUS + Meters(2_000_000.0),
+            ..campaign()
+        };
+        let start = MARS_RADIUS + Meters(20_000_000.0);
+        let target = MARS_RADIUS + Meters(5_000_000.0);
+        assert_eq!(high.passes(start, target).count(), 0);
+        assert!(high.summary(start, target).is_err());
+
+        // Any atmosphere will do; the banded Earth matches its own band
+        let periapsis = EARTH_RADIUS + Meters(105_000.0);
+        let apoapsis = EARTH_RADIUS + Meters(1_000_000.0);
+        let mu = EARTH.mu;
+        assert_relative_eq!(
+            pass_delta_v(&EarthAtmosphere, periapsis, apoapsis, 50.0, mu),
+            pass_delta_v(
+                &ExponentialAtmosphere::earth(),
+                periapsis,
+                apoapsis,
+                50.0,
+                mu
+            ),
+            max_relative = 1e-6
+        );
+        let earth = Aerobraking {
+            atmosphere: EarthAtmosphere,
+            periapsis,
+            ballistic_coefficient: 50.0,
+            mu,
+        };
+        let summary = earth
+            .summary(apoapsis, EARTH_RADIUS + Meters(500_000.0))
+            .unwrap();
+        assert!(summary.passes > 1);
+    }
+}
@@ -0,0 +1,258 @@
+//! Free-flight relations for ballistic (suborbital) trajectories.
+//!
+//! The trajectory is treated as a Keplerian arc that leaves burnout and
+//! returns to the same radius, symmetric about apoapsis. Everything is
+//! expressed through the burnout parameter Q = v²r/μ, the ratio of
+//! twice the kinetic energy to the potential energy at burnout: Q < 1
+//! is the usual ballistic regime, Q = 1 is circular speed and Q ≥ 2
+//! escapes. Angles are in radians and flight-path angles are measured
+//! from the local horizontal.
+
+use libm::{acos, asin, cos, sin, sqrt};
+
+use crate::utils::{GravParam, Meters, PI, Real, Seconds, TAU};
+
+/// The burnout parameter Q = v²r/μ, with `speed` in m/s
+pub fn q_parameter(radius: Meters, speed: Real, mu: GravParam) -> Real {
+    speed * speed * radius.value() / mu.value()
+}
+
+/// Free-flight range angle Ψ: the central angle between burnout and
+/// reentry at the burnout radius. `None` if the vehicle escapes or
+/// never rises above the burnout radius.
+pub fn free_flight_range_angle(q: Real, flight_path_angle: Real) -> Option<Real> {
+    let cos2 = cos(flight_path_angle) * cos(flight_path_angle);
+    let e = eccentricity(q, flight_path_angle);
+    if q <= 0.0 || q >= 2.0 || e <= 0.0 || flight_path_angle <= 0.0 {
+        return None;
+    }
+    Some(2.0 * acos(((1.0 - q * cos2) / e).clamp(-1.0, 1.0)))
+}
+
+/// The two burnout flight-path angles that reach range angle `psi`
+/// with burnout parameter `q`, as (low, high) trajectories; `None`
+/// if the range is out of reach. Above circular speed (Q ≥ 1) the
+/// low solution can fall below the horizon, which also yields `None`.
+pub fn flight_path_angles_for_range(q: Real, psi: Real) -> Option<(Real, Real)> {
+    if q <= 0.0 || q >= 2.0 || psi <= 0.0 || psi >= TAU {
+        return None;
+    }
+    // sin(2φ + Ψ/2) = (2 − Q)/Q · sin(Ψ/2)
+    let k = (2.0 - q) / q * sin(psi / 2.0);
+    if k > 1.0 {
+        return None;
+    }
+    let s = asin(k);
+    let low = (s - psi / 2.0) / 2.0;
+    let high = (PI - s - psi / 2.0) / 2.0;
+    if low <= 0.0 {
+        return None;
+    }
+    Some((low, high))
+}
+
+/// Burnout parameter needed to reach range angle `psi` when leaving
+/// at `flight_path_angle`; `None` if no such trajectory exists
+pub fn q_for_range(psi: Real, flight_path_angle: Real) -> Option<Real> {
+    let half = psi / 2.0;
+    let k = sin(2.0 * flight_path_angle + half) / sin(half);
+    let q = 2.0 / (1.0 + k);
+    if k <= -1.0 || !(0.0..2.0).contains(&q) {
+        None
+    } else {
+        Some(q)
+    }
+}
+
+/// Smallest burnout parameter that can reach range angle `psi`;
+/// it is achieved at the maximum-range flight-path angle
+pub fn minimum_q_for_range(psi: Real) -> Real {
+    let s = sin(psi / 2.0);
+    2.0 * s / (1.0 + s)
+}
+
+/// The longest trajectory available with a given burnout parameter
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaximumRange {
+    /// Range angle Ψ, in radians
+    pub range_angle: Real,
+    /// Burnout flight-path angle that achieves it, in radians
+    pub flight_path_angle: Real,
+}
+
+/// Maximum range for burnout parameter `q`; only defined for Q < 1,
+/// since at circular speed and above the range is unbounded
+pub fn maximum_range(q: Real) -> Option<MaximumRange> {
+    if q <= 0.0 || q >= 1.0 {
+        return None;
+    }
+    // sin(Ψ/2) = Q / (2 − Q), φ = (π − Ψ) / 4
+    let range_angle = 2.0 * asin(q / (2.0 - q));
+    Some(MaximumRange {
+        range_angle,
+        flight_path_angle: (PI - range_angle) / 4.0,
+    })
+}
+
+/// A free-flight arc from burnout back down to the burnout radius
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BallisticTrajectory {
+    /// Burnout parameter Q = v²r/μ
+    pub q: Real,
+    /// Free-flight range angle Ψ, in radians
+    pub range_angle: Real,
+    /// Time from burnout to reentry
+    pub time_of_flight: Seconds,
+    /// Eccentricity of the free-flight ellipse
+    pub eccentricity: Real,
+    /// Highest radius reached
+    pub apoapsis: Meters,
+}
+
+impl BallisticTrajectory {
+    /// Describe the free flight following burnout at `radius` with
+    /// `speed` (m/s) and `flight_path_angle` (radians above the
+    /// horizontal). `None` if the vehicle escapes or does not climb.
+    pub fn new(
+        radius: Meters,
+        speed: Real,
+        flight_path_angle: Real,
+        mu: GravParam,
+    ) -> Option<Self> {
+        let q = q_parameter(radius, speed, mu);
+        let range_angle = free_flight_range_angle(q, flight_path_angle)?;
+        let e = eccentricity(q, flight_path_angle);
+        let a = radius.value() / (2.0 - q);
+
+        // Burnout sits at ν = π − Ψ/2, symmetric with reentry about
+        // apoapsis; double the time from burnout to apoapsis
+        let nu = PI - range_angle / 2.0;
+        let ecc_anomaly = acos(((e + cos(nu)) / (1.0 + e * cos(nu))).clamp(-1.0, 1.0));
+        let mean_anomaly = ecc_anomaly - e * sin(ecc_anomaly);
+        let n = sqrt(mu.value() / (a * a * a));
+
+        Some(BallisticTrajectory {
+            q,
+            range_angle,
+            time_of_flight: Seconds(2.0 * (PI - mean_anomaly) / n),
+            eccentricity: e,
+            apoapsis: Meters(a * (1.0 + e)),
+        })
+    }
+
+    /// Distance flown over a spherical body of the given radius
+    pub fn ground_range(&self, body_radius: Meters) -> Meters {
+        body_radius * self.range_angle
+    }
+}
+
+// e² = 1 + Q(Q − 2)cos²φ
+fn eccentricity(q: Real, flight_path_angle: Real) -> Real {
+    let cos2 = cos(flight_path_angle) * cos(flight_path_angle);
+    sqrt((1.0 + q * (q - 2.0) * cos2).max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use approx::assert_relative_eq;
+
+    const EARTH_RADIUS: Meters = Meters(6_378_136.3);
+
+    #[test]
+    fn q_is_one_at_circular_speed() {
+        let r = Meters(7_000_000.0);
+        let v = sqrt(EARTH.mu.value() / r.value());
+        assert_relative_eq!(q_parameter(r, v, EARTH.mu), 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn range_angle_for_quarter_orbit() {
+        // Q = 1 at 45° gives e = √½ and Ψ = 90°
+        let psi = free_flight_range_angle(1.0, PI / 4.0).unwrap();
+        assert_relative_eq!(psi, PI / 2.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn rejects_escape_and_horizontal_burnout() {
+        assert!(free_flight_range_angle(2.0, 0.5).is_none());
+        assert!(free_flight_range_angle(0.5, 0.0).is_none());
+        assert!(maximum_range(1.0).is_none());
+    }
+
+    #[test]
+    fn maximum_range_is_the_peak() {
+        let q = 0.9;
+        let max = maximum_range(q).unwrap();
+        assert_relative_eq!(
+            free_flight_range_angle(q, max.flight_path_angle).unwrap(),
+            max.range_angle,
+            epsilon = 1e-10
+        );
+        for offset in [-0.05, 0.05] {
+            let psi = free_flight_range_angle(q, max.flight_path_angle + offset).unwrap();
+            assert!(psi < max.range_angle);
+        }
+        assert_relative_eq!(minimum_q_for_range(max.range_angle), q, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn high_and_low_trajectories() {
+        let q = 0.8;
+        let psi = 0.6;
+        let (low, high) = flight_path_angles_for_range(q, psi).unwrap();
+        assert!(low < high);
+        assert_relative_eq!(
+            free_flight_range_angle(q, low).unwrap(),
+            psi,
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(
+            free_flight_range_angle(q, high).unwrap(),
+            psi,
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(q_for_range(psi, low).unwrap(), q, epsilon = 1e-10);
+        assert_relative_eq!(q_for_range(psi, high).unwrap(), q, epsilon = 1e-10);
+        // Beyond maximum range nothing works
+        assert!(flight_path_angles_for_range(q, 2.5).is_none());
+    }
+
+    #[test]
+    fn time_of_flight_for_quarter_orbit() {
+        let r = Meters(7_000_000.0);
+        let v = sqrt(EARTH.mu.value() / r.value());
+        let trajectory = BallisticTrajectory::new(r, v, PI / 4.0, EARTH.mu).unwrap();
+        // a = r, E at burnout = 90°, so TOF = (π + 2e)/n
+        let n = sqrt(EARTH.mu.value() / (r.value() * r.value() * r.value()));
+        let e = sqrt(0.5);
+        assert_relative_eq!(trajectory.eccentricity, e, epsilon = 1e-12);
+        assert_relative_eq!(
+            trajectory.time_of_flight.value(),
+            (PI + 2.0 * e) / n,
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            trajectory.apoapsis.value(),
+            r.value() * (1.0 + e),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn ground_range_scales_with_radius() {
+        let r = EARTH_RADIUS + Meters(100_000.0);
+        let trajectory = BallisticTrajectory::new(r, 6_000.0, 0.4, EARTH.mu).unwrap();
+        let ground = trajectory.ground_range(EARTH_RADIUS);
+        assert_relative_eq!(
+            ground.value(),
+            EARTH_RADIUS.value() * trajectory.range_angle,
+            epsilon = 1e-6
+        );
+        assert!(trajectory.time_of_flight > Seconds::ZERO);
+        assert!(trajectory.apoapsis > r);
+    }
+}
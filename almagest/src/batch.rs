@@ -0,0 +1,212 @@
+//! Two-body propagation of a whole catalog over one epoch grid.
+//!
+//! States are written into a caller's buffer, one row of
+//! `grid.count` states per orbit, so each orbit's trajectory is
+//! contiguous and `out.chunks(grid.count)` walks the catalog. The
+//! orientation and mean motion of an orbit are worked out once per
+//! row, leaving Kepler's equation and a handful of multiplies per
+//! state. With the `rayon` feature, [`par_propagate_catalog`] spreads
+//! the rows over threads.
+
+use libm::{cos, sin, sqrt};
+
+use crate::ephemeris::EpochGrid;
+use crate::error::AlmagestError;
+use crate::kepler::{KeplerianElements, solve_kepler};
+use crate::state::StateVector;
+use crate::utils::{GravParam, Real, Seconds};
+use crate::vectors::Vec3;
+
+/// Elements and the epoch they hold at, on the grid's time scale
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EpochElements {
+    pub epoch: Seconds,
+    pub elements: KeplerianElements,
+}
+
+// What a row needs of an orbit, computed once
+struct Prepared {
+    p_hat: Vec3,
+    q_hat: Vec3,
+    a: Real,
+    e: Real,
+    // √(1 − e²)
+    beta: Real,
+    mean_motion: Real,
+    // Mean anomaly at time zero
+    mean_anomaly: Real,
+    sqrt_mu_a: Real,
+}
+
+impl Prepared {
+    fn new(orbit: &EpochElements, mu: GravParam) -> Result<Self, AlmagestError> {
+        let elements = &orbit.elements;
+        let e = elements.eccentricity.value();
+        if e >= 1.0 {
+            return Err(AlmagestError::InvalidEccentricity(
+                "Batch propagation needs elliptic orbits",
+            ));
+        }
+        let (p_hat, q_hat) = elements.perifocal_axes();
+        let a = elements.semi_major_axis.value();
+        let mean_motion = elements.mean_motion(mu).value();
+        Ok(Prepared {
+            p_hat,
+            q_hat,
+            a,
+            e,
+            beta: sqrt(1.0 - e * e),
+            mean_motion,
+            mean_anomaly: elements.mean_anomaly() - mean_motion * orbit.epoch.value(),
+            sqrt_mu_a: sqrt(mu.value() * a),
+        })
+    }
+
+    // The state from the eccentric anomaly, skipping the true anomaly
+    fn state_at(&self, time: Seconds) -> Result<StateVector, AlmagestError> {
+        let mean_anomaly = self.mean_anomaly + self.mean_motion * time.value();
+        let ecc_anomaly = solve_kepler(mean_anomaly, self.e)?;
+        let (s, c) = (sin(ecc_anomaly), cos(ecc_anomaly));
+        let r = self.a * (1.0 - self.e * c);
+        let v = self.sqrt_mu_a / r;
+        Ok(StateVector::new(
+            self.p_hat * (self.a * (c - self.e)) + self.q_hat * (self.a * self.beta * s),
+            self.p_hat * (-v * s) + self.q_hat * (v * self.beta * c),
+        ))
+    }
+}
+
+fn propagate_row(
+    orbit: &EpochElements,
+    mu: GravParam,
+    grid: &EpochGrid,
+    row: &mut [StateVector],
+) -> Result<(), AlmagestError> {
+    let prepared = Prepared::new(orbit, mu)?;
+    for (state, time) in row.iter_mut().zip(grid.epochs()) {
+        *state = prepared.state_at(time)?;
+    }
+    Ok(())
+}
+
+fn check_size(
+    catalog: &[EpochElements],
+    grid: &EpochGrid,
+    out: &[StateVector],
+) -> Result<(), AlmagestError> {
+    if out.len() != catalog.len() * grid.count {
+        return Err(AlmagestError::InvalidInput(
+            "Output must hold one state per orbit and epoch",
+        ));
+    }
+    Ok(())
+}
+
+/// Propagate every orbit of `catalog` to every epoch of `grid`,
+/// writing orbit `i` at epoch `k` to `out[i * grid.count + k]`. Only
+/// elliptic orbits are supported.
+pub fn propagate_catalog(
+    catalog: &[EpochElements],
+    mu: GravParam,
+    grid: &EpochGrid,
+    out: &mut [StateVector],
+) -> Result<(), AlmagestError> {
+    check_size(catalog, grid, out)?;
+    if grid.count == 0 {
+        return Ok(());
+    }
+    catalog
+        .iter()
+        .zip(out.chunks_mut(grid.count))
+        .try_for_each(|(orbit, row)| propagate_row(orbit, mu, grid, row))
+}
+
+/// As [`propagate_catalog`], a row per task on rayon's thread pool
+#[cfg(feature = "rayon")]
+pub fn par_propagate_catalog(
+    catalog: &[EpochElements],
+    mu: GravParam,
+    grid: &EpochGrid,
+    out: &mut [StateVector],
+) -> Result<(), AlmagestError> {
+    use rayon::prelude::*;
+
+    check_size(catalog, grid, out)?;
+    if grid.count == 0 {
+        return Ok(());
+    }
+    catalog
+        .par_iter()
+        .zip(out.par_chunks_mut(grid.count))
+        .try_for_each(|(orbit, row)| propagate_row(orbit, mu, grid, row))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::utils::{Eccentricity, Meters, Radians};
+    use approx::assert_relative_eq;
+
+    fn catalog() -> [EpochElements; 3] {
+        let orbit = |a: Real, e: Real, nu: Real, epoch: Real| EpochElements {
+            epoch: Seconds(epoch),
+            elements: KeplerianElements {
+                semi_major_axis: Meters(a),
+                eccentricity: Eccentricity::new(e).unwrap(),
+                inclination: Radians(0.9),
+                raan: Radians(1.1),
+                argument_of_periapsis: Radians(0.3),
+                true_anomaly: Radians(nu),
+            },
+        };
+        [
+            orbit(6_900_000.0, 0.001, 0.0, 0.0),
+            orbit(26_560_000.0, 0.7, 2.5, -3_600.0),
+            orbit(42_164_000.0, 0.0, 4.0, 600.0),
+        ]
+    }
+
+    #[test]
+    fn matches_element_propagation() {
+        let catalog = catalog();
+        let grid = EpochGrid::new(Seconds::ZERO, Seconds(6_000.0), Seconds(1_000.0)).unwrap();
+        let mut out = [StateVector::ZERO; 21];
+        propagate_catalog(&catalog, EARTH.mu, &grid, &mut out).unwrap();
+        for (orbit, row) in catalog.iter().zip(out.chunks(grid.count)) {
+            for (state, time) in row.iter().zip(grid.epochs()) {
+                let expected = orbit
+                    .elements
+                    .propagate(time - orbit.epoch, EARTH.mu)
+                    .unwrap()
+                    .to_state(EARTH.mu);
+                assert_relative_eq!(
+                    (state.position - expected.position).norm(),
+                    0.0,
+                    epsilon = 1e-5
+                );
+                assert_relative_eq!(
+                    (state.velocity - expected.velocity).norm(),
+                    0.0,
+                    epsilon = 1e-8
+                );
+            }
+        }
+
+        let mut short = [StateVector::ZERO; 20];
+        assert!(propagate_catalog(&catalog, EARTH.mu, &grid, &mut short).is_err());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_matches_sequential() {
+        let catalog = catalog();
+        let grid = EpochGrid::new(Seconds::ZERO, Seconds(86_400.0), Seconds(60.0)).unwrap();
+        let mut sequential = [StateVector::ZERO; 3 * 1_441];
+        let mut parallel = [StateVector::ZERO; 3 * 1_441];
+        propagate_catalog(&catalog, EARTH.mu, &grid, &mut sequential).unwrap();
+        par_propagate_catalog(&catalog, EARTH.mu, &grid, &mut parallel).unwrap();
+        assert_eq!(sequential, parallel);
+    }
+}
@@ -0,0 +1,205 @@
+//! Physical constants of the Sun, Moon and planets.
+//!
+//! Values follow Vallado's Appendix D, updated to the IAU/IERS figures
+//! the rest of the crate already uses for the Earth, Moon and Sun.
+//! The per-body constants elsewhere in the crate, such as
+//! [`crate::groundtrack::EARTH_RADIUS`] and [`crate::lunar::MU_MOON`],
+//! are taken from here. Rotation rates are sidereal; a negative rate
+//! is retrograde.
+
+use crate::utils::{GravParam, Meters, MetersPerSecondSquared, Real, Seconds};
+
+/// A central body as the crate's models see it. The radius is the
+/// reference radius of the body's gravity field, which for the Moon
+/// is a little above its mean radius.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CelestialBody {
+    pub name: &'static str,
+    pub mu: GravParam,
+    pub equatorial_radius: Meters,
+    /// Second zonal harmonic, unnormalized
+    pub j2: Real,
+    /// Sidereal rotation rate, in rad/s
+    pub rotation_rate: Real,
+    pub flattening: Real,
+}
+
+impl CelestialBody {
+    /// Radius at the poles, R(1 − f)
+    pub fn polar_radius(&self) -> Meters {
+        self.equatorial_radius * (1.0 - self.flattening)
+    }
+
+    /// Point-mass gravity at the equator, μ/R²
+    pub fn surface_gravity(&self) -> MetersPerSecondSquared {
+        let r = self.equatorial_radius.value();
+        MetersPerSecondSquared(self.mu.value() / (r * r))
+    }
+}
+
+/// A body's figure and spin as the ground-track models see it: an
+/// oblate spheroid turning at a steady rate about the inertial z axis.
+/// Models that treat the body as a sphere use the equatorial radius
+/// and ignore the flattening.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RotatingBody {
+    pub equatorial_radius: Meters,
+    pub flattening: Real,
+    /// Sidereal rotation rate, in rad/s
+    pub rotation_rate: Real,
+    /// Angle of the prime meridian from the inertial x axis at time
+    /// zero, in radians
+    pub greenwich_angle: Real,
+}
+
+impl RotatingBody {
+    /// `body` with its prime meridian at `greenwich_angle` at time zero
+    pub const fn of(body: &CelestialBody, greenwich_angle: Real) -> Self {
+        Self {
+            equatorial_radius: body.equatorial_radius,
+            flattening: body.flattening,
+            rotation_rate: body.rotation_rate,
+            greenwich_angle,
+        }
+    }
+
+    /// The Earth, with Greenwich at `greenwich_angle` at time zero
+    pub const fn earth(greenwich_angle: Real) -> Self {
+        Self::of(&EARTH, greenwich_angle)
+    }
+
+    /// Angle of the prime meridian from the inertial x axis at `time`
+    pub fn angle(&self, time: Seconds) -> Real {
+        self.greenwich_angle + self.rotation_rate * time.value()
+    }
+}
+
+impl From<CelestialBody> for GravParam {
+    fn from(body: CelestialBody) -> Self {
+        body.mu
+    }
+}
+
+pub const SUN: CelestialBody = CelestialBody {
+    name: "Sun",
+    mu: GravParam(1.327_124_400_18e20),
+    equatorial_radius: Meters(695_700_000.0),
+    j2: 2.2e-7,
+    rotation_rate: 2.865_3e-6,
+    flattening: 0.0,
+};
+
+pub const MERCURY: CelestialBody = CelestialBody {
+    name: "Mercury",
+    mu: GravParam(2.203_2e13),
+    equatorial_radius: Meters(2_439_700.0),
+    j2: 6.0e-5,
+    rotation_rate: 1.240_013e-6,
+    flattening: 0.0,
+};
+
+pub const VENUS: CelestialBody = CelestialBody {
+    name: "Venus",
+    mu: GravParam(3.248_59e14),
+    equatorial_radius: Meters(6_051_800.0),
+    j2: 4.458e-6,
+    rotation_rate: -2.992_4e-7,
+    flattening: 0.0,
+};
+
+pub const EARTH: CelestialBody = CelestialBody {
+    name: "Earth",
+    mu: GravParam(3.986_004_418e14),
+    equatorial_radius: Meters(6_378_137.0),
+    j2: 1.082_626_68e-3,
+    rotation_rate: 7.292_115_146_706_979e-5,
+    flattening: 1.0 / 298.257_223_563,
+};
+
+pub const MOON: CelestialBody = CelestialBody {
+    name: "Moon",
+    mu: GravParam(4.902_800_118e12),
+    equatorial_radius: Meters(1_738_000.0),
+    j2: 2.032_156_8e-4,
+    rotation_rate: 2.661_699_5e-6,
+    flattening: 0.001_2,
+};
+
+pub const MARS: CelestialBody = CelestialBody {
+    name: "Mars",
+    mu: GravParam(4.282_837e13),
+    equatorial_radius: Meters(3_396_190.0),
+    j2: 1.960_45e-3,
+    rotation_rate: 7.088_218e-5,
+    flattening: 0.005_89,
+};
+
+pub const JUPITER: CelestialBody = CelestialBody {
+    name: "Jupiter",
+    mu: GravParam(1.266_865_34e17),
+    equatorial_radius: Meters(71_492_000.0),
+    j2: 1.473_6e-2,
+    rotation_rate: 1.758_53e-4,
+    flattening: 0.064_87,
+};
+
+pub const SATURN: CelestialBody = CelestialBody {
+    name: "Saturn",
+    mu: GravParam(3.793_118_7e16),
+    equatorial_radius: Meters(60_268_000.0),
+    j2: 1.629_8e-2,
+    rotation_rate: 1.637_8e-4,
+    flattening: 0.097_96,
+};
+
+pub const URANUS: CelestialBody = CelestialBody {
+    name: "Uranus",
+    mu: GravParam(5.793_939e15),
+    equatorial_radius: Meters(25_559_000.0),
+    j2: 3.343_43e-3,
+    rotation_rate: -1.012_4e-4,
+    flattening: 0.022_93,
+};
+
+pub const NEPTUNE: CelestialBody = CelestialBody {
+    name: "Neptune",
+    mu: GravParam(6.836_529e15),
+    equatorial_radius: Meters(24_764_000.0),
+    j2: 3.411e-3,
+    rotation_rate: 1.083_4e-4,
+    flattening: 0.017_08,
+};
+
+pub const PLUTO: CelestialBody = CelestialBody {
+    name: "Pluto",
+    mu: GravParam(8.696e11),
+    equatorial_radius: Meters(1_188_300.0),
+    j2: 0.0,
+    rotation_rate: -1.138_6e-5,
+    flattening: 0.0,
+};
+
+/// The Sun and planets, inner to outer
+pub const SOLAR_SYSTEM: [CelestialBody; 10] = [
+    SUN, MERCURY, VENUS, EARTH, MARS, JUPITER, SATURN, URANUS, NEPTUNE, PLUTO,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn earth_figures() {
+        assert_relative_eq!(EARTH.polar_radius().value(), 6_356_752.3, epsilon = 0.1);
+        assert_relative_eq!(EARTH.surface_gravity().value(), 9.798, epsilon = 1e-3);
+        let mu: GravParam = EARTH.into();
+        assert_eq!(mu, EARTH.mu);
+        // Sorted by distance from the Sun, and the Sun outweighs all
+        assert_eq!(SOLAR_SYSTEM[3].name, "Earth");
+        assert!(SOLAR_SYSTEM[1..].iter().all(|body| body.mu < SUN.mu));
+        assert!(JUPITER.surface_gravity().value() > 2.0 * EARTH.surface_gravity().value());
+    }
+}
@@ -0,0 +1,333 @@
+//! Propellant and delta-v budgets from the rocket equation.
+//!
+//! Specific impulse is in seconds. A [`ManeuverPlan`] spends propellant
+//! burn by burn, so each burn starts from the mass the previous one
+//! left behind.
+
+use libm::{exp, log};
+
+use crate::error::AlmagestError;
+use crate::maneuvers::{
+    BiEllipticTransfer, CombinedTransfer, HohmannTransfer, Phasing, PlaneChange, Rendezvous,
+};
+use crate::utils::{Kilograms, MetersPerSecond, Real};
+
+/// Standard gravity, used to turn specific impulse into exhaust
+/// velocity, in m/s²
+pub const STANDARD_GRAVITY: Real = 9.806_65;
+
+/// Effective exhaust velocity for a specific impulse
+pub fn exhaust_velocity(isp: Real) -> MetersPerSecond {
+    MetersPerSecond(isp * STANDARD_GRAVITY)
+}
+
+fn check_isp(isp: Real) -> Result<(), AlmagestError> {
+    if isp <= 0.0 {
+        return Err(AlmagestError::InvalidInput(
+            "Specific impulse must be positive",
+        ));
+    }
+    Ok(())
+}
+
+/// Delta-v from burning down from `initial_mass` to `final_mass`
+/// (Tsiolkovsky): Δv = Isp g₀ ln(m₀ / m_f)
+pub fn delta_v(
+    initial_mass: Kilograms,
+    final_mass: Kilograms,
+    isp: Real,
+) -> Result<MetersPerSecond, AlmagestError> {
+    if final_mass.value() <= 0.0 || final_mass > initial_mass {
+        return Err(AlmagestError::InvalidInput(
+            "Final mass must be positive and no more than the initial mass",
+        ));
+    }
+    check_isp(isp)?;
+    Ok(exhaust_velocity(isp) * log(initial_mass / final_mass))
+}
+
+/// Initial over final mass needed for a delta-v
+pub fn mass_ratio(delta_v: MetersPerSecond, isp: Real) -> Result<Real, AlmagestError> {
+    check_isp(isp)?;
+    Ok(exp(delta_v / exhaust_velocity(isp)))
+}
+
+/// Propellant burned to give `initial_mass` a delta-v
+pub fn propellant_mass(
+    initial_mass: Kilograms,
+    delta_v: MetersPerSecond,
+    isp: Real,
+) -> Result<Kilograms, AlmagestError> {
+    Ok(initial_mass * (1.0 - 1.0 / mass_ratio(delta_v, isp)?))
+}
+
+/// A maneuver made of one or more impulsive burns
+pub trait Burns {
+    /// The delta-v of each burn, in the order they are made
+    fn burns(&self) -> impl Iterator<Item = MetersPerSecond>;
+}
+
+impl Burns for HohmannTransfer {
+    fn burns(&self) -> impl Iterator<Item = MetersPerSecond> {
+        [self.departure_delta_v, self.arrival_delta_v].into_iter()
+    }
+}
+
+impl Burns for BiEllipticTransfer {
+    fn burns(&self) -> impl Iterator<Item = MetersPerSecond> {
+        [self.first_delta_v, self.second_delta_v, self.third_delta_v].into_iter()
+    }
+}
+
+impl Burns for PlaneChange {
+    fn burns(&self) -> impl Iterator<Item = MetersPerSecond> {
+        [self.delta_v].into_iter()
+    }
+}
+
+impl Burns for CombinedTransfer {
+    fn burns(&self) -> impl Iterator<Item = MetersPerSecond> {
+        [self.departure_delta_v, self.arrival_delta_v].into_iter()
+    }
+}
+
+impl Burns for Phasing {
+    // Onto and off the phasing orbit, which cost the same
+    fn burns(&self) -> impl Iterator<Item = MetersPerSecond> {
+        [self.delta_v / 2.0; 2].into_iter()
+    }
+}
+
+impl Burns for Rendezvous {
+    fn burns(&self) -> impl Iterator<Item = MetersPerSecond> {
+        self.transfer.burns()
+    }
+}
+
+/// One line of a [`ManeuverPlan`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BurnBudget {
+    pub label: &'static str,
+    pub delta_v: MetersPerSecond,
+    pub isp: Real,
+    /// Mass before the burn
+    pub initial_mass: Kilograms,
+    pub propellant: Kilograms,
+}
+
+impl BurnBudget {
+    // Filler for the unused slots of a plan
+    const EMPTY: Self = BurnBudget {
+        label: "",
+        delta_v: MetersPerSecond::ZERO,
+        isp: 0.0,
+        initial_mass: Kilograms(0.0),
+        propellant: Kilograms(0.0),
+    };
+
+    /// Mass after the burn
+    pub fn final_mass(&self) -> Kilograms {
+        self.initial_mass - self.propellant
+    }
+}
+
+/// A sequence of up to `N` burns and the propellant they use,
+/// starting from a wet mass
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ManeuverPlan<const N: usize> {
+    initial_mass: Kilograms,
+    burns: [BurnBudget; N],
+    len: usize,
+}
+
+impl<const N: usize> ManeuverPlan<N> {
+    /// An empty plan for a vehicle of `initial_mass`
+    pub fn new(initial_mass: Kilograms) -> Self {
+        ManeuverPlan {
+            initial_mass,
+            burns: [BurnBudget::EMPTY; N],
+            len: 0,
+        }
+    }
+
+    // Reject a burn before anything is recorded
+    fn check(delta_v: MetersPerSecond, isp: Real) -> Result<(), AlmagestError> {
+        if delta_v < MetersPerSecond::ZERO {
+            return Err(AlmagestError::InvalidInput("Delta-v must not be negative"));
+        }
+        check_isp(isp)
+    }
+
+    // Record a burn already checked to fit
+    fn push(
+        &mut self,
+        label: &'static str,
+        delta_v: MetersPerSecond,
+        isp: Real,
+    ) -> Result<(), AlmagestError> {
+        let initial_mass = self.final_mass();
+        self.burns[self.len] = BurnBudget {
+            label,
+            delta_v,
+            isp,
+            initial_mass,
+            propellant: propellant_mass(initial_mass, delta_v, isp)?,
+        };
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Add a single burn
+    pub fn burn(
+        &mut self,
+        label: &'static str,
+        delta_v: MetersPerSecond,
+        isp: Real,
+    ) -> Result<&mut Self, AlmagestError> {
+        if self.len == N {
+            return Err(AlmagestError::InvalidInput("Maneuver plan is full"));
+        }
+        Self::check(delta_v, isp)?;
+        self.push(label, delta_v, isp)?;
+        Ok(self)
+    }
+
+    /// Add every burn of a maneuver under the same label. Either all
+    /// of them are added or, on an error, none are.
+    pub fn maneuver(
+        &mut self,
+        label: &'static str,
+        maneuver: &impl Burns,
+        isp: Real,
+    ) -> Result<&mut Self, AlmagestError> {
+        if self.len + maneuver.burns().count() > N {
+            return Err(AlmagestError::InvalidInput("Maneuver plan is full"));
+        }
+        for delta_v in maneuver.burns() {
+            Self::check(delta_v, isp)?;
+        }
+        for delta_v in maneuver.burns() {
+            self.push(label, delta_v, isp)?;
+        }
+        Ok(self)
+    }
+
+    /// The burns so far, in order
+    pub fn burns(&self) -> &[BurnBudget] {
+        &self.burns[..self.len]
+    }
+
+    pub fn initial_mass(&self) -> Kilograms {
+        self.initial_mass
+    }
+
+    /// Mass left after the last burn
+    pub fn final_mass(&self) -> Kilograms {
+        self.burns()
+            .last()
+            .map_or(self.initial_mass, BurnBudget::final_mass)
+    }
+
+    pub fn total_delta_v(&self) -> MetersPerSecond {
+        self.burns()
+            .iter()
+            .fold(MetersPerSecond::ZERO, |total, burn| total + burn.delta_v)
+    }
+
+    pub fn total_propellant(&self) -> Kilograms {
+        self.initial_mass - self.final_mass()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::kepler::KeplerianElements;
+    use crate::maneuvers::{hohmann, inclination_change};
+    use crate::state::StateVector;
+    use crate::utils::{Meters, Radians};
+    use crate::vectors::Vec3;
+    use approx::assert_relative_eq;
+    use libm::sqrt;
+
+    #[test]
+    fn rocket_equation_round_trip() {
+        // A mass ratio of e gives one exhaust velocity
+        let dv = delta_v(Kilograms(core::f64::consts::E), Kilograms(1.0), 300.0).unwrap();
+        assert_relative_eq!(dv.value(), 2_941.995, epsilon = 1e-9);
+        assert_relative_eq!(
+            mass_ratio(dv, 300.0).unwrap(),
+            core::f64::consts::E,
+            epsilon = 1e-12
+        );
+        let wet = Kilograms(1_000.0);
+        let propellant = propellant_mass(wet, MetersPerSecond(1_500.0), 320.0).unwrap();
+        assert_relative_eq!(
+            delta_v(wet, wet - propellant, 320.0).unwrap().value(),
+            1_500.0,
+            max_relative = 1e-12
+        );
+        assert!(delta_v(Kilograms(1.0), Kilograms(2.0), 300.0).is_err());
+        assert!(delta_v(Kilograms(2.0), Kilograms(1.0), 0.0).is_err());
+        assert!(mass_ratio(dv, 0.0).is_err());
+        assert!(propellant_mass(wet, dv, -1.0).is_err());
+    }
+
+    #[test]
+    fn plan_spends_mass_burn_by_burn() {
+        let leo = Meters(6_678_137.0);
+        let transfer = hohmann(leo, Meters(42_164_137.0), EARTH.mu).unwrap();
+        let mut plan = ManeuverPlan::<4>::new(Kilograms(2_000.0));
+        plan.maneuver("GTO", &transfer, 310.0)
+            .unwrap()
+            .burn("trim", MetersPerSecond(5.0), 220.0)
+            .unwrap();
+        assert_eq!(plan.burns().len(), 3);
+        assert_relative_eq!(
+            plan.total_delta_v().value(),
+            transfer.total_delta_v().value() + 5.0
+        );
+
+        // Each burn starts where the last left off
+        for pair in plan.burns().windows(2) {
+            assert_eq!(pair[1].initial_mass, pair[0].final_mass());
+        }
+        let propellant: Real = plan.burns().iter().map(|b| b.propellant.value()).sum();
+        assert_relative_eq!(
+            plan.total_propellant().value(),
+            propellant,
+            max_relative = 1e-12
+        );
+        // Burns on the same engine add up like one larger burn
+        let total = transfer.total_delta_v();
+        let single = propellant_mass(Kilograms(2_000.0), total, 310.0).unwrap();
+        let first_two = plan.burns()[0].propellant + plan.burns()[1].propellant;
+        assert_relative_eq!(first_two.value(), single.value(), max_relative = 1e-12);
+
+        let v = sqrt(EARTH.mu.value() / leo.value());
+        let state = StateVector::new(Vec3::new(leo.value(), 0.0, 0.0), Vec3::new(0.0, v, 0.0));
+        let elements = KeplerianElements::from_state(&state, EARTH.mu).unwrap();
+        let turn = inclination_change(&elements, Radians(0.1), EARTH.mu).unwrap();
+        assert!(plan.maneuver("plane", &turn, 310.0).is_ok());
+        assert_eq!(
+            plan.maneuver("plane", &turn, 310.0),
+            Err(AlmagestError::InvalidInput("Maneuver plan is full"))
+        );
+    }
+
+    #[test]
+    fn maneuvers_are_added_whole_or_not_at_all() {
+        let transfer = hohmann(Meters(6_678_137.0), Meters(42_164_137.0), EARTH.mu).unwrap();
+        let mut plan = ManeuverPlan::<2>::new(Kilograms(2_000.0));
+        plan.burn("trim", MetersPerSecond(5.0), 220.0).unwrap();
+        // Two burns into the one slot left
+        assert!(plan.maneuver("GTO", &transfer, 310.0).is_err());
+        assert_eq!(plan.burns().len(), 1);
+        assert!(plan.maneuver("GTO", &transfer, 0.0).is_err());
+        assert_eq!(plan.burns().len(), 1);
+        assert!(plan.burn("trim", MetersPerSecond(5.0), 0.0).is_err());
+        assert_eq!(plan.burns().len(), 1);
+    }
+}
@@ -0,0 +1,302 @@
+//! Close approaches between two ephemerides.
+//!
+//! The screen steps through a window sampling the separation ρ of the
+//! secondary from the primary. A step is only searched when it could
+//! hold a minimum inside the threshold: ρ·ρ̇ must go from negative to
+//! positive across it, and the separation at its ends must leave room
+//! to close to within the threshold at the relative speeds there.
+//! Survivors are refined by bisection on ρ·ρ̇ to the time of closest
+//! approach. Approaches closer together than one step can merge, so
+//! the step should be short against the time between them; for LEO
+//! pairs a minute is plenty.
+//!
+//! Encounter geometry is given in the primary's RIC frame: radial,
+//! in-track and cross-track, the axes of [`hill_axes`].
+//!
+//! ```
+//! use almagest::bodies::EARTH;
+//! use almagest::conjunction::screen;
+//! use almagest::ephemeris::TwoBody;
+//! use almagest::kepler::KeplerianElements;
+//! use almagest::utils::{Eccentricity, Meters, Radians, Seconds};
+//!
+//! let mu = EARTH.mu;
+//! let orbit = |inclination: f64, true_anomaly: f64| TwoBody {
+//!     elements: KeplerianElements {
+//!         semi_major_axis: Meters(7_000_000.0),
+//!         eccentricity: Eccentricity::new(0.0).unwrap(),
+//!         inclination: Radians(inclination),
+//!         raan: Radians(0.0),
+//!         argument_of_periapsis: Radians(0.0),
+//!         true_anomaly: Radians(true_anomaly),
+//!     },
+//!     mu,
+//!     epoch: Seconds::ZERO,
+//!     frame: 1,
+//! };
+//! // Two planes crossing at the node, met a few hundred meters apart
+//! let (primary, secondary) = (orbit(0.0, 5.0), orbit(0.3, 5.0001));
+//! let (start, end, step) = (Seconds::ZERO, Seconds(3_600.0), Seconds(60.0));
+//! let mut approaches = screen(primary, secondary, start, end, Meters(5_000.0), step).unwrap();
+//! let first = approaches.next().unwrap();
+//! assert!(first.miss_distance < Meters(700.0));
+//! ```
+
+use crate::ephemeris::Ephemeris;
+use crate::error::AlmagestError;
+use crate::relative::hill_axes;
+use crate::state::StateVector;
+use crate::utils::{Meters, MetersPerSecond, Seconds};
+use crate::vectors::Vec3;
+
+// Closest approach times are refined to a microsecond
+const TIME_TOLERANCE: Seconds = Seconds(1e-6);
+
+/// The secondary's closest approach to the primary
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Conjunction {
+    /// Time of closest approach
+    pub epoch: Seconds,
+    pub miss_distance: Meters,
+    pub relative_speed: MetersPerSecond,
+    /// Position of the secondary relative to the primary in the
+    /// primary's RIC frame, in meters
+    pub position: Vec3,
+    /// Inertial relative velocity resolved on the primary's RIC axes,
+    /// in m/s; normal to `position` at closest approach
+    pub velocity: Vec3,
+}
+
+/// Iterator over the close approaches of a pair, in time order,
+/// created by [`screen`]
+#[derive(Copy, Clone, Debug)]
+pub struct Screening<A, B> {
+    primary: A,
+    secondary: B,
+    threshold: Meters,
+    step: Seconds,
+    cursor: Seconds,
+    end: Seconds,
+}
+
+/// Approaches of `secondary` to `primary` within `threshold` between
+/// `start` and `end`, sampled every `step`. Both ephemerides must be
+/// in one frame and cover the window.
+pub fn screen<A: Ephemeris, B: Ephemeris>(
+    primary: A,
+    secondary: B,
+    start: Seconds,
+    end: Seconds,
+    threshold: Meters,
+    step: Seconds,
+) -> Result<Screening<A, B>, AlmagestError> {
+    if primary.frame() != secondary.frame() {
+        return Err(AlmagestError::InvalidInput(
+            "Ephemerides are in different frames",
+        ));
+    }
+    if step <= Seconds::ZERO {
+        return Err(AlmagestError::InvalidInput("Step must be positive"));
+    }
+    if end < start {
+        return Err(AlmagestError::InvalidInput("Window ends before it starts"));
+    }
+    let covers = |(first, last): (Seconds, Seconds)| start >= first && end <= last;
+    if !covers(primary.span()) || !covers(secondary.span()) {
+        return Err(AlmagestError::EpochOutOfRange(
+            "Window runs outside an ephemeris",
+        ));
+    }
+    Ok(Screening {
+        primary,
+        secondary,
+        threshold,
+        step,
+        cursor: start,
+        end,
+    })
+}
+
+impl<A: Ephemeris, B: Ephemeris> Screening<A, B> {
+    // The secondary's inertial state less the primary's
+    fn relative(&self, epoch: Seconds) -> Option<StateVector> {
+        Some(self.secondary.state(epoch)? - self.primary.state(epoch)?)
+    }
+
+    // Whether a minimum within the threshold can lie between states
+    // `step` apart, taking the relative speed to stay within its
+    // values at the ends
+    fn may_close(&self, before: &StateVector, after: &StateVector, step: Seconds) -> bool {
+        if before.position.dot(before.velocity) >= 0.0 || after.position.dot(after.velocity) < 0.0 {
+            return false;
+        }
+        let speed = before.velocity.norm().max(after.velocity.norm());
+        let nearest = (before.position.norm() + after.position.norm() - speed * step.value()) / 2.0;
+        nearest <= self.threshold.value()
+    }
+
+    // Bisect on ρ·ρ̇ for the time of closest approach in [a, b]
+    fn refine(&self, mut a: Seconds, mut b: Seconds) -> Option<Seconds> {
+        while b - a > TIME_TOLERANCE {
+            let mid = a + (b - a) / 2.0;
+            let relative = self.relative(mid)?;
+            if relative.position.dot(relative.velocity) < 0.0 {
+                a = mid;
+            } else {
+                b = mid;
+            }
+        }
+        Some(a + (b - a) / 2.0)
+    }
+
+    fn conjunction(&self, epoch: Seconds) -> Option<Conjunction> {
+        let relative = self.relative(epoch)?;
+        let to_ric = hill_axes(&self.primary.state(epoch)?).transpose();
+        Some(Conjunction {
+            epoch,
+            miss_distance: Meters(relative.position.norm()),
+            relative_speed: MetersPerSecond(relative.velocity.norm()),
+            position: to_ric * relative.position,
+            velocity: to_ric * relative.velocity,
+        })
+    }
+}
+
+impl<A: Ephemeris, B: Ephemeris> Iterator for Screening<A, B> {
+    type Item = Conjunction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut before = self.relative(self.cursor)?;
+        while self.cursor < self.end {
+            let t = self.cursor;
+            let next = (t + self.step).min(self.end);
+            let after = self.relative(next)?;
+            self.cursor = next;
+            if self.may_close(&before, &after, next - t) {
+                let approach = self.conjunction(self.refine(t, next)?)?;
+                if approach.miss_distance <= self.threshold {
+                    return Some(approach);
+                }
+            }
+            before = after;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::ephemeris::{Tabulated, TwoBody};
+    use crate::kepler::KeplerianElements;
+    use crate::utils::{Eccentricity, Radians, Real};
+    use approx::assert_relative_eq;
+    use std::vec::Vec;
+
+    const J2000: i32 = 1;
+    const RADIUS: Real = 7_000_000.0;
+
+    fn circular(inclination: Real, true_anomaly: Real) -> TwoBody {
+        TwoBody {
+            elements: KeplerianElements {
+                semi_major_axis: Meters(RADIUS),
+                eccentricity: Eccentricity::new(0.0).unwrap(),
+                inclination: Radians(inclination),
+                raan: Radians(0.0),
+                argument_of_periapsis: Radians(0.0),
+                true_anomaly: Radians(true_anomaly),
+            },
+            mu: EARTH.mu,
+            epoch: Seconds::ZERO,
+            frame: J2000,
+        }
+    }
+
+    #[test]
+    fn crossing_planes_meet_at_the_nodes() {
+        // Both reach the ascending node about 927 s in and the
+        // descending node half a revolution later, 0.7 km apart along
+        // track
+        let (primary, secondary) = (circular(0.0, -1.0), circular(0.5, -1.0 + 1e-4));
+        let (start, end, step) = (Seconds::ZERO, Seconds(7_200.0), Seconds(60.0));
+        let found: Vec<Conjunction> = screen(primary, secondary, start, end, Meters(2_000.0), step)
+            .unwrap()
+            .collect();
+        assert_eq!(found.len(), 3);
+
+        let speed = libm::sqrt(EARTH.mu.value() / RADIUS);
+        for approach in &found {
+            // A brute-force search around the reported time agrees
+            let separation = |t: Seconds| {
+                (secondary.state(t).unwrap().position - primary.state(t).unwrap().position).norm()
+            };
+            let closest = (-1_000..=1_000)
+                .map(|k| separation(approach.epoch + Seconds(k as Real * 1e-3)))
+                .fold(Real::INFINITY, Real::min);
+            assert_relative_eq!(approach.miss_distance.value(), closest, epsilon = 1e-2);
+            assert!(approach.miss_distance < Meters(RADIUS * 1e-4));
+
+            // Equal speeds meeting at the mutual inclination, and
+            // nothing radial between two circles of one radius
+            assert_relative_eq!(
+                approach.relative_speed.value(),
+                2.0 * speed * libm::sin(0.25),
+                max_relative = 1e-3
+            );
+            assert_relative_eq!(approach.velocity.norm(), approach.relative_speed.value());
+            let normal = approach.position.unit().dot(approach.velocity.unit());
+            assert_relative_eq!(normal, 0.0, epsilon = 1e-5);
+            assert!(approach.position.x.abs() < 1.0);
+        }
+        let half_period = core::f64::consts::PI * RADIUS / speed;
+        let apart = (found[1].epoch - found[0].epoch).value();
+        assert_relative_eq!(apart, half_period, epsilon = 1.0);
+
+        // Nothing inside a tighter threshold
+        let tight = screen(primary, secondary, start, end, Meters(100.0), step).unwrap();
+        assert_eq!(tight.count(), 0);
+    }
+
+    #[test]
+    fn screens_tabulated_ephemerides() {
+        let (primary, secondary) = (circular(0.0, -1.0), circular(0.5, -1.0 + 1e-4));
+        let epochs: Vec<Seconds> = (0..=60).map(|i| Seconds(i as Real * 30.0)).collect();
+        let states: Vec<StateVector> = epochs
+            .iter()
+            .map(|&t| secondary.state(t).unwrap())
+            .collect();
+        let table = Tabulated::new(&epochs, &states, J2000).unwrap();
+        let (start, end, step) = (Seconds::ZERO, Seconds(1_800.0), Seconds(60.0));
+
+        let exact = screen(primary, secondary, start, end, Meters(2_000.0), step)
+            .unwrap()
+            .next()
+            .unwrap();
+        let interpolated = screen(primary, table, start, end, Meters(2_000.0), step)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_relative_eq!(
+            interpolated.epoch.value(),
+            exact.epoch.value(),
+            epsilon = 1e-2
+        );
+        assert_relative_eq!(
+            interpolated.miss_distance.value(),
+            exact.miss_distance.value(),
+            epsilon = 1.0
+        );
+
+        assert!(screen(primary, table, start, end * 2.0, Meters(2_000.0), step).is_err());
+        assert!(screen(primary, table, start, end, Meters(2_000.0), Seconds::ZERO).is_err());
+        let elsewhere = TwoBody {
+            frame: 13_000,
+            ..secondary
+        };
+        assert!(screen(primary, elsewhere, start, end, Meters(2_000.0), step).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+//! End-of-life disposal compliance with the IADC debris mitigation
+//! guidelines.
+//!
+//! Two rules are checked by evolving the disposal orbit with averaged
+//! perturbations from [`crate::milankovitch`]:
+//!
+//! - An object whose orbit passes through the LEO protected region,
+//!   below 2000 km, should reenter within 25 years. Decay comes from
+//!   drag in the banded static atmosphere of
+//!   [`crate::aerobraking::EarthAtmosphere`], alongside J2, Sun and
+//!   Moon. With no solar cycle in the density, lifetimes are rough.
+//! - A GEO spacecraft should be raised to a graveyard orbit with
+//!   perigee at least 235 km + 1000 C_R A/m above GEO (A/m in m²/kg),
+//!   eccentricity no more than 0.003, and stay clear of the GEO
+//!   protected region, GEO ± 200 km, under J2, Sun and Moon.
+
+use crate::aerobraking::EarthAtmosphere;
+use crate::error::AlmagestError;
+use crate::forces::Drag;
+use crate::groundtrack::EARTH_RADIUS;
+use crate::milankovitch::{
+    Averaged, MilankovitchElements, SecularModel, ThirdBody, ZonalJ2, rk4_step,
+};
+use crate::utils::{GravParam, Meters, Real, Seconds};
+
+/// Upper edge of the LEO protected region, as an altitude
+pub const LEO_CEILING: Meters = Meters(2_000_000.0);
+
+/// Altitude of the geostationary ring
+pub const GEO_ALTITUDE: Meters = Meters(35_786_000.0);
+
+/// Half-width of the GEO protected region in altitude
+pub const GEO_PROTECTED_HALF_WIDTH: Meters = Meters(200_000.0);
+
+/// Longest time an object may stay in LEO after end of life
+pub const LEO_LIFETIME_LIMIT: Seconds = Seconds(25.0 * YEAR);
+
+/// Perigee altitude below which an orbit is taken to have reentered
+pub const REENTRY_ALTITUDE: Meters = Meters(120_000.0);
+
+/// Highest graveyard-orbit eccentricity the guidelines allow
+pub const GRAVEYARD_MAX_ECCENTRICITY: Real = 0.003;
+
+const YEAR: Real = 365.25 * 86_400.0;
+
+/// Least perigee altitude above GEO for a graveyard orbit, 235 km +
+/// 1000 C_R A/m, for `reflectivity_area_to_mass` C_R A/m in m²/kg
+pub fn graveyard_perigee_increase(reflectivity_area_to_mass: Real) -> Meters {
+    Meters(235_000.0 + 1.0e6 * reflectivity_area_to_mass)
+}
+
+/// Outcome of a LEO disposal
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LeoCompliance {
+    /// Time to reentry, if within the run
+    pub lifetime: Option<Seconds>,
+    /// Perigee altitude at reentry or at the end of the run
+    pub final_perigee_altitude: Meters,
+    pub compliant: bool,
+}
+
+/// Outcome of a GEO graveyard disposal
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeoCompliance {
+    /// Perigee altitude above GEO the guidelines ask for
+    pub required_perigee_increase: Meters,
+    pub initial_perigee_altitude: Meters,
+    pub initial_eccentricity: Real,
+    /// Lowest perigee altitude over the run
+    pub minimum_perigee_altitude: Meters,
+    /// Whether the perigee stayed above the GEO protected region
+    pub clears_protected_region: bool,
+    pub compliant: bool,
+}
+
+/// Compliance of a disposal orbit with whichever rule applies to it
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ComplianceReport {
+    /// The orbit passes through the LEO protected region
+    Leo(LeoCompliance),
+    /// The orbit reaches up to the GEO protected region
+    Geo(GeoCompliance),
+    /// The orbit touches neither protected region
+    Unprotected,
+}
+
+impl ComplianceReport {
+    pub fn compliant(&self) -> bool {
+        match self {
+            ComplianceReport::Leo(leo) => leo.compliant,
+            ComplianceReport::Geo(geo) => geo.compliant,
+            ComplianceReport::Unprotected => true,
+        }
+    }
+}
+
+/// A spacecraft at end of life and how far to follow it
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Disposal {
+    /// m / (C_D A), in kg/m²
+    pub ballistic_coefficient: Real,
+    /// C_R A / m, in m²/kg
+    pub reflectivity_area_to_mass: Real,
+    /// Length of the run
+    pub horizon: Seconds,
+    /// Longest integration step
+    pub step: Seconds,
+}
+
+impl Disposal {
+    /// A century-long run in steps of at most a day
+    pub const fn new(ballistic_coefficient: Real, reflectivity_area_to_mass: Real) -> Self {
+        Disposal {
+            ballistic_coefficient,
+            reflectivity_area_to_mass,
+            horizon: Seconds(100.0 * YEAR),
+            step: Seconds(86_400.0),
+        }
+    }
+
+    /// Check a disposal orbit against the rule for the region it
+    /// passes through
+    pub fn assess(
+        &self,
+        elements: &MilankovitchElements,
+        mu: GravParam,
+    ) -> Result<ComplianceReport, AlmagestError> {
+        if elements.periapsis_radius() - EARTH_RADIUS < LEO_CEILING {
+            Ok(ComplianceReport::Leo(self.leo(elements, mu)?))
+        } else if elements.apoapsis_radius() - EARTH_RADIUS
+            >= GEO_ALTITUDE - GEO_PROTECTED_HALF_WIDTH
+        {
+            Ok(ComplianceReport::Geo(self.geo(elements, mu)?))
+        } else {
+            Ok(ComplianceReport::Unprotected)
+        }
+    }
+
+    /// Follow a LEO disposal until reentry or the end of the run
+    pub fn leo(
+        &self,
+        elements: &MilankovitchElements,
+        mu: GravParam,
+    ) -> Result<LeoCompliance, AlmagestError> {
+        let drag = Averaged::new(Drag {
+            atmosphere: EarthAtmosphere,
+            ballistic_coefficient: self.ballistic_coefficient,
+        });
+        let model = (
+            ZonalJ2::EARTH,
+            (drag, (ThirdBody::sun(), ThirdBody::moon())),
+        );
+        let mut lifetime = None;
+        let end = self.run(&model, elements, mu, |time, current| {
+            let reentered = current.periapsis_radius() - EARTH_RADIUS <= REENTRY_ALTITUDE;
+            if reentered {
+                lifetime = Some(time);
+            }
+            reentered
+        })?;
+        Ok(LeoCompliance {
+            lifetime,
+            final_perigee_altitude: end.periapsis_radius() - EARTH_RADIUS,
+            compliant: lifetime.is_some_and(|t| t <= LEO_LIFETIME_LIMIT),
+        })
+    }
+
+    /// Follow a GEO graveyard orbit to the end of the run
+    pub fn geo(
+        &self,
+        elements: &MilankovitchElements,
+        mu: GravParam,
+    ) -> Result<GeoCompliance, AlmagestError> {
+        let model = (ZonalJ2::EARTH, (ThirdBody::sun(), ThirdBody::moon()));
+        let altitude = |e: &MilankovitchElements| e.periapsis_radius() - EARTH_RADIUS;
+        let mut minimum = altitude(elements).value();
+        self.run(&model, elements, mu, |_, current| {
+            minimum = minimum.min(altitude(current).value());
+            false
+        })?;
+        let required = graveyard_perigee_increase(self.reflectivity_area_to_mass);
+        let eccentricity = elements.eccentricity.norm();
+        let clears = Meters(minimum) > GEO_ALTITUDE + GEO_PROTECTED_HALF_WIDTH;
+        Ok(GeoCompliance {
+            required_perigee_increase: required,
+            initial_perigee_altitude: altitude(elements),
+            initial_eccentricity: eccentricity,
+            minimum_perigee_altitude: Meters(minimum),
+            clears_protected_region: clears,
+            compliant: altitude(elements) >= GEO_ALTITUDE + required
+                && eccentricity <= GRAVEYARD_MAX_ECCENTRICITY
+                && clears,
+        })
+    }
+
+    // Evolve to the horizon or until `stop` says so, returning the last
+    // elements. Steps shrink as drag speeds up, so that none moves the
+    // semi-major axis by more than 1% or periapsis by more than 1 km.
+    fn run<M: SecularModel>(
+        &self,
+        model: &M,
+        elements: &MilankovitchElements,
+        mu: GravParam,
+        mut stop: impl FnMut(Seconds, &MilankovitchElements) -> bool,
+    ) -> Result<MilankovitchElements, AlmagestError> {
+        if self.step <= Seconds::ZERO {
+            return Err(AlmagestError::InvalidInput("Step must be positive"));
+        }
+        let mut time = Seconds::ZERO;
+        let mut current = *elements;
+        while time < self.horizon && !stop(time, &current) {
+            let rates = model.rates(time, &current, mu);
+            let a = current.semi_major_axis.value();
+            let e = current.eccentricity.norm();
+            // ṙp = ȧ(1 − e) − a ė·ê
+            let e_rate = if e > 0.0 {
+                rates.eccentricity.dot(current.eccentricity) / e
+            } else {
+                rates.eccentricity.norm()
+            };
+            let periapsis_rate = rates.semi_major_axis * (1.0 - e) - a * e_rate;
+            let h = Seconds(
+                self.step
+                    .value()
+                    .min((self.horizon - time).value())
+                    .min(0.01 * a / rates.semi_major_axis.abs())
+                    .min(1_000.0 / periapsis_rate.abs()),
+            );
+            current = rk4_step(model, time, &current, mu, h);
+            time = time + h;
+        }
+        Ok(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::kepler::KeplerianElements;
+    use crate::utils::{Eccentricity, Radians};
+    use approx::assert_relative_eq;
+    use libm::sqrt;
+
+    fn orbit(perigee: Real, apogee: Real, inclination: Real) -> MilankovitchElements {
+        let (rp, ra) = (
+            EARTH_RADIUS.value() + perigee,
+            EARTH_RADIUS.value() + apogee,
+        );
+        MilankovitchElements::from_keplerian(&KeplerianElements {
+            semi_major_axis: Meters((rp + ra) / 2.0),
+            eccentricity: Eccentricity::new((ra - rp) / (ra + rp)).unwrap(),
+            inclination: Radians(inclination),
+            raan: Radians(0.3),
+            argument_of_periapsis: Radians(0.0),
+            true_anomaly: Radians(0.0),
+        })
+    }
+
+    #[test]
+    fn averaged_drag_matches_circular_decay() {
+        // ȧ = −ρ√(μa)/β on a circular orbit
+        let circular = orbit(500_000.0, 500_000.0, 0.9);
+        let drag = Averaged::new(Drag {
+            atmosphere: EarthAtmosphere,
+            ballistic_coefficient: 100.0,
+        });
+        let rates = drag.rates(Seconds::ZERO, &circular, EARTH.mu);
+        let a = circular.semi_major_axis.value();
+        assert_relative_eq!(
+            rates.semi_major_axis,
+            -6.967e-13 * sqrt(EARTH.mu.value() * a) / 100.0,
+            max_relative = 1e-5
+        );
+        assert_relative_eq!(rates.eccentricity.norm(), 0.0, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn low_orbit_reenters_within_25_years() {
+        let disposal = Disposal::new(50.0, 0.02);
+        let low = orbit(450_000.0, 450_000.0, 0.9);
+        let ComplianceReport::Leo(report) = disposal.assess(&low, EARTH.mu).unwrap() else {
+            panic!("expected a LEO report");
+        };
+        let lifetime = report.lifetime.unwrap();
+        assert!((0.5 * YEAR..10.0 * YEAR).contains(&lifetime.value()));
+        assert!(report.compliant);
+        assert!(report.final_perigee_altitude <= REENTRY_ALTITUDE);
+
+        // Lowering perigee of a higher orbit brings reentry forward
+        let high = orbit(800_000.0, 800_000.0, 0.9);
+        let stays = Disposal {
+            horizon: Seconds(30.0 * YEAR),
+            ..disposal
+        };
+        let report = stays.assess(&high, EARTH.mu).unwrap();
+        assert!(!report.compliant());
+        let lowered = orbit(250_000.0, 800_000.0, 0.9);
+        assert!(stays.assess(&lowered, EARTH.mu).unwrap().compliant());
+    }
+
+    #[test]
+    fn graveyard_clearance() {
+        let disposal = Disposal {
+            horizon: Seconds(50.0 * YEAR),
+            step: Seconds(2.0 * 86_400.0),
+            ..Disposal::new(50.0, 0.02)
+        };
+        let required = graveyard_perigee_increase(0.02);
+        assert_relative_eq!(required.value(), 255_000.0);
+        let raised = 300_000.0 + GEO_ALTITUDE.value();
+        let ComplianceReport::Geo(report) = disposal
+            .assess(&orbit(raised, raised, 0.01), EARTH.mu)
+            .unwrap()
+        else {
+            panic!("expected a GEO report");
+        };
+        assert!(report.compliant);
+        assert!(report.minimum_perigee_altitude > GEO_ALTITUDE + GEO_PROTECTED_HALF_WIDTH);
+
+        // Clears the protected region but short of the margin
+        let short = 220_000.0 + GEO_ALTITUDE.value();
+        let report = disposal
+            .assess(&orbit(short, short, 0.01), EARTH.mu)
+            .unwrap();
+        let ComplianceReport::Geo(geo) = report else {
+            panic!("expected a GEO report");
+        };
+        assert!(geo.clears_protected_region);
+        assert!(!report.compliant());
+
+        let mid = orbit(20_000_000.0, 20_000_000.0, 0.96);
+        assert_eq!(
+            disposal.assess(&mid, EARTH.mu).unwrap(),
+            ComplianceReport::Unprotected
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! Atmospheric entry targeting from a hyperbolic approach.
+//!
+//! An approach is described by its B-plane: the plane through the
+//! target body's center perpendicular to the incoming asymptote Ŝ. The
+//! B vector runs from the center to where the asymptote pierces the
+//! plane; its length is the impact parameter b = h / v∞. T̂ lies along
+//! Ŝ × ẑ of the frame the state is given in, and R̂ = Ŝ × T̂.
+//!
+//! The flight-path angle at an entry interface radius depends only on
+//! b and v∞, so an entry corridor is a ring in the B-plane and a
+//! targeting burn only has to move B onto it.
+
+use libm::{acos, atan2, cos, sin, sqrt};
+
+use crate::bodies::MARS;
+use crate::error::AlmagestError;
+use crate::state::StateVector;
+use crate::utils::{GravParam, Meters, Real};
+use crate::vectors::Vec3;
+
+/// Gravitational parameter of Mars
+pub const MU_MARS: GravParam = MARS.mu;
+
+/// Equatorial radius of Mars
+pub const MARS_RADIUS: Meters = MARS.equatorial_radius;
+
+/// B-plane coordinates of a hyperbolic approach
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BPlane {
+    pub b_t: Real,
+    pub b_r: Real,
+    /// Hyperbolic excess speed, in m/s
+    pub v_infinity: Real,
+    /// Incoming asymptote
+    pub s: Vec3,
+    pub t: Vec3,
+    pub r: Vec3,
+}
+
+impl BPlane {
+    /// B-plane of a hyperbolic state relative to the target body
+    pub fn from_state(state: &StateVector, mu: GravParam) -> Result<Self, AlmagestError> {
+        let mu = mu.value();
+        let (r, v) = (state.position, state.velocity);
+        let h = r.cross(v);
+        let energy = v.norm_squared() / 2.0 - mu / r.norm();
+        if energy <= 0.0 {
+            return Err(AlmagestError::InvalidEccentricity(
+                "B-plane needs a hyperbolic approach",
+            ));
+        }
+        if h.norm() == 0.0 {
+            return Err(AlmagestError::Coplanarity("Approach is rectilinear"));
+        }
+        let e_vec = (r * (v.norm_squared() - mu / r.norm()) - v * r.dot(v)) / mu;
+        let e = e_vec.norm();
+        let p_hat = e_vec / e;
+        let q_hat = h.unit().cross(p_hat);
+        let s = p_hat / e + q_hat * (sqrt(e * e - 1.0) / e);
+        let t = s.cross(Vec3::Z).unit();
+        let r_hat = s.cross(t);
+        let v_infinity = sqrt(2.0 * energy);
+        let b = s.cross(h.unit()) * (h.norm() / v_infinity);
+        Ok(BPlane {
+            b_t: b.dot(t),
+            b_r: b.dot(r_hat),
+            v_infinity,
+            s,
+            t,
+            r: r_hat,
+        })
+    }
+
+    /// Impact parameter, |B|
+    pub fn magnitude(&self) -> Real {
+        sqrt(self.b_t * self.b_t + self.b_r * self.b_r)
+    }
+
+    /// Angle of B from T̂ toward R̂
+    pub fn angle(&self) -> Real {
+        atan2(self.b_r, self.b_t)
+    }
+
+    /// Closest approach if the body were a point
+    pub fn periapsis_radius(&self, mu: GravParam) -> Meters {
+        let scale = mu.value() / (self.v_infinity * self.v_infinity);
+        let b = self.magnitude() / scale;
+        Meters(scale * (sqrt(1.0 + b * b) - 1.0))
+    }
+}
+
+/// Impact parameter that crosses `radius` at `flight_path_angle`
+/// (negative when descending): b = r v cos γ / v∞
+pub fn impact_parameter(
+    v_infinity: Real,
+    radius: Meters,
+    flight_path_angle: Real,
+    mu: GravParam,
+) -> Real {
+    let r = radius.value();
+    let speed = sqrt(v_infinity * v_infinity + 2.0 * mu.value() / r);
+    r * speed * cos(flight_path_angle) / v_infinity
+}
+
+/// Flight-path angle on reaching `radius` inbound, or `None` if the
+/// approach passes above it
+pub fn entry_flight_path_angle(
+    impact_parameter: Real,
+    v_infinity: Real,
+    radius: Meters,
+    mu: GravParam,
+) -> Option<Real> {
+    let r = radius.value();
+    let speed = sqrt(v_infinity * v_infinity + 2.0 * mu.value() / r);
+    let cosine = impact_parameter * v_infinity / (r * speed);
+    (cosine <= 1.0).then(|| -acos(cosine))
+}
+
+/// The band of impact parameters that enter between a steep and a
+/// shallow flight-path angle
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EntryCorridor {
+    /// Impact parameter of the steepest entry
+    pub steep: Real,
+    /// Impact parameter of the shallowest entry
+    pub shallow: Real,
+}
+
+impl EntryCorridor {
+    /// Corridor at the entry interface `radius` for an approach at
+    /// `v_infinity`, between the flight-path angles `steep` and
+    /// `shallow`
+    pub fn new(
+        v_infinity: Real,
+        radius: Meters,
+        steep: Real,
+        shallow: Real,
+        mu: GravParam,
+    ) -> Self {
+        EntryCorridor {
+            steep: impact_parameter(v_infinity, radius, steep, mu),
+            shallow: impact_parameter(v_infinity, radius, shallow, mu),
+        }
+    }
+
+    /// Width of the corridor in the B-plane
+    pub fn width(&self) -> Real {
+        (self.shallow - self.steep).abs()
+    }
+
+    pub fn contains(&self, b_plane: &BPlane) -> bool {
+        let b = b_plane.magnitude();
+        b >= self.steep.min(self.shallow) && b <= self.steep.max(self.shallow)
+    }
+}
+
+/// Where to cross the entry interface
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EntryTarget {
+    pub radius: Meters,
+    pub flight_path_angle: Real,
+    /// B-plane angle to aim for; `None` keeps the current one
+    pub angle: Option<Real>,
+}
+
+/// A burn that puts an approach on an entry target
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EntryManeuver {
+    pub delta_v: Vec3,
+    /// The B-plane after the burn
+    pub b_plane: BPlane,
+}
+
+/// Burn at `state` that moves B onto `target`, found by Newton
+/// iteration on B·T and B·R with the burn kept in the initial B-plane
+pub fn target_entry(
+    state: &StateVector,
+    target: EntryTarget,
+    mu: GravParam,
+) -> Result<EntryManeuver, AlmagestError> {
+    const STEP: Real = 1e-3;
+    let initial = BPlane::from_state(state, mu)?;
+    let angle = target.angle.unwrap_or_else(|| initial.angle());
+    let (t_dir, r_dir) = (initial.t, initial.r);
+    let miss = |dv: Vec3| -> Result<(Real, Real, BPlane), AlmagestError> {
+        let plane = BPlane::from_state(&StateVector::new(state.position, state.velocity + dv), mu)?;
+        let b = impact_parameter(
+            plane.v_infinity,
+            target.radius,
+            target.flight_path_angle,
+            mu,
+        );
+        Ok((
+            plane.b_t - b * cos(angle),
+            plane.b_r - b * sin(angle),
+            plane,
+        ))
+    };
+
+    let mut dv = Vec3::new(0.0, 0.0, 0.0);
+    for _ in 0..20 {
+        let (f_t, f_r, plane) = miss(dv)?;
+        if sqrt(f_t * f_t + f_r * f_r) < 1e-3 {
+            return Ok(EntryManeuver {
+                delta_v: dv,
+                b_plane: plane,
+            });
+        }
+        let (t1, r1, _) = miss(dv + t_dir * STEP)?;
+        let (t2, r2, _) = miss(dv + r_dir * STEP)?;
+        let (a, b) = ((t1 - f_t) / STEP, (t2 - f_t) / STEP);
+        let (c, d) = ((r1 - f_r) / STEP, (r2 - f_r) / STEP);
+        let det = a * d - b * c;
+        if det == 0.0 {
+            return Err(AlmagestError::NoConvergence(
+                "B-plane targeting is singular",
+            ));
+        }
+        dv -= t_dir * ((d * f_t - b * f_r) / det) + r_dir * ((a * f_r - c * f_t) / det);
+    }
+    Err(AlmagestError::NoConvergence(
+        "B-plane targeting did not converge",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use approx::assert_relative_eq;
+
+    // Approach from far out along +x with a sideways offset in y and z
+    fn approach(v_infinity: Real, mu: GravParam) -> StateVector {
+        let position = Vec3::new(5.0e8, 2.0e6, 1.0e6);
+        let speed = sqrt(v_infinity * v_infinity + 2.0 * mu.value() / position.norm());
+        StateVector::new(position, Vec3::new(-speed, 0.0, 0.0))
+    }
+
+    #[test]
+    fn b_plane_geometry() {
+        let state = approach(3_000.0, MU_MARS);
+        let plane = BPlane::from_state(&state, MU_MARS).unwrap();
+        let h = state.position.cross(state.velocity).norm();
+        assert_relative_eq!(
+            plane.magnitude(),
+            h / plane.v_infinity,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(plane.v_infinity, 3_000.0, max_relative = 1e-9);
+        // Far out, the asymptote is nearly the velocity
+        assert!(plane.s.dot(Vec3::new(-1.0, 0.0, 0.0)) > 0.99);
+        assert_relative_eq!(plane.s.dot(plane.t), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(plane.t.z, 0.0, epsilon = 1e-12);
+
+        let mu = MU_MARS.value();
+        let e_vec = (state.position * (state.velocity.norm_squared() - mu / state.position.norm())
+            - state.velocity * state.position.dot(state.velocity))
+            / mu;
+        let periapsis = h * h / mu / (1.0 + e_vec.norm());
+        assert_relative_eq!(
+            plane.periapsis_radius(MU_MARS).value(),
+            periapsis,
+            max_relative = 1e-9
+        );
+        assert!(
+            BPlane::from_state(
+                &StateVector::new(Vec3::X * 7.0e6, Vec3::Y * 7_000.0),
+                EARTH.mu
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn corridor_round_trip() {
+        let radius = Meters(3_522_200.0);
+        let corridor = EntryCorridor::new(
+            5_500.0,
+            radius,
+            -14.0_f64.to_radians(),
+            -10.0_f64.to_radians(),
+            MU_MARS,
+        );
+        assert!(corridor.steep < corridor.shallow);
+        assert!(corridor.width() > 0.0);
+        let gamma = entry_flight_path_angle(corridor.steep, 5_500.0, radius, MU_MARS).unwrap();
+        assert_relative_eq!(gamma.to_degrees(), -14.0, epsilon = 1e-9);
+        assert!(entry_flight_path_angle(1.0e8, 5_500.0, radius, MU_MARS).is_none());
+    }
+
+    #[test]
+    fn targets_mars_entry() {
+        let state = approach(3_000.0, MU_MARS);
+        let before = BPlane::from_state(&state, MU_MARS).unwrap();
+        let target = EntryTarget {
+            radius: Meters(3_522_200.0),
+            flight_path_angle: -12.0_f64.to_radians(),
+            angle: None,
+        };
+        let maneuver = target_entry(&state, target, MU_MARS).unwrap();
+        let after = maneuver.b_plane;
+        let gamma =
+            entry_flight_path_angle(after.magnitude(), after.v_infinity, target.radius, MU_MARS)
+                .unwrap();
+        assert_relative_eq!(gamma.to_degrees(), -12.0, epsilon = 1e-6);
+        assert_relative_eq!(after.angle(), before.angle(), epsilon = 1e-6);
+        // About the change in B over the time to arrival
+        let expected = (after.magnitude() - before.magnitude()) * 3_000.0 / 5.0e8;
+        assert_relative_eq!(maneuver.delta_v.norm(), expected, max_relative = 0.1);
+        assert!(maneuver.delta_v.dot(before.s).abs() < 1e-9);
+    }
+
+    #[test]
+    fn targets_earth_entry_at_an_angle() {
+        let state = approach(4_000.0, EARTH.mu);
+        let target = EntryTarget {
+            radius: Meters(6_500_000.0),
+            flight_path_angle: -6.0_f64.to_radians(),
+            angle: Some(1.0),
+        };
+        let maneuver = target_entry(&state, target, EARTH.mu).unwrap();
+        assert_relative_eq!(maneuver.b_plane.angle(), 1.0, epsilon = 1e-6);
+        let corridor = EntryCorridor::new(
+            maneuver.b_plane.v_infinity,
+            target.radius,
+            -7.0_f64.to_radians(),
+            -5.0_f64.to_radians(),
+            EARTH.mu,
+        );
+        assert!(corridor.contains(&maneuver.b_plane));
+    }
+}
@@ -0,0 +1,434 @@
+//! Tabulated ephemerides and resampling onto a common epoch grid.
+//!
+//! Ephemerides from different sources rarely share epochs. Each is
+//! interpolated with cubic Hermite polynomials through the tabulated
+//! positions and velocities, which keeps the velocity consistent with
+//! the position, and [`align`] samples any number of them on one grid.
+//!
+//! Every ephemeris carries the NAIF ID of its frame, as SPK segments
+//! do, and only ephemerides in the same frame can be aligned. States
+//! in a rotating frame are brought into another with [`Reframed`],
+//! which applies a time-dependent transformation, velocity terms and
+//! all, to each interpolated state. An orbit with no table behind it
+//! is an ephemeris too, through [`TwoBody`].
+
+use crate::error::AlmagestError;
+use crate::kepler::KeplerianElements;
+use crate::state::StateVector;
+use crate::utils::{GravParam, Real, Seconds};
+
+// Slack allowed at the ends of a table for rounding in grid epochs
+const EPOCH_TOLERANCE: Seconds = Seconds(1e-9);
+
+/// A source of states over a span of epochs
+pub trait Ephemeris {
+    /// NAIF ID of the frame the states are in
+    fn frame(&self) -> i32;
+
+    /// First and last epochs covered
+    fn span(&self) -> (Seconds, Seconds);
+
+    /// State at `epoch`, or `None` outside the span
+    fn state(&self, epoch: Seconds) -> Option<StateVector>;
+}
+
+impl<E: Ephemeris> Ephemeris for &E {
+    fn frame(&self) -> i32 {
+        (*self).frame()
+    }
+
+    fn span(&self) -> (Seconds, Seconds) {
+        (*self).span()
+    }
+
+    fn state(&self, epoch: Seconds) -> Option<StateVector> {
+        (*self).state(epoch)
+    }
+}
+
+/// States at increasing epochs, borrowed from the caller
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Tabulated<'a> {
+    epochs: &'a [Seconds],
+    states: &'a [StateVector],
+    frame: i32,
+}
+
+impl<'a> Tabulated<'a> {
+    /// A table of at least two states, with strictly increasing epochs
+    pub fn new(
+        epochs: &'a [Seconds],
+        states: &'a [StateVector],
+        frame: i32,
+    ) -> Result<Self, AlmagestError> {
+        if epochs.len() != states.len() {
+            return Err(AlmagestError::InvalidInput(
+                "Epochs and states differ in number",
+            ));
+        }
+        if epochs.len() < 2 {
+            return Err(AlmagestError::InvalidInput(
+                "At least two states are needed to interpolate",
+            ));
+        }
+        if epochs.windows(2).any(|pair| pair[1] <= pair[0]) {
+            return Err(AlmagestError::InvalidInput(
+                "Epochs must be strictly increasing",
+            ));
+        }
+        Ok(Tabulated {
+            epochs,
+            states,
+            frame,
+        })
+    }
+}
+
+impl Ephemeris for Tabulated<'_> {
+    fn frame(&self) -> i32 {
+        self.frame
+    }
+
+    fn span(&self) -> (Seconds, Seconds) {
+        (self.epochs[0], self.epochs[self.epochs.len() - 1])
+    }
+
+    fn state(&self, epoch: Seconds) -> Option<StateVector> {
+        let (first, last) = self.span();
+        if epoch < first - EPOCH_TOLERANCE || epoch > last + EPOCH_TOLERANCE {
+            return None;
+        }
+        let i = self
+            .epochs
+            .partition_point(|&t| t <= epoch)
+            .clamp(1, self.epochs.len() - 1);
+        let (t0, t1) = (self.epochs[i - 1], self.epochs[i]);
+        let (a, b) = (self.states[i - 1], self.states[i]);
+        let h = (t1 - t0).value();
+        let s = ((epoch - t0).value() / h).clamp(0.0, 1.0);
+        let (s2, s3) = (s * s, s * s * s);
+        // Hermite basis and its derivative with respect to s
+        let (h00, h10, h01, h11) = (
+            2.0 * s3 - 3.0 * s2 + 1.0,
+            s3 - 2.0 * s2 + s,
+            3.0 * s2 - 2.0 * s3,
+            s3 - s2,
+        );
+        let (d00, d10, d01, d11) = (
+            6.0 * s2 - 6.0 * s,
+            3.0 * s2 - 4.0 * s + 1.0,
+            6.0 * s - 6.0 * s2,
+            3.0 * s2 - 2.0 * s,
+        );
+        Some(StateVector::new(
+            a.position * h00 + a.velocity * (h10 * h) + b.position * h01 + b.velocity * (h11 * h),
+            (a.position * d00 + b.position * d01) / h + a.velocity * d10 + b.velocity * d11,
+        ))
+    }
+}
+
+/// Unperturbed motion on a closed orbit, covering all time
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoBody {
+    pub elements: KeplerianElements,
+    pub mu: GravParam,
+    /// Epoch of the elements
+    pub epoch: Seconds,
+    pub frame: i32,
+}
+
+impl Ephemeris for TwoBody {
+    fn frame(&self) -> i32 {
+        self.frame
+    }
+
+    fn span(&self) -> (Seconds, Seconds) {
+        (Seconds(Real::NEG_INFINITY), Seconds(Real::INFINITY))
+    }
+
+    fn state(&self, epoch: Seconds) -> Option<StateVector> {
+        let elements = self.elements.propagate(epoch - self.epoch, self.mu);
+        elements.ok().map(|e| e.to_state(self.mu))
+    }
+}
+
+/// An ephemeris carried into another frame by a transformation of
+/// epoch and state, such as body-fixed to inertial
+#[derive(Copy, Clone, Debug)]
+pub struct Reframed<E, F> {
+    pub ephemeris: E,
+    /// NAIF ID of the frame the transformation leads to
+    pub frame: i32,
+    pub transform: F,
+}
+
+impl<E: Ephemeris, F: Fn(Seconds, &StateVector) -> StateVector> Ephemeris for Reframed<E, F> {
+    fn frame(&self) -> i32 {
+        self.frame
+    }
+
+    fn span(&self) -> (Seconds, Seconds) {
+        self.ephemeris.span()
+    }
+
+    fn state(&self, epoch: Seconds) -> Option<StateVector> {
+        self.ephemeris
+            .state(epoch)
+            .map(|state| (self.transform)(epoch, &state))
+    }
+}
+
+/// Evenly spaced epochs
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EpochGrid {
+    pub start: Seconds,
+    pub step: Seconds,
+    pub count: usize,
+}
+
+impl EpochGrid {
+    /// Epochs from `start` every `step`, up to `end`
+    pub fn new(start: Seconds, end: Seconds, step: Seconds) -> Result<Self, AlmagestError> {
+        if !start.value().is_finite() || !end.value().is_finite() || !step.value().is_finite() {
+            return Err(AlmagestError::InvalidInput(
+                "Grid bounds and step must be finite",
+            ));
+        }
+        if step <= Seconds::ZERO {
+            return Err(AlmagestError::InvalidInput("Step must be positive"));
+        }
+        if end < start {
+            return Err(AlmagestError::InvalidInput("Grid ends before it starts"));
+        }
+        let count = (((end - start) / step + EPOCH_TOLERANCE.value()) as usize)
+            .checked_add(1)
+            .ok_or(AlmagestError::InvalidInput("Grid has too many epochs"))?;
+        Ok(EpochGrid { start, step, count })
+    }
+
+    /// The grid over the span every ephemeris covers, every `step`;
+    /// an error if none of them bounds it
+    pub fn covering(ephemerides: &[&dyn Ephemeris], step: Seconds) -> Result<Self, AlmagestError> {
+        let (start, end) = ephemerides
+            .iter()
+            .map(|e| e.span())
+            .reduce(|(a0, a1), (b0, b1)| (a0.max(b0), a1.min(b1)))
+            .ok_or(AlmagestError::InvalidInput("No ephemerides to cover"))?;
+        if end < start {
+            return Err(AlmagestError::EpochOutOfRange(
+                "Ephemerides have no epochs in common",
+            ));
+        }
+        EpochGrid::new(start, end, step)
+    }
+
+    pub fn epoch(&self, index: usize) -> Seconds {
+        self.start + self.step * index as Real
+    }
+
+    pub fn end(&self) -> Seconds {
+        self.epoch(self.count - 1)
+    }
+
+    pub fn epochs(&self) -> impl Iterator<Item = Seconds> + '_ {
+        (0..self.count).map(|i| self.epoch(i))
+    }
+}
+
+/// Iterator over epochs of a grid with the state of every ephemeris
+/// there, created by [`align`]
+#[derive(Copy, Clone)]
+pub struct Aligned<'a, const N: usize> {
+    ephemerides: [&'a dyn Ephemeris; N],
+    grid: EpochGrid,
+    index: usize,
+}
+
+/// Sample ephemerides on a common grid. They must share a frame, and
+/// each must cover the whole grid.
+pub fn align<'a, const N: usize>(
+    ephemerides: [&'a dyn Ephemeris; N],
+    grid: EpochGrid,
+) -> Result<Aligned<'a, N>, AlmagestError> {
+    if ephemerides
+        .windows(2)
+        .any(|pair| pair[0].frame() != pair[1].frame())
+    {
+        return Err(AlmagestError::InvalidInput(
+            "Ephemerides are in different frames",
+        ));
+    }
+    let covered = |e: &&dyn Ephemeris| {
+        let (first, last) = e.span();
+        grid.start >= first - EPOCH_TOLERANCE && grid.end() <= last + EPOCH_TOLERANCE
+    };
+    if grid.count > 0 && !ephemerides.iter().all(covered) {
+        return Err(AlmagestError::EpochOutOfRange(
+            "Grid runs outside an ephemeris",
+        ));
+    }
+    Ok(Aligned {
+        ephemerides,
+        grid,
+        index: 0,
+    })
+}
+
+impl<const N: usize> Iterator for Aligned<'_, N> {
+    type Item = (Seconds, [StateVector; N]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.grid.count {
+            return None;
+        }
+        let epoch = self.grid.epoch(self.index);
+        self.index += 1;
+        let mut states = [StateVector::ZERO; N];
+        for (state, ephemeris) in states.iter_mut().zip(self.ephemerides) {
+            *state = ephemeris.state(epoch)?;
+        }
+        Some((epoch, states))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::groundtrack::{EARTH_ROTATION_RATE, body_fixed};
+    use crate::kepler::KeplerianElements;
+    use crate::utils::Seconds;
+    use crate::vectors::Vec3;
+    use approx::assert_relative_eq;
+    use std::vec::Vec;
+
+    const J2000: i32 = 1;
+    const ITRF93: i32 = 13_000;
+
+    fn orbit(time: Seconds) -> StateVector {
+        let state = StateVector::new(
+            Vec3::new(7_000_000.0, 0.0, 0.0),
+            Vec3::new(0.0, 6_000.0, 4_000.0),
+        );
+        KeplerianElements::from_state(&state, EARTH.mu)
+            .unwrap()
+            .propagate(time, EARTH.mu)
+            .unwrap()
+            .to_state(EARTH.mu)
+    }
+
+    fn table(start: Real, step: Real, count: usize) -> (Vec<Seconds>, Vec<StateVector>) {
+        let epochs: Vec<Seconds> = (0..count)
+            .map(|i| Seconds(start + i as Real * step))
+            .collect();
+        let states = epochs.iter().map(|&t| orbit(t)).collect();
+        (epochs, states)
+    }
+
+    #[test]
+    fn interpolates_between_samples() {
+        let (epochs, states) = table(0.0, 60.0, 50);
+        let ephemeris = Tabulated::new(&epochs, &states, J2000).unwrap();
+        for t in [0.0, 17.0, 1_234.5, 2_940.0].map(Seconds) {
+            let state = ephemeris.state(t).unwrap();
+            let truth = orbit(t);
+            assert_relative_eq!((state.position - truth.position).norm(), 0.0, epsilon = 1.0);
+            assert_relative_eq!(
+                (state.velocity - truth.velocity).norm(),
+                0.0,
+                epsilon = 0.05
+            );
+        }
+        assert_eq!(ephemeris.state(Seconds(-1.0)), None);
+        assert_eq!(ephemeris.state(Seconds(3_000.0)), None);
+
+        assert!(Tabulated::new(&epochs[..1], &states[..1], J2000).is_err());
+        assert!(Tabulated::new(&epochs, &states[1..], J2000).is_err());
+        let backwards = [0.0, 10.0, 5.0].map(Seconds);
+        assert!(Tabulated::new(&backwards, &states[..3], J2000).is_err());
+    }
+
+    #[test]
+    fn aligns_different_grids() {
+        let (epochs_a, states_a) = table(0.0, 60.0, 60);
+        let (epochs_b, states_b) = table(125.0, 45.0, 60);
+        let a = Tabulated::new(&epochs_a, &states_a, J2000).unwrap();
+        let b = Tabulated::new(&epochs_b, &states_b, J2000).unwrap();
+        let grid = EpochGrid::covering(&[&a, &b], Seconds(10.0)).unwrap();
+        assert_relative_eq!(grid.start.value(), 125.0);
+        assert!(grid.end() <= Seconds(2_780.0));
+        let mut count = 0;
+        for (_, [sa, sb]) in align([&a, &b], grid).unwrap() {
+            assert_relative_eq!((sa.position - sb.position).norm(), 0.0, epsilon = 2.0);
+            count += 1;
+        }
+        assert_eq!(count, grid.count);
+
+        let long = EpochGrid::new(Seconds::ZERO, Seconds(4_000.0), Seconds(10.0)).unwrap();
+        assert!(align([&a, &b], long).is_err());
+    }
+
+    #[test]
+    fn grids_must_be_bounded() {
+        let two_body = TwoBody {
+            elements: KeplerianElements::from_state(&orbit(Seconds::ZERO), EARTH.mu).unwrap(),
+            mu: EARTH.mu,
+            epoch: Seconds::ZERO,
+            frame: J2000,
+        };
+        assert!(EpochGrid::covering(&[&two_body], Seconds(10.0)).is_err());
+        let (epochs, states) = table(0.0, 60.0, 10);
+        let table = Tabulated::new(&epochs, &states, J2000).unwrap();
+        let grid = EpochGrid::covering(&[&two_body, &table], Seconds(10.0)).unwrap();
+        assert_eq!(grid.count, 55);
+
+        let (zero, day) = (Seconds::ZERO, Seconds(86_400.0));
+        assert!(EpochGrid::new(zero, Seconds(Real::INFINITY), day).is_err());
+        assert!(EpochGrid::new(Seconds(Real::NAN), day, day).is_err());
+        assert!(EpochGrid::new(zero, day, Seconds(Real::NAN)).is_err());
+        assert!(EpochGrid::new(zero, Seconds(Real::MAX), Seconds(Real::MIN_POSITIVE)).is_err());
+    }
+
+    #[test]
+    fn frames_must_match() {
+        let gmst = |t: Seconds| 0.5 + EARTH_ROTATION_RATE * t.value();
+        let (epochs, inertial) = table(0.0, 30.0, 40);
+        let fixed: Vec<StateVector> = epochs
+            .iter()
+            .zip(&inertial)
+            .map(|(&t, s)| {
+                let velocity = s.velocity - (Vec3::Z * EARTH_ROTATION_RATE).cross(s.position);
+                StateVector::new(
+                    body_fixed(s.position, gmst(t)),
+                    body_fixed(velocity, gmst(t)),
+                )
+            })
+            .collect();
+        let a = Tabulated::new(&epochs, &inertial, J2000).unwrap();
+        let b = Tabulated::new(&epochs, &fixed, ITRF93).unwrap();
+        let grid = EpochGrid::covering(&[&a, &b], Seconds(7.0)).unwrap();
+        assert!(align([&a, &b], grid).is_err());
+
+        let b = Reframed {
+            ephemeris: b,
+            frame: J2000,
+            transform: |t: Seconds, s: &StateVector| {
+                let position = body_fixed(s.position, -gmst(t));
+                StateVector::new(
+                    position,
+                    body_fixed(s.velocity, -gmst(t))
+                        + (Vec3::Z * EARTH_ROTATION_RATE).cross(position),
+                )
+            },
+        };
+        // The tables agree to within their interpolation error
+        for (_, [sa, sb]) in align([&a, &b], grid).unwrap() {
+            assert_relative_eq!((sa.position - sb.position).norm(), 0.0, epsilon = 0.01);
+            assert_relative_eq!((sa.velocity - sb.velocity).norm(), 0.0, epsilon = 1e-3);
+        }
+    }
+}
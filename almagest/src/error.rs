@@ -0,0 +1,57 @@
+//! The error type for the crate's fallible constructors and solvers.
+//!
+//! Each variant names the kind of failure and carries a short
+//! description of the particular one, so callers can branch on the
+//! kind and still show the user what went wrong:
+//!
+//! ```
+//! use almagest::error::AlmagestError;
+//! use almagest::utils::Eccentricity;
+//!
+//! let err = Eccentricity::new(-0.1).unwrap_err();
+//! assert!(matches!(err, AlmagestError::InvalidEccentricity(_)));
+//! assert_eq!(err.message(), "Eccentricity cannot be negative");
+//! ```
+
+use core::fmt::{self, Display};
+
+/// Why a calculation could not produce a result
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AlmagestError {
+    /// An eccentricity outside the range the calculation covers, such
+    /// as a hyperbolic orbit given to Kepler's equation
+    InvalidEccentricity(&'static str),
+    /// An iterative solver ran out of iterations or hit a singular step
+    NoConvergence(&'static str),
+    /// Vectors that should span a plane don't: collinear positions, or
+    /// a state with no angular momentum
+    Coplanarity(&'static str),
+    /// An epoch outside the span of the data that should cover it
+    EpochOutOfRange(&'static str),
+    /// Malformed input data, such as a corrupt SPK kernel
+    Parse(&'static str),
+    /// Any other argument outside the domain of the calculation
+    InvalidInput(&'static str),
+}
+
+impl AlmagestError {
+    /// The description of the particular failure
+    pub fn message(&self) -> &'static str {
+        match *self {
+            AlmagestError::InvalidEccentricity(message)
+            | AlmagestError::NoConvergence(message)
+            | AlmagestError::Coplanarity(message)
+            | AlmagestError::EpochOutOfRange(message)
+            | AlmagestError::Parse(message)
+            | AlmagestError::InvalidInput(message) => message,
+        }
+    }
+}
+
+impl Display for AlmagestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl core::error::Error for AlmagestError {}
@@ -0,0 +1,90 @@
+//! Escape speed, characteristic energy and hyperbolic excess speed.
+//!
+//! C3 is twice the specific orbital energy, v² − 2μ/r: negative for a
+//! bound orbit, zero for a parabola and v∞² on an escape trajectory.
+//! Launch vehicle performance is quoted against it.
+
+use crate::error::AlmagestError;
+use crate::state::StateVector;
+use crate::utils::{GravParam, Meters, MetersPerSecond, MetersSquaredPerSecondSquared};
+
+/// Speed needed to escape from `radius`, √(2μ/r)
+pub fn escape_speed(radius: Meters, mu: GravParam) -> MetersPerSecond {
+    ((mu / radius) * 2.0).sqrt()
+}
+
+/// Characteristic energy C3 of a state
+pub fn characteristic_energy(state: &StateVector, mu: GravParam) -> MetersSquaredPerSecondSquared {
+    let v = state.speed();
+    v * v - (mu / state.radius()) * 2.0
+}
+
+/// Hyperbolic excess speed v∞ = √C3; an error for a bound orbit
+pub fn hyperbolic_excess_speed(
+    c3: MetersSquaredPerSecondSquared,
+) -> Result<MetersPerSecond, AlmagestError> {
+    if c3.value() < 0.0 {
+        return Err(AlmagestError::InvalidInput(
+            "Orbit with negative C3 does not escape",
+        ));
+    }
+    Ok(c3.sqrt())
+}
+
+/// Speed at `radius` on a trajectory with characteristic energy `c3`,
+/// such as the injection burn from a parking orbit
+pub fn speed_for_energy(
+    radius: Meters,
+    c3: MetersSquaredPerSecondSquared,
+    mu: GravParam,
+) -> Result<MetersPerSecond, AlmagestError> {
+    let v2 = c3 + (mu / radius) * 2.0;
+    if v2.value() < 0.0 {
+        return Err(AlmagestError::InvalidInput(
+            "Orbit with that C3 does not reach the radius",
+        ));
+    }
+    Ok(v2.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::groundtrack::EARTH_RADIUS;
+    use crate::vectors::Vec3;
+    use approx::assert_relative_eq;
+    use libm::sqrt;
+
+    #[test]
+    fn escape_from_the_surface() {
+        assert_relative_eq!(
+            escape_speed(EARTH_RADIUS, EARTH.mu).value(),
+            11_180.0,
+            epsilon = 10.0
+        );
+    }
+
+    #[test]
+    fn c3_and_excess_speed() {
+        let r = 6_678_137.0;
+        let circular = sqrt(EARTH.mu.value() / r);
+        let parking = StateVector::new(Vec3::new(r, 0.0, 0.0), Vec3::new(0.0, circular, 0.0));
+        let c3 = characteristic_energy(&parking, EARTH.mu);
+        assert_relative_eq!(c3.value(), -EARTH.mu.value() / r, max_relative = 1e-12);
+        assert!(hyperbolic_excess_speed(c3).is_err());
+
+        // A Mars-bound C3 of 10 km²/s² from the same radius
+        let c3 = MetersSquaredPerSecondSquared(1.0e7);
+        let injection = speed_for_energy(Meters(r), c3, EARTH.mu).unwrap();
+        assert_relative_eq!(injection.value(), 11_374.3, epsilon = 0.1);
+        let departing = StateVector::new(parking.position, Vec3::new(0.0, injection.value(), 0.0));
+        let found = characteristic_energy(&departing, EARTH.mu);
+        assert_relative_eq!(found.value(), 1.0e7, max_relative = 1e-9);
+        let v_infinity = hyperbolic_excess_speed(found).unwrap();
+        assert_relative_eq!(v_infinity.value(), 3_162.3, epsilon = 0.1);
+        assert!(
+            speed_for_energy(Meters(1e9), MetersSquaredPerSecondSquared(-1e6), EARTH.mu).is_err()
+        );
+    }
+}
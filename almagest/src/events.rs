@@ -0,0 +1,533 @@
+//! Events found while integrating a force model.
+//!
+//! An event is a sign change in an [`EventFunction`] of time and
+//! state: radial velocity for the apsides, height above the equator
+//! for the nodes, and so on. [`detect_events`] steps the RK4
+//! integrator of [`crate::forces`] and, wherever a function changes
+//! sign over a step, bisects on the step length to the crossing time.
+//! Two crossings of the same function within one step cancel and are
+//! missed, so the step should be short against the events' spacing.
+
+use libm::asin;
+
+use crate::bodies::RotatingBody;
+use crate::error::AlmagestError;
+use crate::forces::{ForceModel, rk4_step};
+use crate::groundtrack::{GroundPoint, Region, body_fixed};
+use crate::state::StateVector;
+use crate::sun::sun_position;
+use crate::utils::{Meters, Real, Seconds};
+use crate::vectors::Vec3;
+
+/// A quantity whose zero crossings are events
+pub trait EventFunction {
+    /// Value at `time` and `state`
+    fn value(&self, time: Seconds, state: &StateVector) -> Real;
+}
+
+/// Direction of a crossing as time runs forward
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Crossing {
+    /// From negative to non-negative
+    Rising,
+    /// From non-negative to negative
+    Falling,
+}
+
+/// Periapsis (rising) and apoapsis (falling) passages, where the
+/// radial velocity r·v changes sign
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Apsides;
+
+impl EventFunction for Apsides {
+    fn value(&self, _time: Seconds, state: &StateVector) -> Real {
+        state.position.dot(state.velocity)
+    }
+}
+
+/// Ascending (rising) and descending (falling) node crossings of the
+/// frame's xy plane
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Nodes;
+
+impl EventFunction for Nodes {
+    fn value(&self, _time: Seconds, state: &StateVector) -> Real {
+        state.position.z
+    }
+}
+
+/// Passing a radius: falling on the way below it, rising on the way
+/// back up
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Altitude {
+    pub radius: Meters,
+}
+
+impl EventFunction for Altitude {
+    fn value(&self, _time: Seconds, state: &StateVector) -> Real {
+        state.position.norm() - self.radius.value()
+    }
+}
+
+/// Entry into (falling) and exit from (rising) the cylindrical shadow
+/// of the Earth, with the Sun where [`sun_position`] puts it
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shadow {
+    /// Julian date at time zero
+    pub epoch: Real,
+    pub body_radius: Meters,
+}
+
+impl EventFunction for Shadow {
+    fn value(&self, time: Seconds, state: &StateVector) -> Real {
+        let sun = sun_position(self.epoch + time.to_days().value()).unit();
+        let r = state.position;
+        let along = r.dot(sun);
+        // Distance from the shadow axis behind the body; on the sunlit
+        // side the distance from its center, which agrees at the
+        // terminator
+        let distance = if along < 0.0 {
+            (r - sun * along).norm()
+        } else {
+            r.norm()
+        };
+        distance - self.body_radius.value()
+    }
+}
+
+/// A satellite rising above (rising) and setting below (falling) an
+/// elevation mask at a site on a spherical rotating body
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Elevation {
+    pub site: GroundPoint,
+    pub body: RotatingBody,
+    /// Lowest elevation that counts as in view, in radians
+    pub mask: Real,
+}
+
+impl Elevation {
+    /// A site on the Earth
+    pub fn earth(site: GroundPoint, mask: Real, greenwich_angle: Real) -> Self {
+        Elevation {
+            site,
+            body: RotatingBody::earth(greenwich_angle),
+            mask,
+        }
+    }
+
+    /// Elevation of `position` at `time`, in radians
+    pub fn elevation(&self, time: Seconds, position: Vec3) -> Real {
+        let zenith = body_fixed(self.site.unit(), -self.body.angle(time));
+        let range = position - zenith * self.body.equatorial_radius.value();
+        asin((range.dot(zenith) / range.norm()).clamp(-1.0, 1.0))
+    }
+}
+
+impl EventFunction for Elevation {
+    fn value(&self, time: Seconds, state: &StateVector) -> Real {
+        self.elevation(time, state.position) - self.mask
+    }
+}
+
+/// The sub-satellite point entering (rising) and leaving (falling) a
+/// region on a rotating ellipsoid
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RegionBoundary<'a> {
+    pub region: Region<'a>,
+    pub body: RotatingBody,
+}
+
+impl<'a> RegionBoundary<'a> {
+    /// A region on the Earth
+    pub fn earth(region: Region<'a>, greenwich_angle: Real) -> Self {
+        RegionBoundary {
+            region,
+            body: RotatingBody::earth(greenwich_angle),
+        }
+    }
+}
+
+impl EventFunction for RegionBoundary<'_> {
+    fn value(&self, time: Seconds, state: &StateVector) -> Real {
+        if self.region.overflown_by(&self.body, time, state.position) {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+/// Any closure of time and state as an event function
+#[derive(Copy, Clone, Debug)]
+pub struct Switching<F>(pub F);
+
+impl<F: Fn(Seconds, &StateVector) -> Real> EventFunction for Switching<F> {
+    fn value(&self, time: Seconds, state: &StateVector) -> Real {
+        (self.0)(time, state)
+    }
+}
+
+/// A crossing found by [`detect_events`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Event {
+    /// Position of the function in the slice given to [`detect_events`]
+    pub index: usize,
+    /// Just past the crossing, within the search's tolerance
+    pub time: Seconds,
+    pub state: StateVector,
+    pub crossing: Crossing,
+}
+
+/// Iterator over the events met while integrating, created by
+/// [`detect_events`]
+#[derive(Clone)]
+pub struct Events<'a, M> {
+    model: &'a M,
+    functions: &'a [&'a dyn EventFunction],
+    time: Real,
+    state: StateVector,
+    end: Real,
+    step: Real,
+    tolerance: Real,
+}
+
+/// Integrate `model` from `state` at `start` to `end` in RK4 steps of
+/// at most `step`, as [`crate::forces::propagate`] does,
+/// yielding each sign change of the `functions` in time order. Crossing
+/// times are refined to a microsecond.
+pub fn detect_events<'a, M: ForceModel>(
+    model: &'a M,
+    state: &StateVector,
+    start: Seconds,
+    end: Seconds,
+    step: Seconds,
+    functions: &'a [&'a dyn EventFunction],
+) -> Result<Events<'a, M>, AlmagestError> {
+    if step <= Seconds::ZERO {
+        return Err(AlmagestError::InvalidInput("Step must be positive"));
+    }
+    Ok(Events {
+        model,
+        functions,
+        time: start.value(),
+        state: *state,
+        end: end.value(),
+        step: step.value(),
+        tolerance: 1e-6,
+    })
+}
+
+impl<'a, M: ForceModel> Events<'a, M> {
+    /// Refine crossing times to `tolerance` instead, which must be
+    /// positive and finite
+    pub fn with_tolerance(self, tolerance: Seconds) -> Result<Self, AlmagestError> {
+        let seconds = tolerance.value();
+        if !(seconds > 0.0 && seconds.is_finite()) {
+            return Err(AlmagestError::InvalidInput(
+                "Tolerance must be positive and finite",
+            ));
+        }
+        Ok(Events {
+            tolerance: seconds,
+            ..self
+        })
+    }
+
+    /// Time and state the integration has reached: the last event, or
+    /// `end` once the iterator is exhausted
+    pub fn current(&self) -> (Seconds, StateVector) {
+        (Seconds(self.time), self.state)
+    }
+
+    // Bisect on the step length for the crossing of `function` in
+    // (0, h], whose sign at the step's start is `before`
+    fn refine(&self, function: &dyn EventFunction, before: bool, h: Real) -> (Real, StateVector) {
+        let at =
+            |offset: Real| rk4_step(self.model, Seconds(self.time), &self.state, Seconds(offset));
+        let (mut a, mut b) = (0.0, h);
+        let mut state = at(h);
+        while (b - a).abs() > self.tolerance {
+            let mid = (a + b) / 2.0;
+            let trial = at(mid);
+            if (function.value(Seconds(self.time + mid), &trial) >= 0.0) == before {
+                a = mid;
+            } else {
+                b = mid;
+                state = trial;
+            }
+        }
+        (b, state)
+    }
+}
+
+impl<M: ForceModel> Iterator for Events<'_, M> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let direction = if self.end >= self.time { 1.0 } else { -1.0 };
+        while (self.end - self.time) * direction > 0.0 {
+            let h = ((self.end - self.time) * direction).min(self.step) * direction;
+            let next = rk4_step(self.model, Seconds(self.time), &self.state, Seconds(h));
+            // The earliest crossing over the step; each later function
+            // only searches up to the earliest found so far
+            let mut earliest: Option<(Real, StateVector, usize, Crossing)> = None;
+            for (index, function) in self.functions.iter().enumerate() {
+                let before = function.value(Seconds(self.time), &self.state) >= 0.0;
+                let (limit, limit_state) = earliest.map_or((h, next), |(b, s, _, _)| (b, s));
+                let after = function.value(Seconds(self.time + limit), &limit_state) >= 0.0;
+                if before != after {
+                    let (offset, state) = self.refine(*function, before, limit);
+                    let crossing = if after {
+                        Crossing::Rising
+                    } else {
+                        Crossing::Falling
+                    };
+                    earliest = Some((offset, state, index, crossing));
+                }
+            }
+            if let Some((offset, state, index, crossing)) = earliest {
+                self.time += offset;
+                self.state = state;
+                return Some(Event {
+                    index,
+                    time: Seconds(self.time),
+                    state,
+                    crossing,
+                });
+            }
+            self.time += h;
+            self.state = next;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::forces::{TwoBody, propagate};
+    use crate::groundtrack::{EARTH_RADIUS, RegionSearch};
+    use crate::kepler::KeplerianElements;
+    use crate::utils::{Eccentricity, PI, Radians};
+    use approx::assert_relative_eq;
+
+    fn orbit(eccentricity: Real, inclination: Real) -> KeplerianElements {
+        KeplerianElements {
+            semi_major_axis: Meters(8_000_000.0),
+            eccentricity: Eccentricity::new(eccentricity).unwrap(),
+            inclination: Radians(inclination),
+            raan: Radians(0.4),
+            argument_of_periapsis: Radians(1.0),
+            true_anomaly: Radians(0.5),
+        }
+    }
+
+    #[test]
+    fn apsides_and_nodes() {
+        let elements = orbit(0.1, 0.9);
+        let state = elements.to_state(EARTH.mu);
+        let period = elements.period(EARTH.mu);
+        let model = TwoBody { mu: EARTH.mu };
+        let functions: [&dyn EventFunction; 2] = [&Apsides, &Nodes];
+        let mut events = detect_events(
+            &model,
+            &state,
+            Seconds::ZERO,
+            period * 2.0,
+            Seconds(30.0),
+            &functions,
+        )
+        .unwrap();
+
+        let (mut periapses, mut found) = ([Seconds::ZERO; 2], 0);
+        let mut count = 0;
+        for event in events.by_ref() {
+            let r = event.state.position.norm();
+            match (event.index, event.crossing) {
+                (0, Crossing::Rising) => {
+                    assert_relative_eq!(r, 7_200_000.0, max_relative = 1e-6);
+                    periapses[found] = event.time;
+                    found += 1;
+                }
+                (0, Crossing::Falling) => assert_relative_eq!(r, 8_800_000.0, max_relative = 1e-6),
+                (1, crossing) => {
+                    assert_relative_eq!(event.state.position.z, 0.0, epsilon = 0.05);
+                    assert_eq!(event.state.velocity.z > 0.0, crossing == Crossing::Rising);
+                }
+                _ => unreachable!(),
+            }
+            count += 1;
+        }
+        // Two of each per revolution
+        assert_eq!(count, 8);
+        let apart = periapses[1] - periapses[0];
+        assert_relative_eq!(apart.value(), period.value(), epsilon = 1e-2);
+        assert_eq!(events.current().0, period * 2.0);
+        assert!(
+            detect_events(
+                &model,
+                &state,
+                Seconds::ZERO,
+                Seconds(1.0),
+                Seconds::ZERO,
+                &functions
+            )
+            .is_err()
+        );
+
+        // A tolerance that bisection can never reach is refused
+        let short = detect_events(
+            &model,
+            &state,
+            Seconds::ZERO,
+            Seconds(1.0),
+            Seconds(1.0),
+            &functions,
+        )
+        .unwrap();
+        assert!(short.clone().with_tolerance(Seconds::ZERO).is_err());
+        assert!(short.clone().with_tolerance(Seconds(Real::NAN)).is_err());
+        assert!(short.with_tolerance(Seconds(1e-3)).is_ok());
+    }
+
+    #[test]
+    fn altitude_and_shadow() {
+        let elements = orbit(0.1, 0.9);
+        let state = elements.to_state(EARTH.mu);
+        let period = elements.period(EARTH.mu);
+        let model = TwoBody { mu: EARTH.mu };
+        let low = Altitude {
+            radius: Meters(7_500_000.0),
+        };
+        let shadow = Shadow {
+            epoch: 2_460_000.5,
+            body_radius: EARTH_RADIUS,
+        };
+        let functions: [&dyn EventFunction; 2] = [&low, &shadow];
+        let events = detect_events(
+            &model,
+            &state,
+            Seconds::ZERO,
+            period,
+            Seconds(30.0),
+            &functions,
+        )
+        .unwrap();
+        let (mut below, mut dark) = (0, 0);
+        let mut entered = None;
+        for event in events {
+            match event.index {
+                0 => {
+                    assert_relative_eq!(event.state.position.norm(), 7_500_000.0, epsilon = 1e-2);
+                    below += 1;
+                }
+                _ => {
+                    let sun = sun_position(2_460_000.5 + event.time.to_days().value()).unit();
+                    let r = event.state.position;
+                    assert!(r.dot(sun) < 0.0);
+                    assert_relative_eq!(
+                        (r - sun * r.dot(sun)).norm(),
+                        EARTH_RADIUS.value(),
+                        epsilon = 0.05
+                    );
+                    if event.crossing == Crossing::Falling {
+                        entered = Some(event.time);
+                    } else if let Some(start) = entered {
+                        // A few tenths of an orbit at most
+                        assert!(event.time - start < period * 0.5);
+                    }
+                    dark += 1;
+                }
+            }
+        }
+        assert_eq!(below, 2);
+        assert!(dark > 0);
+    }
+
+    #[test]
+    fn rises_above_the_mask() {
+        // An equatorial orbit passing over a site on the equator
+        let elements = KeplerianElements {
+            raan: Radians(0.0),
+            argument_of_periapsis: Radians(0.0),
+            true_anomaly: Radians(0.0),
+            ..orbit(0.0, 0.0)
+        };
+        let state = elements.to_state(EARTH.mu);
+        let mask = 10.0_f64.to_radians();
+        let site = Elevation::earth(GroundPoint::new(0.0, PI / 2.0), mask, 0.0);
+        let model = TwoBody { mu: EARTH.mu };
+        let functions: [&dyn EventFunction; 1] = [&site];
+        let mut events = detect_events(
+            &model,
+            &state,
+            Seconds::ZERO,
+            Seconds(10_000.0),
+            Seconds(60.0),
+            &functions,
+        )
+        .unwrap();
+        let rise = events.next().unwrap();
+        let set = events.next().unwrap();
+        assert_eq!(rise.crossing, Crossing::Rising);
+        assert_eq!(set.crossing, Crossing::Falling);
+        for event in [rise, set] {
+            assert_relative_eq!(
+                site.elevation(event.time, event.state.position),
+                mask,
+                epsilon = 1e-8
+            );
+        }
+        // Nearly overhead halfway between
+        let t = rise.time + (set.time - rise.time) / 2.0;
+        let mid = propagate(&model, &state, Seconds::ZERO, t, Seconds(60.0)).unwrap();
+        assert!(site.elevation(t, mid.position) > 1.5);
+    }
+
+    #[test]
+    fn enters_and_leaves_a_region() {
+        let elements = orbit(0.01, 1.2);
+        let state = elements.to_state(EARTH.mu);
+        let region = Region::BoundingBox {
+            south: -0.4,
+            north: 0.6,
+            west: -1.0,
+            east: 2.0,
+        };
+        let boundary = RegionBoundary::earth(region, 0.7);
+        let model = TwoBody { mu: EARTH.mu };
+        let functions: [&dyn EventFunction; 1] = [&boundary];
+        let end = Seconds(20_000.0);
+        let events = detect_events(
+            &model,
+            &state,
+            Seconds::ZERO,
+            end,
+            Seconds(10.0),
+            &functions,
+        )
+        .unwrap();
+
+        // The same crossings as the ground-track search
+        let visits = RegionSearch::earth(region, 0.7)
+            .visits(&elements, EARTH.mu, Seconds::ZERO, end)
+            .unwrap()
+            .filter(|v| v.start > Seconds::ZERO && v.end < end);
+        let mut count = 0;
+        for (event, visit) in events.zip(visits.flat_map(|v| [v.start, v.end])) {
+            let entering = count % 2 == 0;
+            assert_eq!(event.crossing == Crossing::Rising, entering);
+            assert_relative_eq!(event.time.value(), visit.value(), epsilon = 1e-2);
+            count += 1;
+        }
+        assert!(count >= 4);
+    }
+}
@@ -0,0 +1,391 @@
+//! C entry points for the core algorithms (`ffi` feature).
+//!
+//! States, element sets and epoch states cross the boundary as flat
+//! `#[repr(C)]` structs of doubles in SI units and radians. Every
+//! function returns an [`AlmagestStatus`] and writes its result
+//! through an out pointer, which may not be null:
+//!
+//! ```c
+//! AlmagestElements coe;
+//! if (almagest_rv2coe(state, 3.986004418e14, &coe) != 0) {
+//!     /* handle the error */
+//! }
+//! ```
+//!
+//! Build a static library to link against with
+//! `cargo rustc --release --features ffi --crate-type staticlib`; the
+//! declarations are in `include/almagest.h`.
+
+use core::ffi::{CStr, c_char};
+
+use crate::error::AlmagestError;
+use crate::frames::{EpochState, Gcrf, Itrf, State};
+use crate::kepler::{KeplerianElements, solve_kepler};
+use crate::sgp4::{Sgp4, Tle};
+use crate::state::StateVector;
+use crate::utils::{Eccentricity, GravParam, Meters, Radians, Seconds};
+use crate::vectors::Vec3;
+
+/// Outcome of a call: zero on success, otherwise the kind of
+/// [`AlmagestError`], or a null out pointer
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AlmagestStatus {
+    Ok = 0,
+    InvalidEccentricity = 1,
+    NoConvergence = 2,
+    Coplanarity = 3,
+    EpochOutOfRange = 4,
+    Parse = 5,
+    InvalidInput = 6,
+    NullPointer = 7,
+}
+
+impl From<AlmagestError> for AlmagestStatus {
+    fn from(error: AlmagestError) -> Self {
+        match error {
+            AlmagestError::InvalidEccentricity(_) => AlmagestStatus::InvalidEccentricity,
+            AlmagestError::NoConvergence(_) => AlmagestStatus::NoConvergence,
+            AlmagestError::Coplanarity(_) => AlmagestStatus::Coplanarity,
+            AlmagestError::EpochOutOfRange(_) => AlmagestStatus::EpochOutOfRange,
+            AlmagestError::Parse(_) => AlmagestStatus::Parse,
+            AlmagestError::InvalidInput(_) => AlmagestStatus::InvalidInput,
+        }
+    }
+}
+
+/// Position in meters and velocity in m/s
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AlmagestState {
+    pub position: [f64; 3],
+    pub velocity: [f64; 3],
+}
+
+/// Classical elements: semi-major axis in meters, angles in radians
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AlmagestElements {
+    pub semi_major_axis: f64,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    pub raan: f64,
+    pub argument_of_periapsis: f64,
+    pub true_anomaly: f64,
+}
+
+/// A state at an epoch in seconds past J2000
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AlmagestEpochState {
+    pub epoch: f64,
+    pub state: AlmagestState,
+}
+
+impl From<StateVector> for AlmagestState {
+    fn from(state: StateVector) -> Self {
+        let flat = |v: Vec3| [v.x, v.y, v.z];
+        AlmagestState {
+            position: flat(state.position),
+            velocity: flat(state.velocity),
+        }
+    }
+}
+
+impl From<AlmagestState> for StateVector {
+    fn from(state: AlmagestState) -> Self {
+        let [x, y, z] = state.position;
+        let [vx, vy, vz] = state.velocity;
+        StateVector::new(Vec3::new(x, y, z), Vec3::new(vx, vy, vz))
+    }
+}
+
+impl From<KeplerianElements> for AlmagestElements {
+    fn from(elements: KeplerianElements) -> Self {
+        AlmagestElements {
+            semi_major_axis: elements.semi_major_axis.value(),
+            eccentricity: elements.eccentricity.value(),
+            inclination: elements.inclination.value(),
+            raan: elements.raan.value(),
+            argument_of_periapsis: elements.argument_of_periapsis.value(),
+            true_anomaly: elements.true_anomaly.value(),
+        }
+    }
+}
+
+impl TryFrom<AlmagestElements> for KeplerianElements {
+    type Error = AlmagestError;
+    fn try_from(elements: AlmagestElements) -> Result<Self, Self::Error> {
+        Ok(KeplerianElements {
+            semi_major_axis: Meters(elements.semi_major_axis),
+            eccentricity: Eccentricity::new(elements.eccentricity)?,
+            inclination: Radians(elements.inclination),
+            raan: Radians(elements.raan),
+            argument_of_periapsis: Radians(elements.argument_of_periapsis),
+            true_anomaly: Radians(elements.true_anomaly),
+        })
+    }
+}
+
+// Store a result through an out pointer
+fn write<T>(out: Option<&mut T>, result: Result<T, AlmagestError>) -> AlmagestStatus {
+    match (out, result) {
+        (None, _) => AlmagestStatus::NullPointer,
+        (Some(out), Ok(value)) => {
+            *out = value;
+            AlmagestStatus::Ok
+        }
+        (Some(_), Err(error)) => error.into(),
+    }
+}
+
+/// Eccentric anomaly for a mean anomaly, as [`solve_kepler`]
+#[unsafe(no_mangle)]
+pub extern "C" fn almagest_solve_kepler(
+    mean_anomaly: f64,
+    eccentricity: f64,
+    ecc_anomaly: Option<&mut f64>,
+) -> AlmagestStatus {
+    write(ecc_anomaly, solve_kepler(mean_anomaly, eccentricity))
+}
+
+/// Elements from a state, as [`KeplerianElements::from_state`]
+#[unsafe(no_mangle)]
+pub extern "C" fn almagest_rv2coe(
+    state: AlmagestState,
+    mu: f64,
+    elements: Option<&mut AlmagestElements>,
+) -> AlmagestStatus {
+    let result = KeplerianElements::from_state(&state.into(), GravParam(mu));
+    write(elements, result.map(Into::into))
+}
+
+/// A state from elements, as [`KeplerianElements::to_state`]
+#[unsafe(no_mangle)]
+pub extern "C" fn almagest_coe2rv(
+    elements: AlmagestElements,
+    mu: f64,
+    state: Option<&mut AlmagestState>,
+) -> AlmagestStatus {
+    let result = KeplerianElements::try_from(elements).map(|e| e.to_state(GravParam(mu)).into());
+    write(state, result)
+}
+
+/// Elements `dt` seconds later on the two-body orbit, as
+/// [`KeplerianElements::propagate`]
+#[unsafe(no_mangle)]
+pub extern "C" fn almagest_propagate_elements(
+    elements: AlmagestElements,
+    dt: f64,
+    mu: f64,
+    propagated: Option<&mut AlmagestElements>,
+) -> AlmagestStatus {
+    let result = KeplerianElements::try_from(elements)
+        .and_then(|e| e.propagate(Seconds(dt), GravParam(mu)))
+        .map(Into::into);
+    write(propagated, result)
+}
+
+/// Earth-fixed state from an inertial one, as
+/// [`EpochState::<Gcrf>::to_itrf`]
+#[unsafe(no_mangle)]
+pub extern "C" fn almagest_gcrf_to_itrf(
+    inertial: AlmagestEpochState,
+    fixed: Option<&mut AlmagestEpochState>,
+) -> AlmagestStatus {
+    let epoch = Seconds(inertial.epoch);
+    let state = EpochState::new(epoch, State::<Gcrf>::new(inertial.state.into()));
+    let result = state.to_itrf();
+    write(
+        fixed,
+        Ok(AlmagestEpochState {
+            epoch: result.epoch.value(),
+            state: result.state.state.into(),
+        }),
+    )
+}
+
+/// Inertial state from an Earth-fixed one, as
+/// [`EpochState::<Itrf>::to_gcrf`]
+#[unsafe(no_mangle)]
+pub extern "C" fn almagest_itrf_to_gcrf(
+    fixed: AlmagestEpochState,
+    inertial: Option<&mut AlmagestEpochState>,
+) -> AlmagestStatus {
+    let epoch = Seconds(fixed.epoch);
+    let state = EpochState::new(epoch, State::<Itrf>::new(fixed.state.into()));
+    let result = state.to_gcrf();
+    write(
+        inertial,
+        Ok(AlmagestEpochState {
+            epoch: result.epoch.value(),
+            state: result.state.state.into(),
+        }),
+    )
+}
+
+/// TEME state `since_epoch` seconds after a two-line element set,
+/// as [`Sgp4::propagate`]
+///
+/// # Safety
+///
+/// `line1` and `line2` must each be null or point to a
+/// NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn almagest_sgp4(
+    line1: *const c_char,
+    line2: *const c_char,
+    since_epoch: f64,
+    state: Option<&mut AlmagestState>,
+) -> AlmagestStatus {
+    if line1.is_null() || line2.is_null() {
+        return AlmagestStatus::NullPointer;
+    }
+    // SAFETY: both are non-null and NUL-terminated per the contract
+    let (line1, line2) = unsafe { (CStr::from_ptr(line1), CStr::from_ptr(line2)) };
+    let result = match (line1.to_str(), line2.to_str()) {
+        (Ok(line1), Ok(line2)) => Tle::parse(line1, line2)
+            .and_then(|tle| Sgp4::new(&tle))
+            .and_then(|sgp4| sgp4.propagate(Seconds(since_epoch)))
+            .map(|teme| teme.state.into()),
+        _ => Err(AlmagestError::Parse("TLE lines must be ASCII")),
+    };
+    write(state, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use approx::assert_relative_eq;
+
+    const MU_EARTH: f64 = EARTH.mu.value();
+
+    #[test]
+    fn elements_round_trip() {
+        let state = AlmagestState {
+            position: [7_000_000.0, 1_000_000.0, 500_000.0],
+            velocity: [-1_000.0, 7_000.0, 1_000.0],
+        };
+        let mut elements = AlmagestElements::default();
+        assert_eq!(
+            almagest_rv2coe(state, MU_EARTH, Some(&mut elements)),
+            AlmagestStatus::Ok
+        );
+        let mut back = AlmagestState::default();
+        assert_eq!(
+            almagest_coe2rv(elements, MU_EARTH, Some(&mut back)),
+            AlmagestStatus::Ok
+        );
+        for i in 0..3 {
+            assert_relative_eq!(back.position[i], state.position[i], epsilon = 1e-6);
+            assert_relative_eq!(back.velocity[i], state.velocity[i], epsilon = 1e-9);
+        }
+
+        let period = core::f64::consts::TAU
+            * libm::sqrt(libm::pow(elements.semi_major_axis, 3.0) / MU_EARTH);
+        let mut later = AlmagestElements::default();
+        let status = almagest_propagate_elements(elements, period, MU_EARTH, Some(&mut later));
+        assert_eq!(status, AlmagestStatus::Ok);
+        assert_relative_eq!(later.true_anomaly, elements.true_anomaly, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn reports_errors_by_status() {
+        let mut ecc_anomaly = 0.0;
+        assert_eq!(
+            almagest_solve_kepler(1.0, 0.5, Some(&mut ecc_anomaly)),
+            AlmagestStatus::Ok
+        );
+        assert_relative_eq!(
+            ecc_anomaly - 0.5 * libm::sin(ecc_anomaly),
+            1.0,
+            epsilon = 1e-14
+        );
+        assert_eq!(
+            almagest_solve_kepler(1.0, 1.5, Some(&mut ecc_anomaly)),
+            AlmagestStatus::InvalidEccentricity
+        );
+        assert_eq!(
+            almagest_solve_kepler(1.0, 0.5, None),
+            AlmagestStatus::NullPointer
+        );
+        let negative = AlmagestElements {
+            eccentricity: -0.1,
+            ..AlmagestElements::default()
+        };
+        let mut state = AlmagestState::default();
+        assert_eq!(
+            almagest_coe2rv(negative, MU_EARTH, Some(&mut state)),
+            AlmagestStatus::InvalidEccentricity
+        );
+    }
+
+    #[test]
+    fn frame_round_trip() {
+        let inertial = AlmagestEpochState {
+            epoch: 86_400.0,
+            state: AlmagestState {
+                position: [7_000_000.0, 0.0, 0.0],
+                velocity: [0.0, 7_500.0, 0.0],
+            },
+        };
+        let mut fixed = AlmagestEpochState::default();
+        assert_eq!(
+            almagest_gcrf_to_itrf(inertial, Some(&mut fixed)),
+            AlmagestStatus::Ok
+        );
+        let mut back = AlmagestEpochState::default();
+        assert_eq!(
+            almagest_itrf_to_gcrf(fixed, Some(&mut back)),
+            AlmagestStatus::Ok
+        );
+        assert_eq!(back.epoch, inertial.epoch);
+        for i in 0..3 {
+            assert_relative_eq!(
+                back.state.position[i],
+                inertial.state.position[i],
+                epsilon = 1e-6
+            );
+            assert_relative_eq!(
+                back.state.velocity[i],
+                inertial.state.velocity[i],
+                epsilon = 1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn propagates_a_tle() {
+        let line1 = c"1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753";
+        let line2 = c"2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667";
+        let mut state = AlmagestState::default();
+        let status =
+            unsafe { almagest_sgp4(line1.as_ptr(), line2.as_ptr(), 0.0, Some(&mut state)) };
+        assert_eq!(status, AlmagestStatus::Ok);
+        assert_relative_eq!(state.position[0], 7_022_465.292_66, epsilon = 1e-3);
+
+        let status =
+            unsafe { almagest_sgp4(line2.as_ptr(), line1.as_ptr(), 0.0, Some(&mut state)) };
+        assert_eq!(status, AlmagestStatus::Parse);
+        let status =
+            unsafe { almagest_sgp4(core::ptr::null(), line2.as_ptr(), 0.0, Some(&mut state)) };
+        assert_eq!(status, AlmagestStatus::NullPointer);
+    }
+
+    #[test]
+    fn header_declares_every_entry_point() {
+        let header = include_str!("../include/almagest.h");
+        let entry_points = include_str!("ffi.rs")
+            .lines()
+            .filter_map(|line| line.split("extern \"C\" fn ").nth(1))
+            .filter_map(|rest| rest.split('(').next());
+        let mut count = 0;
+        for name in entry_points {
+            let declaration = std::format!("{name}(");
+            assert!(header.contains(&declaration), "{name} is not in the header");
+            count += 1;
+        }
+        assert_eq!(count, 7);
+    }
+}
@@ -0,0 +1,177 @@
+//! Floating-point scalars the crate's generic types accept.
+//!
+//! The unit newtypes, [`crate::vectors::Vec3`], [`crate::state::StateVector`],
+//! the Kepler's equation solvers and the [`crate::forces`] propagator
+//! are generic over a [`Float`], defaulting to [`Real`] (`f64`). On
+//! targets where double precision is emulated, such as Cortex-M4F, they
+//! run in `f32`, reading quantities through [`crate::utils::Quantity`]:
+//!
+//! ```
+//! use almagest::kepler::solve_kepler;
+//! use almagest::utils::{Meters, Quantity};
+//!
+//! let r = Meters(7_000_000.0_f32) + Meters(1_000.0);
+//! assert_eq!(r.value(), 7_001_000.0_f32);
+//! let ecc_anomaly = solve_kepler(1.0_f32, 0.1).unwrap();
+//! assert!((ecc_anomaly - 0.1 * libm::sinf(ecc_anomaly) - 1.0).abs() < 1e-6);
+//! ```
+//!
+//! Everything else, including the element sets, stays in `f64`.
+
+use core::fmt::{Debug, Display};
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+use crate::utils::Real;
+
+/// `f32` or `f64`, with the `libm` functions the crate needs
+pub trait Float:
+    Copy
+    + Debug
+    + Default
+    + Display
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+    + Sum
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const PI: Self;
+    const TAU: Self;
+    const EPSILON: Self;
+
+    /// Convert a constant, rounding to the nearest value
+    fn from_real(value: Real) -> Self;
+    fn to_real(self) -> Real;
+
+    fn abs(self) -> Self;
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn copysign(self, sign: Self) -> Self;
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn powf(self, exponent: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+}
+
+impl Float for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const PI: Self = core::f64::consts::PI;
+    const TAU: Self = core::f64::consts::TAU;
+    const EPSILON: Self = f64::EPSILON;
+
+    fn from_real(value: Real) -> Self {
+        value
+    }
+    fn to_real(self) -> Real {
+        self
+    }
+    fn abs(self) -> Self {
+        libm::fabs(self)
+    }
+    fn min(self, other: Self) -> Self {
+        libm::fmin(self, other)
+    }
+    fn max(self, other: Self) -> Self {
+        libm::fmax(self, other)
+    }
+    fn copysign(self, sign: Self) -> Self {
+        libm::copysign(self, sign)
+    }
+    fn sqrt(self) -> Self {
+        libm::sqrt(self)
+    }
+    fn cbrt(self) -> Self {
+        libm::cbrt(self)
+    }
+    fn powf(self, exponent: Self) -> Self {
+        libm::pow(self, exponent)
+    }
+    fn sin(self) -> Self {
+        libm::sin(self)
+    }
+    fn cos(self) -> Self {
+        libm::cos(self)
+    }
+    fn tan(self) -> Self {
+        libm::tan(self)
+    }
+    fn asin(self) -> Self {
+        libm::asin(self)
+    }
+    fn acos(self) -> Self {
+        libm::acos(self)
+    }
+    fn atan2(self, x: Self) -> Self {
+        libm::atan2(self, x)
+    }
+}
+
+impl Float for f32 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+    const PI: Self = core::f32::consts::PI;
+    const TAU: Self = core::f32::consts::TAU;
+    const EPSILON: Self = f32::EPSILON;
+
+    fn from_real(value: Real) -> Self {
+        value as f32
+    }
+    fn to_real(self) -> Real {
+        self as Real
+    }
+    fn abs(self) -> Self {
+        libm::fabsf(self)
+    }
+    fn min(self, other: Self) -> Self {
+        libm::fminf(self, other)
+    }
+    fn max(self, other: Self) -> Self {
+        libm::fmaxf(self, other)
+    }
+    fn copysign(self, sign: Self) -> Self {
+        libm::copysignf(self, sign)
+    }
+    fn sqrt(self) -> Self {
+        libm::sqrtf(self)
+    }
+    fn cbrt(self) -> Self {
+        libm::cbrtf(self)
+    }
+    fn powf(self, exponent: Self) -> Self {
+        libm::powf(self, exponent)
+    }
+    fn sin(self) -> Self {
+        libm::sinf(self)
+    }
+    fn cos(self) -> Self {
+        libm::cosf(self)
+    }
+    fn tan(self) -> Self {
+        libm::tanf(self)
+    }
+    fn asin(self) -> Self {
+        libm::asinf(self)
+    }
+    fn acos(self) -> Self {
+        libm::acosf(self)
+    }
+    fn atan2(self, x: Self) -> Self {
+        libm::atan2f(self, x)
+    }
+}
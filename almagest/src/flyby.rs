@@ -0,0 +1,185 @@
+//! Spheres of influence and gravity-assist flybys.
+//!
+//! A flyby is patched into a heliocentric trajectory as an instant
+//! rotation of the hyperbolic excess velocity v∞ by the turn angle δ,
+//! with sin(δ/2) = 1/e and e = 1 + rₚv∞²/μ. Its speed relative to the
+//! planet is unchanged, but relative to the Sun it gains or loses the
+//! difference the rotation makes against the planet's own velocity.
+//!
+//! Where the flyby passes is given as in [`crate::entry`]: by the angle
+//! of the B vector in the B-plane, from T̂ toward R̂. The spacecraft is
+//! bent toward the planet, away from B.
+
+use libm::{asin, cos, pow, sin};
+
+use crate::error::AlmagestError;
+use crate::utils::{GravParam, Meters, Real};
+use crate::vectors::Vec3;
+
+/// Radius of a body's sphere of influence about its primary, Laplace's
+/// r = D (μ / μ_primary)^(2/5) at a distance D
+pub fn sphere_of_influence(distance: Meters, mu: GravParam, primary: GravParam) -> Meters {
+    distance * pow(mu.value() / primary.value(), 0.4)
+}
+
+/// Angle through which a flyby at `periapsis` turns the hyperbolic
+/// excess velocity
+pub fn turn_angle(v_infinity: Real, periapsis: Meters, mu: GravParam) -> Real {
+    let e = 1.0 + periapsis.value() * v_infinity * v_infinity / mu.value();
+    2.0 * asin(1.0 / e)
+}
+
+/// Periapsis radius that turns the hyperbolic excess velocity by
+/// `turn_angle`
+pub fn periapsis_for_turn(v_infinity: Real, turn_angle: Real, mu: GravParam) -> Meters {
+    Meters(mu.value() / (v_infinity * v_infinity) * (1.0 / sin(turn_angle / 2.0) - 1.0))
+}
+
+/// A gravity-assist flyby, planet-relative
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GravityAssist {
+    pub v_infinity_in: Vec3,
+    pub v_infinity_out: Vec3,
+    pub turn_angle: Real,
+    pub eccentricity: Real,
+    pub periapsis: Meters,
+    /// Size of the impulsive burn that would make the same change,
+    /// 2v∞ sin(δ/2)
+    pub delta_v: Real,
+}
+
+impl GravityAssist {
+    /// Flyby with incoming excess velocity `v_infinity_in` passing
+    /// at `periapsis`, with the B vector at `b_plane_angle`
+    pub fn new(
+        v_infinity_in: Vec3,
+        periapsis: Meters,
+        b_plane_angle: Real,
+        mu: GravParam,
+    ) -> Result<Self, AlmagestError> {
+        let v_infinity = v_infinity_in.norm();
+        if v_infinity == 0.0 {
+            return Err(AlmagestError::InvalidEccentricity(
+                "Flyby needs a hyperbolic approach",
+            ));
+        }
+        if periapsis.value() <= 0.0 {
+            return Err(AlmagestError::InvalidInput("Periapsis must be positive"));
+        }
+        let s = v_infinity_in / v_infinity;
+        let t = s.cross(Vec3::Z);
+        let t = if t.norm() > 1e-12 {
+            t.unit()
+        } else {
+            s.cross(Vec3::X).unit()
+        };
+        let r = s.cross(t);
+        let b = t * cos(b_plane_angle) + r * sin(b_plane_angle);
+        let e = 1.0 + periapsis.value() * v_infinity * v_infinity / mu.value();
+        let turn_angle = 2.0 * asin(1.0 / e);
+        let v_infinity_out = (s * cos(turn_angle) - b * sin(turn_angle)) * v_infinity;
+        Ok(GravityAssist {
+            v_infinity_in,
+            v_infinity_out,
+            turn_angle,
+            eccentricity: e,
+            periapsis,
+            delta_v: 2.0 * v_infinity / e,
+        })
+    }
+
+    /// Change in speed relative to the Sun, for a planet moving at
+    /// `planet_velocity`
+    pub fn speed_gain(&self, planet_velocity: Vec3) -> Real {
+        (planet_velocity + self.v_infinity_out).norm()
+            - (planet_velocity + self.v_infinity_in).norm()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::forces::{TwoBody, propagate};
+    use crate::lunar::MU_MOON;
+    use crate::state::StateVector;
+    use crate::utils::Seconds;
+    use approx::assert_relative_eq;
+    use libm::{acos, sqrt};
+
+    const MU_SUN: GravParam = GravParam(1.327_124_400_18e20);
+
+    #[test]
+    fn spheres_of_influence() {
+        let earth = sphere_of_influence(Meters(1.495_978_707e11), EARTH.mu, MU_SUN);
+        assert_relative_eq!(earth.value(), 9.25e8, max_relative = 1e-2);
+        let moon = sphere_of_influence(Meters(384_400_000.0), MU_MOON, EARTH.mu);
+        assert_relative_eq!(moon.value(), 6.6e7, max_relative = 1e-2);
+    }
+
+    #[test]
+    fn turn_and_equivalent_burn() {
+        let v_in = Vec3::new(5_000.0, 0.0, 0.0);
+        let rp = Meters(7_000_000.0);
+        let flyby = GravityAssist::new(v_in, rp, 0.0, EARTH.mu).unwrap();
+        assert_relative_eq!(flyby.turn_angle.to_degrees(), 88.05, epsilon = 0.01);
+        assert_relative_eq!(flyby.turn_angle, turn_angle(5_000.0, rp, EARTH.mu));
+        assert_relative_eq!(flyby.v_infinity_out.norm(), 5_000.0, max_relative = 1e-12);
+        let angle = acos(flyby.v_infinity_out.unit().dot(v_in.unit()));
+        assert_relative_eq!(angle, flyby.turn_angle, epsilon = 1e-12);
+        assert_relative_eq!(
+            flyby.delta_v,
+            (flyby.v_infinity_out - v_in).norm(),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            periapsis_for_turn(5_000.0, flyby.turn_angle, EARTH.mu).value(),
+            rp.value(),
+            max_relative = 1e-12
+        );
+        assert!(GravityAssist::new(Vec3::ZERO, rp, 0.0, EARTH.mu).is_err());
+    }
+
+    #[test]
+    fn passing_behind_the_planet_speeds_up() {
+        // Planet moving along +y; approach along +x, passing behind
+        // the planet (on its -y side) bends the path toward +y
+        let planet = Vec3::new(0.0, 30_000.0, 0.0);
+        let v_in = Vec3::new(5_000.0, 0.0, 0.0);
+        let rp = Meters(7_000_000.0);
+        // T̂ = Ŝ × ẑ = -ŷ, so B along T̂ is behind
+        let behind = GravityAssist::new(v_in, rp, 0.0, EARTH.mu).unwrap();
+        assert!(behind.v_infinity_out.y > 0.0);
+        assert!(behind.speed_gain(planet) > 0.0);
+        let ahead = GravityAssist::new(v_in, rp, core::f64::consts::PI, EARTH.mu).unwrap();
+        assert!(ahead.speed_gain(planet) < 0.0);
+    }
+
+    #[test]
+    fn matches_integrated_hyperbola() {
+        let v_in = Vec3::new(3_000.0, 1_000.0, -2_000.0);
+        let v_inf = v_in.norm();
+        let rp = Meters(8_000_000.0);
+        let angle = 0.7;
+        let flyby = GravityAssist::new(v_in, rp, angle, EARTH.mu).unwrap();
+
+        // Start far up the incoming asymptote, offset by B
+        let s = v_in.unit();
+        let t = s.cross(Vec3::Z).unit();
+        let b_hat = t * cos(angle) + s.cross(t) * sin(angle);
+        let b = rp.value() * sqrt(1.0 + 2.0 * EARTH.mu.value() / (rp.value() * v_inf * v_inf));
+        let distance = 2.0e10;
+        let start = StateVector::new(b_hat * b - s * distance, v_in);
+        let end = propagate(
+            &TwoBody { mu: EARTH.mu },
+            &start,
+            Seconds::ZERO,
+            Seconds(2.0 * distance / v_inf),
+            Seconds(20.0),
+        )
+        .unwrap();
+        let miss = acos(end.velocity.unit().dot(flyby.v_infinity_out.unit()));
+        assert!(miss < 2e-3);
+    }
+}
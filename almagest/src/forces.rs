@@ -0,0 +1,295 @@
+//! Force models and a fixed-step integrator to fly them.
+//!
+//! A [`ForceModel`] gives the inertial acceleration for a time and
+//! state; models add up as tuples, so a perturbation rides on top of
+//! central gravity as `(TwoBody { mu }, perturbation)`. [`propagate`]
+//! integrates any model with classical fourth-order Runge-Kutta, in
+//! `f64` or, for the models that support it, `f32`.
+
+use crate::aerobraking::Atmosphere;
+use crate::error::AlmagestError;
+use crate::float::Float;
+use crate::relative::hill_axes;
+use crate::state::StateVector;
+use crate::utils::{GravParam, Meters, Quantity, Real, Seconds};
+use crate::vectors::Vec3;
+
+/// A source of acceleration, in scalar type `T`
+pub trait ForceModel<T: Float = Real> {
+    /// Inertial acceleration at `time`, in m/s²
+    fn acceleration(&self, time: Seconds<T>, state: &StateVector<T>) -> Vec3<T>;
+}
+
+/// Point-mass gravity of the central body
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoBody<T = Real> {
+    pub mu: GravParam<T>,
+}
+
+impl<T: Float> ForceModel<T> for TwoBody<T> {
+    fn acceleration(&self, _time: Seconds<T>, state: &StateVector<T>) -> Vec3<T> {
+        let r = state.position.norm();
+        state.position * (-self.mu.value() / (r * r * r))
+    }
+}
+
+impl<T: Float, A: ForceModel<T>, B: ForceModel<T>> ForceModel<T> for (A, B) {
+    fn acceleration(&self, time: Seconds<T>, state: &StateVector<T>) -> Vec3<T> {
+        self.0.acceleration(time, state) + self.1.acceleration(time, state)
+    }
+}
+
+impl<T: Float, M: ForceModel<T>> ForceModel<T> for &M {
+    fn acceleration(&self, time: Seconds<T>, state: &StateVector<T>) -> Vec3<T> {
+        (*self).acceleration(time, state)
+    }
+}
+
+/// A steady push, such as low thrust at a fixed thrust-to-mass ratio
+/// or electrodynamic tether drag
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConstantAcceleration {
+    /// Fixed in inertial axes
+    Inertial(Vec3),
+    /// Fixed in the satellite's radial, along-track and cross-track
+    /// (RSW) axes, so it turns with the orbit
+    Rsw(Vec3),
+}
+
+impl ForceModel for ConstantAcceleration {
+    fn acceleration(&self, _time: Seconds, state: &StateVector) -> Vec3 {
+        match *self {
+            ConstantAcceleration::Inertial(acceleration) => acceleration,
+            ConstantAcceleration::Rsw(acceleration) => hill_axes(state) * acceleration,
+        }
+    }
+}
+
+/// Atmospheric drag, −ρ|v|v / 2β, against an atmosphere at rest in
+/// the inertial frame
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Drag<A> {
+    pub atmosphere: A,
+    /// m / (C_D A), in kg/m²
+    pub ballistic_coefficient: Real,
+}
+
+impl<A: Atmosphere> ForceModel for Drag<A> {
+    fn acceleration(&self, _time: Seconds, state: &StateVector) -> Vec3 {
+        let density = self.atmosphere.density(Meters(state.position.norm()));
+        let v = state.velocity;
+        v * (-density * v.norm() / (2.0 * self.ballistic_coefficient))
+    }
+}
+
+/// Any closure of time and state as a force model, for what-ifs that
+/// don't warrant a model of their own
+#[derive(Copy, Clone, Debug)]
+pub struct Acceleration<F>(pub F);
+
+impl<T: Float, F: Fn(Seconds<T>, &StateVector<T>) -> Vec3<T>> ForceModel<T> for Acceleration<F> {
+    fn acceleration(&self, time: Seconds<T>, state: &StateVector<T>) -> Vec3<T> {
+        (self.0)(time, state)
+    }
+}
+
+/// One Runge-Kutta step of length `step` from `state` at `time`
+pub fn rk4_step<T: Float, M: ForceModel<T>>(
+    model: &M,
+    time: Seconds<T>,
+    state: &StateVector<T>,
+    step: Seconds<T>,
+) -> StateVector<T> {
+    let (time, step) = (time.value(), step.value());
+    let derivative =
+        |t: T, s: &StateVector<T>| StateVector::new(s.velocity, model.acceleration(Seconds(t), s));
+    let advance = |s: &StateVector<T>, d: &StateVector<T>, h: T| {
+        StateVector::new(s.position + d.position * h, s.velocity + d.velocity * h)
+    };
+    let two = T::from_real(2.0);
+    let half = step / two;
+    let k1 = derivative(time, state);
+    let k2 = derivative(time + half, &advance(state, &k1, half));
+    let k3 = derivative(time + half, &advance(state, &k2, half));
+    let k4 = derivative(time + step, &advance(state, &k3, step));
+    let sum = StateVector::new(
+        k1.position + (k2.position + k3.position) * two + k4.position,
+        k1.velocity + (k2.velocity + k3.velocity) * two + k4.velocity,
+    );
+    advance(state, &sum, step / T::from_real(6.0))
+}
+
+/// Integrate `state` from `start` to `end` in steps of at most `step`,
+/// shortening the last one to land on `end`
+pub fn propagate<T: Float, M: ForceModel<T>>(
+    model: &M,
+    state: &StateVector<T>,
+    start: Seconds<T>,
+    end: Seconds<T>,
+    step: Seconds<T>,
+) -> Result<StateVector<T>, AlmagestError> {
+    let (start, end, step) = (start.value(), end.value(), step.value());
+    if step <= T::ZERO {
+        return Err(AlmagestError::InvalidInput("Step must be positive"));
+    }
+    let direction = if end >= start { T::ONE } else { -T::ONE };
+    let mut time = start;
+    let mut current = *state;
+    while (end - time) * direction > T::ZERO {
+        let h = ((end - time) * direction).min(step) * direction;
+        current = rk4_step(model, Seconds(time), &current, Seconds(h));
+        time += h;
+    }
+    Ok(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::kepler::KeplerianElements;
+    use crate::utils::Seconds;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn matches_kepler_propagation() {
+        let state = StateVector::new(
+            Vec3::new(7_000_000.0, 0.0, 0.0),
+            Vec3::new(0.0, 7_000.0, 2_000.0),
+        );
+        let end = propagate(
+            &TwoBody { mu: EARTH.mu },
+            &state,
+            Seconds::ZERO,
+            Seconds(5_000.0),
+            Seconds(10.0),
+        )
+        .unwrap();
+        let expected = KeplerianElements::from_state(&state, EARTH.mu)
+            .unwrap()
+            .propagate(Seconds(5_000.0), EARTH.mu)
+            .unwrap()
+            .to_state(EARTH.mu);
+        assert_relative_eq!(
+            (end.position - expected.position).norm(),
+            0.0,
+            epsilon = 0.1
+        );
+
+        // And back again
+        let back = propagate(
+            &TwoBody { mu: EARTH.mu },
+            &end,
+            Seconds(5_000.0),
+            Seconds::ZERO,
+            Seconds(10.0),
+        )
+        .unwrap();
+        assert_relative_eq!((back.position - state.position).norm(), 0.0, epsilon = 0.1);
+    }
+
+    #[test]
+    fn single_precision() {
+        let state = StateVector::new(
+            Vec3::new(7_000_000.0, 0.0, 0.0),
+            Vec3::new(0.0, 7_000.0, 2_000.0),
+        );
+        let expected = propagate(
+            &TwoBody { mu: EARTH.mu },
+            &state,
+            Seconds::ZERO,
+            Seconds(1_000.0),
+            Seconds(10.0),
+        )
+        .unwrap();
+        let single = StateVector::new(
+            Vec3::new(7_000_000.0_f32, 0.0, 0.0),
+            Vec3::new(0.0, 7_000.0, 2_000.0),
+        );
+        let model = TwoBody {
+            mu: GravParam(EARTH.mu.value() as f32),
+        };
+        let end = propagate(
+            &model,
+            &single,
+            Seconds::ZERO,
+            Seconds(1_000.0),
+            Seconds(10.0),
+        )
+        .unwrap()
+        .position;
+        let error = Vec3::new(end.x as Real, end.y as Real, end.z as Real) - expected.position;
+        // Rounding at 7000 km is half a meter a step
+        assert_relative_eq!(error.norm(), 0.0, epsilon = 10.0);
+    }
+
+    #[test]
+    fn models_add_up() {
+        struct Push;
+        impl ForceModel for Push {
+            fn acceleration(&self, _time: Seconds, _state: &StateVector) -> Vec3 {
+                Vec3::new(0.0, 0.0, 1e-3)
+            }
+        }
+        let state = StateVector::new(Vec3::new(7_000_000.0, 0.0, 0.0), Vec3::ZERO);
+        let gravity = TwoBody { mu: EARTH.mu };
+        let both = (gravity, Push).acceleration(Seconds::ZERO, &state);
+        let alone = gravity.acceleration(Seconds::ZERO, &state);
+        assert_relative_eq!(both.z - alone.z, 1e-3);
+        assert!(propagate(&gravity, &state, Seconds::ZERO, Seconds(1.0), Seconds::ZERO).is_err());
+    }
+
+    #[test]
+    fn inertial_push_in_free_space() {
+        let push = ConstantAcceleration::Inertial(Vec3::new(0.0, 2e-3, 0.0));
+        let state = StateVector::new(Vec3::X, Vec3::ZERO);
+        let end = propagate(
+            &push,
+            &state,
+            Seconds::ZERO,
+            Seconds(1_000.0),
+            Seconds(100.0),
+        )
+        .unwrap();
+        assert_relative_eq!(end.position.y, 1_000.0, max_relative = 1e-12);
+        assert_relative_eq!(end.velocity.y, 2.0, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn along_track_thrust_raises_the_orbit() {
+        // Gauss: a circular orbit's semi-major axis grows at 2 a_S / n
+        let r = 7_000_000.0;
+        let v = libm::sqrt(EARTH.mu.value() / r);
+        let state = StateVector::new(Vec3::new(r, 0.0, 0.0), Vec3::new(0.0, v * 0.8, v * 0.6));
+        let thrust = 1e-4;
+        let model = (
+            TwoBody { mu: EARTH.mu },
+            ConstantAcceleration::Rsw(Vec3::new(0.0, thrust, 0.0)),
+        );
+        let time = 6_000.0;
+        let end = propagate(&model, &state, Seconds::ZERO, Seconds(time), Seconds(10.0)).unwrap();
+        let a = KeplerianElements::from_state(&end, EARTH.mu)
+            .unwrap()
+            .semi_major_axis
+            .value();
+        assert_relative_eq!(a - r, 2.0 * thrust * r / v * time, max_relative = 1e-2);
+
+        // A tether's drag is the same push backwards, spelled as a closure
+        let tether = Acceleration(|_: Seconds, s: &StateVector| s.velocity.unit() * -thrust);
+        let lowered = propagate(
+            &(TwoBody { mu: EARTH.mu }, tether),
+            &state,
+            Seconds::ZERO,
+            Seconds(time),
+            Seconds(10.0),
+        )
+        .unwrap();
+        let a = KeplerianElements::from_state(&lowered, EARTH.mu)
+            .unwrap()
+            .semi_major_axis
+            .value();
+        assert_relative_eq!(r - a, 2.0 * thrust * r / v * time, max_relative = 1e-2);
+    }
+}
@@ -0,0 +1,468 @@
+//! Unit-aware formatting.
+//!
+//! Quantities are stored in SI internally. A [`UnitSystem`] picks the
+//! convention a value is presented in, so a report can be produced in
+//! kilometers or canonical units without hand-written conversions:
+//!
+//! ```
+//! use almagest::format::{Quantity, UnitSystem};
+//! use almagest::utils::Meters;
+//!
+//! let r = Meters(6_778_000.0);
+//! assert_eq!(UnitSystem::Kilometer.convert(r), 6_778.0);
+//! let _shown = r.display_in(UnitSystem::Kilometer); // "6778 km"
+//! ```
+//!
+//! With the `serde` feature a quantity wrapped by
+//! [`Quantity::display_in`] serializes as its value in the system and
+//! the unit symbol, and [`crate::report::MissionReport::display_in`]
+//! serializes a whole report in one system.
+
+use core::fmt::{Display, Formatter, Result};
+
+use libm::{pow, sqrt};
+
+use crate::utils::{
+    AstronomicalUnits, Days, GravParam, Kilometers, METERS_PER_AU, Meters, MetersCubed,
+    MetersPerSecond, MetersPerSecondSquared, MetersSquared, Real, SECONDS_PER_DAY, Seconds,
+};
+
+/// A convention for expressing lengths and times
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnitSystem {
+    /// Meters and seconds
+    Si,
+    /// Kilometers and seconds
+    Kilometer,
+    /// Canonical units: one distance unit (DU) and the time unit (TU)
+    /// in which the central body's gravitational parameter is 1 DU³/TU²
+    Canonical {
+        /// Length of one DU
+        distance_unit: Meters,
+        /// Length of one TU
+        time_unit: Seconds,
+    },
+}
+
+impl UnitSystem {
+    /// Canonical units for a body, usually with its equatorial
+    /// radius as the distance unit
+    pub fn canonical(distance_unit: Meters, mu: GravParam) -> Self {
+        let du = distance_unit.value();
+        UnitSystem::Canonical {
+            distance_unit,
+            time_unit: Seconds(sqrt(du * du * du / mu.value())),
+        }
+    }
+
+    /// Meters in one length unit of this system
+    pub fn length_scale(&self) -> Real {
+        match self {
+            UnitSystem::Si => 1.0,
+            UnitSystem::Kilometer => 1_000.0,
+            UnitSystem::Canonical { distance_unit, .. } => distance_unit.value(),
+        }
+    }
+
+    /// Seconds in one time unit of this system
+    pub fn time_scale(&self) -> Real {
+        match self {
+            UnitSystem::Si | UnitSystem::Kilometer => 1.0,
+            UnitSystem::Canonical { time_unit, .. } => time_unit.value(),
+        }
+    }
+
+    /// The numeric value of a quantity in this system
+    pub fn convert<Q: Quantity>(&self, quantity: Q) -> Real {
+        quantity.to_si() / self.scale::<Q>()
+    }
+
+    /// Build a quantity from a value expressed in this system
+    pub fn quantity<Q: Quantity>(&self, value: Real) -> Q {
+        Q::from_si(value * self.scale::<Q>())
+    }
+
+    fn scale<Q: Quantity>(&self) -> Real {
+        pow(self.length_scale(), Q::LENGTH as Real) * pow(self.time_scale(), Q::TIME as Real)
+    }
+
+    fn length_symbol(&self) -> &'static str {
+        match self {
+            UnitSystem::Si => "m",
+            UnitSystem::Kilometer => "km",
+            UnitSystem::Canonical { .. } => "DU",
+        }
+    }
+
+    fn time_symbol(&self) -> &'static str {
+        match self {
+            UnitSystem::Si | UnitSystem::Kilometer => "s",
+            UnitSystem::Canonical { .. } => "TU",
+        }
+    }
+}
+
+// The unit symbol for length^length · time^time in a system
+struct Symbol {
+    system: UnitSystem,
+    length: i32,
+    time: i32,
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let (length, time) = (self.length, self.time);
+        if length != 0 {
+            write!(f, "{}{}", self.system.length_symbol(), superscript(length))?;
+        } else if time < 0 {
+            write!(f, "1")?;
+        }
+        let symbol = self.system.time_symbol();
+        if length == 0 && time > 0 {
+            write!(f, "{}{}", symbol, superscript(time))?;
+        } else if time > 0 {
+            write!(f, "·{}{}", symbol, superscript(time))?;
+        } else if time < 0 {
+            write!(f, "/{}{}", symbol, superscript(-time))?;
+        }
+        Ok(())
+    }
+}
+
+fn superscript(exponent: i32) -> &'static str {
+    match exponent {
+        1 => "",
+        2 => "²",
+        3 => "³",
+        4 => "⁴",
+        -1 => "⁻¹",
+        -2 => "⁻²",
+        _ => "^?",
+    }
+}
+
+/// A dimensioned quantity that can be expressed in any [`UnitSystem`]
+pub trait Quantity: Copy {
+    /// Exponent of length in the quantity's dimension
+    const LENGTH: i32;
+    /// Exponent of time in the quantity's dimension
+    const TIME: i32;
+
+    /// The value in meters and seconds
+    fn to_si(self) -> Real;
+
+    /// Build the quantity from a value in meters and seconds
+    fn from_si(value: Real) -> Self;
+
+    /// Wrap the quantity so that `Display` renders it in `system`
+    fn display_in(self, system: UnitSystem) -> InUnits<Self> {
+        InUnits {
+            quantity: self,
+            system,
+        }
+    }
+}
+
+/// A quantity paired with the unit system it is displayed in.
+/// Any precision given in the format string is honored.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct InUnits<Q> {
+    quantity: Q,
+    system: UnitSystem,
+}
+
+impl<Q: Quantity> InUnits<Q> {
+    fn symbol(&self) -> Symbol {
+        Symbol {
+            system: self.system,
+            length: Q::LENGTH,
+            time: Q::TIME,
+        }
+    }
+}
+
+impl<Q: Quantity> Display for InUnits<Q> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let value = self.system.convert(self.quantity);
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*} ", precision, value)?,
+            None => write!(f, "{} ", value)?,
+        }
+        self.symbol().fmt(f)
+    }
+}
+
+/// Serializes as the value in the system and its unit symbol, as
+/// `{"value": 6778.0, "unit": "km"}`
+#[cfg(feature = "serde")]
+impl<Q: Quantity> serde::Serialize for InUnits<Q> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        struct Unit(Symbol);
+
+        impl serde::Serialize for Unit {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                serializer.collect_str(&self.0)
+            }
+        }
+
+        let mut fields = serializer.serialize_struct("InUnits", 2)?;
+        fields.serialize_field("value", &self.system.convert(self.quantity))?;
+        fields.serialize_field("unit", &Unit(self.symbol()))?;
+        fields.end()
+    }
+}
+
+impl Quantity for Meters {
+    const LENGTH: i32 = 1;
+    const TIME: i32 = 0;
+    fn to_si(self) -> Real {
+        self.0
+    }
+    fn from_si(value: Real) -> Self {
+        Meters(value)
+    }
+}
+
+impl Quantity for Kilometers {
+    const LENGTH: i32 = 1;
+    const TIME: i32 = 0;
+    fn to_si(self) -> Real {
+        self.0 * 1_000.0
+    }
+    fn from_si(value: Real) -> Self {
+        Kilometers(value / 1_000.0)
+    }
+}
+
+impl Quantity for AstronomicalUnits {
+    const LENGTH: i32 = 1;
+    const TIME: i32 = 0;
+    fn to_si(self) -> Real {
+        self.0 * METERS_PER_AU
+    }
+    fn from_si(value: Real) -> Self {
+        AstronomicalUnits(value / METERS_PER_AU)
+    }
+}
+
+impl Quantity for MetersSquared {
+    const LENGTH: i32 = 2;
+    const TIME: i32 = 0;
+    fn to_si(self) -> Real {
+        self.0
+    }
+    fn from_si(value: Real) -> Self {
+        MetersSquared(value)
+    }
+}
+
+impl Quantity for MetersCubed {
+    const LENGTH: i32 = 3;
+    const TIME: i32 = 0;
+    fn to_si(self) -> Real {
+        self.0
+    }
+    fn from_si(value: Real) -> Self {
+        MetersCubed(value)
+    }
+}
+
+impl Quantity for Seconds {
+    const LENGTH: i32 = 0;
+    const TIME: i32 = 1;
+    fn to_si(self) -> Real {
+        self.0
+    }
+    fn from_si(value: Real) -> Self {
+        Seconds(value)
+    }
+}
+
+impl Quantity for Days {
+    const LENGTH: i32 = 0;
+    const TIME: i32 = 1;
+    fn to_si(self) -> Real {
+        self.0 * SECONDS_PER_DAY
+    }
+    fn from_si(value: Real) -> Self {
+        Days(value / SECONDS_PER_DAY)
+    }
+}
+
+impl Quantity for MetersPerSecond {
+    const LENGTH: i32 = 1;
+    const TIME: i32 = -1;
+    fn to_si(self) -> Real {
+        self.0
+    }
+    fn from_si(value: Real) -> Self {
+        MetersPerSecond(value)
+    }
+}
+
+impl Quantity for MetersPerSecondSquared {
+    const LENGTH: i32 = 1;
+    const TIME: i32 = -2;
+    fn to_si(self) -> Real {
+        self.0
+    }
+    fn from_si(value: Real) -> Self {
+        MetersPerSecondSquared(value)
+    }
+}
+
+impl Quantity for GravParam {
+    const LENGTH: i32 = 3;
+    const TIME: i32 = -2;
+    fn to_si(self) -> Real {
+        self.0
+    }
+    fn from_si(value: Real) -> Self {
+        GravParam(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use approx::assert_relative_eq;
+    use core::fmt::Write;
+
+    // Fixed-capacity sink so formatting can be checked without `std`
+    struct Buffer {
+        bytes: [u8; 64],
+        len: usize,
+    }
+
+    impl Buffer {
+        fn new() -> Self {
+            Buffer {
+                bytes: [0; 64],
+                len: 0,
+            }
+        }
+
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+        }
+    }
+
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> Result {
+            let end = self.len + s.len();
+            self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    const EARTH_RADIUS: Meters = Meters(6_378_136.3);
+
+    #[test]
+    fn converts_between_systems() {
+        let r = Meters(7_000_000.0);
+        assert_eq!(UnitSystem::Si.convert(r), 7_000_000.0);
+        assert_eq!(UnitSystem::Kilometer.convert(r), 7_000.0);
+        assert_eq!(UnitSystem::Kilometer.convert(EARTH.mu), 398_600.441_8);
+        assert_eq!(UnitSystem::Kilometer.convert(Kilometers(7.0)), 7.0);
+        assert_eq!(UnitSystem::Si.convert(Kilometers(7.0)), 7_000.0);
+        assert_eq!(UnitSystem::Kilometer.convert(MetersSquared(1e6)), 1.0);
+    }
+
+    #[test]
+    fn canonical_units_normalize_mu() {
+        let canonical = UnitSystem::canonical(EARTH_RADIUS, EARTH.mu);
+        assert_relative_eq!(canonical.convert(EARTH.mu), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(canonical.convert(EARTH_RADIUS), 1.0, epsilon = 1e-12);
+        // Vallado's Earth time unit is about 806.81 s
+        assert_relative_eq!(canonical.time_scale(), 806.81, epsilon = 0.01);
+    }
+
+    #[test]
+    fn round_trips_values() {
+        let canonical = UnitSystem::canonical(EARTH_RADIUS, EARTH.mu);
+        let r: Meters = canonical.quantity(1.5);
+        assert_relative_eq!(canonical.convert(r), 1.5, epsilon = 1e-12);
+        let km: Kilometers = UnitSystem::Si.quantity(2_500.0);
+        assert_eq!(km, Kilometers(2.5));
+    }
+
+    #[test]
+    fn formats_with_unit_symbols() {
+        let mut buf = Buffer::new();
+        write!(
+            buf,
+            "{}",
+            Meters(7_000_000.0).display_in(UnitSystem::Kilometer)
+        )
+        .unwrap();
+        assert_eq!(buf.as_str(), "7000 km");
+
+        let mut buf = Buffer::new();
+        write!(buf, "{:.1}", EARTH.mu.display_in(UnitSystem::Kilometer)).unwrap();
+        assert_eq!(buf.as_str(), "398600.4 km³/s²");
+
+        let mut buf = Buffer::new();
+        let canonical = UnitSystem::canonical(EARTH_RADIUS, EARTH.mu);
+        write!(buf, "{:.3}", EARTH.mu.display_in(canonical)).unwrap();
+        assert_eq!(buf.as_str(), "1.000 DU³/TU²");
+
+        let mut buf = Buffer::new();
+        write!(buf, "{}", MetersSquared(3.0).display_in(UnitSystem::Si)).unwrap();
+        assert_eq!(buf.as_str(), "3 m²");
+
+        let mut buf = Buffer::new();
+        write!(
+            buf,
+            "{:.2}",
+            MetersPerSecond(7_500.0).display_in(UnitSystem::Kilometer)
+        )
+        .unwrap();
+        assert_eq!(buf.as_str(), "7.50 km/s");
+
+        let mut buf = Buffer::new();
+        write!(buf, "{:.1}", Days(1.0).display_in(UnitSystem::Si)).unwrap();
+        assert_eq!(buf.as_str(), "86400.0 s");
+    }
+
+    #[test]
+    fn velocities_and_times_scale_with_the_system() {
+        let canonical = UnitSystem::canonical(EARTH_RADIUS, EARTH.mu);
+        // Circular speed at one DU is one DU/TU
+        let circular = MetersPerSecond(libm::sqrt(EARTH.mu.value() / EARTH_RADIUS.value()));
+        assert_relative_eq!(canonical.convert(circular), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(canonical.convert(Seconds(806.81)), 1.0, epsilon = 1e-4);
+        assert_eq!(UnitSystem::Kilometer.convert(MetersPerSecond(7_500.0)), 7.5);
+        assert_eq!(UnitSystem::Kilometer.convert(Days(0.5)), 43_200.0);
+        assert_relative_eq!(
+            UnitSystem::Kilometer.convert(MetersPerSecondSquared(9.8)),
+            0.009_8
+        );
+        let day: Days = UnitSystem::Si.quantity(86_400.0);
+        assert_eq!(day, Days(1.0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_in_the_system() {
+        let speed = MetersPerSecond(7_500.0).display_in(UnitSystem::Kilometer);
+        let json = serde_json::to_string(&speed).unwrap();
+        assert_eq!(json, r#"{"value":7.5,"unit":"km/s"}"#);
+        let time = Seconds(1_613.62).display_in(UnitSystem::canonical(EARTH_RADIUS, EARTH.mu));
+        assert!(
+            serde_json::to_string(&time)
+                .unwrap()
+                .ends_with(r#""unit":"TU"}"#)
+        );
+    }
+}
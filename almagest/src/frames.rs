@@ -0,0 +1,342 @@
+//! Reference frame tags for states.
+//!
+//! A [`State`] carries its frame in its type, so states in different
+//! frames can't be added, differenced or handed to a function expecting
+//! another frame; the compiler catches it as it catches adding meters
+//! to seconds:
+//!
+//! ```compile_fail
+//! use almagest::frames::{Gcrf, Itrf, State};
+//! use almagest::state::StateVector;
+//!
+//! let inertial: State<Gcrf> = State::new(StateVector::ZERO);
+//! let fixed: State<Itrf> = State::new(StateVector::ZERO);
+//! let _ = inertial - fixed;
+//! ```
+//!
+//! Moving between frames goes through an explicit conversion. The
+//! crate models the Earth's orientation by sidereal rotation alone, so
+//! [`State::<Gcrf>::to_itrf`] ignores precession, nutation and polar
+//! motion; TEME states are tagged for bookkeeping but have no
+//! conversion.
+//!
+//! An [`EpochState`] also carries the time the state holds at. Epochs
+//! are only known at run time, so arithmetic between epoch states is
+//! checked instead: differencing states at different times is an
+//! error rather than a silently wrong answer.
+
+use core::fmt::Debug;
+use core::marker::PhantomData;
+use core::ops::{Add, Sub};
+
+use crate::error::AlmagestError;
+use crate::groundtrack::{EARTH_ROTATION_RATE, body_fixed, greenwich_sidereal_angle};
+use crate::state::StateVector;
+use crate::sun::J2000;
+use crate::utils::{Real, Seconds};
+use crate::vectors::Vec3;
+
+/// A reference frame a [`State`] can be tagged with
+pub trait Frame: Copy + Debug + PartialEq {
+    const NAME: &'static str;
+}
+
+/// Geocentric Celestial Reference Frame, inertial
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gcrf;
+
+/// International Terrestrial Reference Frame, Earth-fixed
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Itrf;
+
+/// True equator, mean equinox: the frame of SGP4 output
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Teme;
+
+impl Frame for Gcrf {
+    const NAME: &'static str = "GCRF";
+}
+
+impl Frame for Itrf {
+    const NAME: &'static str = "ITRF";
+}
+
+impl Frame for Teme {
+    const NAME: &'static str = "TEME";
+}
+
+/// A [`StateVector`] tagged with its frame
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct State<F: Frame> {
+    pub state: StateVector,
+    frame: PhantomData<F>,
+}
+
+impl<F: Frame> State<F> {
+    pub const fn new(state: StateVector) -> Self {
+        State {
+            state,
+            frame: PhantomData,
+        }
+    }
+
+    pub fn position(&self) -> Vec3 {
+        self.state.position
+    }
+
+    pub fn velocity(&self) -> Vec3 {
+        self.state.velocity
+    }
+
+    pub fn frame_name(&self) -> &'static str {
+        F::NAME
+    }
+}
+
+// The frame's name on the wire; reading it back checks it against the
+// frame the state is being read into
+#[cfg(feature = "serde")]
+struct FrameTag<F>(PhantomData<F>);
+
+#[cfg(feature = "serde")]
+impl<F: Frame> serde::Serialize for FrameTag<F> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(F::NAME)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, F: Frame> serde::Deserialize<'de> for FrameTag<F> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Expect<F>(PhantomData<F>);
+
+        impl<F: Frame> serde::de::Visitor<'_> for Expect<F> {
+            type Value = FrameTag<F>;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "the frame name {}", F::NAME)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<Self::Value, E> {
+                if name == F::NAME {
+                    Ok(FrameTag(PhantomData))
+                } else {
+                    Err(E::invalid_value(serde::de::Unexpected::Str(name), &self))
+                }
+            }
+        }
+
+        deserializer.deserialize_str(Expect(PhantomData))
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(bound = "")]
+struct Tagged<F: Frame> {
+    frame: FrameTag<F>,
+    state: StateVector,
+}
+
+/// Written with the frame's name, so a state saved in one frame is an
+/// error rather than a state when read back as another
+#[cfg(feature = "serde")]
+impl<F: Frame> serde::Serialize for State<F> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let tagged = Tagged::<F> {
+            frame: FrameTag(PhantomData),
+            state: self.state,
+        };
+        tagged.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, F: Frame> serde::Deserialize<'de> for State<F> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Tagged::<F> { state, .. } = Tagged::deserialize(deserializer)?;
+        Ok(State::new(state))
+    }
+}
+
+impl<F: Frame> Add for State<F> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        State::new(self.state + rhs.state)
+    }
+}
+
+impl<F: Frame> Sub for State<F> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        State::new(self.state - rhs.state)
+    }
+}
+
+impl State<Gcrf> {
+    /// The Earth-fixed state, given the Greenwich sidereal angle
+    pub fn to_itrf(&self, greenwich_angle: Real) -> State<Itrf> {
+        let rotation = Vec3::Z * EARTH_ROTATION_RATE;
+        let velocity = self.state.velocity - rotation.cross(self.state.position);
+        State::new(StateVector::new(
+            body_fixed(self.state.position, greenwich_angle),
+            body_fixed(velocity, greenwich_angle),
+        ))
+    }
+}
+
+impl State<Itrf> {
+    /// The inertial state, given the Greenwich sidereal angle
+    pub fn to_gcrf(&self, greenwich_angle: Real) -> State<Gcrf> {
+        let position = body_fixed(self.state.position, -greenwich_angle);
+        let rotation = Vec3::Z * EARTH_ROTATION_RATE;
+        State::new(StateVector::new(
+            position,
+            body_fixed(self.state.velocity, -greenwich_angle) + rotation.cross(position),
+        ))
+    }
+}
+
+/// Largest gap between epochs that [`EpochState`]
+/// arithmetic treats as the same instant. A microsecond absorbs the
+/// rounding of epochs near the present, which are some 10⁹ s past
+/// J2000 and carry about 10⁻⁷ s of precision, while any real offset
+/// in time still shows.
+pub const EPOCH_TOLERANCE: Seconds = Seconds(1e-6);
+
+/// A [`State`] at an epoch, counted from J2000
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct EpochState<F: Frame> {
+    pub epoch: Seconds,
+    pub state: State<F>,
+}
+
+impl<F: Frame> EpochState<F> {
+    pub const fn new(epoch: Seconds, state: State<F>) -> Self {
+        EpochState { epoch, state }
+    }
+
+    /// Julian date of the epoch
+    pub fn julian_date(&self) -> Real {
+        J2000 + self.epoch.to_days().value()
+    }
+
+    /// `self − other`, provided both are at the same epoch to within
+    /// [`EPOCH_TOLERANCE`]
+    pub fn checked_sub(&self, other: &Self) -> Result<State<F>, AlmagestError> {
+        self.same_epoch(other)?;
+        Ok(self.state - other.state)
+    }
+
+    /// `self + other`, provided both are at the same epoch to within
+    /// [`EPOCH_TOLERANCE`]
+    pub fn checked_add(&self, other: &Self) -> Result<State<F>, AlmagestError> {
+        self.same_epoch(other)?;
+        Ok(self.state + other.state)
+    }
+
+    fn same_epoch(&self, other: &Self) -> Result<(), AlmagestError> {
+        if (self.epoch - other.epoch).abs() <= EPOCH_TOLERANCE {
+            Ok(())
+        } else {
+            Err(AlmagestError::InvalidInput(
+                "States are at different epochs",
+            ))
+        }
+    }
+}
+
+impl EpochState<Gcrf> {
+    /// The Earth-fixed state, at the sidereal angle of the epoch
+    pub fn to_itrf(&self) -> EpochState<Itrf> {
+        let angle = greenwich_sidereal_angle(self.julian_date());
+        EpochState::new(self.epoch, self.state.to_itrf(angle))
+    }
+}
+
+impl EpochState<Itrf> {
+    /// The inertial state, at the sidereal angle of the epoch
+    pub fn to_gcrf(&self) -> EpochState<Gcrf> {
+        let angle = greenwich_sidereal_angle(self.julian_date());
+        EpochState::new(self.epoch, self.state.to_gcrf(angle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn earth_fixed_round_trip() {
+        let inertial: State<Gcrf> = State::new(StateVector::new(
+            Vec3::new(7_000_000.0, 1_000_000.0, 500_000.0),
+            Vec3::new(-1_000.0, 7_000.0, 1_000.0),
+        ));
+        let fixed = inertial.to_itrf(2.1);
+        assert_eq!(fixed.frame_name(), "ITRF");
+        assert_relative_eq!(fixed.position().norm(), inertial.position().norm());
+        // Slower relative to the rotating Earth
+        assert!(fixed.velocity().norm() < inertial.velocity().norm());
+        let back = fixed.to_gcrf(2.1);
+        let difference = back - inertial;
+        assert_relative_eq!(difference.position().norm(), 0.0, epsilon = 1e-6);
+        assert_relative_eq!(difference.velocity().norm(), 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn differencing_needs_matching_epochs() {
+        let state = |x: Real| {
+            State::<Gcrf>::new(StateVector::new(
+                Vec3::new(x, 0.0, 0.0),
+                Vec3::new(0.0, 7_500.0, 0.0),
+            ))
+        };
+        let a = EpochState::new(Seconds(100.0), state(7_000_000.0));
+        let b = EpochState::new(Seconds(100.0), state(7_000_100.0));
+        let later = EpochState::new(Seconds(160.0), state(7_000_100.0));
+        assert_relative_eq!(b.checked_sub(&a).unwrap().position().x, 100.0);
+        assert!(later.checked_sub(&a).is_err());
+        assert!(later.checked_add(&a).is_err());
+
+        // Epochs that differ only by rounding still match
+        let epoch = Seconds(8.2e8);
+        let now = EpochState::new(epoch, state(7_000_000.0));
+        let rounded = EpochState::new(epoch + Seconds(1e-7), state(7_000_100.0));
+        assert!(rounded.checked_sub(&now).is_ok());
+        let offset = EpochState::new(epoch + Seconds(1e-3), state(7_000_100.0));
+        assert!(offset.checked_sub(&now).is_err());
+
+        // Frame conversions use the epoch's own sidereal angle
+        let fixed = a.to_itrf();
+        assert_eq!(fixed.epoch, a.epoch);
+        let angle = greenwich_sidereal_angle(a.julian_date());
+        assert_eq!(fixed.state, a.state.to_itrf(angle));
+        let back = fixed.to_gcrf().checked_sub(&a).unwrap();
+        assert_relative_eq!(back.position().norm(), 0.0, epsilon = 1e-6);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn epoch_state_through_json() {
+        let state = EpochState::new(
+            Seconds(86_400.0),
+            State::<Itrf>::new(StateVector::new(
+                Vec3::new(7_000_000.0, 0.0, 0.0),
+                Vec3::new(0.0, 7_500.0, 0.0),
+            )),
+        );
+        let json = serde_json::to_string(&state).unwrap();
+        let back: EpochState<Itrf> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, state);
+
+        // The frame travels with the state and must match on the way in
+        assert!(json.contains("\"frame\":\"ITRF\""));
+        assert!(serde_json::from_str::<EpochState<Gcrf>>(&json).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+//! Geostationary longitude drift and stationkeeping.
+//!
+//! Longitudes are east-positive in radians and rates are per second,
+//! except for the yearly delta-v budgets. The Earth's ellipticity of
+//! the equator (J22) pulls satellites toward two stable longitudes
+//! near 75°E and 105°W, which east-west stationkeeping must fight;
+//! the Sun and Moon tilt the orbit by under a degree a year, which
+//! north-south stationkeeping takes back out.
+
+use libm::{cbrt, cos, sin, sqrt};
+
+use crate::groundtrack::{EARTH_RADIUS, EARTH_ROTATION_RATE};
+use crate::kepler::wrap_pi;
+use crate::sun::J2000;
+use crate::utils::{GravParam, Meters, PI, Real};
+
+/// Radius of the geostationary orbit
+pub const GEO_RADIUS: Meters = Meters(42_164_172.0);
+
+/// Amplitude of the Earth's sectoral J22 harmonic, √(C22² + S22²)
+pub const EARTH_J22: Real = 1.814_6e-6;
+
+/// Longitude of the J22 bulge's axis, ½ atan2(S22, C22)
+pub const J22_LONGITUDE: Real = -0.260_3;
+
+const JULIAN_YEAR: Real = 365.25 * 86_400.0;
+
+/// Radius of a circular orbit whose period matches the rotation
+pub fn synchronous_radius(mu: GravParam, rotation_rate: Real) -> Meters {
+    Meters(cbrt(mu.value() / (rotation_rate * rotation_rate)))
+}
+
+/// Longitude drift of a near-circular equatorial orbit, positive
+/// eastward; orbits above GEO drift west
+pub fn longitude_drift_rate(semi_major_axis: Meters, mu: GravParam) -> Real {
+    let a = semi_major_axis.value();
+    sqrt(mu.value() / (a * a * a)) - EARTH_ROTATION_RATE
+}
+
+/// Longitude acceleration from J22 at `longitude`:
+/// λ̈ = 18 ω² (R/a)² J22 sin 2(λ − λ22)
+pub fn triaxiality_acceleration(longitude: Real) -> Real {
+    let ratio = EARTH_RADIUS.value() / GEO_RADIUS.value();
+    18.0 * EARTH_ROTATION_RATE
+        * EARTH_ROTATION_RATE
+        * ratio
+        * ratio
+        * EARTH_J22
+        * sin(2.0 * (longitude - J22_LONGITUDE))
+}
+
+/// The stable longitude a free-drifting satellite oscillates about
+pub fn nearest_stable_longitude(longitude: Real) -> Real {
+    let east = J22_LONGITUDE + PI / 2.0;
+    if wrap_pi(longitude - east).abs() <= PI / 2.0 {
+        east
+    } else {
+        wrap_pi(east + PI)
+    }
+}
+
+/// Yearly east-west stationkeeping delta-v to hold `longitude`
+/// against J22. A tangential burn Δv changes the drift rate by
+/// 3Δv / a, so the budget is a |λ̈| / 3 per unit time.
+pub fn east_west_delta_v(longitude: Real) -> Real {
+    GEO_RADIUS.value() / 3.0 * triaxiality_acceleration(longitude).abs() * JULIAN_YEAR
+}
+
+/// Yearly north-south stationkeeping delta-v around `julian_date`.
+/// The lunisolar inclination drift runs from about 0.75° a year, with
+/// the Moon's ascending node at 180°, to 0.95° with it at 0°.
+pub fn north_south_delta_v(julian_date: Real) -> Real {
+    let t = (julian_date - J2000) / 36_525.0;
+    let lunar_node = (125.044_52 - 1_934.136_261 * t).to_radians();
+    let drift = (0.847_5 + 0.098_3 * cos(lunar_node)).to_radians();
+    EARTH_ROTATION_RATE * GEO_RADIUS.value() * drift
+}
+
+/// Yearly stationkeeping delta-v for a slot, in m/s
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StationkeepingBudget {
+    pub east_west: Real,
+    pub north_south: Real,
+}
+
+impl StationkeepingBudget {
+    /// Budget for a slot at `longitude` in the year around
+    /// `julian_date`
+    pub fn new(longitude: Real, julian_date: Real) -> Self {
+        StationkeepingBudget {
+            east_west: east_west_delta_v(longitude),
+            north_south: north_south_delta_v(julian_date),
+        }
+    }
+
+    pub fn total(&self) -> Real {
+        self.east_west + self.north_south
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn drift_from_a_kilometer_off() {
+        let geo = synchronous_radius(EARTH.mu, EARTH_ROTATION_RATE);
+        assert_relative_eq!(geo.value(), GEO_RADIUS.value(), epsilon = 1.0);
+        // About 0.0128° a day westward per kilometer too high
+        let rate = longitude_drift_rate(GEO_RADIUS + Meters(1_000.0), EARTH.mu);
+        assert_relative_eq!(rate.to_degrees() * 86_400.0, -0.0128, epsilon = 1e-4);
+        assert_relative_eq!(longitude_drift_rate(geo, EARTH.mu), 0.0, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn triaxiality_pulls_toward_stable_points() {
+        let stable = nearest_stable_longitude(60.0_f64.to_radians());
+        assert_relative_eq!(stable.to_degrees(), 75.1, epsilon = 0.1);
+        let west = nearest_stable_longitude(-120.0_f64.to_radians());
+        assert_relative_eq!(west.to_degrees(), -104.9, epsilon = 0.1);
+        assert_relative_eq!(triaxiality_acceleration(stable), 0.0, epsilon = 1e-20);
+        // West of a stable point the pull is eastward, and vice versa
+        assert!(triaxiality_acceleration(stable - 0.2) > 0.0);
+        assert!(triaxiality_acceleration(stable + 0.2) < 0.0);
+        // Peak of about 0.0017°/day²
+        let peak = triaxiality_acceleration(stable - PI / 4.0);
+        assert_relative_eq!(
+            peak.to_degrees() * 86_400.0 * 86_400.0,
+            0.0017,
+            epsilon = 1e-4
+        );
+    }
+
+    #[test]
+    fn yearly_budget() {
+        let budget = StationkeepingBudget::new(30.0_f64.to_radians(), 2_460_000.5);
+        assert!(budget.east_west > 1.0 && budget.east_west < 2.0);
+        assert!(budget.north_south > 40.0 && budget.north_south < 52.0);
+        assert_relative_eq!(budget.total(), budget.east_west + budget.north_south);
+        assert!(east_west_delta_v(nearest_stable_longitude(0.0)) < 1e-6);
+    }
+}
@@ -0,0 +1,986 @@
+//! Sub-satellite points and overflight prediction for ground targets.
+//!
+//! The central body is a sphere turning at a constant rate about the
+//! inertial z axis; latitudes are geocentric and longitudes are east
+//! positive, both in radians. Orbits follow unperturbed two-body motion
+//! from a set of [`KeplerianElements`], and times are seconds after the
+//! epoch of those elements.
+//!
+//! Overflights are found without sampling the ground track densely.
+//! The target can only be seen while its angular distance from the
+//! orbit plane is within reach, which picks out a few windows per
+//! sidereal day in closed form. Inside a window the satellite passes
+//! the target's projection onto the plane about once a revolution;
+//! each such pass is refined to the closest approach.
+//!
+//! Regions of interest are the exception to the spherical body: their
+//! boundaries are drawn in geodetic coordinates on an oblate
+//! ellipsoid, and the satellite is placed over them by its geodetic
+//! sub-satellite point.
+
+use libm::{asin, atan2, cos, sin, sqrt};
+
+use crate::bodies::{EARTH, RotatingBody};
+use crate::error::AlmagestError;
+use crate::kepler::{KeplerianElements, wrap_pi, wrap_two_pi};
+use crate::search::{Visits, visits};
+use crate::solver::{Convergence, SolverConfig};
+use crate::utils::{GravParam, Meters, PI, Real, Seconds, TAU};
+use crate::vectors::Vec3;
+use crate::windows::{Interval, Windows};
+
+/// Rotation rate of the Earth relative to the stars, in rad/s
+pub const EARTH_ROTATION_RATE: Real = EARTH.rotation_rate;
+
+/// Equatorial radius of the Earth (WGS-84)
+pub const EARTH_RADIUS: Meters = EARTH.equatorial_radius;
+
+/// Flattening of the Earth's reference ellipsoid (WGS-84)
+pub const EARTH_FLATTENING: Real = EARTH.flattening;
+
+/// A location on the surface of the central body, in radians
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroundPoint {
+    pub latitude: Real,
+    pub longitude: Real,
+}
+
+impl GroundPoint {
+    pub const fn new(latitude: Real, longitude: Real) -> Self {
+        GroundPoint {
+            latitude,
+            longitude,
+        }
+    }
+
+    /// Unit vector toward the point in the body-fixed frame
+    pub fn unit(&self) -> Vec3 {
+        let c = cos(self.latitude);
+        Vec3::new(
+            c * cos(self.longitude),
+            c * sin(self.longitude),
+            sin(self.latitude),
+        )
+    }
+
+    // The point under the body-fixed direction `r`
+    fn under(r: Vec3) -> Self {
+        GroundPoint::new(atan2(r.z, sqrt(r.x * r.x + r.y * r.y)), atan2(r.y, r.x))
+    }
+}
+
+/// Greenwich mean sidereal angle at a UT1 Julian date, in radians on
+/// [0, 2π) (Vallado, Equation 3-47)
+pub fn greenwich_sidereal_angle(julian_date: Real) -> Real {
+    let t = (julian_date - 2_451_545.0) / 36_525.0;
+    let seconds = 67_310.548_41 + (876_600.0 * 3_600.0 + 8_640_184.812_866) * t + 0.093_104 * t * t
+        - 6.2e-6 * t * t * t;
+    wrap_two_pi((seconds / 240.0).to_radians())
+}
+
+/// Rotate an inertial `position` into the body-fixed frame, given the
+/// angle of the prime meridian from the inertial x axis (for the
+/// Earth, the Greenwich sidereal angle)
+pub fn body_fixed(position: Vec3, greenwich_angle: Real) -> Vec3 {
+    let (s, c) = (sin(greenwich_angle), cos(greenwich_angle));
+    Vec3::new(
+        c * position.x + s * position.y,
+        -s * position.x + c * position.y,
+        position.z,
+    )
+}
+
+/// The point directly beneath an inertial `position`, given the
+/// angle of the prime meridian from the inertial x axis
+pub fn subsatellite_point(position: Vec3, greenwich_angle: Real) -> GroundPoint {
+    GroundPoint::under(body_fixed(position, greenwich_angle))
+}
+
+/// The geodetic point directly beneath an inertial `position` on an
+/// ellipsoid with the given equatorial radius and flattening
+/// (Vallado, Algorithm 12)
+pub fn geodetic_subsatellite_point(
+    position: Vec3,
+    greenwich_angle: Real,
+    equatorial_radius: Meters,
+    flattening: Real,
+) -> GroundPoint {
+    // Whether or not it converges, the last iterate is the best there is
+    let (latitude, _) =
+        geodetic_latitude(position, equatorial_radius, flattening, &GEODETIC_SOLVER);
+    let geocentric = subsatellite_point(position, greenwich_angle);
+    GroundPoint::new(latitude, geocentric.longitude)
+}
+
+/// Stopping criteria for [`geodetic_subsatellite_point`]: a latitude
+/// step under 1e-13 rad, within 10 iterations
+pub const GEODETIC_SOLVER: SolverConfig = SolverConfig {
+    tolerance: 1e-13,
+    max_iterations: 10,
+};
+
+/// As [`geodetic_subsatellite_point`], stopping as `config` says, and
+/// failing if the latitude has not settled; the residual is the
+/// change in latitude, in radians, another step would make
+pub fn geodetic_subsatellite_point_with(
+    position: Vec3,
+    greenwich_angle: Real,
+    equatorial_radius: Meters,
+    flattening: Real,
+    config: &SolverConfig,
+) -> Result<(GroundPoint, Convergence), AlmagestError> {
+    let (latitude, convergence) =
+        geodetic_latitude(position, equatorial_radius, flattening, config);
+    let convergence = convergence.ok_or(AlmagestError::NoConvergence(
+        "Geodetic latitude failed to converge",
+    ))?;
+    let geocentric = subsatellite_point(position, greenwich_angle);
+    Ok((
+        GroundPoint::new(latitude, geocentric.longitude),
+        convergence,
+    ))
+}
+
+// Fixed-point iteration on the geodetic latitude, from the geocentric
+fn geodetic_latitude(
+    position: Vec3,
+    equatorial_radius: Meters,
+    flattening: Real,
+    config: &SolverConfig,
+) -> (Real, Option<Convergence>) {
+    let e2 = flattening * (2.0 - flattening);
+    let rho = sqrt(position.x * position.x + position.y * position.y);
+    let next = |latitude: Real| {
+        let s = sin(latitude);
+        let c = equatorial_radius.value() / sqrt(1.0 - e2 * s * s);
+        atan2(position.z + c * e2 * s, rho)
+    };
+    let mut latitude = atan2(position.z, rho);
+    for iteration in 1..=config.max_iterations {
+        let step = next(latitude) - latitude;
+        latitude += step;
+        if step.abs() < config.tolerance {
+            let residual = (next(latitude) - latitude).abs();
+            return (
+                latitude,
+                Some(Convergence {
+                    iterations: iteration,
+                    residual,
+                }),
+            );
+        }
+    }
+    (latitude, None)
+}
+
+/// Great-circle angle between two ground points, in radians
+pub fn central_angle(a: &GroundPoint, b: &GroundPoint) -> Real {
+    // Haversine form, well conditioned for small separations
+    let dlat = sin((b.latitude - a.latitude) / 2.0);
+    let dlon = sin((b.longitude - a.longitude) / 2.0);
+    let h = dlat * dlat + cos(a.latitude) * cos(b.latitude) * dlon * dlon;
+    2.0 * asin(sqrt(h.clamp(0.0, 1.0)))
+}
+
+/// How close the satellite must come for a pass to count
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Reach {
+    /// Sub-satellite point within this surface distance of the target
+    GroundDistance(Meters),
+    /// Target within this angle of nadir as seen from the satellite,
+    /// in radians; limited by the horizon
+    OffNadir(Real),
+}
+
+impl Reach {
+    /// Largest central angle between target and sub-satellite point
+    /// that still counts, for a satellite at `radius`
+    pub fn central_angle(&self, radius: Meters, body_radius: Meters) -> Real {
+        match *self {
+            Reach::GroundDistance(distance) => distance / body_radius,
+            Reach::OffNadir(eta) => {
+                let k = radius / body_radius * sin(eta);
+                if k >= 1.0 {
+                    horizon(radius, body_radius)
+                } else {
+                    (asin(k) - eta).min(horizon(radius, body_radius))
+                }
+            }
+        }
+    }
+}
+
+// Central angle to the horizon from `radius`
+fn horizon(radius: Meters, body_radius: Meters) -> Real {
+    libm::acos((body_radius / radius).min(1.0))
+}
+
+/// Angle from nadir at which a satellite at `radius` sees a ground
+/// point `central_angle` away from its sub-satellite point
+pub fn off_nadir_angle(central_angle: Real, radius: Meters, body_radius: Meters) -> Real {
+    let (r, rb) = (radius.value(), body_radius.value());
+    atan2(rb * sin(central_angle), r - rb * cos(central_angle))
+}
+
+/// A pass of the satellite over the target, at closest approach
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Overflight {
+    /// Time after the epoch of the elements
+    pub time: Seconds,
+    /// Surface distance from the sub-satellite point to the target
+    pub ground_distance: Meters,
+    /// Angle of the target from nadir, in radians
+    pub off_nadir: Real,
+}
+
+/// A target on a rotating body and the reach that counts as a pass
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OverflightSearch {
+    pub target: GroundPoint,
+    pub reach: Reach,
+    /// The body, with time zero at the epoch of the elements
+    pub body: RotatingBody,
+}
+
+impl OverflightSearch {
+    /// A search over a target on the Earth
+    pub fn earth(target: GroundPoint, reach: Reach, greenwich_angle: Real) -> Self {
+        OverflightSearch {
+            target,
+            reach,
+            body: RotatingBody::earth(greenwich_angle),
+        }
+    }
+
+    /// Overflights of the target between `start` and `end` after the
+    /// epoch of `elements`, in time order. The orbit must be
+    /// elliptic and turn faster than the body; synchronous and higher
+    /// orbits are rejected. The body must rotate, since passes are
+    /// found by waiting for the target to turn under the orbit plane.
+    pub fn overflights(
+        &self,
+        elements: &KeplerianElements,
+        mu: GravParam,
+        start: Seconds,
+        end: Seconds,
+    ) -> Result<Overflights, AlmagestError> {
+        let a = elements.semi_major_axis;
+        if a <= Meters::ZERO {
+            return Err(AlmagestError::InvalidEccentricity(
+                "Overflight prediction needs an elliptic orbit",
+            ));
+        }
+        if self.body.rotation_rate == 0.0 || !self.body.rotation_rate.is_finite() {
+            return Err(AlmagestError::InvalidInput("Body must rotate"));
+        }
+        let mean_motion = elements.mean_motion(mu).value();
+        if mean_motion <= self.body.rotation_rate.abs() {
+            return Err(AlmagestError::InvalidInput(
+                "Orbit must turn faster than the central body",
+            ));
+        }
+        let apoapsis = a * (1.0 + elements.eccentricity.value());
+        let body_radius = self.body.equatorial_radius;
+        let max_reach = self.reach.central_angle(apoapsis, body_radius);
+        let windows = PlaneWindows::new(self, elements, max_reach);
+        let (so, co) = elements.raan.sin_cos();
+        let (si, ci) = elements.inclination.sin_cos();
+        let node = Vec3::new(co, so, 0.0);
+        let normal = Vec3::new(si * so, -si * co, ci);
+        Ok(Overflights {
+            search: *self,
+            elements: *elements,
+            mu,
+            node,
+            ahead: normal.cross(node),
+            windows,
+            period: TAU / mean_motion,
+            start: start.value(),
+            cursor: start.value(),
+            end: end.value(),
+        })
+    }
+
+    // Inertial direction of the target at time t
+    fn target_at(&self, t: Real) -> Vec3 {
+        let lon = self.target.longitude + self.body.angle(Seconds(t));
+        GroundPoint::new(self.target.latitude, lon).unit()
+    }
+}
+
+// Where the target lies within reach of the orbit plane, as ranges of
+// x = α − Ω, the target's inertial longitude from the ascending node.
+// Its angular distance from the plane has sine
+// sinφ cos i − cosφ sin i sin x, so each window is a range of sin x.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum PlaneWindows {
+    Never,
+    Always,
+    // Up to two ranges of x on [−π/2, 3π/2), start ≤ end
+    Ranges([(Real, Real); 2], usize),
+}
+
+impl PlaneWindows {
+    fn new(search: &OverflightSearch, elements: &KeplerianElements, reach: Real) -> Self {
+        let phi = search.target.latitude;
+        let offset = sin(phi) * elements.inclination.cos();
+        let scale = cos(phi) * elements.inclination.sin();
+        let s = sin(reach.min(PI / 2.0));
+        if scale.abs() < 1e-12 {
+            return if offset.abs() <= s {
+                PlaneWindows::Always
+            } else {
+                PlaneWindows::Never
+            };
+        }
+        // Order the sin x bounds for either sign of `scale`
+        let (b1, b2) = ((offset - s) / scale, (offset + s) / scale);
+        let (lo, hi) = (b1.min(b2), b1.max(b2));
+        if lo > 1.0 || hi < -1.0 {
+            return PlaneWindows::Never;
+        }
+        if lo <= -1.0 && hi >= 1.0 {
+            return PlaneWindows::Always;
+        }
+        let (a_lo, a_hi) = (asin(lo.max(-1.0)), asin(hi.min(1.0)));
+        if hi >= 1.0 {
+            // Rising and falling ranges meet at x = π/2
+            PlaneWindows::Ranges([(a_lo, PI - a_lo), (0.0, 0.0)], 1)
+        } else if lo <= -1.0 {
+            // ...or at x = −π/2
+            PlaneWindows::Ranges([(PI - a_hi - TAU, a_hi), (0.0, 0.0)], 1)
+        } else {
+            PlaneWindows::Ranges([(a_lo, a_hi), (PI - a_hi, PI - a_lo)], 2)
+        }
+    }
+
+    // Smallest x' ≥ x inside a window, or None if never
+    fn next_inside(&self, x: Real) -> Option<Real> {
+        let ranges = match self {
+            PlaneWindows::Never => return None,
+            PlaneWindows::Always => return Some(x),
+            PlaneWindows::Ranges(ranges, count) => &ranges[..*count],
+        };
+        let local = wrap_two_pi(x + PI / 2.0) - PI / 2.0;
+        let base = x - local;
+        let mut best = Real::INFINITY;
+        for &(start, end) in ranges {
+            // Ranges may start before −π/2 once merged
+            for shift in [-TAU, 0.0, TAU] {
+                let (s, e) = (start + shift, end + shift);
+                if local >= s && local <= e {
+                    return Some(x);
+                }
+                if s > local {
+                    best = best.min(s);
+                }
+            }
+        }
+        Some(base + best)
+    }
+}
+
+/// Iterator over the overflights of one target, created by
+/// [`OverflightSearch::overflights`]
+#[derive(Copy, Clone, Debug)]
+pub struct Overflights {
+    search: OverflightSearch,
+    elements: KeplerianElements,
+    mu: GravParam,
+    // Ascending node direction, and 90° ahead of it in the orbit plane
+    node: Vec3,
+    ahead: Vec3,
+    windows: PlaneWindows,
+    period: Real,
+    start: Real,
+    cursor: Real,
+    end: Real,
+}
+
+impl Overflights {
+    // Satellite argument of latitude and radius at time t
+    fn satellite_at(&self, t: Real) -> Option<(Real, Meters)> {
+        let elements = self.elements.propagate(Seconds(t), self.mu).ok()?;
+        let e = elements.eccentricity.value();
+        let r = elements.semi_latus_rectum() / (1.0 + e * elements.true_anomaly.cos());
+        Some((elements.argument_of_latitude().value(), r))
+    }
+
+    // Central angle from the sub-satellite point to the target
+    fn separation(&self, t: Real) -> Option<(Real, Meters)> {
+        let (u, r) = self.satellite_at(t)?;
+        let sat = self.node * cos(u) + self.ahead * sin(u);
+        let target = self.search.target_at(t);
+        // atan2 form of the angle between unit vectors, accurate near zero
+        Some((atan2(sat.cross(target).norm(), sat.dot(target)), r))
+    }
+
+    // How far the satellite trails the target's projection onto the
+    // orbit plane, as an angle wrapped onto (−π, π]
+    fn lag(&self, t: Real) -> Option<Real> {
+        let target = self.search.target_at(t);
+        let u_target = atan2(target.dot(self.ahead), target.dot(self.node));
+        let (u, _) = self.satellite_at(t)?;
+        Some(wrap_pi(u_target - u))
+    }
+
+    // Next time at or after t at which the satellite passes the
+    // target's projection onto the orbit plane
+    fn next_pass(&self, t: Real) -> Option<Real> {
+        let rate = TAU / self.period;
+        let mut time = t + wrap_two_pi(self.lag(t)?) / rate;
+        for _ in 0..20 {
+            let step = self.lag(time)? / rate;
+            time += step;
+            if step.abs() < 1e-3 {
+                break;
+            }
+        }
+        Some(time.max(t))
+    }
+
+    // Start of the first plane window at or after t
+    fn next_window(&self, t: Real) -> Option<Real> {
+        let search = &self.search;
+        let x =
+            search.target.longitude + search.body.angle(Seconds(t)) - self.elements.raan.value();
+        let inside = self.windows.next_inside(x)?;
+        Some(t + (inside - x) / search.body.rotation_rate)
+    }
+
+    // Golden-section search for the closest approach on [a, b]
+    fn closest_approach(&self, mut a: Real, mut b: Real) -> Option<Real> {
+        const RATIO: Real = 0.618_033_988_749_894_9;
+        let mut c = b - RATIO * (b - a);
+        let mut d = a + RATIO * (b - a);
+        let mut fc = self.separation(c)?.0;
+        let mut fd = self.separation(d)?.0;
+        while b - a > 1e-2 {
+            if fc < fd {
+                b = d;
+                d = c;
+                fd = fc;
+                c = b - RATIO * (b - a);
+                fc = self.separation(c)?.0;
+            } else {
+                a = c;
+                c = d;
+                fc = fd;
+                d = a + RATIO * (b - a);
+                fd = self.separation(d)?.0;
+            }
+        }
+        Some((a + b) / 2.0)
+    }
+}
+
+impl Iterator for Overflights {
+    type Item = Overflight;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let search = self.search;
+        let body_radius = search.body.equatorial_radius;
+        while self.cursor <= self.end {
+            let window = self.next_window(self.cursor)?;
+            let pass = self.next_pass(window)?;
+            if pass > self.end + self.period / 8.0 {
+                return None;
+            }
+            // Only passes inside a plane window can reach the target;
+            // otherwise skip ahead to the next window
+            if self.next_window(pass)? > pass + 1e-6 {
+                self.cursor = pass.max(self.cursor + 1e-3);
+                continue;
+            }
+            self.cursor = pass + self.period / 2.0;
+
+            let span = self.period / 8.0;
+            let time = self.closest_approach((pass - span).max(self.start), pass + span)?;
+            if time > self.end {
+                return None;
+            }
+            let (angle, radius) = self.separation(time)?;
+            if angle <= search.reach.central_angle(radius, body_radius) {
+                return Some(Overflight {
+                    time: Seconds(time),
+                    ground_distance: body_radius * angle,
+                    off_nadir: off_nadir_angle(angle, radius, body_radius),
+                });
+            }
+        }
+        None
+    }
+}
+
+/// An area of interest, bounded in geodetic latitude and longitude
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Region<'a> {
+    /// Latitude and longitude limits, in radians. A box whose `west`
+    /// edge lies east of its `east` edge spans the antimeridian.
+    BoundingBox {
+        south: Real,
+        north: Real,
+        west: Real,
+        east: Real,
+    },
+    /// A closed polygon of three or more vertices, with edges drawn
+    /// straight in latitude and longitude. It may span the
+    /// antimeridian but must not enclose a pole.
+    Polygon(&'a [GroundPoint]),
+}
+
+impl Region<'_> {
+    /// Whether the region contains `point`; points on the edge of a
+    /// bounding box count as inside
+    pub fn contains(&self, point: &GroundPoint) -> bool {
+        match *self {
+            Region::BoundingBox {
+                south,
+                north,
+                west,
+                east,
+            } => {
+                point.latitude >= south
+                    && point.latitude <= north
+                    && wrap_two_pi(point.longitude - west) <= wrap_two_pi(east - west)
+            }
+            Region::Polygon(vertices) => {
+                if vertices.len() < 3 {
+                    return false;
+                }
+                // Unwrap longitudes along the boundary and bring the point
+                // onto the same branch, so the antimeridian is no seam
+                let origin = vertices[0].longitude;
+                let x = origin + wrap_pi(point.longitude - origin);
+                let y = point.latitude;
+                let mut inside = false;
+                let mut previous = vertices[vertices.len() - 1];
+                let mut x0 = origin + wrap_pi(previous.longitude - origin);
+                for vertex in vertices {
+                    let x1 = x0 + wrap_pi(vertex.longitude - previous.longitude);
+                    let (y0, y1) = (previous.latitude, vertex.latitude);
+                    // Crossing-number test along a ray toward +longitude
+                    if (y0 > y) != (y1 > y) && x < x0 + (y - y0) * (x1 - x0) / (y1 - y0) {
+                        inside = !inside;
+                    }
+                    previous = *vertex;
+                    x0 = x1;
+                }
+                inside
+            }
+        }
+    }
+
+    /// Whether the inertial `position` lies over the region at `time`
+    /// on `body`, judged by its geodetic sub-satellite point
+    pub fn overflown_by(&self, body: &RotatingBody, time: Seconds, position: Vec3) -> bool {
+        let point = geodetic_subsatellite_point(
+            position,
+            body.angle(time),
+            body.equatorial_radius,
+            body.flattening,
+        );
+        self.contains(&point)
+    }
+}
+
+/// A stretch of time the sub-satellite point spends inside a region,
+/// timed from the epoch of the elements
+pub type RegionVisit = Interval;
+
+/// A region on a rotating ellipsoid, and how finely to look for
+/// the satellite crossing its boundary
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RegionSearch<'a> {
+    pub region: Region<'a>,
+    /// The body, with time zero at the epoch of the elements
+    pub body: RotatingBody,
+    /// Sampling interval; visits shorter than this can be missed.
+    /// Crossings found between samples are refined to a millisecond.
+    pub step: Seconds,
+}
+
+impl<'a> RegionSearch<'a> {
+    /// A search over a region on the Earth, sampled every 10 s
+    pub fn earth(region: Region<'a>, greenwich_angle: Real) -> Self {
+        RegionSearch {
+            region,
+            body: RotatingBody::earth(greenwich_angle),
+            step: Seconds(10.0),
+        }
+    }
+
+    /// Visits of the sub-satellite point to the region between `start`
+    /// and `end` after the epoch of `elements`, in time order;
+    /// visits under way at `start` or `end` are cut off there. The
+    /// orbit must be elliptic and the step positive.
+    pub fn visits(
+        &self,
+        elements: &KeplerianElements,
+        mu: GravParam,
+        start: Seconds,
+        end: Seconds,
+    ) -> Result<Visits<impl Fn(Seconds) -> bool + 'a>, AlmagestError> {
+        if self.step <= Seconds::ZERO {
+            return Err(AlmagestError::InvalidInput("Step must be positive"));
+        }
+        if elements.semi_major_axis <= Meters::ZERO {
+            return Err(AlmagestError::InvalidEccentricity(
+                "Region visits need an elliptic orbit",
+            ));
+        }
+        let (search, elements) = (*self, *elements);
+        // Kepler's equation always solves on an elliptic orbit, so the
+        // propagation cannot fail once the orbit has been checked
+        let inside = move |t: Seconds| {
+            elements.propagate(t, mu).is_ok_and(|now| {
+                let position = now.to_state(mu).position;
+                search.region.overflown_by(&search.body, t, position)
+            })
+        };
+        Ok(visits(inside, start, end, self.step))
+    }
+
+    /// Total time spent over the region between `start` and `end`
+    pub fn time_in_region(
+        &self,
+        elements: &KeplerianElements,
+        mu: GravParam,
+        start: Seconds,
+        end: Seconds,
+    ) -> Result<Seconds, AlmagestError> {
+        let visits = self.visits(elements, mu, start, end)?;
+        Ok(visits.statistics().total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::utils::{Eccentricity, Radians};
+    use approx::assert_relative_eq;
+    use std::vec::Vec;
+
+    fn sun_synchronous() -> KeplerianElements {
+        KeplerianElements {
+            semi_major_axis: EARTH_RADIUS + Meters(700_000.0),
+            eccentricity: Eccentricity::new(0.001).unwrap(),
+            inclination: Radians(98.2_f64.to_radians()),
+            raan: Radians(0.3),
+            argument_of_periapsis: Radians(1.0),
+            true_anomaly: Radians(0.0),
+        }
+    }
+
+    // Closest approach by brute-force sampling, for comparison
+    fn sampled(search: &OverflightSearch, elements: &KeplerianElements, end: Real) -> Vec<Real> {
+        let mut passes = Vec::new();
+        let reach = |t: Real| {
+            let state = elements
+                .propagate(Seconds(t), EARTH.mu)
+                .unwrap()
+                .to_state(EARTH.mu);
+            let point = subsatellite_point(state.position, search.body.angle(Seconds(t)));
+            let r = Meters(state.position.norm());
+            central_angle(&point, &search.target)
+                - search.reach.central_angle(r, search.body.equatorial_radius)
+        };
+        let step = 5.0;
+        let mut t = 0.0;
+        let mut inside = false;
+        let mut best = (Real::INFINITY, 0.0);
+        while t <= end {
+            let m = reach(t);
+            if m <= 0.0 {
+                inside = true;
+                if m < best.0 {
+                    best = (m, t);
+                }
+            } else if inside {
+                passes.push(best.1);
+                inside = false;
+                best = (Real::INFINITY, 0.0);
+            }
+            t += step;
+        }
+        passes
+    }
+
+    #[test]
+    fn subsatellite_point_follows_rotation() {
+        let p = subsatellite_point(Vec3::new(7.0e6, 0.0, 7.0e6), PI / 2.0);
+        assert_relative_eq!(p.latitude, PI / 4.0, epsilon = 1e-12);
+        assert_relative_eq!(p.longitude, -PI / 2.0, epsilon = 1e-12);
+    }
+
+    // Vallado Example 3-5
+    #[test]
+    fn sidereal_angle() {
+        let jd = 2_448_855.0 + (12.0 + 14.0 / 60.0 - 12.0) / 24.0;
+        assert_relative_eq!(
+            greenwich_sidereal_angle(jd).to_degrees(),
+            152.578_787_886,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn central_angles() {
+        let a = GroundPoint::new(0.0, 0.0);
+        let b = GroundPoint::new(0.0, 1.0);
+        assert_relative_eq!(central_angle(&a, &b), 1.0, epsilon = 1e-12);
+        let pole = GroundPoint::new(PI / 2.0, 2.0);
+        assert_relative_eq!(central_angle(&a, &pole), PI / 2.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn off_nadir_reach_round_trips() {
+        let r = EARTH_RADIUS + Meters(500_000.0);
+        let eta = 0.4;
+        let lambda = Reach::OffNadir(eta).central_angle(r, EARTH_RADIUS);
+        assert_relative_eq!(
+            off_nadir_angle(lambda, r, EARTH_RADIUS),
+            eta,
+            epsilon = 1e-12
+        );
+        // Past the horizon, reach stops at the horizon
+        let wide = Reach::OffNadir(1.5).central_angle(r, EARTH_RADIUS);
+        assert_relative_eq!(wide, libm::acos(EARTH_RADIUS / r), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn matches_dense_sampling() {
+        let end = 3.0 * 86_400.0;
+        let low = KeplerianElements {
+            inclination: Radians(0.4),
+            eccentricity: Eccentricity::new(0.05).unwrap(),
+            ..sun_synchronous()
+        };
+        // Mid-latitude, near the northern turning point, and equatorial
+        for (elements, target) in [
+            (sun_synchronous(), GroundPoint::new(0.7, -1.3)),
+            (sun_synchronous(), GroundPoint::new(1.4, 2.0)),
+            (low, GroundPoint::new(0.0, 0.5)),
+            (low, GroundPoint::new(-0.42, 0.5)),
+        ] {
+            for reach in [
+                Reach::GroundDistance(Meters(400_000.0)),
+                Reach::OffNadir(0.5),
+            ] {
+                let search = OverflightSearch::earth(target, reach, 0.2);
+                let found: Vec<Overflight> = search
+                    .overflights(&elements, EARTH.mu, Seconds::ZERO, Seconds(end))
+                    .unwrap()
+                    .collect();
+                let expected = sampled(&search, &elements, end);
+                assert!(!expected.is_empty());
+                assert_eq!(found.len(), expected.len());
+                for (pass, t) in found.iter().zip(expected) {
+                    assert_relative_eq!(pass.time.value(), t, epsilon = 10.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unreachable_latitudes_have_no_passes() {
+        let elements = KeplerianElements {
+            inclination: Radians(0.5),
+            ..sun_synchronous()
+        };
+        let target = GroundPoint::new(1.2, 0.0);
+        let search = OverflightSearch::earth(target, Reach::GroundDistance(Meters(100_000.0)), 0.0);
+        let mut passes = search
+            .overflights(&elements, EARTH.mu, Seconds::ZERO, Seconds(86_400.0))
+            .unwrap();
+        assert!(passes.next().is_none());
+    }
+
+    #[test]
+    fn rejects_orbits_above_synchronous() {
+        let elements = KeplerianElements {
+            semi_major_axis: Meters(45_000_000.0),
+            ..sun_synchronous()
+        };
+        let search = OverflightSearch::earth(GroundPoint::new(0.0, 0.0), Reach::OffNadir(0.1), 0.0);
+        assert!(
+            search
+                .overflights(&elements, EARTH.mu, Seconds::ZERO, Seconds(86_400.0))
+                .is_err()
+        );
+
+        // Without rotation the target never turns under the plane
+        let still = OverflightSearch {
+            body: RotatingBody {
+                rotation_rate: 0.0,
+                ..search.body
+            },
+            ..search
+        };
+        let low = sun_synchronous();
+        assert!(
+            still
+                .overflights(&low, EARTH.mu, Seconds::ZERO, Seconds(86_400.0))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn geodetic_latitude_exceeds_geocentric() {
+        let f = EARTH_FLATTENING;
+        // On the equator and at the pole the two agree
+        let equator = geodetic_subsatellite_point(Vec3::new(7.0e6, 0.0, 0.0), 0.0, EARTH_RADIUS, f);
+        assert_relative_eq!(equator.latitude, 0.0);
+        let pole = geodetic_subsatellite_point(Vec3::new(0.0, 0.0, 7.0e6), 0.0, EARTH_RADIUS, f);
+        assert_relative_eq!(pole.latitude, PI / 2.0, epsilon = 1e-12);
+
+        // A point on the surface at geodetic latitude φ
+        let phi: Real = 0.8;
+        let e2 = f * (2.0 - f);
+        let n = EARTH_RADIUS.value() / sqrt(1.0 - e2 * sin(phi) * sin(phi));
+        let surface = Vec3::new(n * cos(phi), 0.0, n * (1.0 - e2) * sin(phi));
+        let point = geodetic_subsatellite_point(surface, 0.0, EARTH_RADIUS, f);
+        assert_relative_eq!(point.latitude, phi, epsilon = 1e-12);
+        assert!(point.latitude > subsatellite_point(surface, 0.0).latitude);
+    }
+
+    #[test]
+    fn geodetic_solver_reports_convergence() {
+        let surface = Vec3::new(4.0e6, 1.0e6, 4.8e6);
+        let f = EARTH_FLATTENING;
+        let (point, report) =
+            geodetic_subsatellite_point_with(surface, 0.3, EARTH_RADIUS, f, &GEODETIC_SOLVER)
+                .unwrap();
+        assert_eq!(
+            point,
+            geodetic_subsatellite_point(surface, 0.3, EARTH_RADIUS, f)
+        );
+        assert!(report.iterations <= GEODETIC_SOLVER.max_iterations);
+        assert!(report.residual < 1e-13);
+        let starved = SolverConfig {
+            tolerance: 0.0,
+            max_iterations: 3,
+        };
+        assert!(geodetic_subsatellite_point_with(surface, 0.3, EARTH_RADIUS, f, &starved).is_err());
+    }
+
+    #[test]
+    fn bounding_boxes() {
+        let deg = |lat: Real, lon: Real| GroundPoint::new(lat.to_radians(), lon.to_radians());
+        let europe = Region::BoundingBox {
+            south: 35.0_f64.to_radians(),
+            north: 60.0_f64.to_radians(),
+            west: -10.0_f64.to_radians(),
+            east: 30.0_f64.to_radians(),
+        };
+        assert!(europe.contains(&deg(48.0, 2.0)));
+        assert!(!europe.contains(&deg(48.0, 40.0)));
+        assert!(!europe.contains(&deg(20.0, 2.0)));
+
+        let pacific = Region::BoundingBox {
+            south: -30.0_f64.to_radians(),
+            north: 0.0,
+            west: 170.0_f64.to_radians(),
+            east: -170.0_f64.to_radians(),
+        };
+        assert!(pacific.contains(&deg(-10.0, 175.0)));
+        assert!(pacific.contains(&deg(-10.0, -175.0)));
+        assert!(!pacific.contains(&deg(-10.0, 0.0)));
+    }
+
+    #[test]
+    fn polygons() {
+        let deg = |lat: Real, lon: Real| GroundPoint::new(lat.to_radians(), lon.to_radians());
+        // An L shape, concave at its inner corner
+        let l_shape = [
+            deg(0.0, 0.0),
+            deg(0.0, 20.0),
+            deg(10.0, 20.0),
+            deg(10.0, 10.0),
+            deg(30.0, 10.0),
+            deg(30.0, 0.0),
+        ];
+        let region = Region::Polygon(&l_shape);
+        assert!(region.contains(&deg(5.0, 15.0)));
+        assert!(region.contains(&deg(20.0, 5.0)));
+        assert!(!region.contains(&deg(20.0, 15.0)));
+        assert!(!region.contains(&deg(5.0, -5.0)));
+
+        // A triangle across the antimeridian
+        let triangle = [deg(-10.0, 170.0), deg(-10.0, -170.0), deg(10.0, 180.0)];
+        let region = Region::Polygon(&triangle);
+        assert!(region.contains(&deg(0.0, 179.0)));
+        assert!(region.contains(&deg(0.0, -179.0)));
+        assert!(!region.contains(&deg(0.0, 0.0)));
+        assert!(!Region::Polygon(&triangle[..2]).contains(&deg(0.0, 180.0)));
+    }
+
+    #[test]
+    fn time_over_a_region() {
+        let elements = sun_synchronous();
+        let region = Region::BoundingBox {
+            south: 0.3,
+            north: 0.9,
+            west: -0.5,
+            east: 0.8,
+        };
+        let search = RegionSearch::earth(region, 0.2);
+        let end = 86_400.0;
+        let visits: Vec<RegionVisit> = search
+            .visits(&elements, EARTH.mu, Seconds::ZERO, Seconds(end))
+            .unwrap()
+            .collect();
+        assert!(!visits.is_empty());
+
+        // Compare with one-second sampling
+        let mut sampled = 0.0;
+        let mut t = 0.5;
+        while t < end {
+            let state = elements
+                .propagate(Seconds(t), EARTH.mu)
+                .unwrap()
+                .to_state(EARTH.mu);
+            let angle = 0.2 + EARTH_ROTATION_RATE * t;
+            let point =
+                geodetic_subsatellite_point(state.position, angle, EARTH_RADIUS, EARTH_FLATTENING);
+            if region.contains(&point) {
+                sampled += 1.0;
+            }
+            t += 1.0;
+        }
+        let total = search
+            .time_in_region(&elements, EARTH.mu, Seconds::ZERO, Seconds(end))
+            .unwrap();
+        assert_relative_eq!(total.value(), sampled, epsilon = 1.0 * visits.len() as Real);
+        for pair in visits.windows(2) {
+            assert!(pair[0].end < pair[1].start);
+        }
+
+        // A step that could never advance is refused rather than hung on
+        let stuck = RegionSearch {
+            step: Seconds::ZERO,
+            ..search
+        };
+        assert!(
+            stuck
+                .visits(&elements, EARTH.mu, Seconds::ZERO, Seconds(end))
+                .is_err()
+        );
+        let escape = KeplerianElements {
+            semi_major_axis: Meters(-7_000_000.0),
+            eccentricity: Eccentricity::new(1.5).unwrap(),
+            ..elements
+        };
+        assert!(
+            search
+                .time_in_region(&escape, EARTH.mu, Seconds::ZERO, Seconds(end))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Conservation checks for propagated trajectories.
+//!
+//! A perfect integrator of the two-body problem conserves specific
+//! orbital energy and specific angular momentum; one of the circular
+//! restricted three-body problem conserves the Jacobi constant. Feeding
+//! the states of any ephemeris through these checks measures how far
+//! the integrator wandered from those invariants over the run.
+
+use libm::sqrt;
+
+use crate::state::StateVector;
+use crate::utils::{GravParam, Real};
+use crate::vectors::Vec3;
+
+/// Specific orbital energy, ε = v²/2 − μ/r, in m²/s²
+pub fn specific_energy(state: &StateVector, mu: GravParam) -> Real {
+    state.velocity.norm_squared() / 2.0 - mu.value() / state.position.norm()
+}
+
+/// Specific angular momentum, h = r × v, in m²/s
+pub fn specific_angular_momentum(state: &StateVector) -> Vec3 {
+    state.position.cross(state.velocity)
+}
+
+/// Jacobi constant of a CR3BP state.
+///
+/// The state must be in the normalized rotating frame: unit distance
+/// between the primaries, unit mean motion, the larger primary at
+/// (−μ, 0, 0) and the smaller at (1 − μ, 0, 0), where `mass_ratio`
+/// is μ = m₂ / (m₁ + m₂).
+pub fn jacobi_constant(state: &StateVector, mass_ratio: Real) -> Real {
+    let r = state.position;
+    let r1 = (r - Vec3::new(-mass_ratio, 0.0, 0.0)).norm();
+    let r2 = (r - Vec3::new(1.0 - mass_ratio, 0.0, 0.0)).norm();
+    r.x * r.x + r.y * r.y + 2.0 * (1.0 - mass_ratio) / r1 + 2.0 * mass_ratio / r2
+        - state.velocity.norm_squared()
+}
+
+/// How far a conserved quantity drifted from its initial value
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DriftStats {
+    /// Value of the quantity at the first state
+    pub initial: Real,
+    /// Largest absolute deviation from the initial value
+    pub max_abs: Real,
+    /// Largest deviation relative to the magnitude of the initial value
+    pub max_relative: Real,
+    /// Root-mean-square deviation over all states
+    pub rms: Real,
+    /// Deviation at the last state
+    pub last: Real,
+    /// Number of states examined
+    pub samples: usize,
+}
+
+/// Drift of the two-body invariants along an ephemeris
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoBodyDrift {
+    pub energy: DriftStats,
+    /// Magnitude of the deviation of the h vector from its initial
+    /// value, so changes in the orbit plane are counted too
+    pub angular_momentum: DriftStats,
+}
+
+/// Evaluate energy and angular momentum conservation along
+/// an ephemeris; `None` if it contains no states
+pub fn two_body_drift<I>(states: I, mu: GravParam) -> Option<TwoBodyDrift>
+where
+    I: IntoIterator<Item = StateVector>,
+{
+    let mut states = states.into_iter();
+    let first = states.next()?;
+    let e0 = specific_energy(&first, mu);
+    let h0 = specific_angular_momentum(&first);
+    let mut energy = Accumulator::new(e0);
+    let mut momentum = Accumulator::new(h0.norm());
+    for state in states {
+        energy.push(specific_energy(&state, mu) - e0);
+        momentum.push((specific_angular_momentum(&state) - h0).norm());
+    }
+    Some(TwoBodyDrift {
+        energy: energy.finish(),
+        angular_momentum: momentum.finish(),
+    })
+}
+
+/// Evaluate Jacobi constant conservation along a CR3BP ephemeris
+/// (see [`jacobi_constant`] for the expected frame and units);
+/// `None` if it contains no states
+pub fn jacobi_drift<I>(states: I, mass_ratio: Real) -> Option<DriftStats>
+where
+    I: IntoIterator<Item = StateVector>,
+{
+    let mut states = states.into_iter();
+    let c0 = jacobi_constant(&states.next()?, mass_ratio);
+    let mut jacobi = Accumulator::new(c0);
+    for state in states {
+        jacobi.push(jacobi_constant(&state, mass_ratio) - c0);
+    }
+    Some(jacobi.finish())
+}
+
+// Running statistics over the deviations of one quantity
+struct Accumulator {
+    initial: Real,
+    max_abs: Real,
+    sum_squares: Real,
+    last: Real,
+    samples: usize,
+}
+
+impl Accumulator {
+    fn new(initial: Real) -> Self {
+        // The first state deviates from itself by exactly zero
+        Accumulator {
+            initial,
+            max_abs: 0.0,
+            sum_squares: 0.0,
+            last: 0.0,
+            samples: 1,
+        }
+    }
+
+    fn push(&mut self, deviation: Real) {
+        self.max_abs = self.max_abs.max(deviation.abs());
+        self.sum_squares += deviation * deviation;
+        self.last = deviation;
+        self.samples += 1;
+    }
+
+    fn finish(self) -> DriftStats {
+        let scale = self.initial.abs();
+        DriftStats {
+            initial: self.initial,
+            max_abs: self.max_abs,
+            max_relative: if scale > 0.0 {
+                self.max_abs / scale
+            } else {
+                self.max_abs
+            },
+            rms: sqrt(self.sum_squares / self.samples as Real),
+            last: self.last,
+            samples: self.samples,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use approx::assert_relative_eq;
+    use libm::{cos, sin};
+
+    fn circular_state(radius: Real, angle: Real) -> StateVector {
+        let speed = sqrt(EARTH.mu.value() / radius);
+        StateVector::new(
+            Vec3::new(radius * cos(angle), radius * sin(angle), 0.0),
+            Vec3::new(-speed * sin(angle), speed * cos(angle), 0.0),
+        )
+    }
+
+    #[test]
+    fn energy_of_circular_orbit() {
+        let r = 7_000_000.0;
+        let state = circular_state(r, 0.3);
+        // ε = −μ / 2a
+        assert_relative_eq!(
+            specific_energy(&state, EARTH.mu),
+            -EARTH.mu.value() / (2.0 * r),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn exact_orbit_has_no_drift() {
+        let states = (0..100).map(|i| circular_state(7_000_000.0, i as Real * 0.1));
+        let drift = two_body_drift(states, EARTH.mu).unwrap();
+        assert_eq!(drift.energy.samples, 100);
+        assert!(drift.energy.max_relative < 1e-12);
+        assert!(drift.angular_momentum.max_relative < 1e-12);
+    }
+
+    #[test]
+    fn reports_energy_leak() {
+        // Each state gains a little speed, as a leaky integrator might
+        let states = (0..10).map(|i| {
+            let mut s = circular_state(7_000_000.0, 0.0);
+            s.velocity = s.velocity * (1.0 + i as Real * 1e-6);
+            s
+        });
+        let drift = two_body_drift(states, EARTH.mu).unwrap();
+        assert!(drift.energy.max_abs > 0.0);
+        assert!(drift.energy.last > 0.0);
+        assert_eq!(drift.energy.max_abs, drift.energy.last);
+        assert!(drift.energy.rms < drift.energy.max_abs);
+        assert!(drift.angular_momentum.max_abs > 0.0);
+    }
+
+    #[test]
+    fn empty_ephemeris_has_no_report() {
+        assert!(two_body_drift(core::iter::empty(), EARTH.mu).is_none());
+        assert!(jacobi_drift(core::iter::empty(), 0.012_15).is_none());
+    }
+
+    #[test]
+    fn jacobi_constant_at_rest_on_axis() {
+        // A body at rest midway between equal masses
+        let state = StateVector::new(Vec3::ZERO, Vec3::ZERO);
+        assert_relative_eq!(jacobi_constant(&state, 0.5), 4.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn jacobi_drift_tracks_deviation() {
+        let mu = 0.012_15;
+        let base = StateVector::new(Vec3::new(0.8, 0.1, 0.0), Vec3::new(0.0, 0.2, 0.0));
+        let nudged = StateVector::new(base.position, base.velocity * 1.01);
+        let drift = jacobi_drift([base, base, nudged], mu).unwrap();
+        let expected = jacobi_constant(&nudged, mu) - jacobi_constant(&base, mu);
+        assert_relative_eq!(drift.last, expected, epsilon = 1e-15);
+        assert_eq!(drift.samples, 3);
+    }
+}
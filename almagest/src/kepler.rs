@@ -1,40 +1,145 @@
-use libm::sqrt;
+use libm::{acos, cos, sin, sqrt};
 
-use crate::utils::{Eccentricity, Meters, Real};
+use crate::error::AlmagestError;
+use crate::float::Float;
+use crate::solver::{Convergence, SolverConfig};
+use crate::state::StateVector;
+use crate::utils::{
+    Eccentricity, GravParam, Meters, MetersPerSecond, Radians, RadiansPerSecond, Real, Seconds, TAU,
+};
+use crate::vectors::Vec3;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     x: Meters,
     y: Meters,
 }
-// impl Point {
-//     pub fn new(x: Meters, y: Meters) -> Self {
-//         Point(x, y)
-//     }
-// }
+impl Point {
+    pub const ORIGIN: Self = Point::new(Meters::ZERO, Meters::ZERO);
 
+    pub const fn new(x: Meters, y: Meters) -> Self {
+        Point { x, y }
+    }
+
+    pub fn x(&self) -> Meters {
+        self.x
+    }
+
+    pub fn y(&self) -> Meters {
+        self.y
+    }
+}
+
+/// A point in space, in meters
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point3 {
+    x: Meters,
+    y: Meters,
+    z: Meters,
+}
+
+impl Point3 {
+    pub const ORIGIN: Self = Point3::new(Meters::ZERO, Meters::ZERO, Meters::ZERO);
+
+    pub const fn new(x: Meters, y: Meters, z: Meters) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn x(&self) -> Meters {
+        self.x
+    }
+
+    pub fn y(&self) -> Meters {
+        self.y
+    }
+
+    pub fn z(&self) -> Meters {
+        self.z
+    }
+
+    /// Straight-line distance to `other`
+    pub fn distance_to(&self, other: &Point3) -> Meters {
+        Meters((other.to_vec3() - self.to_vec3()).norm())
+    }
+
+    /// The point `offset` meters away
+    pub fn translated(&self, offset: Vec3) -> Point3 {
+        Point3::new(
+            self.x + Meters(offset.x),
+            self.y + Meters(offset.y),
+            self.z + Meters(offset.z),
+        )
+    }
+
+    /// Position from the origin, in meters
+    pub fn to_vec3(&self) -> Vec3 {
+        Vec3::new(self.x.value(), self.y.value(), self.z.value())
+    }
+}
+
+/// The point in the x–y plane
+impl From<Point> for Point3 {
+    fn from(point: Point) -> Self {
+        Point3::new(point.x, point.y, Meters::ZERO)
+    }
+}
+
+impl From<Vec3> for Point3 {
+    fn from(position: Vec3) -> Self {
+        Point3::ORIGIN.translated(position)
+    }
+}
+
+/// A conic lying in the plane through its primary focus parallel to
+/// x–y, with periapsis along x unless turned by
+/// [`Ellipse::with_argument_of_periapsis`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ellipse {
     // Eccentricity
     e: Eccentricity,
     // Primary focus
-    f: Point,
+    f: Point3,
     // Radius of periapsis
     r_p: Meters,
+    // Argument of periapsis: angle from the x axis to periapsis
+    w: Radians,
 }
 
 impl Ellipse {
-    pub fn new(e: Eccentricity, f: Point, r_p: Meters) -> Self {
-        Ellipse { e, f, r_p }
+    pub fn new(e: Eccentricity, f: impl Into<Point3>, r_p: Meters) -> Self {
+        Ellipse {
+            e,
+            f: f.into(),
+            r_p,
+            w: Radians::ZERO,
+        }
     }
 
     /// Construct an ellipse from periapsis and apoapsis distances.
-    pub fn from_periapsis_apoapsis(r_p: Meters, r_a: Meters, f: Point) -> Self {
-        let e = (r_a.value() - r_p.value()) / (r_a.value() + r_p.value());
-        Ellipse {
-            e: Eccentricity::new(e).unwrap(),
-            f,
-            r_p,
+    pub fn from_periapsis_apoapsis(
+        r_p: Meters,
+        r_a: Meters,
+        f: impl Into<Point3>,
+    ) -> Result<Self, AlmagestError> {
+        if r_p.value() <= 0.0 {
+            return Err(AlmagestError::InvalidInput("Periapsis must be positive"));
+        }
+        if r_a < r_p {
+            return Err(AlmagestError::InvalidInput(
+                "Apoapsis must not be below periapsis",
+            ));
         }
+        let e = (r_a.value() - r_p.value()) / (r_a.value() + r_p.value());
+        Ok(Ellipse::new(Eccentricity::new(e)?, f, r_p))
+    }
+
+    /// The same conic turned in its plane so periapsis lies `w` from
+    /// the x axis
+    pub fn with_argument_of_periapsis(self, w: Radians) -> Self {
+        Ellipse { w, ..self }
     }
 
     pub fn eccentricity(&self) -> Eccentricity {
@@ -42,10 +147,22 @@ impl Ellipse {
     }
 
     /// The gravitational center of attraction
-    pub fn primary_focus(&self) -> Point {
+    pub fn primary_focus(&self) -> Point3 {
         self.f
     }
 
+    /// The empty focus, `2ae` from the primary toward apoapsis
+    pub fn secondary_focus(&self) -> Point3 {
+        let offset = -2.0 * self.focal_distance().value();
+        let (s, c) = self.w.sin_cos();
+        self.f.translated(Vec3::new(offset * c, offset * s, 0.0))
+    }
+
+    /// Angle from the x axis to periapsis
+    pub fn argument_of_periapsis(&self) -> Radians {
+        self.w
+    }
+
     /// The distance from the primary focus to the
     /// nearest edge of the ellipse, along the
     /// semi-major axis
@@ -82,14 +199,130 @@ impl Ellipse {
         Meters(self.semi_major_axis().value() * (1.0 + self.eccentricity().value()))
     }
 
-    /// The distance between foci;
-    /// half of the this value is denoted in formula
-    /// by `c`
+    /// The distance from the center to either focus,
+    /// denoted in formula by `c = ae`; the foci are
+    /// `2c` apart
     pub fn focal_distance(&self) -> Meters {
         Meters(self.eccentricity().value() * self.semi_major_axis().value())
     }
+
+    /// The semi-latus rectum, `p = r_p (1 + e)`; the
+    /// distance from the primary focus at 90° from periapsis
+    pub fn semi_latus_rectum(&self) -> Meters {
+        self.periapsis() * (1.0 + self.eccentricity().value())
+    }
+
+    /// Distance from the primary focus at true anomaly `nu`, from
+    /// the conic equation r = p / (1 + e cos ν). On an open orbit it
+    /// is negative beyond the asymptotes, where 1 + e cos ν ≤ 0.
+    pub fn radius_at(&self, nu: Radians) -> Meters {
+        self.semi_latus_rectum() / (1.0 + self.eccentricity().value() * nu.cos())
+    }
+
+    /// Position at true anomaly `nu`: offset from the primary focus
+    /// by r at ω + ν from the x axis. Unturned, the offset is in
+    /// perifocal coordinates, with x toward periapsis and y 90° ahead
+    /// in the direction of motion.
+    pub fn point_at(&self, nu: Radians) -> Point3 {
+        let r = self.radius_at(nu).value();
+        let (s, c) = (self.w + nu).sin_cos();
+        self.f.translated(Vec3::new(r * c, r * s, 0.0))
+    }
+
+    /// `n` points evenly spaced in true anomaly around the orbit,
+    /// starting at periapsis; the loop is left open. On an open
+    /// orbit they span the arc between the asymptotes, ends excluded.
+    pub fn sample(&self, n: usize) -> ConicSamples {
+        let e = self.eccentricity().value();
+        if e < 1.0 {
+            ConicSamples::new(*self, Radians::ZERO, Radians(TAU / n as Real), n)
+        } else {
+            let limit = Radians::acos(-1.0 / e);
+            let step = limit * 2.0 / (n + 1) as Real;
+            ConicSamples::new(*self, step - limit, step, n)
+        }
+    }
+
+    /// `n` points evenly spaced in true anomaly from `nu_start` to
+    /// `nu_end`, both included
+    pub fn sample_range(&self, nu_start: Radians, nu_end: Radians, n: usize) -> ConicSamples {
+        let step = if n > 1 {
+            (nu_end - nu_start) / (n - 1) as Real
+        } else {
+            Radians::ZERO
+        };
+        ConicSamples::new(*self, nu_start, step, n)
+    }
+
+    /// Speed at a distance `r` from the primary focus, from vis-viva:
+    /// v² = μ(2/r − 1/a). Parabolas (e = 1) and hyperbolas (e > 1)
+    /// work too, with a infinite or negative.
+    pub fn speed_at(&self, r: Meters, mu: GravParam) -> MetersPerSecond {
+        ((mu / r) * 2.0 - mu / self.semi_major_axis()).sqrt()
+    }
+
+    /// The fastest speed on the orbit
+    pub fn periapsis_speed(&self, mu: GravParam) -> MetersPerSecond {
+        self.speed_at(self.periapsis(), mu)
+    }
+
+    /// The slowest speed on the orbit; `None` if it is open
+    pub fn apoapsis_speed(&self, mu: GravParam) -> Option<MetersPerSecond> {
+        if self.eccentricity().value() >= 1.0 {
+            return None;
+        }
+        Some(self.speed_at(self.apoapsis(), mu))
+    }
+}
+
+/// Iterator over points along a conic, created by
+/// [`Ellipse::sample`] and [`Ellipse::sample_range`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConicSamples {
+    ellipse: Ellipse,
+    start: Radians,
+    step: Radians,
+    index: usize,
+    count: usize,
 }
 
+impl ConicSamples {
+    fn new(ellipse: Ellipse, start: Radians, step: Radians, count: usize) -> Self {
+        ConicSamples {
+            ellipse,
+            start,
+            step,
+            index: 0,
+            count,
+        }
+    }
+
+    /// True anomaly of the next point
+    fn anomaly(&self) -> Radians {
+        self.start + self.step * self.index as Real
+    }
+}
+
+impl Iterator for ConicSamples {
+    type Item = Point3;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.count {
+            return None;
+        }
+        let point = self.ellipse.point_at(self.anomaly());
+        self.index += 1;
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for ConicSamples {}
+
 /// Calculate double the length of the semimajor axis,
 /// using the distance from the primary focus to a point
 /// on the orbit as well as the distance from the secondary
@@ -112,18 +345,366 @@ pub fn calc_2c(r_f: Meters, r_f_p: Meters) -> Meters {
 
 /// Calculate the eccentricity of an orbit from the lengths
 /// of both foci to a single point on the orbit.
-// TODO: lots of tests to ensure the returned value is never negative
-pub fn calc_ecc(r_f: Meters, r_f_p: Meters) -> Eccentricity {
+pub fn calc_ecc(r_f: Meters, r_f_p: Meters) -> Result<Eccentricity, AlmagestError> {
+    if r_f.value() < 0.0 || r_f_p.value() < 0.0 || r_f.value() + r_f_p.value() <= 0.0 {
+        return Err(AlmagestError::InvalidInput(
+            "Focal distances must be positive",
+        ));
+    }
     let two_a = calc_2a(r_f, r_f_p);
     let two_c = calc_2c(r_f, r_f_p);
     let a = two_a.value() / 2.0;
     let c = two_c.value() / 2.0;
-    Eccentricity::new(c / a).unwrap()
+    Eccentricity::new(c / a)
+}
+
+/// Solve Kepler's equation, M = E − e sin E, for the eccentric
+/// anomaly of an elliptic orbit (Vallado, Algorithm 2).
+/// Angles are in radians; the result lies in (−π, π].
+pub fn solve_kepler<T: Float>(mean_anomaly: T, e: T) -> Result<T, AlmagestError> {
+    solve_kepler_with(mean_anomaly, e, &SolverConfig::default()).map(|(ecc_anomaly, _)| ecc_anomaly)
+}
+
+/// As [`solve_kepler`], stopping as `config` says; the residual is
+/// |E − e sin E − M| in radians
+pub fn solve_kepler_with<T: Float>(
+    mean_anomaly: T,
+    e: T,
+    config: &SolverConfig<T>,
+) -> Result<(T, Convergence<T>), AlmagestError> {
+    if !(T::ZERO..T::ONE).contains(&e) {
+        return Err(AlmagestError::InvalidEccentricity(
+            "Kepler's equation needs an elliptic eccentricity",
+        ));
+    }
+    let m = wrap_pi(mean_anomaly);
+    let mut ecc_anomaly = if m < T::ZERO { m - e } else { m + e };
+    for iteration in 1..=config.max_iterations {
+        let step = (ecc_anomaly - e * ecc_anomaly.sin() - m) / (T::ONE - e * ecc_anomaly.cos());
+        ecc_anomaly -= step;
+        if step.abs() < config.tolerance {
+            let residual = (ecc_anomaly - e * ecc_anomaly.sin() - m).abs();
+            return Ok((
+                ecc_anomaly,
+                Convergence {
+                    iterations: iteration,
+                    residual,
+                },
+            ));
+        }
+    }
+    Err(AlmagestError::NoConvergence(
+        "Kepler's equation failed to converge",
+    ))
+}
+
+/// A method for solving Kepler's equation. Newton's method iterates
+/// to convergence; Markley's and Mikkola's start from a cubic
+/// approximation and apply one high-order correction, so they cost
+/// the same for every input, at a few ulps of accuracy.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KeplerSolver {
+    /// Newton-Raphson iteration (Vallado, Algorithm 2)
+    #[default]
+    Newton,
+    /// Markley (1995), "Kepler equation solver"
+    Markley,
+    /// Mikkola (1987), "A cubic approximation for Kepler's equation"
+    Mikkola,
+}
+
+impl KeplerSolver {
+    /// Eccentric anomaly for a mean anomaly, as [`solve_kepler`]
+    pub fn solve<T: Float>(&self, mean_anomaly: T, e: T) -> Result<T, AlmagestError> {
+        if !(T::ZERO..T::ONE).contains(&e) {
+            return Err(AlmagestError::InvalidEccentricity(
+                "Kepler's equation needs an elliptic eccentricity",
+            ));
+        }
+        let m = wrap_pi(mean_anomaly);
+        match self {
+            KeplerSolver::Newton => solve_kepler(m, e),
+            // Both are set up for M ≥ 0; E(−M) = −E(M)
+            KeplerSolver::Markley => Ok(markley(m.abs(), e).copysign(m)),
+            KeplerSolver::Mikkola => Ok(mikkola(m.abs(), e).copysign(m)),
+        }
+    }
+}
+
+// Markley's starter, for 0 ≤ M ≤ π
+fn markley<T: Float>(m: T, e: T) -> T {
+    let c = T::from_real;
+    let (one, pi) = (T::ONE, T::PI);
+    let pi2 = pi * pi;
+    let alpha = (c(3.0) * pi2 + c(1.6) * pi * (pi - m) / (one + e)) / (pi2 - c(6.0));
+    let d = c(3.0) * (one - e) + alpha * e;
+    let q = c(2.0) * alpha * d * (one - e) - m * m;
+    let r = c(3.0) * alpha * d * (d - one + e) * m + m * m * m;
+    let w = (r.abs() + (q * q * q + r * r).sqrt()).powf(c(2.0 / 3.0));
+    let ecc_anomaly = (c(2.0) * r * w / (w * w + w * q + q * q) + m) / d;
+    fifth_order_correction(ecc_anomaly, m, e)
+}
+
+// Mikkola's starter, for 0 ≤ M ≤ π
+fn mikkola<T: Float>(m: T, e: T) -> T {
+    let c = T::from_real;
+    let denominator = c(4.0) * e + c(0.5);
+    let alpha = (T::ONE - e) / denominator;
+    let beta = m / (c(2.0) * denominator);
+    let z = (beta + (beta * beta + alpha * alpha * alpha).sqrt()).cbrt();
+    let mut s = z - alpha / z;
+    s -= c(0.078) * s.powf(c(5.0)) / (T::ONE + e);
+    let ecc_anomaly = m + e * (c(3.0) * s - c(4.0) * s * s * s);
+    fifth_order_correction(ecc_anomaly, m, e)
+}
+
+// One step of the nested fifth-order Householder correction both
+// starters finish with
+fn fifth_order_correction<T: Float>(ecc_anomaly: T, m: T, e: T) -> T {
+    let (s, c) = (e * ecc_anomaly.sin(), e * ecc_anomaly.cos());
+    let (half, sixth) = (T::from_real(0.5), T::from_real(6.0));
+    let f0 = ecc_anomaly - s - m;
+    let f1 = T::ONE - c;
+    let d3 = -f0 / (f1 - half * f0 * s / f1);
+    let d4 = -f0 / (f1 + half * d3 * s + d3 * d3 * c / sixth);
+    let d5 =
+        -f0 / (f1 + half * d4 * s + d4 * d4 * c / sixth - d4 * d4 * d4 * s / T::from_real(24.0));
+    ecc_anomaly + d5
+}
+
+/// Eccentric anomaly from true anomaly, for an elliptic orbit
+pub fn true_to_eccentric<T: Float>(true_anomaly: T, e: T) -> T {
+    ((T::ONE - e * e).sqrt() * true_anomaly.sin()).atan2(e + true_anomaly.cos())
+}
+
+/// True anomaly from eccentric anomaly, for an elliptic orbit
+pub fn eccentric_to_true<T: Float>(ecc_anomaly: T, e: T) -> T {
+    ((T::ONE - e * e).sqrt() * ecc_anomaly.sin()).atan2(ecc_anomaly.cos() - e)
+}
+
+/// Mean anomaly from eccentric anomaly (Kepler's equation)
+pub fn eccentric_to_mean<T: Float>(ecc_anomaly: T, e: T) -> T {
+    ecc_anomaly - e * ecc_anomaly.sin()
+}
+
+/// Classical orbital elements
+///
+/// For circular orbits the argument of periapsis is zero and the true
+/// anomaly is measured from the ascending node (argument of latitude);
+/// for equatorial orbits the ascending node is zero and angles are
+/// measured from the x axis.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeplerianElements {
+    pub semi_major_axis: Meters,
+    pub eccentricity: Eccentricity,
+    pub inclination: Radians,
+    /// Right ascension of the ascending node, Ω
+    pub raan: Radians,
+    /// Argument of periapsis, ω
+    pub argument_of_periapsis: Radians,
+    pub true_anomaly: Radians,
+}
+
+impl KeplerianElements {
+    /// Elements from a Cartesian state (Vallado, Algorithm 9)
+    pub fn from_state(state: &StateVector, mu: GravParam) -> Result<Self, AlmagestError> {
+        const SMALL: Real = 1e-11;
+        let mu = mu.value();
+        let (r, v) = (state.position, state.velocity);
+        let r_norm = r.norm();
+        let h = r.cross(v);
+        if r_norm == 0.0 || h.norm() == 0.0 {
+            return Err(AlmagestError::Coplanarity("State has no angular momentum"));
+        }
+        let node = Vec3::Z.cross(h);
+        let e_vec = (r * (v.norm_squared() - mu / r_norm) - v * r.dot(v)) / mu;
+        let e = e_vec.norm();
+        let energy = v.norm_squared() / 2.0 - mu / r_norm;
+        if (1.0 - e).abs() < SMALL {
+            return Err(AlmagestError::InvalidEccentricity(
+                "Parabolic orbits have no semi-major axis",
+            ));
+        }
+
+        let inclination = acos((h.z / h.norm()).clamp(-1.0, 1.0));
+        let equatorial = node.norm() < SMALL * h.norm();
+        let circular = e < SMALL;
+        let angle = |a: Vec3, b: Vec3| acos((a.dot(b) / (a.norm() * b.norm())).clamp(-1.0, 1.0));
+
+        let raan = if equatorial {
+            0.0
+        } else if node.y < 0.0 {
+            TAU - angle(Vec3::X, node)
+        } else {
+            angle(Vec3::X, node)
+        };
+        // Direction the remaining angles are measured from
+        let reference = if equatorial { Vec3::X } else { node };
+        // Orbit-normal sense of rotation, to resolve quadrants
+        let normal = h.unit();
+        let signed = |from: Vec3, to: Vec3| {
+            let a = angle(from, to);
+            if from.cross(to).dot(normal) < 0.0 {
+                TAU - a
+            } else {
+                a
+            }
+        };
+        let (argument_of_periapsis, true_anomaly) = if circular {
+            (0.0, signed(reference, r))
+        } else {
+            (signed(reference, e_vec), signed(e_vec, r))
+        };
+
+        Ok(KeplerianElements {
+            semi_major_axis: Meters(-mu / (2.0 * energy)),
+            eccentricity: Eccentricity::new(e)?,
+            inclination: Radians(inclination),
+            raan: Radians(raan),
+            argument_of_periapsis: Radians(argument_of_periapsis),
+            true_anomaly: Radians(true_anomaly),
+        })
+    }
+
+    /// Cartesian state from the elements (Vallado, Algorithm 10)
+    pub fn to_state(&self, mu: GravParam) -> StateVector {
+        let e = self.eccentricity.value();
+        let p = self.semi_latus_rectum().value();
+        let nu = self.true_anomaly.value();
+        let r = p / (1.0 + e * cos(nu));
+        let speed = sqrt(mu.value() / p);
+        let (p_hat, q_hat) = self.perifocal_axes();
+        StateVector::new(
+            p_hat * (r * cos(nu)) + q_hat * (r * sin(nu)),
+            p_hat * (-speed * sin(nu)) + q_hat * (speed * (e + cos(nu))),
+        )
+    }
+
+    /// Unit vectors toward periapsis (P) and 90° ahead of it in the
+    /// orbit plane (Q), in the inertial frame
+    pub fn perifocal_axes(&self) -> (Vec3, Vec3) {
+        let (so, co) = self.raan.sin_cos();
+        let (sw, cw) = self.argument_of_periapsis.sin_cos();
+        let (si, ci) = self.inclination.sin_cos();
+        (
+            Vec3::new(co * cw - so * sw * ci, so * cw + co * sw * ci, sw * si),
+            Vec3::new(-co * sw - so * cw * ci, -so * sw + co * cw * ci, cw * si),
+        )
+    }
+
+    /// The orbit's shape in its own plane, focused on the origin
+    pub fn conic(&self) -> Ellipse {
+        let e = self.eccentricity.value();
+        Ellipse::new(
+            self.eccentricity,
+            Point::ORIGIN,
+            self.semi_major_axis * (1.0 - e),
+        )
+    }
+
+    /// `n` positions around the orbit in the inertial frame, as
+    /// [`Ellipse::sample`] spaces them
+    pub fn sample(&self, n: usize) -> impl Iterator<Item = Vec3> {
+        self.orient(self.conic().sample(n))
+    }
+
+    /// `n` positions in the inertial frame from `nu_start` to `nu_end`
+    pub fn sample_range(
+        &self,
+        nu_start: Radians,
+        nu_end: Radians,
+        n: usize,
+    ) -> impl Iterator<Item = Vec3> {
+        self.orient(self.conic().sample_range(nu_start, nu_end, n))
+    }
+
+    // Rotate perifocal points by Ω, i and ω
+    fn orient(&self, samples: ConicSamples) -> impl Iterator<Item = Vec3> {
+        let (p_hat, q_hat) = self.perifocal_axes();
+        samples.map(move |point| p_hat * point.x().value() + q_hat * point.y().value())
+    }
+
+    /// Unit vector along the orbital angular momentum
+    pub fn orbit_normal(&self) -> Vec3 {
+        let (so, co) = self.raan.sin_cos();
+        let (si, ci) = self.inclination.sin_cos();
+        Vec3::new(si * so, -si * co, ci)
+    }
+
+    /// p = a(1 − e²)
+    pub fn semi_latus_rectum(&self) -> Meters {
+        let e = self.eccentricity.value();
+        self.semi_major_axis * (1.0 - e * e)
+    }
+
+    /// Mean motion n = √(μ/a³)
+    pub fn mean_motion(&self, mu: GravParam) -> RadiansPerSecond {
+        let a = self.semi_major_axis.value().abs();
+        RadiansPerSecond(sqrt(mu.value() / (a * a * a)))
+    }
+
+    /// Orbital period
+    pub fn period(&self, mu: GravParam) -> Seconds {
+        Seconds(TAU / self.mean_motion(mu).value())
+    }
+
+    /// Angle from the ascending node to the body, u = ω + ν
+    pub fn argument_of_latitude(&self) -> Radians {
+        (self.argument_of_periapsis + self.true_anomaly).normalized()
+    }
+
+    /// Mean anomaly at the current true anomaly
+    pub fn mean_anomaly(&self) -> Real {
+        let e = self.eccentricity.value();
+        eccentric_to_mean(true_to_eccentric(self.true_anomaly.value(), e), e)
+    }
+
+    /// The elements `dt` later on the unperturbed two-body orbit;
+    /// only elliptic orbits are supported
+    pub fn propagate(&self, dt: Seconds, mu: GravParam) -> Result<Self, AlmagestError> {
+        self.propagate_with(dt, mu, KeplerSolver::Newton)
+    }
+
+    /// As [`KeplerianElements::propagate`], solving Kepler's equation
+    /// with `solver`
+    pub fn propagate_with(
+        &self,
+        dt: Seconds,
+        mu: GravParam,
+        solver: KeplerSolver,
+    ) -> Result<Self, AlmagestError> {
+        let e = self.eccentricity.value();
+        let mean_anomaly = self.mean_anomaly() + (self.mean_motion(mu) * dt).value();
+        let ecc_anomaly = solver.solve(mean_anomaly, e)?;
+        Ok(KeplerianElements {
+            true_anomaly: Radians(wrap_two_pi(eccentric_to_true(ecc_anomaly, e))),
+            ..*self
+        })
+    }
+}
+
+// Wrap an angle onto (−π, π]
+pub(crate) fn wrap_pi<T: Float>(angle: T) -> T {
+    let wrapped = wrap_two_pi(angle);
+    if wrapped > T::PI {
+        wrapped - T::TAU
+    } else {
+        wrapped
+    }
+}
+
+// Wrap an angle onto [0, 2π)
+pub(crate) fn wrap_two_pi<T: Float>(angle: T) -> T {
+    Radians(angle).normalized().0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bodies::EARTH;
+    use crate::utils::PI;
     use approx::assert_relative_eq;
 
     #[test]
@@ -132,12 +713,8 @@ mod tests {
             x: Meters(1.0),
             y: Meters(1.0),
         };
-        let e = Ellipse {
-            e: Eccentricity::new(1.0).unwrap(),
-            f: f,
-            r_p: Meters(1.0),
-        };
-        assert_eq!(e.f, f);
+        let e = Ellipse::new(Eccentricity::new(1.0).unwrap(), f, Meters(1.0));
+        assert_eq!(e.primary_focus(), Point3::from(f));
     }
 
     #[test]
@@ -146,11 +723,7 @@ mod tests {
             x: Meters(1.0),
             y: Meters(1.0),
         };
-        let e = Ellipse {
-            e: Eccentricity::new(0.5).unwrap(),
-            f: f,
-            r_p: Meters(1.0),
-        };
+        let e = Ellipse::new(Eccentricity::new(0.5).unwrap(), f, Meters(1.0));
         let expected = Meters(2.0);
         assert_eq!(e.semi_major_axis(), expected);
     }
@@ -158,14 +731,14 @@ mod tests {
     // Test case 1: Circle (e = 0)
     #[test]
     fn test_circle() {
-        let ellipse = Ellipse {
-            e: Eccentricity::new(0.0).unwrap(),
-            f: Point {
+        let ellipse = Ellipse::new(
+            Eccentricity::new(0.0).unwrap(),
+            Point {
                 x: Meters(0.0),
                 y: Meters(0.0),
             },
-            r_p: Meters(1000.0),
-        };
+            Meters(1000.0),
+        );
 
         // For a circle: r_p = r_a = a = b
         assert_relative_eq!(ellipse.semi_major_axis().0, 1000.0, epsilon = 1e-10);
@@ -180,14 +753,14 @@ mod tests {
         let e_val = 0.0167; // Earth's orbital eccentricity
         let r_p_val = 147_097_000_000.0; // Perihelion in meters
 
-        let ellipse = Ellipse {
-            e: Eccentricity::new(e_val).unwrap(),
-            f: Point {
+        let ellipse = Ellipse::new(
+            Eccentricity::new(e_val).unwrap(),
+            Point {
                 x: Meters(0.0),
                 y: Meters(0.0),
             },
-            r_p: Meters(r_p_val),
-        };
+            Meters(r_p_val),
+        );
 
         let expected_a = 149_595_240_516.6277;
         let expected_r_a = 152_093_481_033.25537;
@@ -199,14 +772,14 @@ mod tests {
     // Test case 3: Highly eccentric orbit (comet-like)
     #[test]
     fn test_highly_eccentric_orbit() {
-        let ellipse = Ellipse {
-            e: Eccentricity::new(0.9).unwrap(),
-            f: Point {
+        let ellipse = Ellipse::new(
+            Eccentricity::new(0.9).unwrap(),
+            Point {
                 x: Meters(0.0),
                 y: Meters(0.0),
             },
-            r_p: Meters(1000.0),
-        };
+            Meters(1000.0),
+        );
 
         // For e = 0.9, r_p = 1000:
         // a = r_p / (1 - e) = 1000 / 0.1 = 10000
@@ -228,14 +801,14 @@ mod tests {
     #[test]
     fn test_low_earth_orbit() {
         let earth_radius = 6_371_000.0; // Earth radius in meters
-        let ellipse = Ellipse {
-            e: Eccentricity::new(0.0002).unwrap(), // Very low eccentricity
-            f: Point {
+        let ellipse = Ellipse::new(
+            Eccentricity::new(0.0002).unwrap(), // Very low eccentricity
+            Point {
                 x: Meters(0.0),
                 y: Meters(0.0),
             },
-            r_p: Meters(earth_radius + 408_000.0), // ~408 km altitude at perigee
-        };
+            Meters(earth_radius + 408_000.0), // ~408 km altitude at perigee
+        );
 
         // For nearly circular LEO
         let expected_a = (earth_radius + 408_000.0) / (1.0 - 0.0002);
@@ -249,14 +822,14 @@ mod tests {
     #[test]
     fn test_geostationary_transfer_orbit() {
         let earth_radius = 6_371_000.0;
-        let ellipse = Ellipse {
-            e: Eccentricity::new(0.7308).unwrap(), // Typical GTO eccentricity
-            f: Point {
+        let ellipse = Ellipse::new(
+            Eccentricity::new(0.7308).unwrap(), // Typical GTO eccentricity
+            Point {
                 x: Meters(0.0),
                 y: Meters(0.0),
             },
-            r_p: Meters(earth_radius + 200_000.0), // 200 km perigee
-        };
+            Meters(earth_radius + 200_000.0), // 200 km perigee
+        );
 
         // Calculate expected values using orbital mechanics formulas
         let expected_a = ellipse.r_p.0 / (1.0 - ellipse.e.value());
@@ -269,14 +842,14 @@ mod tests {
     // Test case 6: Parabolic trajectory (e = 1.0)
     #[test]
     fn test_parabolic_trajectory() {
-        let ellipse = Ellipse {
-            e: Eccentricity::new(1.0).unwrap(),
-            f: Point {
+        let ellipse = Ellipse::new(
+            Eccentricity::new(1.0).unwrap(),
+            Point {
                 x: Meters(0.0),
                 y: Meters(0.0),
             },
-            r_p: Meters(1000.0),
-        };
+            Meters(1000.0),
+        );
 
         // For parabolic orbit: a approaches infinity, r_a approaches infinity
         // This is a degenerate case that might need special handling
@@ -294,7 +867,7 @@ mod tests {
             y: Meters(0.0),
         };
 
-        let ellipse = Ellipse::from_periapsis_apoapsis(r_p, r_a, f);
+        let ellipse = Ellipse::from_periapsis_apoapsis(r_p, r_a, f).unwrap();
 
         let expected_a = (r_p.0 + r_a.0) / 2.0;
         let expected_e = (r_a.0 - r_p.0) / (r_a.0 + r_p.0);
@@ -305,17 +878,215 @@ mod tests {
         assert_relative_eq!(ellipse.periapsis().0, r_p.0, epsilon = 1e-6);
     }
 
+    // Test case 6c: Swapped or non-positive apsides are errors, not panics
+    #[test]
+    fn test_invalid_apsides() {
+        let swapped = Ellipse::from_periapsis_apoapsis(
+            Meters(9_000_000.0),
+            Meters(7_000_000.0),
+            Point::ORIGIN,
+        );
+        assert!(matches!(swapped, Err(AlmagestError::InvalidInput(_))));
+        let zero = Ellipse::from_periapsis_apoapsis(Meters(0.0), Meters(0.0), Point::ORIGIN);
+        assert!(zero.is_err());
+
+        assert_relative_eq!(calc_ecc(Meters(3.0), Meters(1.0)).unwrap().value(), 0.5);
+        assert!(calc_ecc(Meters(0.0), Meters(0.0)).is_err());
+        assert!(calc_ecc(Meters(-3.0), Meters(1.0)).is_err());
+    }
+
+    // Test case 6d: Vis-viva speeds on a GTO
+    #[test]
+    fn test_vis_viva_speeds() {
+        let mu = EARTH.mu;
+        let ellipse = Ellipse::from_periapsis_apoapsis(
+            Meters(6_578_000.0),
+            Meters(42_164_000.0),
+            Point::ORIGIN,
+        )
+        .unwrap();
+        let vp = ellipse.periapsis_speed(mu).value();
+        let va = ellipse.apoapsis_speed(mu).unwrap().value();
+        assert_relative_eq!(vp, 10_239.0, epsilon = 1.0);
+        assert_relative_eq!(va, 1_597.0, epsilon = 1.0);
+        // Angular momentum is the same at both apsides
+        assert_relative_eq!(vp * 6_578_000.0, va * 42_164_000.0, max_relative = 1e-12);
+        let middle = ellipse.speed_at(ellipse.semi_major_axis(), mu).value();
+        assert_relative_eq!(
+            middle,
+            sqrt(mu.value() / ellipse.semi_major_axis().value()),
+            max_relative = 1e-12
+        );
+
+        // A parabola moves at escape speed everywhere
+        let e = Eccentricity::new(1.0).unwrap();
+        let parabola = Ellipse::new(e, Point::ORIGIN, Meters(7e6));
+        assert_relative_eq!(
+            parabola.speed_at(Meters(1e7), mu).value(),
+            sqrt(2.0 * mu.value() / 1e7),
+            max_relative = 1e-12
+        );
+        assert_eq!(parabola.apoapsis_speed(mu), None);
+    }
+
+    #[test]
+    fn test_sampling() {
+        let ellipse = Ellipse::from_periapsis_apoapsis(
+            Meters(7_000_000.0),
+            Meters(9_000_000.0),
+            Point::ORIGIN,
+        )
+        .unwrap();
+        let mut samples = ellipse.sample(8);
+        assert_eq!(samples.len(), 8);
+        assert_eq!(samples.next(), Some(ellipse.point_at(Radians::ZERO)));
+        let apoapsis = samples.nth(3).unwrap();
+        assert_relative_eq!(apoapsis.x().value(), -9_000_000.0, max_relative = 1e-12);
+        assert!(
+            ellipse
+                .sample_range(Radians(-0.5), Radians(0.5), 3)
+                .eq([-0.5, 0.0, 0.5].map(|nu| ellipse.point_at(Radians(nu))))
+        );
+        assert_eq!(
+            ellipse.sample_range(Radians(1.0), Radians(2.0), 1).next(),
+            Some(ellipse.point_at(Radians(1.0)))
+        );
+        assert_eq!(ellipse.sample(0).next(), None);
+
+        // An open orbit stays between its asymptotes
+        let e = Eccentricity::new(2.0).unwrap();
+        let hyperbola = Ellipse::new(e, Point::ORIGIN, Meters(7e6));
+        assert!(hyperbola.sample(50).all(|point| {
+            let nu = Radians::atan2(point.y().value(), point.x().value());
+            point.x().value().is_finite() && hyperbola.radius_at(nu).value() > 0.0
+        }));
+
+        // In space, every sample lies in the orbit plane at its radius
+        let elements = KeplerianElements {
+            semi_major_axis: Meters(8_000_000.0),
+            eccentricity: Eccentricity::new(0.125).unwrap(),
+            inclination: Radians(0.9),
+            raan: Radians(2.0),
+            argument_of_periapsis: Radians(0.4),
+            true_anomaly: Radians(0.0),
+        };
+        let normal = elements.orbit_normal();
+        let anomalies = (0..12).map(|k| Radians(TAU * k as Real / 12.0));
+        for (position, nu) in elements.sample(12).zip(anomalies) {
+            assert_relative_eq!(position.dot(normal), 0.0, epsilon = 1e-6);
+            let radius = elements.conic().radius_at(nu).value();
+            assert_relative_eq!(position.norm(), radius, max_relative = 1e-12);
+        }
+        let at = KeplerianElements {
+            true_anomaly: Radians(1.0),
+            ..elements
+        }
+        .to_state(EARTH.mu);
+        let sampled = elements
+            .sample_range(Radians(1.0), Radians(2.0), 2)
+            .next()
+            .unwrap();
+        assert_relative_eq!((sampled - at.position).norm(), 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_radius_and_point_at() {
+        let focus = Point::new(Meters(1_000.0), Meters(-2_000.0));
+        let ellipse =
+            Ellipse::from_periapsis_apoapsis(Meters(7_000_000.0), Meters(9_000_000.0), focus)
+                .unwrap();
+        assert_relative_eq!(
+            ellipse.radius_at(Radians::ZERO).value(),
+            7_000_000.0,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            ellipse.radius_at(Radians(PI)).value(),
+            9_000_000.0,
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            ellipse.radius_at(Radians(PI / 2.0)).value(),
+            ellipse.semi_latus_rectum().value()
+        );
+        // Symmetric about the major axis
+        assert_relative_eq!(
+            ellipse.radius_at(Radians(1.0)).value(),
+            ellipse.radius_at(Radians(-1.0)).value()
+        );
+
+        let periapsis = ellipse.point_at(Radians::ZERO);
+        assert_relative_eq!(periapsis.x().value(), 7_001_000.0, max_relative = 1e-12);
+        assert_relative_eq!(periapsis.y().value(), -2_000.0, epsilon = 1e-6);
+        let apoapsis = ellipse.point_at(Radians(PI));
+        assert_relative_eq!(apoapsis.x().value(), -8_999_000.0, max_relative = 1e-12);
+        // Distances to the two foci add up to 2a
+        let point = ellipse.point_at(Radians(2.0));
+        let (x, y) = (
+            (point.x() - focus.x()).value(),
+            (point.y() - focus.y()).value(),
+        );
+        let c2 = 2.0 * ellipse.focal_distance().value();
+        let sum = sqrt(x * x + y * y) + sqrt((x + c2) * (x + c2) + y * y);
+        assert_relative_eq!(
+            sum,
+            2.0 * ellipse.semi_major_axis().value(),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_oriented_ellipse() {
+        let a = Point3::new(Meters(1.0), Meters(2.0), Meters(3.0));
+        let b = a.translated(Vec3::new(3.0, 4.0, 12.0));
+        assert_eq!(b, Point3::new(Meters(4.0), Meters(6.0), Meters(15.0)));
+        assert_relative_eq!(a.distance_to(&b).value(), 13.0);
+        assert_eq!(Point3::from(Vec3::new(1.0, 2.0, 3.0)), a);
+
+        let w = Radians(PI / 2.0);
+        let ellipse = Ellipse::from_periapsis_apoapsis(Meters(7_000_000.0), Meters(9_000_000.0), a)
+            .unwrap()
+            .with_argument_of_periapsis(w);
+        assert_eq!(ellipse.argument_of_periapsis(), w);
+        // Periapsis now lies along +y from the focus, in its plane
+        let periapsis = ellipse.point_at(Radians::ZERO);
+        assert_relative_eq!(periapsis.x().value(), 1.0, epsilon = 1e-6);
+        assert_relative_eq!(periapsis.y().value(), 7_000_002.0, max_relative = 1e-12);
+        assert_eq!(periapsis.z(), Meters(3.0));
+        // The empty focus sits 2c toward apoapsis
+        let empty = ellipse.secondary_focus();
+        assert_relative_eq!(empty.y().value(), 2.0 - 2_000_000.0, max_relative = 1e-12);
+        // Every sample is 2a from the two foci together
+        for point in ellipse.sample(7) {
+            let sum = point.distance_to(&a) + point.distance_to(&empty);
+            assert_relative_eq!(
+                sum.value(),
+                2.0 * ellipse.semi_major_axis().value(),
+                max_relative = 1e-12
+            );
+        }
+        // Turning the orbit turns its samples by the same angle
+        let flat = ellipse
+            .with_argument_of_periapsis(Radians::ZERO)
+            .point_at(Radians(1.0))
+            .to_vec3()
+            - a.to_vec3();
+        let turned = ellipse.point_at(Radians(1.0)).to_vec3() - a.to_vec3();
+        assert_relative_eq!(turned.x, -flat.y, epsilon = 1e-6);
+        assert_relative_eq!(turned.y, flat.x, epsilon = 1e-6);
+    }
+
     // Test case 7: Edge case - very small periapsis
     #[test]
     fn test_small_periapsis() {
-        let ellipse = Ellipse {
-            e: Eccentricity::new(0.5).unwrap(),
-            f: Point {
+        let ellipse = Ellipse::new(
+            Eccentricity::new(0.5).unwrap(),
+            Point {
                 x: Meters(0.0),
                 y: Meters(0.0),
             },
-            r_p: Meters(1.0), // 1 meter periapsis
-        };
+            Meters(1.0), // 1 meter periapsis
+        );
 
         // a = r_p / (1 - e) = 1 / 0.5 = 2
         // r_a = a(1 + e) = 2 * 1.5 = 3
@@ -326,14 +1097,14 @@ mod tests {
     // Test case 8: Mathematical precision test
     #[test]
     fn test_mathematical_relationships() {
-        let ellipse = Ellipse {
-            e: Eccentricity::new(0.3).unwrap(),
-            f: Point {
+        let ellipse = Ellipse::new(
+            Eccentricity::new(0.3).unwrap(),
+            Point {
                 x: Meters(0.0),
                 y: Meters(0.0),
             },
-            r_p: Meters(7000.0),
-        };
+            Meters(7000.0),
+        );
 
         let a = ellipse.semi_major_axis().0;
         let b = ellipse.semi_minor_axis().0;
@@ -354,14 +1125,14 @@ mod tests {
     fn test_eccentricity_bounds() {
         // Test that calculations work for valid eccentricity range [0, 1)
         // First, for a circle:
-        let ellipse = Ellipse {
-            e: Eccentricity::new(0.0).unwrap(),
-            f: Point {
+        let ellipse = Ellipse::new(
+            Eccentricity::new(0.0).unwrap(),
+            Point {
                 x: Meters(0.0),
                 y: Meters(0.0),
             },
-            r_p: Meters(1000.0),
-        };
+            Meters(1000.0),
+        );
 
         // All calculated values should be positive and finite
         assert!(ellipse.semi_major_axis().0 > 0.0);
@@ -372,14 +1143,14 @@ mod tests {
 
         // Then, for the other ranges:
         for e_val in [0.1, 0.25, 0.5, 0.75, 0.9, 0.95, 0.99, 0.999] {
-            let ellipse = Ellipse {
-                e: Eccentricity::new(e_val).unwrap(),
-                f: Point {
+            let ellipse = Ellipse::new(
+                Eccentricity::new(e_val).unwrap(),
+                Point {
                     x: Meters(0.0),
                     y: Meters(0.0),
                 },
-                r_p: Meters(1000.0),
-            };
+                Meters(1000.0),
+            );
 
             // All calculated values should be positive and finite
             assert!(ellipse.semi_major_axis().0 > 0.0);
@@ -393,4 +1164,196 @@ mod tests {
             }
         }
     }
+
+    // Vallado Example 2-1
+    #[test]
+    fn solves_keplers_equation() {
+        let m = 235.4_f64.to_radians();
+        let ecc_anomaly = solve_kepler(m, 0.4).unwrap();
+        assert_relative_eq!(
+            wrap_two_pi(ecc_anomaly).to_degrees(),
+            220.512_074_767_522,
+            epsilon = 1e-9
+        );
+        assert!(solve_kepler(m, 1.2).is_err());
+    }
+
+    #[test]
+    fn solver_config_trades_accuracy_for_iterations() {
+        let m = 235.4_f64.to_radians();
+        let (fine, report) = solve_kepler_with(m, 0.4, &SolverConfig::default()).unwrap();
+        assert_eq!(fine, solve_kepler(m, 0.4).unwrap());
+        assert!(report.residual < 1e-15);
+        let coarse = SolverConfig {
+            tolerance: 1e-3,
+            max_iterations: 50,
+        };
+        let (_, quick) = solve_kepler_with(m, 0.4, &coarse).unwrap();
+        assert!(quick.iterations < report.iterations);
+        let starved = SolverConfig {
+            max_iterations: 1,
+            ..SolverConfig::default()
+        };
+        assert!(matches!(
+            solve_kepler_with(m, 0.4, &starved),
+            Err(AlmagestError::NoConvergence(_))
+        ));
+    }
+
+    #[test]
+    fn fixed_cost_solvers_match_newton() {
+        for solver in [KeplerSolver::Markley, KeplerSolver::Mikkola] {
+            for i in 0..=20 {
+                let e = 0.999 * i as Real / 20.0;
+                for j in -36..=36 {
+                    let m = j as Real * PI / 36.0;
+                    let expected = solve_kepler(m, e).unwrap();
+                    let found = solver.solve(m, e).unwrap();
+                    assert_relative_eq!(wrap_pi(found - expected), 0.0, epsilon = 1e-12);
+                }
+            }
+            assert!(solver.solve(1.0, 1.0).is_err());
+        }
+        let found = KeplerSolver::Markley
+            .solve(235.4_f64.to_radians(), 0.4)
+            .unwrap();
+        assert_relative_eq!(
+            wrap_two_pi(found).to_degrees(),
+            220.512_074_767_522,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn single_precision_solvers() {
+        for solver in [
+            KeplerSolver::Newton,
+            KeplerSolver::Markley,
+            KeplerSolver::Mikkola,
+        ] {
+            for e in [0.0_f32, 0.3, 0.9] {
+                for m in [-3.0_f32, -0.5, 0.01, 1.0, 3.1] {
+                    let found = solver.solve(m, e).unwrap();
+                    let expected = solve_kepler(m as Real, e as Real).unwrap();
+                    assert_relative_eq!(found as Real, expected, epsilon = 1e-5);
+                }
+            }
+        }
+        let nu = eccentric_to_true(true_to_eccentric(2.0_f32, 0.7), 0.7);
+        assert_relative_eq!(nu, 2.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn anomaly_conversions_round_trip() {
+        for nu in [0.0, 0.5, 2.0, 3.0, -1.0] {
+            let e = 0.7;
+            let ecc_anomaly = true_to_eccentric(nu, e);
+            assert_relative_eq!(eccentric_to_true(ecc_anomaly, e), nu, epsilon = 1e-12);
+        }
+    }
+
+    // Vallado Example 2-5
+    #[test]
+    fn elements_from_state() {
+        let mu = EARTH.mu;
+        let state = StateVector::new(
+            Vec3::new(6_524.834, 6_862.875, 6_448.296) * 1_000.0,
+            Vec3::new(4.901_327, 5.533_756, -1.976_341) * 1_000.0,
+        );
+        let elements = KeplerianElements::from_state(&state, mu).unwrap();
+        assert_relative_eq!(
+            elements.semi_latus_rectum().value(),
+            11_067_790.0,
+            epsilon = 50.0
+        );
+        assert_relative_eq!(
+            elements.semi_major_axis.value(),
+            36_127_343.0,
+            epsilon = 200.0
+        );
+        assert_relative_eq!(elements.eccentricity.value(), 0.832_853, epsilon = 1e-6);
+        assert_relative_eq!(
+            elements.inclination.to_degrees().value(),
+            87.870,
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(elements.raan.to_degrees().value(), 227.89, epsilon = 1e-2);
+        assert_relative_eq!(
+            elements.argument_of_periapsis.to_degrees().value(),
+            53.38,
+            epsilon = 1e-2
+        );
+        assert_relative_eq!(
+            elements.true_anomaly.to_degrees().value(),
+            92.335,
+            epsilon = 1e-3
+        );
+
+        let back = elements.to_state(mu);
+        assert_relative_eq!((back.position - state.position).norm(), 0.0, epsilon = 1e-3);
+        assert_relative_eq!((back.velocity - state.velocity).norm(), 0.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn circular_equatorial_elements() {
+        let mu = EARTH.mu;
+        let r = 7_000_000.0;
+        let v = sqrt(mu.value() / r);
+        let state = StateVector::new(Vec3::new(0.0, r, 0.0), Vec3::new(-v, 0.0, 0.0));
+        let elements = KeplerianElements::from_state(&state, mu).unwrap();
+        assert_relative_eq!(elements.eccentricity.value(), 0.0, epsilon = 1e-12);
+        assert_eq!(elements.raan, Radians::ZERO);
+        assert_eq!(elements.argument_of_periapsis, Radians::ZERO);
+        assert_relative_eq!(elements.true_anomaly.value(), PI / 2.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn propagation_advances_mean_anomaly() {
+        let mu = EARTH.mu;
+        let elements = KeplerianElements {
+            semi_major_axis: Meters(10_000_000.0),
+            eccentricity: Eccentricity::new(0.3).unwrap(),
+            inclination: Radians(0.9),
+            raan: Radians(1.0),
+            argument_of_periapsis: Radians(2.0),
+            true_anomaly: Radians(0.4),
+        };
+        let period = elements.period(mu);
+        let later = elements.propagate(period / 3.0, mu).unwrap();
+        let dm = wrap_two_pi(later.mean_anomaly() - elements.mean_anomaly());
+        assert_relative_eq!(dm, TAU / 3.0, epsilon = 1e-10);
+        let full = elements.propagate(period, mu).unwrap();
+        assert_relative_eq!(
+            full.true_anomaly.value(),
+            elements.true_anomaly.value(),
+            epsilon = 1e-9
+        );
+        // Energy is unchanged
+        let e0 = elements.to_state(mu);
+        let e1 = later.to_state(mu);
+        let energy =
+            |s: StateVector| s.velocity.norm_squared() / 2.0 - mu.value() / s.position.norm();
+        assert_relative_eq!(energy(e0), energy(e1), max_relative = 1e-12);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn elements_through_json() {
+        extern crate std;
+        let elements = KeplerianElements {
+            semi_major_axis: Meters(7_000_000.0),
+            eccentricity: Eccentricity::new(0.01).unwrap(),
+            inclination: Radians(0.9),
+            raan: Radians(1.2),
+            argument_of_periapsis: Radians(0.3),
+            true_anomaly: Radians(2.0),
+        };
+        let json = serde_json::to_string(&elements).unwrap();
+        assert!(json.contains("\"eccentricity\":0.01"));
+        let back: KeplerianElements = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, elements);
+
+        let negative = json.replace("0.01", "-0.01");
+        assert!(serde_json::from_str::<KeplerianElements>(&negative).is_err());
+    }
 }
@@ -0,0 +1,627 @@
+//! Lambert's problem: find the orbit connecting two positions in a
+//! given time of flight.
+//!
+//! Implements Izzo's method (Izzo, "Revisiting Lambert's problem",
+//! 2015), which solves a single time-of-flight equation in a
+//! universal variable with Householder iterations. It converges in
+//! a handful of steps for elliptic, parabolic and hyperbolic transfers
+//! alike, and handles multi-revolution transfers.
+
+use libm::{acos, asinh, exp, floor, log, pow, sqrt};
+
+use crate::error::AlmagestError;
+use crate::solver::{Convergence, SolverConfig};
+use crate::utils::{GravParam, PI, Real, Seconds};
+use crate::vectors::Vec3;
+
+/// Izzo's stopping criteria, which [`lambert`] uses: a step in x
+/// under 1e-8, within 35 iterations
+pub const DEFAULT_SOLVER: SolverConfig = SolverConfig {
+    tolerance: 1e-8,
+    max_iterations: 35,
+};
+
+/// Sense of motion of the transfer orbit about the +z axis
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransferDirection {
+    /// Counter-clockwise seen from +z, like almost every
+    /// Earth satellite and planet
+    Prograde,
+    /// Clockwise seen from +z
+    Retrograde,
+}
+
+/// Which of the two multi-revolution solutions to return.
+///
+/// For a given number of complete revolutions there are two
+/// transfers with the requested time of flight; Izzo names them
+/// the left and right branches, the left being the one on the smaller
+/// orbit. Single-revolution transfers have only one solution, and the
+/// branch is ignored.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Branch {
+    Left,
+    Right,
+}
+
+/// Velocities at both ends of a Lambert transfer, in m/s
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LambertSolution {
+    pub departure_velocity: Vec3,
+    pub arrival_velocity: Vec3,
+}
+
+/// Solve Lambert's problem.
+///
+/// `r1` and `r2` are the departure and arrival positions in meters,
+/// `tof` the time of flight and `revolutions` the number
+/// of complete orbits to fly before arriving.
+pub fn lambert(
+    mu: GravParam,
+    r1: Vec3,
+    r2: Vec3,
+    tof: Seconds,
+    direction: TransferDirection,
+    revolutions: u32,
+    branch: Branch,
+) -> Result<LambertSolution, AlmagestError> {
+    lambert_with(
+        mu,
+        r1,
+        r2,
+        tof,
+        direction,
+        revolutions,
+        branch,
+        &DEFAULT_SOLVER,
+    )
+    .map(|(solution, _)| solution)
+}
+
+/// As [`lambert`], stopping as `config` says; the residual is the
+/// error in Izzo's non-dimensional time of flight
+#[allow(clippy::too_many_arguments)]
+pub fn lambert_with(
+    mu: GravParam,
+    r1: Vec3,
+    r2: Vec3,
+    tof: Seconds,
+    direction: TransferDirection,
+    revolutions: u32,
+    branch: Branch,
+    config: &SolverConfig,
+) -> Result<(LambertSolution, Convergence), AlmagestError> {
+    let geometry = Geometry::new(mu, r1, r2, tof.value(), direction)?;
+    let m = revolutions as Real;
+    if m > geometry.max_revolutions(config)? {
+        return Err(AlmagestError::InvalidInput(
+            "No Lambert solution with that many revolutions",
+        ));
+    }
+
+    let x0 = initial_guess(geometry.t, geometry.lambda, m, branch);
+    let (x, convergence) = householder(x0, geometry.t, geometry.lambda, m, config)?;
+    Ok((geometry.velocities(x), convergence))
+}
+
+/// The largest number of complete revolutions for which a transfer
+/// between `r1` and `r2` in `tof` exists
+pub fn max_revolutions(
+    mu: GravParam,
+    r1: Vec3,
+    r2: Vec3,
+    tof: Seconds,
+    direction: TransferDirection,
+) -> Result<u32, AlmagestError> {
+    let geometry = Geometry::new(mu, r1, r2, tof.value(), direction)?;
+    Ok(geometry.max_revolutions(&DEFAULT_SOLVER)? as u32)
+}
+
+// The transfer geometry, normalized as in Izzo's paper
+struct Geometry {
+    mu: Real,
+    r1: Vec3,
+    r2: Vec3,
+    r1_norm: Real,
+    r2_norm: Real,
+    chord: Real,
+    semiperimeter: Real,
+    // Unit tangential directions at departure and arrival
+    t1: Vec3,
+    t2: Vec3,
+    lambda: Real,
+    // Non-dimensional time of flight
+    t: Real,
+}
+
+impl Geometry {
+    fn new(
+        mu: GravParam,
+        r1: Vec3,
+        r2: Vec3,
+        tof: Real,
+        direction: TransferDirection,
+    ) -> Result<Self, AlmagestError> {
+        if tof <= 0.0 {
+            return Err(AlmagestError::InvalidInput(
+                "Time of flight must be positive",
+            ));
+        }
+        let chord = (r2 - r1).norm();
+        let (r1_norm, r2_norm) = (r1.norm(), r2.norm());
+        let semiperimeter = (r1_norm + r2_norm + chord) / 2.0;
+
+        let ir1 = r1 / r1_norm;
+        let ir2 = r2 / r2_norm;
+        let h = ir1.cross(ir2);
+        if h.norm() < 1e-12 {
+            return Err(AlmagestError::Coplanarity(
+                "Transfer plane is undefined for collinear positions",
+            ));
+        }
+        let ih = h.unit();
+
+        let mut lambda = sqrt(1.0 - (chord / semiperimeter).min(1.0));
+        let (mut t1, mut t2) = if ih.z < 0.0 {
+            lambda = -lambda;
+            (ir1.cross(ih), ir2.cross(ih))
+        } else {
+            (ih.cross(ir1), ih.cross(ir2))
+        };
+        if direction == TransferDirection::Retrograde {
+            lambda = -lambda;
+            t1 = -t1;
+            t2 = -t2;
+        }
+
+        let mu = mu.value();
+        let t = sqrt(2.0 * mu / pow(semiperimeter, 3.0)) * tof;
+        Ok(Geometry {
+            mu,
+            r1,
+            r2,
+            r1_norm,
+            r2_norm,
+            chord,
+            semiperimeter,
+            t1,
+            t2,
+            lambda,
+            t,
+        })
+    }
+
+    fn max_revolutions(&self, config: &SolverConfig) -> Result<Real, AlmagestError> {
+        let (t, lambda) = (self.t, self.lambda);
+        let mut m_max = floor(t / PI);
+        let t00 = acos(lambda) + lambda * sqrt(1.0 - lambda * lambda);
+        if m_max > 0.0 && t < t00 + m_max * PI {
+            // The minimum time of flight for m_max revolutions may
+            // still exceed the requested one
+            let x_min = halley(0.1, lambda, m_max, config)?;
+            if t < tof_equation(x_min, lambda, m_max) {
+                m_max -= 1.0;
+            }
+        }
+        Ok(m_max)
+    }
+
+    // Recover the terminal velocities from the converged x
+    fn velocities(&self, x: Real) -> LambertSolution {
+        let lambda = self.lambda;
+        let y = compute_y(x, lambda);
+        let gamma = sqrt(self.mu * self.semiperimeter / 2.0);
+        let rho = (self.r1_norm - self.r2_norm) / self.chord;
+        let sigma = sqrt(1.0 - rho * rho);
+
+        let vr1 = gamma * ((lambda * y - x) - rho * (lambda * y + x)) / self.r1_norm;
+        let vr2 = -gamma * ((lambda * y - x) + rho * (lambda * y + x)) / self.r2_norm;
+        let vt1 = gamma * sigma * (y + lambda * x) / self.r1_norm;
+        let vt2 = gamma * sigma * (y + lambda * x) / self.r2_norm;
+
+        LambertSolution {
+            departure_velocity: self.r1 / self.r1_norm * vr1 + self.t1 * vt1,
+            arrival_velocity: self.r2 / self.r2_norm * vr2 + self.t2 * vt2,
+        }
+    }
+}
+
+fn compute_y(x: Real, lambda: Real) -> Real {
+    sqrt(1.0 - lambda * lambda * (1.0 - x * x))
+}
+
+fn compute_psi(x: Real, y: Real, lambda: Real) -> Real {
+    if (-1.0..1.0).contains(&x) {
+        // Elliptic
+        acos(x * y + lambda * (1.0 - x * x))
+    } else if x > 1.0 {
+        // Hyperbolic
+        asinh((y - x * lambda) * sqrt(x * x - 1.0))
+    } else {
+        // Parabolic
+        0.0
+    }
+}
+
+// Non-dimensional time of flight as a function of x
+fn tof_equation(x: Real, lambda: Real, m: Real) -> Real {
+    tof_equation_y(x, compute_y(x, lambda), lambda, m)
+}
+
+fn tof_equation_y(x: Real, y: Real, lambda: Real, m: Real) -> Real {
+    if m == 0.0 && x > sqrt(0.6) && x < sqrt(1.4) {
+        // Battin's series avoids the cancellation near parabolic
+        let eta = y - lambda * x;
+        let s1 = (1.0 - lambda - x * eta) / 2.0;
+        let q = 4.0 / 3.0 * hyp2f1b(s1);
+        (eta * eta * eta * q + 4.0 * lambda * eta) / 2.0
+    } else {
+        let psi = compute_psi(x, y, lambda);
+        ((psi + m * PI) / sqrt((1.0 - x * x).abs()) - x + lambda * y) / (1.0 - x * x)
+    }
+}
+
+// First three derivatives of the time of flight with respect to x
+fn tof_derivatives(x: Real, y: Real, t: Real, lambda: Real) -> (Real, Real, Real) {
+    let l2 = lambda * lambda;
+    let l3 = l2 * lambda;
+    let omx2 = 1.0 - x * x;
+    let dt = (3.0 * t * x - 2.0 + 2.0 * l3 * x / y) / omx2;
+    let ddt = (3.0 * t + 5.0 * x * dt + 2.0 * (1.0 - l2) * l3 / (y * y * y)) / omx2;
+    let dddt = (7.0 * x * ddt + 8.0 * dt - 6.0 * (1.0 - l2) * l3 * l2 * x / pow(y, 5.0)) / omx2;
+    (dt, ddt, dddt)
+}
+
+// Gauss hypergeometric function 2F1(3, 1; 5/2; x), see Battin
+fn hyp2f1b(x: Real) -> Real {
+    if x >= 1.0 {
+        return Real::INFINITY;
+    }
+    let mut result = 1.0;
+    let mut term = 1.0;
+    let mut i = 0.0;
+    loop {
+        term *= (3.0 + i) * (1.0 + i) / (2.5 + i) * x / (i + 1.0);
+        let previous = result;
+        result += term;
+        if result == previous {
+            return result;
+        }
+        i += 1.0;
+    }
+}
+
+fn initial_guess(t: Real, lambda: Real, m: Real, branch: Branch) -> Real {
+    if m == 0.0 {
+        let t0 = acos(lambda) + lambda * sqrt(1.0 - lambda * lambda);
+        let t1 = 2.0 * (1.0 - lambda * lambda * lambda) / 3.0;
+        if t >= t0 {
+            pow(t0 / t, 2.0 / 3.0) - 1.0
+        } else if t < t1 {
+            2.5 * t1 / t * (t1 - t) / (1.0 - pow(lambda, 5.0)) + 1.0
+        } else {
+            // The piecewise expression after eq. (30) of the paper
+            // is misprinted; this is the corrected form
+            exp(log(2.0) * log(t / t0) / log(t1 / t0)) - 1.0
+        }
+    } else {
+        let left = pow((m * PI + PI) / (8.0 * t), 2.0 / 3.0);
+        let right = pow(8.0 * t / (m * PI), 2.0 / 3.0);
+        let x_left = (left - 1.0) / (left + 1.0);
+        let x_right = (right - 1.0) / (right + 1.0);
+        match branch {
+            Branch::Left => x_left,
+            Branch::Right => x_right,
+        }
+    }
+}
+
+// Householder iterations on the time of flight equation
+fn householder(
+    mut x0: Real,
+    t0: Real,
+    lambda: Real,
+    m: Real,
+    config: &SolverConfig,
+) -> Result<(Real, Convergence), AlmagestError> {
+    for iteration in 1..=config.max_iterations {
+        let y = compute_y(x0, lambda);
+        let f = tof_equation_y(x0, y, lambda, m) - t0;
+        let (d1, d2, d3) = tof_derivatives(x0, y, f + t0, lambda);
+        let x = x0 - f * ((d1 * d1 - f * d2 / 2.0) / (d1 * (d1 * d1 - f * d2) + d3 * f * f / 6.0));
+        if (x - x0).abs() < config.tolerance {
+            let residual = (tof_equation(x, lambda, m) - t0).abs();
+            return Ok((
+                x,
+                Convergence {
+                    iterations: iteration,
+                    residual,
+                },
+            ));
+        }
+        x0 = x;
+    }
+    Err(AlmagestError::NoConvergence(
+        "Lambert solver failed to converge",
+    ))
+}
+
+// Halley iterations for the x minimizing the time of flight
+fn halley(
+    mut x0: Real,
+    lambda: Real,
+    m: Real,
+    config: &SolverConfig,
+) -> Result<Real, AlmagestError> {
+    for _ in 0..config.max_iterations {
+        let y = compute_y(x0, lambda);
+        let t = tof_equation_y(x0, y, lambda, m);
+        let (d1, d2, d3) = tof_derivatives(x0, y, t, lambda);
+        if d2 == 0.0 {
+            return Err(AlmagestError::NoConvergence(
+                "Lambert solver failed to converge",
+            ));
+        }
+        let x = x0 - 2.0 * d1 * d2 / (2.0 * d2 * d2 - d1 * d3);
+        if (x - x0).abs() < config.tolerance {
+            return Ok(x);
+        }
+        x0 = x;
+    }
+    Err(AlmagestError::NoConvergence(
+        "Lambert solver failed to converge",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use approx::assert_relative_eq;
+    use libm::{atan2, cos, sin};
+
+    const KM: Real = 1_000.0;
+
+    // Time to fly from r1 to r2 on the ellipse through (r1, v1),
+    // counting `revolutions` complete orbits in between
+    fn kepler_time(r1: Vec3, v1: Vec3, r2: Vec3, revolutions: u32) -> Real {
+        let mu = EARTH.mu.value();
+        let h = r1.cross(v1);
+        let energy = v1.norm_squared() / 2.0 - mu / r1.norm();
+        let a = -mu / (2.0 * energy);
+        let e_vec = v1.cross(h) / mu - r1.unit();
+        let e = e_vec.norm();
+        let p_hat = e_vec.unit();
+        let q_hat = h.unit().cross(p_hat);
+        let eccentric = |r: Vec3| {
+            let nu = atan2(r.dot(q_hat), r.dot(p_hat));
+            2.0 * libm::atan(sqrt((1.0 - e) / (1.0 + e)) * libm::tan(nu / 2.0))
+        };
+        let mean = |ea: Real| ea - e * sin(ea);
+        let n = sqrt(mu / (a * a * a));
+        let mut dm = mean(eccentric(r2)) - mean(eccentric(r1));
+        while dm < 0.0 {
+            dm += 2.0 * PI;
+        }
+        (dm + 2.0 * PI * revolutions as Real) / n
+    }
+
+    #[test]
+    fn vallado_example_7_5() {
+        let r1 = Vec3::new(15_945.34, 0.0, 0.0) * KM;
+        let r2 = Vec3::new(12_214.838_99, 10_249.467_31, 0.0) * KM;
+        let solution = lambert(
+            EARTH.mu,
+            r1,
+            r2,
+            Seconds(76.0 * 60.0),
+            TransferDirection::Prograde,
+            0,
+            Branch::Left,
+        )
+        .unwrap();
+        let v1 = solution.departure_velocity / KM;
+        let v2 = solution.arrival_velocity / KM;
+        assert_relative_eq!(v1.x, 2.058_913, epsilon = 1e-4);
+        assert_relative_eq!(v1.y, 2.915_965, epsilon = 1e-4);
+        assert_relative_eq!(v2.x, -3.451_565, epsilon = 1e-4);
+        assert_relative_eq!(v2.y, 0.910_315, epsilon = 1e-4);
+        assert_relative_eq!(v1.z, 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn curtis_example_5_2() {
+        let r1 = Vec3::new(5_000.0, 10_000.0, 2_100.0) * KM;
+        let r2 = Vec3::new(-14_600.0, 2_500.0, 7_000.0) * KM;
+        let solution = lambert(
+            EARTH.mu,
+            r1,
+            r2,
+            Seconds(3_600.0),
+            TransferDirection::Prograde,
+            0,
+            Branch::Left,
+        )
+        .unwrap();
+        let v1 = solution.departure_velocity / KM;
+        let v2 = solution.arrival_velocity / KM;
+        assert_relative_eq!(v1.x, -5.9925, epsilon = 1e-3);
+        assert_relative_eq!(v1.y, 1.9254, epsilon = 1e-3);
+        assert_relative_eq!(v1.z, 3.2456, epsilon = 1e-3);
+        assert_relative_eq!(v2.x, -3.3125, epsilon = 1e-3);
+        assert_relative_eq!(v2.y, -4.1966, epsilon = 1e-3);
+        assert_relative_eq!(v2.z, -0.38529, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn retrograde_transfer_reverses_angular_momentum() {
+        let r1 = Vec3::new(7_000.0, 0.0, 0.0) * KM;
+        let r2 = Vec3::new(0.0, 8_000.0, 1_000.0) * KM;
+        let pro = lambert(
+            EARTH.mu,
+            r1,
+            r2,
+            Seconds(2_000.0),
+            TransferDirection::Prograde,
+            0,
+            Branch::Left,
+        )
+        .unwrap();
+        let retro = lambert(
+            EARTH.mu,
+            r1,
+            r2,
+            Seconds(2_000.0),
+            TransferDirection::Retrograde,
+            0,
+            Branch::Left,
+        )
+        .unwrap();
+        assert!(r1.cross(pro.departure_velocity).z > 0.0);
+        assert!(r1.cross(retro.departure_velocity).z < 0.0);
+        // The retrograde transfer arrives on time as well
+        let t = kepler_time(r1, retro.departure_velocity, r2, 0);
+        assert_relative_eq!(t, 2_000.0, max_relative = 1e-6);
+    }
+
+    #[test]
+    fn single_revolution_reaches_target_on_time() {
+        let r1 = Vec3::new(7_000.0, 1_000.0, -500.0) * KM;
+        let r2 = Vec3::new(-3_000.0, 9_000.0, 2_000.0) * KM;
+        for tof in [1_800.0, 3_600.0, 7_200.0, 20_000.0] {
+            let solution = lambert(
+                EARTH.mu,
+                r1,
+                r2,
+                Seconds(tof),
+                TransferDirection::Prograde,
+                0,
+                Branch::Left,
+            )
+            .unwrap();
+            let t = kepler_time(r1, solution.departure_velocity, r2, 0);
+            assert_relative_eq!(t, tof, max_relative = 1e-6);
+            // Angular momentum is the same at both ends
+            let h1 = r1.cross(solution.departure_velocity);
+            let h2 = r2.cross(solution.arrival_velocity);
+            assert_relative_eq!((h1 - h2).norm() / h1.norm(), 0.0, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn tighter_tolerance_reports_smaller_residual() {
+        let r1 = Vec3::new(7_000.0, 1_000.0, -500.0) * KM;
+        let r2 = Vec3::new(-3_000.0, 9_000.0, 2_000.0) * KM;
+        let solve = |config: &SolverConfig| {
+            lambert_with(
+                EARTH.mu,
+                r1,
+                r2,
+                Seconds(3_600.0),
+                TransferDirection::Prograde,
+                0,
+                Branch::Left,
+                config,
+            )
+        };
+        let (_, default) = solve(&DEFAULT_SOLVER).unwrap();
+        let tight = SolverConfig {
+            tolerance: 1e-13,
+            ..DEFAULT_SOLVER
+        };
+        let (_, report) = solve(&tight).unwrap();
+        assert!(report.iterations >= default.iterations);
+        assert!(report.residual <= default.residual);
+        assert!(report.residual < 1e-12);
+        let starved = SolverConfig {
+            max_iterations: 1,
+            ..DEFAULT_SOLVER
+        };
+        assert!(solve(&starved).is_err());
+    }
+
+    #[test]
+    fn multi_revolution_branches() {
+        let r1 = Vec3::new(7_000.0, 0.0, 0.0) * KM;
+        let angle: Real = 2.0;
+        let r2 = Vec3::new(cos(angle), sin(angle), 0.0) * 7_500.0 * KM;
+        let tof = Seconds(6.0 * 3_600.0);
+        let max = max_revolutions(EARTH.mu, r1, r2, tof, TransferDirection::Prograde).unwrap();
+        assert!(max >= 1);
+
+        let left = lambert(
+            EARTH.mu,
+            r1,
+            r2,
+            tof,
+            TransferDirection::Prograde,
+            1,
+            Branch::Left,
+        )
+        .unwrap();
+        let right = lambert(
+            EARTH.mu,
+            r1,
+            r2,
+            tof,
+            TransferDirection::Prograde,
+            1,
+            Branch::Right,
+        )
+        .unwrap();
+        assert!((left.departure_velocity - right.departure_velocity).norm() > 1.0);
+        let semi_major_axis = |solution: &LambertSolution| {
+            let v = solution.departure_velocity.norm();
+            1.0 / (2.0 / r1.norm() - v * v / EARTH.mu.value())
+        };
+        // Izzo's left branch is the tighter transfer orbit
+        assert!(semi_major_axis(&left) < semi_major_axis(&right));
+        for solution in [left, right] {
+            let t = kepler_time(r1, solution.departure_velocity, r2, 1);
+            assert_relative_eq!(t, tof.value(), max_relative = 1e-6);
+        }
+
+        assert!(
+            lambert(
+                EARTH.mu,
+                r1,
+                r2,
+                tof,
+                TransferDirection::Prograde,
+                max + 1,
+                Branch::Left,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_degenerate_input() {
+        let r1 = Vec3::new(7_000.0, 0.0, 0.0) * KM;
+        let r2 = Vec3::new(-8_000.0, 0.0, 0.0) * KM;
+        let result = lambert(
+            EARTH.mu,
+            r1,
+            r2,
+            Seconds(3_600.0),
+            TransferDirection::Prograde,
+            0,
+            Branch::Left,
+        );
+        assert!(result.is_err());
+        let r2 = Vec3::new(0.0, 8_000.0, 0.0) * KM;
+        let result = lambert(
+            EARTH.mu,
+            r1,
+            r2,
+            Seconds(-1.0),
+            TransferDirection::Prograde,
+            0,
+            Branch::Left,
+        );
+        assert!(result.is_err());
+    }
+}
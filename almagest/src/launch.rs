@@ -0,0 +1,224 @@
+//! Launch windows for direct ascent into a given orbit plane.
+//!
+//! A site can launch straight into a plane only while the Earth's
+//! rotation carries it through that plane, which happens twice a
+//! sidereal day: once on the part of the orbit heading north and once
+//! heading south. Both need the inclination to be at least the site's
+//! latitude (and at most its supplement), and the two coincide when
+//! they are equal. Ascent time and the plane's drift during
+//! the day are ignored, so real windows open a few minutes earlier.
+//!
+//! Times are Julian dates (UT1), angles are radians and azimuths are
+//! measured clockwise from north.
+
+use libm::{asin, atan2, cos, sin, tan};
+
+use crate::bodies::CelestialBody;
+use crate::error::AlmagestError;
+use crate::groundtrack::{EARTH_ROTATION_RATE, GroundPoint, greenwich_sidereal_angle};
+use crate::kepler::wrap_two_pi;
+use crate::utils::{PI, Real};
+
+/// Which half of the target orbit the launch goes into
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pass {
+    /// Heading north, after the ascending node
+    Ascending,
+    /// Heading south, after the descending node
+    Descending,
+}
+
+/// One opportunity to launch into the target plane
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LaunchWindow {
+    /// When the site is in the plane
+    pub julian_date: Real,
+    /// Inertial launch azimuth; see [`rotating_azimuth`] for the
+    /// azimuth to fly from the ground
+    pub azimuth: Real,
+    pub pass: Pass,
+}
+
+/// Inertial launch azimuth into an orbit of `inclination` from
+/// `latitude`: sin β = cos i / cos φ
+pub fn launch_azimuth(
+    latitude: Real,
+    inclination: Real,
+    pass: Pass,
+) -> Result<Real, AlmagestError> {
+    let ratio = cos(inclination) / cos(latitude);
+    if ratio.abs() > 1.0 {
+        return Err(AlmagestError::InvalidInput(
+            "Inclination cannot be reached directly from this latitude",
+        ));
+    }
+    let ascending = asin(ratio);
+    Ok(match pass {
+        Pass::Ascending => wrap_two_pi(ascending),
+        Pass::Descending => PI - ascending,
+    })
+}
+
+/// Azimuth to fly relative to the rotating ground to reach
+/// `orbit_speed` m/s along the inertial `azimuth`, taking out the
+/// eastward speed of a site at `latitude` on the equator of `body`
+pub fn rotating_azimuth(
+    azimuth: Real,
+    latitude: Real,
+    orbit_speed: Real,
+    body: &CelestialBody,
+) -> Real {
+    let ground_speed = body.rotation_rate * body.equatorial_radius.value() * cos(latitude);
+    let east = orbit_speed * sin(azimuth) - ground_speed;
+    let north = orbit_speed * cos(azimuth);
+    wrap_two_pi(atan2(east, north))
+}
+
+/// Launch windows from `site` into the plane of `inclination` and
+/// right ascension of the ascending node `raan`, in time order from
+/// `julian_date`. The iterator does not end.
+pub fn launch_windows(
+    site: GroundPoint,
+    inclination: Real,
+    raan: Real,
+    julian_date: Real,
+) -> Result<LaunchWindows, AlmagestError> {
+    if sin(inclination) == 0.0 && site.latitude == 0.0 {
+        return Err(AlmagestError::InvalidInput(
+            "An equatorial site is always in an equatorial plane",
+        ));
+    }
+    let ascending = launch_azimuth(site.latitude, inclination, Pass::Ascending)?;
+    let descending = launch_azimuth(site.latitude, inclination, Pass::Descending)?;
+    // Angle from the ascending node to the site, along the equator
+    let offset = asin((tan(site.latitude) / tan(inclination)).clamp(-1.0, 1.0));
+    Ok(LaunchWindows {
+        site,
+        ascending: (raan + offset, ascending),
+        descending: (raan + PI - offset, descending),
+        from: julian_date,
+    })
+}
+
+/// Iterator over launch windows, created by [`launch_windows`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LaunchWindows {
+    site: GroundPoint,
+    // Right ascension of the site and azimuth for each pass
+    ascending: (Real, Real),
+    descending: (Real, Real),
+    from: Real,
+}
+
+impl LaunchWindows {
+    // The first time at or after `from` that the site is at right
+    // ascension `alpha`
+    fn next_at(&self, alpha: Real) -> Real {
+        let angle = greenwich_sidereal_angle(self.from) + self.site.longitude;
+        self.from + wrap_two_pi(alpha - angle) / EARTH_ROTATION_RATE / 86_400.0
+    }
+}
+
+impl Iterator for LaunchWindows {
+    type Item = LaunchWindow;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ascending = self.next_at(self.ascending.0);
+        let descending = self.next_at(self.descending.0);
+        let window = if ascending <= descending {
+            LaunchWindow {
+                julian_date: ascending,
+                azimuth: self.ascending.1,
+                pass: Pass::Ascending,
+            }
+        } else {
+            LaunchWindow {
+                julian_date: descending,
+                azimuth: self.descending.1,
+                pass: Pass::Descending,
+            }
+        };
+        // Step past this window so the next call finds the following one
+        self.from = window.julian_date + 1.0 / 86_400.0;
+        Some(window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::{EARTH, MARS};
+    use crate::utils::TAU;
+    use crate::vectors::Vec3;
+    use approx::assert_relative_eq;
+
+    const SIDEREAL_DAY: Real = TAU / EARTH_ROTATION_RATE;
+
+    fn kennedy() -> GroundPoint {
+        GroundPoint::new(28.5_f64.to_radians(), -80.6_f64.to_radians())
+    }
+
+    #[test]
+    fn azimuth_to_the_station() {
+        let latitude = 28.5_f64.to_radians();
+        let inclination = 51.64_f64.to_radians();
+        let ascending = launch_azimuth(latitude, inclination, Pass::Ascending).unwrap();
+        assert_relative_eq!(ascending.to_degrees(), 44.9, epsilon = 0.1);
+        let descending = launch_azimuth(latitude, inclination, Pass::Descending).unwrap();
+        assert_relative_eq!(descending, PI - ascending);
+        // Flying from the ground takes a little more northing
+        let ground = rotating_azimuth(ascending, latitude, 7_700.0, &EARTH);
+        assert!(ground < ascending && ground > ascending - 0.1);
+        // Mars turns slower, but its orbits are slower still
+        let from_mars = rotating_azimuth(ascending, latitude, 3_400.0, &MARS);
+        assert!(from_mars < ground);
+        assert!(launch_azimuth(latitude, 20.0_f64.to_radians(), Pass::Ascending).is_err());
+    }
+
+    #[test]
+    fn windows_put_the_site_in_plane() {
+        let inclination = 51.64_f64.to_radians();
+        let raan = 2.0;
+        let normal = Vec3::new(
+            sin(inclination) * sin(raan),
+            -sin(inclination) * cos(raan),
+            cos(inclination),
+        );
+        let start = 2_460_000.5;
+        let mut windows = launch_windows(kennedy(), inclination, raan, start).unwrap();
+        let first = windows.next().unwrap();
+        let second = windows.next().unwrap();
+        let third = windows.next().unwrap();
+        assert!(start <= first.julian_date && first.julian_date < second.julian_date);
+        assert_ne!(first.pass, second.pass);
+        assert_eq!(first.pass, third.pass);
+        assert_relative_eq!(
+            (third.julian_date - first.julian_date) * 86_400.0,
+            SIDEREAL_DAY,
+            epsilon = 0.01
+        );
+
+        for window in [first, second] {
+            let angle = greenwich_sidereal_angle(window.julian_date);
+            let site = kennedy();
+            let alpha = site.longitude + angle;
+            let position = Vec3::new(
+                cos(site.latitude) * cos(alpha),
+                cos(site.latitude) * sin(alpha),
+                sin(site.latitude),
+            );
+            assert_relative_eq!(position.dot(normal), 0.0, epsilon = 1e-6);
+            // The launch heads along the orbit's direction of motion
+            let along = normal.cross(position);
+            let east = Vec3::new(-sin(alpha), cos(alpha), 0.0);
+            let north = position.cross(east);
+            assert_relative_eq!(
+                atan2(along.dot(east), along.dot(north)),
+                wrap_two_pi(window.azimuth + PI) - PI,
+                epsilon = 1e-6
+            );
+        }
+    }
+}
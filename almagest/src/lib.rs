@@ -1,8 +1,63 @@
 #![no_std]
 
+// A staticlib needs std's panic handler and allocator shims
+#[cfg(feature = "ffi")]
+extern crate std;
+
+pub mod aerobraking;
+pub mod ballistic;
+pub mod batch;
+pub mod bodies;
+pub mod budget;
+pub mod conjunction;
+pub mod disposal;
+pub mod entry;
+pub mod ephemeris;
+pub mod error;
+pub mod escape;
+pub mod events;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod float;
+pub mod flyby;
+pub mod forces;
+pub mod format;
+pub mod frames;
+pub mod geo;
+pub mod groundtrack;
+pub mod invariants;
 pub mod kepler;
+pub mod lambert;
+pub mod launch;
+pub mod lighting;
+pub mod lunar;
+pub mod magnetic;
+pub mod maneuvers;
+pub mod milankovitch;
+pub mod moon;
+pub mod partials;
+pub mod precession;
+pub mod radiation;
+pub mod relative;
+pub mod report;
+pub mod rotation;
+pub mod sail;
+pub mod scan;
+pub mod search;
+pub mod sgp4;
+pub mod similarity;
+pub mod solver;
+pub mod spherical;
+#[cfg(feature = "spice")]
+pub mod spice;
+pub mod state;
+pub mod sun;
+pub mod synodic;
+pub mod topocentric;
+pub mod uncertainty;
 pub mod utils;
-// pub mod vectors;
+pub mod vectors;
+pub mod windows;
 
 #[cfg(test)]
 mod tests {
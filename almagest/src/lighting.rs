@@ -0,0 +1,252 @@
+//! The solar beta angle of an orbit and the shadow it implies.
+//!
+//! β is the elevation of the Sun above the orbit plane, positive on
+//! the side of the angular momentum. It sets how long each revolution
+//! spends in eclipse and how the Sun walks across a spacecraft's faces,
+//! so thermal and power analyses want its history over a mission. It
+//! changes with the seasons as the Sun moves and with the node as J2
+//! turns the plane; a sun-synchronous orbit keeps the two in step.
+//!
+//! The Sun comes from [`sun_position`], so elements should be in an
+//! Earth-centered equatorial frame and epochs are Julian dates.
+//!
+//! ```
+//! use almagest::kepler::KeplerianElements;
+//! use almagest::lighting::{beta_angle_at, eclipse_fraction};
+//! use almagest::groundtrack::EARTH_RADIUS;
+//! use almagest::utils::{Eccentricity, Meters, Radians};
+//!
+//! let iss = KeplerianElements {
+//!     semi_major_axis: Meters(6_798_000.0),
+//!     eccentricity: Eccentricity::new(0.0).unwrap(),
+//!     inclination: Radians(51.6_f64.to_radians()),
+//!     raan: Radians(1.0),
+//!     argument_of_periapsis: Radians(0.0),
+//!     true_anomaly: Radians(0.0),
+//! };
+//! let beta = beta_angle_at(&iss, 2_460_000.5);
+//! let shadowed = eclipse_fraction(iss.semi_major_axis, EARTH_RADIUS, beta);
+//! assert!((0.0..0.4).contains(&shadowed));
+//! ```
+
+use libm::{acos, asin, cos, sin, sqrt};
+
+use crate::error::AlmagestError;
+use crate::kepler::KeplerianElements;
+use crate::precession::{ZonalHarmonics, nodal_precession};
+use crate::sun::sun_position;
+use crate::utils::{Meters, PI, Real, SECONDS_PER_DAY};
+use crate::vectors::Vec3;
+
+/// Angle of `sun`, any vector toward the Sun, above the plane with
+/// node `raan` and `inclination`, in [−π/2, π/2]
+pub fn beta_angle(raan: Real, inclination: Real, sun: Vec3) -> Real {
+    let (si, ci) = (sin(inclination), cos(inclination));
+    let normal = Vec3::new(si * sin(raan), -si * cos(raan), ci);
+    asin(normal.dot(sun.unit()).clamp(-1.0, 1.0))
+}
+
+/// β of `elements` at `julian_date`, with the plane held where the
+/// elements put it
+pub fn beta_angle_at(elements: &KeplerianElements, julian_date: Real) -> Real {
+    beta_angle(
+        elements.raan.value(),
+        elements.inclination.value(),
+        sun_position(julian_date),
+    )
+}
+
+/// Share of a circular orbit of `radius` spent in the cylindrical
+/// shadow of a body of `body_radius` at beta angle `beta`; zero once
+/// |β| passes asin(R/r) and the orbit clears the shadow entirely
+pub fn eclipse_fraction(radius: Meters, body_radius: Meters, beta: Real) -> Real {
+    let (r, body) = (radius.value(), body_radius.value());
+    if r <= body {
+        return 1.0;
+    }
+    // Half the arc behind the body, where r cos β cos u < −√(r² − R²)
+    let threshold = sqrt(r * r - body * body) / (r * cos(beta));
+    if threshold >= 1.0 {
+        0.0
+    } else {
+        acos(threshold) / PI
+    }
+}
+
+/// β at one epoch of a [`BetaHistory`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BetaSample {
+    pub julian_date: Real,
+    pub beta: Real,
+}
+
+/// β at `n` epochs evenly spaced from `start` to `end` (Julian dates),
+/// both included, with the node of `elements` taken at `start` and
+/// regressing at the secular rate `field` drives
+pub fn beta_history(
+    elements: &KeplerianElements,
+    field: &ZonalHarmonics,
+    start: Real,
+    end: Real,
+    n: usize,
+) -> Result<BetaHistory, AlmagestError> {
+    let node_rate = nodal_precession(elements, field)?.value() * SECONDS_PER_DAY;
+    let step = if n > 1 {
+        (end - start) / (n - 1) as Real
+    } else {
+        0.0
+    };
+    Ok(BetaHistory {
+        raan: elements.raan.value(),
+        inclination: elements.inclination.value(),
+        node_rate,
+        start,
+        step,
+        index: 0,
+        count: n,
+    })
+}
+
+/// Iterator over the seasonal evolution of β, created by
+/// [`beta_history`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BetaHistory {
+    raan: Real,
+    inclination: Real,
+    // dΩ/dt in rad/day
+    node_rate: Real,
+    start: Real,
+    step: Real,
+    index: usize,
+    count: usize,
+}
+
+impl Iterator for BetaHistory {
+    type Item = BetaSample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.count {
+            return None;
+        }
+        let days = self.step * self.index as Real;
+        let julian_date = self.start + days;
+        let raan = self.raan + self.node_rate * days;
+        self.index += 1;
+        Some(BetaSample {
+            julian_date,
+            beta: beta_angle(raan, self.inclination, sun_position(julian_date)),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for BetaHistory {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groundtrack::EARTH_RADIUS;
+    use crate::precession::inclination_for_precession;
+    use crate::utils::{Eccentricity, Radians, RadiansPerSecond, TAU};
+    use approx::assert_relative_eq;
+
+    fn circular(radius: Real, inclination: Real, raan: Real) -> KeplerianElements {
+        KeplerianElements {
+            semi_major_axis: Meters(radius),
+            eccentricity: Eccentricity::new(0.0).unwrap(),
+            inclination: Radians(inclination),
+            raan: Radians(raan),
+            argument_of_periapsis: Radians(0.0),
+            true_anomaly: Radians(0.0),
+        }
+    }
+
+    #[test]
+    fn beta_follows_the_seasons() {
+        // An equatorial orbit sees the Sun at its declination
+        let equatorial = circular(7_000_000.0, 0.0, 0.0);
+        let equinox = 2_458_928.659_7;
+        assert_relative_eq!(beta_angle_at(&equatorial, equinox), 0.0, epsilon = 1e-3);
+        let solstice = 2_459_021.5;
+        assert_relative_eq!(
+            beta_angle_at(&equatorial, solstice).to_degrees(),
+            23.44,
+            epsilon = 0.01
+        );
+        // With the Sun down the orbit normal, there is no night
+        let normal = equatorial.orbit_normal();
+        assert_relative_eq!(beta_angle(0.0, 0.0, normal), PI / 2.0);
+
+        // The ISS node sweeps past the Sun every two months, so in a
+        // year β reaches nearly i + ε but no further
+        let iss = circular(6_798_000.0, 51.6_f64.to_radians(), 1.0);
+        let history =
+            beta_history(&iss, &ZonalHarmonics::EARTH, equinox, equinox + 365.0, 731).unwrap();
+        assert_eq!(history.len(), 731);
+        let widest = history.map(|s| s.beta.abs()).fold(0.0, Real::max);
+        assert!(widest.to_degrees() > 70.0 && widest.to_degrees() < 75.1);
+
+        // Holding the plane still reproduces the fixed-node angle
+        let still = ZonalHarmonics {
+            j2: 0.0,
+            j4: 0.0,
+            ..ZonalHarmonics::EARTH
+        };
+        let mut fixed = beta_history(&iss, &still, equinox, solstice, 3).unwrap();
+        let middle = fixed.nth(1).unwrap();
+        assert_relative_eq!(middle.julian_date, (equinox + solstice) / 2.0);
+        assert_relative_eq!(middle.beta, beta_angle_at(&iss, middle.julian_date));
+    }
+
+    #[test]
+    fn sun_synchronous_beta_holds_steady() {
+        let radius = 7_078_137.0;
+        let year = RadiansPerSecond(TAU / (365.242_2 * SECONDS_PER_DAY));
+        let i =
+            inclination_for_precession(Meters(radius), 0.0, year, &ZonalHarmonics::EARTH).unwrap();
+        // A 10:30 descending node: the ascending node 22:30, 157.5°
+        // ahead of the Sun's right ascension
+        let start = 2_458_928.659_7;
+        let sun = sun_position(start);
+        let raan = libm::atan2(sun.y, sun.x) + 157.5_f64.to_radians();
+        let orbit = circular(radius, i, raan);
+        let betas = beta_history(&orbit, &ZonalHarmonics::EARTH, start, start + 365.0, 366)
+            .unwrap()
+            .map(|s| s.beta.to_degrees());
+        let (low, high) = betas.fold((90.0, -90.0), |(lo, hi): (Real, Real), b| {
+            (lo.min(b), hi.max(b))
+        });
+        // Only the Sun's declination and the equation of time move it
+        assert!(high - low < 15.0);
+        assert!(low > 10.0 && high < 35.0);
+    }
+
+    #[test]
+    fn eclipse_matches_the_shadow_cylinder() {
+        let (r, body) = (6_778_137.0, EARTH_RADIUS.value());
+        for beta in [0.0, 0.5, 1.0, 1.3] {
+            // Fraction of points behind the Earth and inside its shadow
+            let steps = 100_000;
+            let sun = Vec3::new(cos(beta), 0.0, sin(beta));
+            let shadowed = (0..steps)
+                .filter(|k| {
+                    let u = TAU * *k as Real / steps as Real;
+                    let position = Vec3::new(cos(u), sin(u), 0.0) * r;
+                    let along = position.dot(sun);
+                    along < 0.0 && (position - sun * along).norm() < body
+                })
+                .count();
+            assert_relative_eq!(
+                eclipse_fraction(Meters(r), EARTH_RADIUS, beta),
+                shadowed as Real / steps as Real,
+                epsilon = 1e-4
+            );
+        }
+        assert_eq!(eclipse_fraction(Meters(r), EARTH_RADIUS, 1.3), 0.0);
+    }
+}
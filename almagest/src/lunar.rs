@@ -0,0 +1,551 @@
+//! Patched-conic lunar transfers, orbit insertion and frozen lunar
+//! orbits.
+//!
+//! The transfer follows Bate, Mueller and White (§7.4): a geocentric
+//! ellipse, injected at perigee from a circular parking orbit in the
+//! Moon's orbit plane, runs out to the Moon's sphere of influence, and
+//! inside it the path is a selenocentric hyperbola. The Moon's
+//! geocentric state at arrival, from [`crate::moon::moon_state`] or an
+//! SPK kernel, fixes the plane and the geometry; the Moon's motion
+//! while the spacecraft is inside the sphere is neglected.
+//!
+//! The result is a starting point for refinement in the restricted
+//! three-body problem (see [`crate::synodic`]), not a flyable design.
+//!
+//! Once in lunar orbit, the Moon's J3 drives the eccentricity and can
+//! walk a low perilune into the surface within months. The frozen-orbit
+//! and perilune-drift estimates here keep only J2 and J3 and first
+//! order in eccentricity; the higher zonals and the Earth's pull matter
+//! for real low orbits, so treat them as guidance.
+
+use libm::{acos, atan2, cos, sin, sqrt};
+
+use crate::bodies::MOON;
+use crate::error::AlmagestError;
+use crate::flyby::sphere_of_influence;
+use crate::kepler::{Ellipse, KeplerianElements, Point};
+use crate::kepler::{eccentric_to_mean, true_to_eccentric, wrap_two_pi};
+use crate::state::StateVector;
+use crate::utils::{GravParam, Meters, PI, Real, Seconds, TAU};
+use crate::vectors::{Matrix3, Vec3};
+
+/// Gravitational parameter of the Moon (DE440)
+pub const MU_MOON: GravParam = MOON.mu;
+
+/// Mean radius of the Moon
+pub const MOON_RADIUS: Meters = Meters(1_737_400.0);
+
+/// Reference radius of the lunar gravity field (LP165P)
+pub const MOON_GRAVITY_RADIUS: Meters = MOON.equatorial_radius;
+
+/// Lunar zonal harmonic J2 (LP165P)
+pub const MOON_J2: Real = MOON.j2;
+
+/// Lunar zonal harmonic J3 (LP165P)
+pub const MOON_J3: Real = 8.459_7e-6;
+
+/// Which way the spacecraft swings around the Moon, compared with the
+/// Moon's own motion about the Earth
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Flyby {
+    Prograde,
+    Retrograde,
+}
+
+/// The selenocentric leg of a lunar transfer
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LunarArrival {
+    /// Moon-centered state on entering the sphere of influence
+    pub entry: StateVector,
+    pub perilune_radius: Meters,
+    /// Speed at perilune, in m/s
+    pub perilune_speed: Real,
+    pub eccentricity: Real,
+    pub flyby: Flyby,
+}
+
+impl LunarArrival {
+    /// Hyperbolic excess speed relative to the Moon, in m/s
+    pub fn v_infinity(&self) -> Real {
+        let r = self.perilune_radius.value();
+        sqrt((self.perilune_speed * self.perilune_speed - 2.0 * MU_MOON.value() / r).max(0.0))
+    }
+
+    /// Burn at perilune into a lunar orbit keeping the arrival
+    /// perilune, with apolune `apolune`
+    pub fn insertion(&self, apolune: Meters) -> Result<LunarOrbitInsertion, AlmagestError> {
+        let perilune = self.perilune_radius;
+        if apolune < perilune {
+            return Err(AlmagestError::InvalidInput(
+                "Apolune must not be below the arrival perilune",
+            ));
+        }
+        let a = (perilune + apolune) / 2.0;
+        let mu = MU_MOON.value();
+        let speed = sqrt(mu * (2.0 / perilune.value() - 1.0 / a.value()));
+        Ok(LunarOrbitInsertion {
+            delta_v: self.perilune_speed - speed,
+            orbit: Ellipse::from_periapsis_apoapsis(perilune, apolune, Point::ORIGIN)?,
+            period: Seconds(TAU * sqrt(a.value() * a.value() * a.value() / mu)),
+        })
+    }
+}
+
+/// Capture into lunar orbit at perilune
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LunarOrbitInsertion {
+    /// Retrograde burn, in m/s
+    pub delta_v: Real,
+    pub orbit: Ellipse,
+    /// Period of the captured orbit
+    pub period: Seconds,
+}
+
+/// A patched-conic transfer from a parking orbit to the Moon
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LunarTransfer {
+    /// Geocentric state just after translunar injection
+    pub injection: StateVector,
+    /// Translunar injection burn, in m/s
+    pub tli_delta_v: Real,
+    /// Time from injection to entering the sphere of influence
+    pub time_of_flight: Seconds,
+    /// Angle the Moon leads the injection point by at injection, on
+    /// [0, 2π)
+    pub departure_phase: Real,
+    pub arrival: LunarArrival,
+    /// Perigee radius of the geocentric conic the spacecraft leaves
+    /// the sphere of influence on; below the top of the atmosphere it
+    /// is a free return
+    pub return_perigee: Meters,
+}
+
+/// What to aim the transfer at
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Aim {
+    /// A perilune radius, passing the Moon the given way
+    Perilune(Meters, Flyby),
+    /// A perigee radius on the way back, for a free return
+    FreeReturn(Meters),
+}
+
+/// Radius of the Moon's sphere of influence, Laplace's
+/// r = D (μ_moon / μ_earth)^(2/5)
+pub fn lunar_sphere_of_influence(distance: Meters, mu: GravParam) -> Meters {
+    sphere_of_influence(distance, MU_MOON, mu)
+}
+
+/// Transfer from a circular parking orbit of `parking_radius`, with
+/// injection to `injection_speed` m/s, meeting the sphere of influence
+/// `arrival_angle` radians around from the point facing the Earth,
+/// measured at the Moon in the direction of its motion. `moon` is the
+/// Moon's geocentric state at arrival and `mu` is the Earth's.
+pub fn lunar_transfer(
+    parking_radius: Meters,
+    injection_speed: Real,
+    arrival_angle: Real,
+    moon: &StateVector,
+    mu: GravParam,
+) -> Result<LunarTransfer, AlmagestError> {
+    let mu_earth = mu.value();
+    let mu_moon = MU_MOON.value();
+    let r0 = parking_radius.value();
+    let v0 = injection_speed;
+
+    // In-plane axes: x toward the Moon, z along its orbit normal
+    let x = moon.position.unit();
+    let z = moon.position.cross(moon.velocity).unit();
+    let axes = Matrix3::from_columns(x, z.cross(x), z);
+    let to_plane = axes.transpose();
+    let distance = moon.position.norm();
+    let moon_velocity = to_plane * moon.velocity;
+
+    // The geocentric transfer ellipse, with perigee at injection
+    let energy = v0 * v0 / 2.0 - mu_earth / r0;
+    if energy >= 0.0 {
+        return Err(AlmagestError::InvalidInput(
+            "Injection speed escapes the Earth",
+        ));
+    }
+    let h = r0 * v0;
+    let p = h * h / mu_earth;
+    let e = r0 * v0 * v0 / mu_earth - 1.0;
+    let a = -mu_earth / (2.0 * energy);
+
+    let soi = lunar_sphere_of_influence(Meters(distance), mu).value();
+    let rho = Vec3::new(-cos(arrival_angle), -sin(arrival_angle), 0.0) * soi;
+    let r1_vec = Vec3::new(distance, 0.0, 0.0) + rho;
+    let r1 = r1_vec.norm();
+    if e <= 0.0 || a * (1.0 + e) < r1 {
+        return Err(AlmagestError::InvalidInput(
+            "Transfer orbit does not reach the sphere of influence",
+        ));
+    }
+    // Outbound, before apogee
+    let nu1 = acos(((p / r1 - 1.0) / e).clamp(-1.0, 1.0));
+    let radial = r1_vec / r1;
+    let transverse = Vec3::Z.cross(radial);
+    let v1_vec = radial * (sqrt(mu_earth / p) * e * sin(nu1)) + transverse * (h / r1);
+    let v2 = v1_vec - moon_velocity;
+    if rho.dot(v2) >= 0.0 {
+        return Err(AlmagestError::InvalidInput(
+            "Transfer leaves the sphere of influence at that point",
+        ));
+    }
+
+    // The selenocentric hyperbola
+    let h_moon = rho.cross(v2).z;
+    let energy_moon = v2.norm_squared() / 2.0 - mu_moon / soi;
+    let p_moon = h_moon * h_moon / mu_moon;
+    let e_moon = sqrt((1.0 + 2.0 * energy_moon * p_moon / mu_moon).max(0.0));
+    let perilune = p_moon / (1.0 + e_moon);
+
+    // Leave the sphere mirrored about the apse line
+    let apse = ((rho * (v2.norm_squared() - mu_moon / soi)) - v2 * rho.dot(v2)).unit();
+    let rho_out = apse * (2.0 * rho.dot(apse)) - rho;
+    let v_out = v2 - apse * (2.0 * v2.dot(apse));
+    let r_return = Vec3::new(distance, 0.0, 0.0) + rho_out;
+    let v_return = moon_velocity + v_out;
+    let h_return = r_return.cross(v_return).norm();
+    let energy_return = v_return.norm_squared() / 2.0 - mu_earth / r_return.norm();
+    let e_return =
+        sqrt((1.0 + 2.0 * energy_return * h_return * h_return / (mu_earth * mu_earth)).max(0.0));
+
+    let ecc_anomaly = true_to_eccentric(nu1, e);
+    let time_of_flight = eccentric_to_mean(ecc_anomaly, e) / sqrt(mu_earth / (a * a * a));
+    let perigee_angle = atan2(r1_vec.y, r1_vec.x) - nu1;
+    let (sin0, cos0) = (sin(perigee_angle), cos(perigee_angle));
+    let moon_rate = moon.position.cross(moon.velocity).norm() / (distance * distance);
+
+    Ok(LunarTransfer {
+        injection: StateVector::new(
+            axes * Vec3::new(cos0, sin0, 0.0) * r0,
+            axes * Vec3::new(-sin0, cos0, 0.0) * v0,
+        ),
+        tli_delta_v: v0 - sqrt(mu_earth / r0),
+        time_of_flight: Seconds(time_of_flight),
+        departure_phase: wrap_two_pi(-moon_rate * time_of_flight - perigee_angle),
+        arrival: LunarArrival {
+            entry: StateVector::new(axes * rho, axes * v2),
+            perilune_radius: Meters(perilune),
+            perilune_speed: h_moon.abs() / perilune,
+            eccentricity: e_moon,
+            flyby: if h_moon > 0.0 {
+                Flyby::Prograde
+            } else {
+                Flyby::Retrograde
+            },
+        },
+        return_perigee: Meters(h_return * h_return / mu_earth / (1.0 + e_return)),
+    })
+}
+
+/// Find the arrival angle for [`lunar_transfer`] that meets `aim`.
+/// Free returns that hit the Moon are passed over, and where several
+/// arrival angles work the one nearest the Earth-facing point of the
+/// sphere of influence is taken.
+pub fn aim_lunar_transfer(
+    parking_radius: Meters,
+    injection_speed: Real,
+    aim: Aim,
+    moon: &StateVector,
+    mu: GravParam,
+) -> Result<LunarTransfer, AlmagestError> {
+    let miss = |angle: Real| {
+        let transfer = lunar_transfer(parking_radius, injection_speed, angle, moon, mu).ok()?;
+        let arrival = transfer.arrival;
+        match aim {
+            Aim::Perilune(radius, flyby) => {
+                (arrival.flyby == flyby).then(|| (arrival.perilune_radius - radius).value())
+            }
+            Aim::FreeReturn(perigee) => (arrival.perilune_radius >= MOON_RADIUS)
+                .then(|| (transfer.return_perigee - perigee).value()),
+        }
+    };
+
+    // Sweep the arrival angle for a change of sign, then bisect
+    const STEPS: usize = 720;
+    let mut best: Option<Real> = None;
+    let mut previous = miss(-PI).map(|f| (-PI, f));
+    for i in 1..=STEPS {
+        let angle = -PI + TAU * i as Real / STEPS as Real;
+        let current = miss(angle).map(|f| (angle, f));
+        if let (Some((mut low, mut f_low)), Some((mut high, f_high))) = (previous, current)
+            && f_low * f_high <= 0.0
+        {
+            for _ in 0..60 {
+                let mid = (low + high) / 2.0;
+                let Some(f_mid) = miss(mid) else { break };
+                if f_low * f_mid <= 0.0 {
+                    high = mid;
+                } else {
+                    (low, f_low) = (mid, f_mid);
+                }
+            }
+            let root = (low + high) / 2.0;
+            if best.is_none_or(|b| root.abs() < b.abs()) {
+                best = Some(root);
+            }
+        }
+        previous = current;
+    }
+    let angle = best.ok_or(AlmagestError::InvalidInput(
+        "No arrival angle meets the aim",
+    ))?;
+    lunar_transfer(parking_radius, injection_speed, angle, moon, mu)
+}
+
+/// The eccentricity and argument of periapsis that hold still under
+/// the Moon's J2 and J3
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrozenOrbit {
+    pub eccentricity: Real,
+    /// Either π/2 or 3π/2
+    pub argument_of_periapsis: Real,
+}
+
+/// Frozen lunar orbit for a semi-major axis and inclination:
+/// e = −(J3 R / 2 J2 a) sin i sin ω, with ω chosen to make e positive
+pub fn frozen_lunar_orbit(
+    semi_major_axis: Meters,
+    inclination: Real,
+) -> Result<FrozenOrbit, AlmagestError> {
+    if semi_major_axis <= MOON_GRAVITY_RADIUS {
+        return Err(AlmagestError::InvalidInput(
+            "Orbit must lie above the Moon's surface",
+        ));
+    }
+    if sin(inclination) == 0.0 {
+        return Err(AlmagestError::InvalidInput(
+            "Equatorial orbits have no frozen eccentricity",
+        ));
+    }
+    let e = MOON_J3 * MOON_GRAVITY_RADIUS.value() * sin(inclination)
+        / (2.0 * MOON_J2 * semi_major_axis.value());
+    Ok(FrozenOrbit {
+        eccentricity: e.abs(),
+        argument_of_periapsis: if e > 0.0 { 3.0 * PI / 2.0 } else { PI / 2.0 },
+    })
+}
+
+/// Secular rates of eccentricity and argument of periapsis from the
+/// Moon's J2 and J3, per second
+pub fn lunar_apsidal_rates(elements: &KeplerianElements) -> (Real, Real) {
+    let a = elements.semi_major_axis.value();
+    let e = elements.eccentricity.value();
+    let (s, c) = elements.inclination.sin_cos();
+    let omega = elements.argument_of_periapsis.value();
+    let n = sqrt(MU_MOON.value() / (a * a * a));
+    let ratio = MOON_GRAVITY_RADIUS.value() / (a * (1.0 - e * e));
+    let tilt = 1.0 - 1.25 * s * s;
+    let j3 = 1.5 * n * MOON_J3 * ratio * ratio * ratio * tilt;
+    let e_rate = -j3 * (1.0 - e * e) * s * cos(omega);
+    let mut omega_rate = 3.0 * n * MOON_J2 * ratio * ratio * tilt;
+    if e > 0.0 && s != 0.0 {
+        omega_rate += j3 * (s * s - e * e * c * c) / (e * s) * sin(omega);
+    }
+    (e_rate, omega_rate)
+}
+
+/// Lowest perilune radius an orbit reaches as its eccentricity vector
+/// circles the frozen point, at fixed semi-major axis and inclination
+pub fn lowest_perilune(elements: &KeplerianElements) -> Result<Meters, AlmagestError> {
+    let a = elements.semi_major_axis;
+    let frozen = frozen_lunar_orbit(a, elements.inclination.value())?;
+    let e = elements.eccentricity.value();
+    let omega = elements.argument_of_periapsis.value();
+    let (x, y) = (e * cos(omega), e * sin(omega));
+    let (fx, fy) = (
+        frozen.eccentricity * cos(frozen.argument_of_periapsis),
+        frozen.eccentricity * sin(frozen.argument_of_periapsis),
+    );
+    let swing = sqrt((x - fx) * (x - fx) + (y - fy) * (y - fy));
+    Ok(a * (1.0 - (frozen.eccentricity + swing).min(1.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::kepler::KeplerianElements;
+    use crate::moon::moon_state;
+    use crate::utils::{Eccentricity, Radians};
+    use approx::assert_relative_eq;
+
+    const PARKING: Meters = Meters(6_678_137.0);
+
+    #[test]
+    fn transfer_ellipse_meets_the_sphere() {
+        let moon = moon_state(2_460_000.5);
+        let transfer = lunar_transfer(PARKING, 10_900.0, 0.3, &moon, EARTH.mu).unwrap();
+        assert_relative_eq!(transfer.tli_delta_v, 3_174.2, epsilon = 0.1);
+        assert!((1.5..2.0).contains(&transfer.time_of_flight.to_days().value()));
+
+        let elements = KeplerianElements::from_state(&transfer.injection, EARTH.mu).unwrap();
+        let entry = elements
+            .propagate(transfer.time_of_flight, EARTH.mu)
+            .unwrap()
+            .to_state(EARTH.mu);
+        let expected = moon.position + transfer.arrival.entry.position;
+        assert_relative_eq!((entry.position - expected).norm(), 0.0, epsilon = 1.0);
+        let relative = entry.velocity - moon.velocity;
+        assert_relative_eq!(
+            (relative - transfer.arrival.entry.velocity).norm(),
+            0.0,
+            epsilon = 1e-6
+        );
+        let soi = lunar_sphere_of_influence(Meters(moon.position.norm()), EARTH.mu);
+        assert_relative_eq!(
+            transfer.arrival.entry.position.norm(),
+            soi.value(),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn aims_for_a_perilune() {
+        let moon = moon_state(2_460_000.5);
+        let target = Meters(1_837_400.0);
+        let aim = Aim::Perilune(target, Flyby::Retrograde);
+        let transfer = aim_lunar_transfer(PARKING, 10_900.0, aim, &moon, EARTH.mu).unwrap();
+        assert_eq!(transfer.arrival.flyby, Flyby::Retrograde);
+        assert_relative_eq!(
+            transfer.arrival.perilune_radius.value(),
+            target.value(),
+            epsilon = 1.0
+        );
+        assert!(transfer.arrival.eccentricity > 1.0);
+    }
+
+    #[test]
+    fn aims_for_a_free_return() {
+        let moon = moon_state(2_460_000.5);
+        let perigee = Meters(6_438_137.0);
+        let aim = Aim::FreeReturn(perigee);
+        let transfer = aim_lunar_transfer(PARKING, 10_850.0, aim, &moon, EARTH.mu).unwrap();
+        assert_relative_eq!(
+            transfer.return_perigee.value(),
+            perigee.value(),
+            epsilon = 1.0
+        );
+        assert!(transfer.arrival.perilune_radius > MOON_RADIUS);
+        // The figure-eight passes behind the Moon against its motion
+        assert_eq!(transfer.arrival.flyby, Flyby::Retrograde);
+
+        assert!(aim_lunar_transfer(PARKING, 9_000.0, aim, &moon, EARTH.mu).is_err());
+    }
+
+    #[test]
+    fn insertion_from_the_arrival_hyperbola() {
+        let moon = moon_state(2_460_000.5);
+        let aim = Aim::Perilune(Meters(1_837_400.0), Flyby::Retrograde);
+        let transfer = aim_lunar_transfer(PARKING, 10_900.0, aim, &moon, EARTH.mu).unwrap();
+        let arrival = transfer.arrival;
+        let soi = lunar_sphere_of_influence(Meters(moon.position.norm()), EARTH.mu).value();
+        let v2 = arrival.entry.velocity.norm_squared();
+        assert_relative_eq!(
+            arrival.v_infinity(),
+            sqrt(v2 - 2.0 * MU_MOON.value() / soi),
+            max_relative = 1e-9
+        );
+
+        let circular = arrival.insertion(arrival.perilune_radius).unwrap();
+        let speed = sqrt(MU_MOON.value() / arrival.perilune_radius.value());
+        assert_relative_eq!(
+            circular.delta_v,
+            arrival.perilune_speed - speed,
+            max_relative = 1e-12
+        );
+        assert!(circular.delta_v > 600.0 && circular.delta_v < 1_200.0);
+        // A loose capture is cheaper
+        let loose = arrival.insertion(Meters(10_000_000.0)).unwrap();
+        assert!(loose.delta_v < circular.delta_v);
+        assert!(loose.period > circular.period);
+        assert!(arrival.insertion(Meters(1_000_000.0)).is_err());
+    }
+
+    fn lunar_orbit(a: Real, e: Real, inclination: Real, omega: Real) -> KeplerianElements {
+        KeplerianElements {
+            semi_major_axis: Meters(a),
+            eccentricity: Eccentricity::new(e).unwrap(),
+            inclination: Radians(inclination),
+            raan: Radians(0.0),
+            argument_of_periapsis: Radians(omega),
+            true_anomaly: Radians(0.0),
+        }
+    }
+
+    #[test]
+    fn frozen_orbit_holds_still() {
+        let a = Meters(1_838_000.0);
+        let inclination = 1.5;
+        let frozen = frozen_lunar_orbit(a, inclination).unwrap();
+        assert_relative_eq!(frozen.eccentricity, 0.0196, epsilon = 1e-3);
+        assert_relative_eq!(frozen.argument_of_periapsis, 3.0 * PI / 2.0);
+
+        let elements = lunar_orbit(
+            a.value(),
+            frozen.eccentricity,
+            inclination,
+            frozen.argument_of_periapsis,
+        );
+        let (e_rate, omega_rate) = lunar_apsidal_rates(&elements);
+        assert_relative_eq!(e_rate, 0.0, epsilon = 1e-20);
+        // Only the neglected e² terms remain
+        let (_, j2_only) = lunar_apsidal_rates(&lunar_orbit(a.value(), 0.0, inclination, 0.0));
+        assert!(omega_rate.abs() < 1e-3 * j2_only.abs());
+
+        let lowest = lowest_perilune(&elements).unwrap();
+        assert_relative_eq!(
+            lowest.value(),
+            a.value() * (1.0 - frozen.eccentricity),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn eccentricity_vector_circles_the_frozen_point() {
+        let (a, inclination) = (1_838_000.0, 1.5);
+        let frozen = frozen_lunar_orbit(Meters(a), inclination).unwrap();
+        let center = (0.0, -frozen.eccentricity);
+        // The J2 rate alone, where J3 leaves ω̇ untouched
+        let (_, j2_rate) = lunar_apsidal_rates(&lunar_orbit(a, 0.01, inclination, 0.0));
+        for angle in [0.0, 1.0, 2.5, 4.0, 5.5] {
+            let offset = (0.005 * cos(angle), 0.005 * sin(angle));
+            let (k, h) = (center.0 + offset.0, center.1 + offset.1);
+            let (e, omega) = (sqrt(k * k + h * h), atan2(h, k));
+            let (e_rate, omega_rate) = lunar_apsidal_rates(&lunar_orbit(a, e, inclination, omega));
+            // d/dt of (e cos ω, e sin ω)
+            let velocity = (
+                e_rate * cos(omega) - e * omega_rate * sin(omega),
+                e_rate * sin(omega) + e * omega_rate * cos(omega),
+            );
+            let speed = sqrt(velocity.0 * velocity.0 + velocity.1 * velocity.1);
+            let radial = (velocity.0 * offset.0 + velocity.1 * offset.1) / (speed * 0.005);
+            assert_relative_eq!(radial, 0.0, epsilon = 1e-2);
+            assert_relative_eq!(speed, j2_rate.abs() * 0.005, max_relative = 1e-2);
+        }
+    }
+
+    #[test]
+    fn circular_low_orbit_sinks() {
+        // 50 km up in a polar orbit
+        let a = 1_788_000.0;
+        let elements = lunar_orbit(a, 0.0, 1.5, 0.0);
+        let frozen = frozen_lunar_orbit(Meters(a), 1.5).unwrap();
+        let lowest = lowest_perilune(&elements).unwrap();
+        assert_relative_eq!(
+            lowest.value(),
+            a * (1.0 - 2.0 * frozen.eccentricity),
+            max_relative = 1e-12
+        );
+        assert!(lowest < MOON_GRAVITY_RADIUS);
+        assert!(frozen_lunar_orbit(Meters(1_700_000.0), 1.5).is_err());
+    }
+}
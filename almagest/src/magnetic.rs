@@ -0,0 +1,404 @@
+//! Dipole models of the Earth's magnetic field.
+//!
+//! The field is described by the degree-one Gauss coefficients of a
+//! spherical-harmonic model such as the IGRF, in nanotesla. Those
+//! alone give a dipole at the Earth's center; adding the degree-two
+//! coefficients moves it to the eccentric dipole that best matches
+//! them (Fraser-Smith, 1987), which is what places the weak field of
+//! the South Atlantic Anomaly. Positions are body-fixed (ECEF), in
+//! meters.
+//!
+//! Magnetic longitude is measured in the dipole frame whose z axis is
+//! the dipole axis and whose y axis is perpendicular to both it and
+//! the geographic pole; magnetic local time is the magnetic longitude
+//! from the anti-sunward meridian, in hours.
+
+use libm::{asin, atan2, cos, sqrt};
+
+use crate::bodies::RotatingBody;
+use crate::groundtrack::body_fixed;
+use crate::search::{Visits, visits};
+use crate::state::StateVector;
+use crate::utils::{Meters, PI, Real, Seconds};
+use crate::vectors::Vec3;
+
+/// Reference radius of the IGRF spherical-harmonic expansion
+pub const IGRF_REFERENCE_RADIUS: Meters = Meters(6_371_200.0);
+
+/// Degree-one IGRF-13 Gauss coefficients at epoch 2020.0, in nT,
+/// as (g₁⁰, g₁¹, h₁¹)
+pub const IGRF_2020_DIPOLE: [Real; 3] = [-29_404.8, -1_450.9, 4_652.5];
+
+/// Degree-two IGRF-13 Gauss coefficients at epoch 2020.0, in nT,
+/// as (g₂⁰, g₂¹, h₂¹, g₂², h₂²)
+pub const IGRF_2020_QUADRUPOLE: [Real; 5] = [-2_499.6, 2_982.0, -2_991.6, 1_677.0, -734.6];
+
+/// A magnetic dipole fixed to the rotating Earth
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DipoleField {
+    // (g₁¹, h₁¹, g₁⁰): antiparallel to the boreal geomagnetic pole
+    gauss: Vec3,
+    // Position of the dipole in the body-fixed frame
+    center: Vec3,
+    reference_radius: Meters,
+}
+
+impl DipoleField {
+    /// A dipole at the Earth's center from degree-one coefficients
+    /// (g₁⁰, g₁¹, h₁¹) in nT
+    pub fn centered(dipole: [Real; 3], reference_radius: Meters) -> Self {
+        let [g10, g11, h11] = dipole;
+        DipoleField {
+            gauss: Vec3::new(g11, h11, g10),
+            center: Vec3::ZERO,
+            reference_radius,
+        }
+    }
+
+    /// The eccentric dipole fitted to degree-one and degree-two
+    /// coefficients (g₂⁰, g₂¹, h₂¹, g₂², h₂²) in nT
+    pub fn eccentric(dipole: [Real; 3], quadrupole: [Real; 5], reference_radius: Meters) -> Self {
+        let [g10, g11, h11] = dipole;
+        let [g20, g21, h21, g22, h22] = quadrupole;
+        let s3 = sqrt(3.0);
+        let b0_2 = g10 * g10 + g11 * g11 + h11 * h11;
+        let l0 = 2.0 * g10 * g20 + s3 * (g11 * g21 + h11 * h21);
+        let l1 = -g11 * g20 + s3 * (g10 * g21 + g11 * g22 + h11 * h22);
+        let l2 = -h11 * g20 + s3 * (g10 * h21 - h11 * g22 + g11 * h22);
+        let e = (l0 * g10 + l1 * g11 + l2 * h11) / (4.0 * b0_2);
+        let scale = reference_radius.value() / (3.0 * b0_2);
+        DipoleField {
+            center: Vec3::new(l1 - g11 * e, l2 - h11 * e, l0 - g10 * e) * scale,
+            ..DipoleField::centered(dipole, reference_radius)
+        }
+    }
+
+    /// The centered IGRF-13 dipole at 2020.0
+    pub fn igrf_2020() -> Self {
+        DipoleField::centered(IGRF_2020_DIPOLE, IGRF_REFERENCE_RADIUS)
+    }
+
+    /// The eccentric IGRF-13 dipole at 2020.0
+    pub fn igrf_2020_eccentric() -> Self {
+        DipoleField::eccentric(
+            IGRF_2020_DIPOLE,
+            IGRF_2020_QUADRUPOLE,
+            IGRF_REFERENCE_RADIUS,
+        )
+    }
+
+    /// Field strength at the reference radius on the magnetic
+    /// equator, B₀, in nT
+    pub fn equatorial_strength(&self) -> Real {
+        self.gauss.norm()
+    }
+
+    /// Unit vector along the dipole axis toward the boreal
+    /// (northern) geomagnetic pole
+    pub fn north_pole(&self) -> Vec3 {
+        -self.gauss.unit()
+    }
+
+    /// Offset of the dipole from the Earth's center
+    pub fn center(&self) -> Vec3 {
+        self.center
+    }
+
+    /// Magnetic (dipole) latitude of a body-fixed position, in radians
+    pub fn magnetic_latitude(&self, position: Vec3) -> Real {
+        let rho = position - self.center;
+        asin((rho.unit().dot(self.north_pole())).clamp(-1.0, 1.0))
+    }
+
+    /// Field strength at a body-fixed position, in nT
+    pub fn field_strength(&self, position: Vec3) -> Real {
+        let rho = position - self.center;
+        let ratio = self.reference_radius.value() / rho.norm();
+        let s = rho.unit().dot(self.north_pole());
+        self.equatorial_strength() * ratio * ratio * ratio * sqrt(1.0 + 3.0 * s * s)
+    }
+
+    /// Magnetic (dipole) longitude of a body-fixed position, in
+    /// radians on (−π, π]
+    pub fn magnetic_longitude(&self, position: Vec3) -> Real {
+        let z = self.north_pole();
+        let y = Vec3::Z.cross(z).unit();
+        let x = y.cross(z);
+        let rho = position - self.center;
+        atan2(rho.dot(y), rho.dot(x))
+    }
+
+    /// Magnetic local time at a body-fixed position, in hours on
+    /// [0, 24), given the body-fixed position of the Sun
+    pub fn magnetic_local_time(&self, position: Vec3, sun: Vec3) -> Real {
+        let offset = self.magnetic_longitude(position) - self.magnetic_longitude(sun);
+        let hours = (12.0 + offset * 12.0 / PI) % 24.0;
+        if hours < 0.0 { hours + 24.0 } else { hours }
+    }
+
+    /// McIlwain L-shell of the dipole field line through a body-fixed
+    /// position: the distance at which it crosses the magnetic
+    /// equator, in reference radii
+    pub fn l_shell(&self, position: Vec3) -> Real {
+        let rho = position - self.center;
+        let s = rho.unit().dot(self.north_pole());
+        rho.norm() / self.reference_radius.value() / (1.0 - s * s)
+    }
+}
+
+/// A simple auroral oval: a band of magnetic latitude in each
+/// hemisphere, pushed poleward on the dayside. The band sits between
+/// `equatorward` and `poleward` at magnetic midnight and moves up by
+/// `dayside_shift` at magnetic noon, following (1 − cos) in between.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuroralOval {
+    pub equatorward: Real,
+    pub poleward: Real,
+    pub dayside_shift: Real,
+}
+
+impl AuroralOval {
+    /// A quiet-time oval, 65°–75° at midnight and 73°–83° at noon
+    pub const QUIET: Self = AuroralOval {
+        equatorward: 65.0_f64.to_radians(),
+        poleward: 75.0_f64.to_radians(),
+        dayside_shift: 8.0_f64.to_radians(),
+    };
+
+    /// Whether a magnetic latitude (radians) and local time (hours)
+    /// falls within the oval of either hemisphere
+    pub fn contains(&self, magnetic_latitude: Real, magnetic_local_time: Real) -> bool {
+        let shift = self.dayside_shift * (1.0 - cos(magnetic_local_time * PI / 12.0)) / 2.0;
+        let latitude = magnetic_latitude.abs();
+        latitude >= self.equatorward + shift && latitude <= self.poleward + shift
+    }
+}
+
+/// Magnetic coordinates at one point of a trajectory
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MagneticSample {
+    /// Time from the epoch of the ephemeris
+    pub time: Seconds,
+    /// Radians
+    pub magnetic_latitude: Real,
+    /// Hours
+    pub magnetic_local_time: Real,
+    pub l_shell: Real,
+}
+
+/// A trajectory viewed in magnetic coordinates. The `ephemeris` and
+/// `sun` give inertial positions at a time, the latter for
+/// example from [`crate::sun::sun_position`].
+#[derive(Copy, Clone, Debug)]
+pub struct MagneticTrack<E, S> {
+    pub field: DipoleField,
+    pub ephemeris: E,
+    pub sun: S,
+    /// The Earth, with time zero at the epoch of the ephemeris
+    pub body: RotatingBody,
+}
+
+impl<E, S> MagneticTrack<E, S>
+where
+    E: Fn(Seconds) -> StateVector,
+    S: Fn(Seconds) -> Vec3,
+{
+    pub fn new(field: DipoleField, ephemeris: E, sun: S, greenwich_angle: Real) -> Self {
+        MagneticTrack {
+            field,
+            ephemeris,
+            sun,
+            body: RotatingBody::earth(greenwich_angle),
+        }
+    }
+
+    /// Magnetic coordinates at time `t`
+    pub fn at(&self, time: Seconds) -> MagneticSample {
+        let angle = self.body.angle(time);
+        let position = body_fixed((self.ephemeris)(time).position, angle);
+        let sun = body_fixed((self.sun)(time), angle);
+        MagneticSample {
+            time,
+            magnetic_latitude: self.field.magnetic_latitude(position),
+            magnetic_local_time: self.field.magnetic_local_time(position, sun),
+            l_shell: self.field.l_shell(position),
+        }
+    }
+
+    /// Samples every `step` from `start` through `end`
+    pub fn samples(
+        &self,
+        start: Seconds,
+        end: Seconds,
+        step: Seconds,
+    ) -> impl Iterator<Item = MagneticSample> + '_ {
+        let count = if step > Seconds::ZERO && end >= start {
+            ((end - start) / step) as usize + 1
+        } else {
+            0
+        };
+        (0..count).map(move |i| self.at(start + step * i as Real))
+    }
+
+    /// Spans between `start` and `end` spent inside the auroral oval,
+    /// sampled every `step`
+    pub fn auroral_crossings(
+        &self,
+        oval: AuroralOval,
+        start: Seconds,
+        end: Seconds,
+        step: Seconds,
+    ) -> Visits<impl Fn(Seconds) -> bool + '_> {
+        visits(
+            move |t| {
+                let sample = self.at(t);
+                oval.contains(sample.magnetic_latitude, sample.magnetic_local_time)
+            },
+            start,
+            end,
+            step,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::groundtrack::GroundPoint;
+    use crate::utils::TAU;
+    use approx::assert_relative_eq;
+
+    fn at(latitude: Real, longitude: Real, radius: Real) -> Vec3 {
+        GroundPoint::new(latitude.to_radians(), longitude.to_radians()).unit() * radius
+    }
+
+    #[test]
+    fn igrf_2020_pole() {
+        let pole = DipoleField::igrf_2020().north_pole();
+        let point = GroundPoint::new(asin(pole.z), libm::atan2(pole.y, pole.x));
+        assert_relative_eq!(point.latitude.to_degrees(), 80.59, epsilon = 1e-2);
+        assert_relative_eq!(point.longitude.to_degrees(), -72.68, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn eccentric_dipole_offset() {
+        let center = DipoleField::igrf_2020_eccentric().center();
+        // Some 590 km toward the western Pacific
+        assert_relative_eq!(center.norm(), 590_481.0, epsilon = 100.0);
+        assert_relative_eq!(
+            libm::atan2(center.y, center.x).to_degrees(),
+            136.97,
+            epsilon = 1e-2
+        );
+    }
+
+    #[test]
+    fn dipole_geometry() {
+        let field = DipoleField::igrf_2020();
+        let a = IGRF_REFERENCE_RADIUS.value();
+        let pole = field.north_pole() * (2.0 * a);
+        assert_relative_eq!(field.magnetic_latitude(pole).to_degrees(), 90.0);
+        // Twice as strong over the pole as at the equator, for the same radius
+        let equator = field.north_pole().cross(Vec3::Z).unit() * (2.0 * a);
+        assert_relative_eq!(field.magnetic_latitude(equator), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(
+            field.field_strength(pole),
+            2.0 * field.field_strength(equator),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            field.field_strength(equator),
+            field.equatorial_strength() / 8.0,
+            max_relative = 1e-12
+        );
+        // On the equator the L-shell is the distance itself
+        assert_relative_eq!(field.l_shell(equator), 2.0, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn l_shell_follows_field_lines() {
+        let field = DipoleField::igrf_2020();
+        let a = IGRF_REFERENCE_RADIUS.value();
+        // r = L cos²λ along a field line
+        let lambda: Real = 0.6;
+        let axis = field.north_pole();
+        let across = axis.cross(Vec3::Z).unit();
+        let r = 4.0 * a * libm::cos(lambda) * libm::cos(lambda);
+        let position = (across * libm::cos(lambda) + axis * libm::sin(lambda)) * r;
+        assert_relative_eq!(field.l_shell(position), 4.0, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn south_atlantic_is_weak() {
+        let field = DipoleField::igrf_2020_eccentric();
+        let r = 6_878_137.0;
+        let atlantic = field.field_strength(at(-26.0, -50.0, r));
+        let pacific = field.field_strength(at(-26.0, 130.0, r));
+        assert!(atlantic < 0.6 * pacific);
+    }
+    #[test]
+    fn magnetic_local_time_follows_the_sun() {
+        let field = DipoleField::igrf_2020();
+        let r = 7.0e6;
+        let sun = at(0.0, 0.0, 1.5e11);
+        // Sub-solar point is at noon, the antisolar point at midnight
+        let noon = field.magnetic_local_time(at(0.0, 0.0, r), sun);
+        let midnight = field.magnetic_local_time(at(0.0, 180.0, r), sun);
+        assert_relative_eq!(noon, 12.0, epsilon = 0.3);
+        assert!(!(0.3..=23.7).contains(&midnight));
+        // Local time runs eastward
+        let dusk = field.magnetic_local_time(at(0.0, 90.0, r), sun);
+        assert!(dusk > 15.0 && dusk < 21.0);
+    }
+
+    #[test]
+    fn oval_moves_poleward_by_day() {
+        let oval = AuroralOval::QUIET;
+        let deg = |d: Real| d.to_radians();
+        assert!(oval.contains(deg(67.0), 0.0));
+        assert!(oval.contains(deg(-67.0), 23.5));
+        assert!(!oval.contains(deg(67.0), 12.0));
+        assert!(oval.contains(deg(80.0), 12.0));
+        assert!(!oval.contains(deg(50.0), 0.0));
+    }
+
+    #[test]
+    fn polar_orbit_crosses_the_oval() {
+        let mu = EARTH.mu.value();
+        let radius = 7_078_137.0;
+        let ephemeris = |t: Seconds| {
+            let v = sqrt(mu / radius);
+            let n = v / radius;
+            let (s, c) = (libm::sin(n * t.value()), libm::cos(n * t.value()));
+            StateVector::new(Vec3::new(c, 0.0, s) * radius, Vec3::new(-s, 0.0, c) * v)
+        };
+        let track = MagneticTrack::new(
+            DipoleField::igrf_2020(),
+            ephemeris,
+            |_| Vec3::new(1.5e11, 0.0, 0.0),
+            0.0,
+        );
+        let period = TAU * sqrt(radius * radius * radius / mu);
+        let crossings = track.auroral_crossings(
+            AuroralOval::QUIET,
+            Seconds::ZERO,
+            Seconds(period),
+            Seconds(10.0),
+        );
+        // Each hemisphere's oval is crossed twice per polar orbit
+        let count = crossings
+            .inspect(|c| {
+                let mid = track.at(c.start + c.duration() / 2.0);
+                assert!(mid.magnetic_latitude.abs() > 60.0_f64.to_radians());
+            })
+            .count();
+        assert!((3..=4).contains(&count));
+        for sample in track.samples(Seconds::ZERO, Seconds(period), Seconds(60.0)) {
+            assert!((0.0..24.0).contains(&sample.magnetic_local_time));
+        }
+    }
+}
@@ -0,0 +1,879 @@
+//! Impulsive orbit transfers, plane changes, phasing and rendezvous.
+//!
+//! Burns are instantaneous changes of velocity, and delta-v values
+//! are their magnitudes.
+
+use libm::{acos, atan2, cbrt, cos, sin, sqrt};
+
+use crate::error::AlmagestError;
+use crate::kepler::{Ellipse, KeplerianElements, Point, Point3, wrap_two_pi};
+use crate::state::StateVector;
+use crate::utils::{GravParam, Meters, MetersPerSecond, PI, Radians, Real, Seconds, TAU};
+use crate::vectors::Vec3;
+use core::f64::consts::SQRT_2;
+
+/// A two-burn Hohmann transfer between coplanar, coaxial orbits
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HohmannTransfer {
+    /// Burn that leaves the initial orbit
+    pub departure_delta_v: MetersPerSecond,
+    /// Burn that joins the final orbit
+    pub arrival_delta_v: MetersPerSecond,
+    /// Time spent on the transfer orbit, half its period
+    pub transfer_time: Seconds,
+    /// The half-ellipse flown between the burns
+    pub transfer_orbit: Ellipse,
+}
+
+impl HohmannTransfer {
+    pub fn total_delta_v(&self) -> MetersPerSecond {
+        self.departure_delta_v + self.arrival_delta_v
+    }
+}
+
+/// Hohmann transfer between circular orbits of radius
+/// `r_initial` and `r_final`
+pub fn hohmann(
+    r_initial: Meters,
+    r_final: Meters,
+    mu: GravParam,
+) -> Result<HohmannTransfer, AlmagestError> {
+    transfer(
+        r_initial,
+        &circle(r_initial)?,
+        r_final,
+        &circle(r_final)?,
+        Point3::ORIGIN,
+        mu,
+    )
+}
+
+/// Hohmann transfer between coaxial elliptic orbits with aligned
+/// apsides. Raising burns at the initial periapsis and arrives at the
+/// final apoapsis; lowering burns at the initial apoapsis and arrives
+/// at the final periapsis, which are the cheaper pairings. Either way
+/// the transfer's periapsis lies along the initial orbit's.
+pub fn hohmann_between(
+    initial: &Ellipse,
+    target: &Ellipse,
+    mu: GravParam,
+) -> Result<HohmannTransfer, AlmagestError> {
+    let (r_departure, r_arrival) = if target.semi_major_axis() >= initial.semi_major_axis() {
+        (initial.periapsis(), target.apoapsis())
+    } else {
+        (initial.apoapsis(), target.periapsis())
+    };
+    let mut hohmann = transfer(
+        r_departure,
+        initial,
+        r_arrival,
+        target,
+        initial.primary_focus(),
+        mu,
+    )?;
+    hohmann.transfer_orbit = hohmann
+        .transfer_orbit
+        .with_argument_of_periapsis(initial.argument_of_periapsis());
+    Ok(hohmann)
+}
+
+/// A three-burn bi-elliptic transfer between coplanar circular orbits
+/// by way of an intermediate apoapsis
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BiEllipticTransfer {
+    /// Burn that leaves the initial orbit for the first ellipse
+    pub first_delta_v: MetersPerSecond,
+    /// Burn at the intermediate apoapsis onto the second ellipse
+    pub second_delta_v: MetersPerSecond,
+    /// Burn that joins the final orbit
+    pub third_delta_v: MetersPerSecond,
+    /// Time on both transfer ellipses, half of each period
+    pub transfer_time: Seconds,
+    /// Ellipse from the initial radius out to the intermediate apoapsis
+    pub first_transfer_orbit: Ellipse,
+    /// Ellipse from the intermediate apoapsis to the final radius
+    pub second_transfer_orbit: Ellipse,
+}
+
+impl BiEllipticTransfer {
+    pub fn total_delta_v(&self) -> MetersPerSecond {
+        self.first_delta_v + self.second_delta_v + self.third_delta_v
+    }
+}
+
+/// Bi-elliptic transfer between circular orbits of radius `r_initial`
+/// and `r_final`, coasting out to `r_intermediate`, which must be at
+/// least as far out as both
+pub fn bi_elliptic(
+    r_initial: Meters,
+    r_final: Meters,
+    r_intermediate: Meters,
+    mu: GravParam,
+) -> Result<BiEllipticTransfer, AlmagestError> {
+    if r_intermediate < r_initial || r_intermediate < r_final {
+        return Err(AlmagestError::InvalidInput(
+            "Intermediate apoapsis must lie beyond both orbits",
+        ));
+    }
+    let intermediate = circle(r_intermediate)?;
+    let outbound = transfer(
+        r_initial,
+        &circle(r_initial)?,
+        r_intermediate,
+        &intermediate,
+        Point3::ORIGIN,
+        mu,
+    )?;
+    let inbound = transfer(
+        r_intermediate,
+        &intermediate,
+        r_final,
+        &circle(r_final)?,
+        Point3::ORIGIN,
+        mu,
+    )?;
+    // The coasts meet at the intermediate apoapsis without the
+    // circularization in between, so the middle burn joins the two
+    // ellipses directly
+    let (first, second) = (outbound.transfer_orbit, inbound.transfer_orbit);
+    Ok(BiEllipticTransfer {
+        first_delta_v: outbound.departure_delta_v,
+        second_delta_v: (second.speed_at(r_intermediate, mu) - first.speed_at(r_intermediate, mu))
+            .abs(),
+        third_delta_v: inbound.arrival_delta_v,
+        transfer_time: outbound.transfer_time + inbound.transfer_time,
+        first_transfer_orbit: outbound.transfer_orbit,
+        second_transfer_orbit: inbound.transfer_orbit,
+    })
+}
+
+/// Whether some bi-elliptic transfer is cheaper than the Hohmann
+/// transfer between circular orbits with radius ratio
+/// `r_final / r_initial`. The best bi-elliptic transfer is the
+/// limit of an infinitely distant intermediate apoapsis, which wins
+/// above a ratio of about 11.94; below it Hohmann is always cheaper.
+/// The cost of a particular intermediate apoapsis can be compared with
+/// [`bi_elliptic`] and [`hohmann`] directly.
+pub fn bi_elliptic_beats_hohmann(radius_ratio: Real) -> bool {
+    // Lowering costs the same as the reverse raise
+    let r = if radius_ratio < 1.0 {
+        1.0 / radius_ratio
+    } else {
+        radius_ratio
+    };
+    // Costs in units of the initial circular speed
+    let hohmann = sqrt(2.0 * r / (1.0 + r)) - 1.0 + sqrt(1.0 / r) * (1.0 - sqrt(2.0 / (1.0 + r)));
+    let bi_parabolic = (SQRT_2 - 1.0) * (1.0 + sqrt(1.0 / r));
+    bi_parabolic < hohmann
+}
+
+/// A single burn that turns the orbit plane
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlaneChange {
+    pub delta_v: MetersPerSecond,
+    /// Angle between the old and new orbit planes
+    pub turn_angle: Radians,
+    /// True anomaly on the original orbit at which to burn
+    pub true_anomaly: Radians,
+    /// The orbit just after the burn
+    pub elements: KeplerianElements,
+}
+
+/// Turn the orbit into the plane with the given inclination and
+/// right ascension of the ascending node. The burn happens where the
+/// old and new planes intersect, at whichever of the two crossings
+/// lies farther out, since the horizontal speed to be turned is
+/// smallest there: Δv = 2 v cos γ sin(θ/2) (Vallado, Algorithms 39–41).
+pub fn plane_change(
+    elements: &KeplerianElements,
+    inclination: Radians,
+    raan: Radians,
+    mu: GravParam,
+) -> Result<PlaneChange, AlmagestError> {
+    let target = KeplerianElements {
+        inclination,
+        raan,
+        ..*elements
+    };
+    let (old_normal, new_normal) = (elements.orbit_normal(), target.orbit_normal());
+    let turn_angle = acos(old_normal.dot(new_normal).clamp(-1.0, 1.0));
+    let line = old_normal.cross(new_normal);
+    if line.norm() < 1e-12 {
+        if turn_angle > PI / 2.0 {
+            return Err(AlmagestError::InvalidInput(
+                "Reversing the direction of motion is not a plane change",
+            ));
+        }
+        // Already in the requested plane
+        return Ok(PlaneChange {
+            delta_v: MetersPerSecond::ZERO,
+            turn_angle: Radians(turn_angle),
+            true_anomaly: elements.true_anomaly,
+            elements: target,
+        });
+    }
+
+    let e = elements.eccentricity.value();
+    let p = elements.semi_latus_rectum().value();
+    let (p_hat, q_hat) = elements.perifocal_axes();
+    let crossing = |direction: Vec3| atan2(direction.dot(q_hat), direction.dot(p_hat));
+    let radius = |nu: Real| {
+        let denominator = 1.0 + e * cos(nu);
+        if denominator > 0.0 {
+            p / denominator
+        } else {
+            Real::NEG_INFINITY
+        }
+    };
+    let (first, second) = (crossing(line), crossing(-line));
+    let true_anomaly = if radius(first) >= radius(second) {
+        first
+    } else {
+        second
+    };
+    let r = radius(true_anomaly);
+    if r <= 0.0 {
+        return Err(AlmagestError::InvalidInput(
+            "Neither plane crossing lies on the orbit",
+        ));
+    }
+
+    // Keep the radial velocity and swing the horizontal velocity into
+    // the new plane
+    let before = KeplerianElements {
+        true_anomaly: Radians(true_anomaly),
+        ..*elements
+    }
+    .to_state(mu);
+    let r_hat = before.position.unit();
+    let radial = before.velocity.dot(r_hat);
+    let horizontal = sqrt(mu.value() * p) / r;
+    let after = StateVector::new(
+        before.position,
+        r_hat * radial + new_normal.cross(r_hat) * horizontal,
+    );
+    Ok(PlaneChange {
+        delta_v: MetersPerSecond(2.0 * horizontal * sin(turn_angle / 2.0)),
+        turn_angle: Radians(turn_angle),
+        true_anomaly: Radians(true_anomaly),
+        elements: KeplerianElements::from_state(&after, mu)?,
+    })
+}
+
+/// Change only the inclination, burning at a node
+pub fn inclination_change(
+    elements: &KeplerianElements,
+    inclination: Radians,
+    mu: GravParam,
+) -> Result<PlaneChange, AlmagestError> {
+    plane_change(elements, inclination, elements.raan, mu)
+}
+
+/// Change only the right ascension of the ascending node
+pub fn raan_change(
+    elements: &KeplerianElements,
+    raan: Radians,
+    mu: GravParam,
+) -> Result<PlaneChange, AlmagestError> {
+    plane_change(elements, elements.inclination, raan, mu)
+}
+
+/// A Hohmann transfer that also changes inclination, with the plane
+/// change split between its two burns
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CombinedTransfer {
+    pub departure_delta_v: MetersPerSecond,
+    pub arrival_delta_v: MetersPerSecond,
+    /// Plane-change angle turned through at the departure burn
+    pub departure_plane_change: Radians,
+    /// Plane-change angle turned through at the arrival burn
+    pub arrival_plane_change: Radians,
+    pub transfer_time: Seconds,
+}
+
+impl CombinedTransfer {
+    pub fn total_delta_v(&self) -> MetersPerSecond {
+        self.departure_delta_v + self.arrival_delta_v
+    }
+}
+
+/// Transfer from `initial` to a circular orbit of radius `r_final` and
+/// the given inclination, splitting the plane change between the two
+/// burns to minimize the total delta-v (Vallado, Algorithm 42).
+///
+/// Raising departs from the initial periapsis and lowering from the
+/// initial apoapsis, as in [`hohmann_between`]; the line of apsides is
+/// taken to lie along the line of nodes, so both burns fall on a node.
+/// From a geostationary transfer orbit this is the usual apogee burn
+/// into GEO, with a small part of the plane change made at perigee.
+pub fn combined_transfer(
+    initial: &KeplerianElements,
+    r_final: Meters,
+    inclination: Radians,
+    mu: GravParam,
+) -> Result<CombinedTransfer, AlmagestError> {
+    let a1 = initial.semi_major_axis;
+    let e = initial.eccentricity.value();
+    if a1 <= Meters::ZERO || e >= 1.0 || r_final <= Meters::ZERO {
+        return Err(AlmagestError::InvalidInput(
+            "Combined transfers need elliptic orbits and a positive radius",
+        ));
+    }
+    let r1 = if r_final >= a1 {
+        a1 * (1.0 - e)
+    } else {
+        a1 * (1.0 + e)
+    };
+    let a_transfer = (r1 + r_final) / 2.0;
+    let (low, high) = if r1 <= r_final {
+        (r1, r_final)
+    } else {
+        (r_final, r1)
+    };
+    let transfer_orbit = Ellipse::from_periapsis_apoapsis(low, high, Point::ORIGIN)?;
+    let v1 = initial.conic().speed_at(r1, mu).value();
+    let vt1 = transfer_orbit.speed_at(r1, mu).value();
+    let vt2 = transfer_orbit.speed_at(r_final, mu).value();
+    let v2 = circle(r_final)?.speed_at(r_final, mu).value();
+    let total_turn = (inclination - initial.inclination).value().abs();
+
+    // Law of cosines for a burn that changes speed and turns by `angle`
+    let burn =
+        |u: Real, w: Real, angle: Real| sqrt((u * u + w * w - 2.0 * u * w * cos(angle)).max(0.0));
+    let cost =
+        |split: Real| burn(v1, vt1, split * total_turn) + burn(vt2, v2, (1.0 - split) * total_turn);
+    // The cost is convex in the split; golden-section search
+    const RATIO: Real = 0.618_033_988_749_894_9;
+    let (mut lo, mut hi) = (0.0, 1.0);
+    while hi - lo > 1e-10 {
+        let c = hi - RATIO * (hi - lo);
+        let d = lo + RATIO * (hi - lo);
+        if cost(c) < cost(d) {
+            hi = d;
+        } else {
+            lo = c;
+        }
+    }
+    let split = (lo + hi) / 2.0;
+    let a = a_transfer.value();
+    Ok(CombinedTransfer {
+        departure_delta_v: MetersPerSecond(burn(v1, vt1, split * total_turn)),
+        arrival_delta_v: MetersPerSecond(burn(vt2, v2, (1.0 - split) * total_turn)),
+        departure_plane_change: Radians(split * total_turn),
+        arrival_plane_change: Radians((1.0 - split) * total_turn),
+        transfer_time: Seconds(PI * sqrt(a * a * a / mu.value())),
+    })
+}
+
+/// A phasing orbit that brings an interceptor back to its starting
+/// point just as a target in the same circular orbit arrives there
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Phasing {
+    /// The orbit flown between the two burns, which touches the
+    /// original orbit at the burn point
+    pub phasing_orbit: Ellipse,
+    /// Time spent on the phasing orbit
+    pub time: Seconds,
+    /// Sum of the burns onto and off the phasing orbit
+    pub delta_v: MetersPerSecond,
+}
+
+/// Phasing within a circular orbit of radius `radius`, with the
+/// target `lead` radians ahead of the interceptor (negative if it
+/// trails) and the interceptor flying `revolutions` laps of the
+/// phasing orbit (Vallado, Algorithm 44). Catching up lowers the
+/// orbit and falling back raises it; more laps make for gentler
+/// burns at the cost of time.
+pub fn phasing(
+    radius: Meters,
+    lead: Real,
+    revolutions: u32,
+    mu: GravParam,
+) -> Result<Phasing, AlmagestError> {
+    if revolutions == 0 {
+        return Err(AlmagestError::InvalidInput(
+            "Phasing needs at least one revolution",
+        ));
+    }
+    let r = radius.value();
+    let rate = sqrt(mu.value() / (r * r * r));
+    // The target covers k laps less its lead while the interceptor
+    // flies k laps of the phasing orbit
+    let time = (TAU * revolutions as Real - lead) / rate;
+    if time <= 0.0 {
+        return Err(AlmagestError::InvalidInput(
+            "Too few revolutions to absorb the phase lead",
+        ));
+    }
+    let period = time / revolutions as Real;
+    let a = cbrt(mu.value() * (period / TAU) * (period / TAU));
+    let other_apsis = Meters(2.0 * a - r);
+    if other_apsis <= Meters::ZERO {
+        return Err(AlmagestError::InvalidInput(
+            "Phasing orbit would pass through the central body",
+        ));
+    }
+    let (low, high) = if other_apsis < radius {
+        (other_apsis, radius)
+    } else {
+        (radius, other_apsis)
+    };
+    let phasing_orbit = Ellipse::from_periapsis_apoapsis(low, high, Point::ORIGIN)?;
+    let burn = phasing_orbit.speed_at(radius, mu) - circle(radius)?.speed_at(radius, mu);
+    Ok(Phasing {
+        phasing_orbit,
+        time: Seconds(time),
+        delta_v: burn.abs() * 2.0,
+    })
+}
+
+/// A Hohmann rendezvous between circular, coplanar orbits, timed so
+/// the interceptor meets the target at the end of the transfer
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rendezvous {
+    /// Time to wait before the first burn
+    pub wait_time: Seconds,
+    /// The angle the target must lead the interceptor by at the first
+    /// burn, on [0, 2π)
+    pub departure_phase: Real,
+    pub transfer: HohmannTransfer,
+}
+
+impl Rendezvous {
+    /// Time from now until the interceptor meets the target
+    pub fn total_time(&self) -> Seconds {
+        self.wait_time + self.transfer.transfer_time
+    }
+
+    pub fn delta_v(&self) -> MetersPerSecond {
+        self.transfer.total_delta_v()
+    }
+}
+
+/// Plan a rendezvous from a circular orbit of radius `r_interceptor`
+/// to a target on a coplanar circular orbit of radius `r_target` that
+/// currently leads by `phase` radians (Vallado, Algorithm 45). The
+/// wait is the shortest one that reaches the departure phase; further
+/// opportunities follow every [`synodic_period`].
+pub fn rendezvous(
+    r_interceptor: Meters,
+    r_target: Meters,
+    phase: Real,
+    mu: GravParam,
+) -> Result<Rendezvous, AlmagestError> {
+    let transfer = hohmann(r_interceptor, r_target, mu)?;
+    let (w_interceptor, w_target) = (angular_rate(r_interceptor, mu), angular_rate(r_target, mu));
+    let relative = w_target - w_interceptor;
+    if relative == 0.0 {
+        return Err(AlmagestError::InvalidInput(
+            "Orbits of equal radius need a phasing orbit instead",
+        ));
+    }
+    // The target must lead by π less the angle it covers in transit
+    let departure_phase = wrap_two_pi(PI - w_target * transfer.transfer_time.value());
+    // The lead grows at the relative rate, which is negative when the
+    // target is the higher, slower orbit
+    let gap = if relative > 0.0 {
+        wrap_two_pi(departure_phase - phase)
+    } else {
+        wrap_two_pi(phase - departure_phase)
+    };
+    Ok(Rendezvous {
+        wait_time: Seconds(gap / relative.abs()),
+        departure_phase,
+        transfer,
+    })
+}
+
+/// Time between repeats of the same relative geometry of two bodies
+/// on circular, coplanar orbits; `None` for equal radii
+pub fn synodic_period(r1: Meters, r2: Meters, mu: GravParam) -> Option<Seconds> {
+    let relative = (angular_rate(r1, mu) - angular_rate(r2, mu)).abs();
+    if relative == 0.0 {
+        None
+    } else {
+        Some(Seconds(TAU / relative))
+    }
+}
+
+// Mean motion of a circular orbit
+fn angular_rate(radius: Meters, mu: GravParam) -> Real {
+    let r = radius.value();
+    sqrt(mu.value() / (r * r * r))
+}
+
+// A circular orbit of the given radius
+fn circle(radius: Meters) -> Result<Ellipse, AlmagestError> {
+    if radius <= Meters::ZERO {
+        return Err(AlmagestError::InvalidInput("Orbit radii must be positive"));
+    }
+    Ellipse::from_periapsis_apoapsis(radius, radius, Point::ORIGIN)
+}
+
+// Half-ellipse from r1 on the initial orbit to r2 on the target orbit
+fn transfer(
+    r1: Meters,
+    initial: &Ellipse,
+    r2: Meters,
+    target: &Ellipse,
+    focus: Point3,
+    mu: GravParam,
+) -> Result<HohmannTransfer, AlmagestError> {
+    let a = ((r1 + r2) / 2.0).value();
+    let (low, high) = if r1 <= r2 { (r1, r2) } else { (r2, r1) };
+    let transfer_orbit = Ellipse::from_periapsis_apoapsis(low, high, focus)?;
+    Ok(HohmannTransfer {
+        departure_delta_v: (transfer_orbit.speed_at(r1, mu) - initial.speed_at(r1, mu)).abs(),
+        arrival_delta_v: (target.speed_at(r2, mu) - transfer_orbit.speed_at(r2, mu)).abs(),
+        transfer_time: Seconds(PI * sqrt(a * a * a / mu.value())),
+        transfer_orbit,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::utils::Eccentricity;
+    use approx::assert_relative_eq;
+
+    const EARTH_RADIUS: Meters = Meters(6_378_137.0);
+
+    // Vallado Example 6-1: LEO to GEO
+    #[test]
+    fn leo_to_geo() {
+        let r1 = EARTH_RADIUS + Meters(191_344.11);
+        let r2 = EARTH_RADIUS + Meters(35_781_348.57);
+        let transfer = hohmann(r1, r2, EARTH.mu).unwrap();
+        assert_relative_eq!(
+            transfer.departure_delta_v.value(),
+            2_457.038,
+            epsilon = 1e-2
+        );
+        assert_relative_eq!(transfer.arrival_delta_v.value(), 1_478.187, epsilon = 1e-2);
+        assert_relative_eq!(transfer.total_delta_v().value(), 3_935.224, epsilon = 1e-2);
+        assert_relative_eq!(
+            transfer.transfer_time.value() / 60.0,
+            315.4028,
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(transfer.transfer_orbit.periapsis().value(), r1.value());
+        assert_relative_eq!(
+            transfer.transfer_orbit.apoapsis().value(),
+            r2.value(),
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn lowering_mirrors_raising() {
+        let r1 = Meters(7_000_000.0);
+        let r2 = Meters(12_000_000.0);
+        let up = hohmann(r1, r2, EARTH.mu).unwrap();
+        let down = hohmann(r2, r1, EARTH.mu).unwrap();
+        assert_relative_eq!(
+            up.departure_delta_v.value(),
+            down.arrival_delta_v.value(),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            up.arrival_delta_v.value(),
+            down.departure_delta_v.value(),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(up.transfer_time.value(), down.transfer_time.value());
+        assert_eq!(up.transfer_orbit, down.transfer_orbit);
+    }
+
+    #[test]
+    fn circular_ellipses_match_radii() {
+        let circle =
+            |r: Real| Ellipse::new(Eccentricity::new(0.0).unwrap(), Point::ORIGIN, Meters(r));
+        let from_radii = hohmann(Meters(7_000_000.0), Meters(9_000_000.0), EARTH.mu).unwrap();
+        let from_ellipses =
+            hohmann_between(&circle(7_000_000.0), &circle(9_000_000.0), EARTH.mu).unwrap();
+        assert_relative_eq!(
+            from_radii.total_delta_v().value(),
+            from_ellipses.total_delta_v().value(),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn elliptic_orbits_use_the_apsides() {
+        let initial = Ellipse::from_periapsis_apoapsis(
+            Meters(7_000_000.0),
+            Meters(8_000_000.0),
+            Point::ORIGIN,
+        )
+        .unwrap()
+        .with_argument_of_periapsis(Radians(1.0));
+        let target = Ellipse::from_periapsis_apoapsis(
+            Meters(20_000_000.0),
+            Meters(30_000_000.0),
+            Point::ORIGIN,
+        )
+        .unwrap()
+        .with_argument_of_periapsis(Radians(1.0));
+        let transfer = hohmann_between(&initial, &target, EARTH.mu).unwrap();
+        let orbit = transfer.transfer_orbit;
+        assert_relative_eq!(orbit.periapsis().value(), 7_000_000.0);
+        // Departing at the initial periapsis, along the shared apsides
+        let departure = orbit
+            .point_at(Radians::ZERO)
+            .distance_to(&initial.point_at(Radians::ZERO));
+        assert_relative_eq!(departure.value(), 0.0, epsilon = 1e-6);
+        assert_relative_eq!(orbit.apoapsis().value(), 30_000_000.0, max_relative = 1e-12);
+
+        // Both burns are prograde speed changes found by vis-viva
+        let mu = EARTH.mu.value();
+        let vis_viva = |r: Real, a: Real| sqrt(mu * (2.0 / r - 1.0 / a));
+        let departure = vis_viva(7.0e6, 18.5e6) - vis_viva(7.0e6, 7.5e6);
+        let arrival = vis_viva(30.0e6, 25.0e6) - vis_viva(30.0e6, 18.5e6);
+        assert_relative_eq!(
+            transfer.departure_delta_v.value(),
+            departure,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(transfer.arrival_delta_v.value(), arrival, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn rejects_nonpositive_radii() {
+        assert!(hohmann(Meters(0.0), Meters(7_000_000.0), EARTH.mu).is_err());
+    }
+    #[test]
+    fn bi_elliptic_burns() {
+        let (r1, r2, rb) = (Meters(7.0e6), Meters(105.0e6), Meters(210.0e6));
+        let transfer = bi_elliptic(r1, r2, rb, EARTH.mu).unwrap();
+        let mu = EARTH.mu.value();
+        let vis_viva = |r: Real, a: Real| sqrt(mu * (2.0 / r - 1.0 / a));
+        let (a1, a2) = (108.5e6, 157.5e6);
+        assert_relative_eq!(
+            transfer.first_delta_v.value(),
+            vis_viva(7.0e6, a1) - vis_viva(7.0e6, 7.0e6),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            transfer.second_delta_v.value(),
+            vis_viva(210.0e6, a2) - vis_viva(210.0e6, a1),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            transfer.third_delta_v.value(),
+            vis_viva(105.0e6, a2) - vis_viva(105.0e6, 105.0e6),
+            epsilon = 1e-9
+        );
+        let half_period = |a: Real| PI * sqrt(a * a * a / mu);
+        assert_relative_eq!(
+            transfer.transfer_time.value(),
+            half_period(a1) + half_period(a2),
+            max_relative = 1e-12
+        );
+        assert_relative_eq!(
+            transfer.second_transfer_orbit.apoapsis().value(),
+            210.0e6,
+            max_relative = 1e-12
+        );
+        // A radius ratio of 15 with a distant intermediate apoapsis
+        // already undercuts Hohmann
+        assert!(transfer.total_delta_v() < hohmann(r1, r2, EARTH.mu).unwrap().total_delta_v());
+    }
+
+    #[test]
+    fn bi_elliptic_reduces_to_hohmann() {
+        let (r1, r2) = (Meters(7.0e6), Meters(20.0e6));
+        let transfer = bi_elliptic(r1, r2, r2, EARTH.mu).unwrap();
+        let hohmann = hohmann(r1, r2, EARTH.mu).unwrap();
+        assert_relative_eq!(transfer.third_delta_v.value(), 0.0, epsilon = 1e-9);
+        assert_relative_eq!(
+            transfer.total_delta_v().value(),
+            hohmann.total_delta_v().value(),
+            epsilon = 1e-9
+        );
+        assert!(bi_elliptic(r1, r2, Meters(10.0e6), EARTH.mu).is_err());
+    }
+
+    #[test]
+    fn bi_elliptic_threshold() {
+        assert!(!bi_elliptic_beats_hohmann(5.0));
+        assert!(!bi_elliptic_beats_hohmann(11.9));
+        assert!(bi_elliptic_beats_hohmann(12.0));
+        assert!(bi_elliptic_beats_hohmann(15.58));
+        assert!(bi_elliptic_beats_hohmann(1.0 / 20.0));
+    }
+    fn circular(radius: Real, inclination: Real, raan: Real) -> KeplerianElements {
+        KeplerianElements {
+            semi_major_axis: Meters(radius),
+            eccentricity: Eccentricity::new(0.0).unwrap(),
+            inclination: Radians(inclination),
+            raan: Radians(raan),
+            argument_of_periapsis: Radians(0.0),
+            true_anomaly: Radians(1.0),
+        }
+    }
+
+    #[test]
+    fn pure_inclination_change() {
+        let orbit = circular(7_000_000.0, 0.5, 1.2);
+        let change = inclination_change(&orbit, Radians(0.7), EARTH.mu).unwrap();
+        let v = sqrt(EARTH.mu.value() / 7_000_000.0);
+        assert_relative_eq!(change.turn_angle.value(), 0.2, epsilon = 1e-12);
+        assert_relative_eq!(change.delta_v.value(), 2.0 * v * sin(0.1), epsilon = 1e-6);
+        // The burn is at a node, and the node stays put
+        assert_relative_eq!(change.true_anomaly.sin(), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(change.elements.inclination.value(), 0.7, epsilon = 1e-12);
+        assert_relative_eq!(change.elements.raan.value(), 1.2, epsilon = 1e-12);
+        assert_relative_eq!(
+            change.elements.semi_major_axis.value(),
+            7_000_000.0,
+            max_relative = 1e-9
+        );
+    }
+
+    #[test]
+    fn node_shift() {
+        let i: Real = 55.0_f64.to_radians();
+        let orbit = circular(26_560_000.0, i, 0.3);
+        let change = raan_change(&orbit, Radians(0.6), EARTH.mu).unwrap();
+        // cos θ = cos²i + sin²i cos ΔΩ
+        let turn = acos(cos(i) * cos(i) + sin(i) * sin(i) * cos(0.3));
+        let v = sqrt(EARTH.mu.value() / 26_560_000.0);
+        assert_relative_eq!(change.turn_angle.value(), turn, epsilon = 1e-12);
+        assert_relative_eq!(
+            change.delta_v.value(),
+            2.0 * v * sin(turn / 2.0),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(change.elements.raan.value(), 0.6, epsilon = 1e-9);
+        assert_relative_eq!(change.elements.inclination.value(), i, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn elliptic_plane_change_burns_at_the_far_node() {
+        let orbit = KeplerianElements {
+            semi_major_axis: Meters(24_400_000.0),
+            eccentricity: Eccentricity::new(0.73).unwrap(),
+            inclination: Radians(0.5),
+            raan: Radians(0.0),
+            argument_of_periapsis: Radians(0.0),
+            true_anomaly: Radians(0.0),
+        };
+        let change = inclination_change(&orbit, Radians::ZERO, EARTH.mu).unwrap();
+        // Periapsis is at the ascending node, so the burn is at apoapsis
+        assert_relative_eq!(change.true_anomaly.value().abs(), PI, epsilon = 1e-9);
+        let r_a = 24_400_000.0 * 1.73;
+        let h = sqrt(EARTH.mu.value() * orbit.semi_latus_rectum().value());
+        assert_relative_eq!(
+            change.delta_v.value(),
+            2.0 * (h / r_a) * sin(0.25),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(change.elements.inclination.value(), 0.0, epsilon = 1e-9);
+        assert_relative_eq!(change.elements.eccentricity.value(), 0.73, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn gto_to_geo() {
+        let (r_p, r_a) = (6_678_137.0, 42_164_137.0);
+        let gto = KeplerianElements {
+            semi_major_axis: Meters((r_p + r_a) / 2.0),
+            eccentricity: Eccentricity::new((r_a - r_p) / (r_a + r_p)).unwrap(),
+            inclination: Radians(28.5_f64.to_radians()),
+            raan: Radians(0.0),
+            argument_of_periapsis: Radians(0.0),
+            true_anomaly: Radians(0.0),
+        };
+        let transfer = combined_transfer(&gto, Meters(r_a), Radians::ZERO, EARTH.mu).unwrap();
+
+        // Doing the whole plane change at apogee costs more
+        let mu = EARTH.mu.value();
+        let v_apogee = sqrt(mu * (2.0 / r_a - 2.0 / (r_a + r_p)));
+        let v_geo = sqrt(mu / r_a);
+        let i = 28.5_f64.to_radians();
+        let apogee_only =
+            sqrt(v_apogee * v_apogee + v_geo * v_geo - 2.0 * v_apogee * v_geo * cos(i));
+        assert!(transfer.total_delta_v().value() < apogee_only);
+        assert!(transfer.total_delta_v().value() > apogee_only - 100.0);
+        assert!(transfer.departure_plane_change > Radians::ZERO);
+        assert!(transfer.departure_plane_change < transfer.arrival_plane_change);
+        assert_relative_eq!(
+            (transfer.departure_plane_change + transfer.arrival_plane_change).value(),
+            i,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn combined_transfer_without_plane_change_is_hohmann() {
+        let orbit = circular(7_000_000.0, 0.4, 0.0);
+        let transfer =
+            combined_transfer(&orbit, Meters(15_000_000.0), Radians(0.4), EARTH.mu).unwrap();
+        let hohmann = hohmann(Meters(7_000_000.0), Meters(15_000_000.0), EARTH.mu).unwrap();
+        assert_relative_eq!(
+            transfer.total_delta_v().value(),
+            hohmann.total_delta_v().value(),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            transfer.transfer_time.value(),
+            hohmann.transfer_time.value()
+        );
+    }
+    #[test]
+    fn phasing_catches_up() {
+        let r = 7_000_000.0;
+        let lead = 0.3;
+        let plan = phasing(Meters(r), lead, 2, EARTH.mu).unwrap();
+        let n = sqrt(EARTH.mu.value() / (r * r * r));
+        // The target ends where the interceptor does
+        assert_relative_eq!(n * plan.time.value(), 2.0 * TAU - lead, epsilon = 1e-12);
+        // Catching up drops the orbit below the starting radius
+        let orbit = plan.phasing_orbit;
+        assert_relative_eq!(orbit.apoapsis().value(), r, max_relative = 1e-12);
+        assert!(orbit.periapsis().value() < r);
+        let a = orbit.semi_major_axis().value();
+        let period = TAU * sqrt(a * a * a / EARTH.mu.value());
+        assert_relative_eq!(2.0 * period, plan.time.value(), max_relative = 1e-10);
+        let expected =
+            2.0 * (sqrt(EARTH.mu.value() / r) - sqrt(EARTH.mu.value() * (2.0 / r - 1.0 / a)));
+        assert_relative_eq!(plan.delta_v.value(), expected, epsilon = 1e-9);
+
+        // More laps spread the same lead more thinly
+        let slow = phasing(Meters(r), lead, 5, EARTH.mu).unwrap();
+        assert!(slow.delta_v < plan.delta_v);
+        assert!(slow.time > plan.time);
+    }
+
+    #[test]
+    fn phasing_falls_back() {
+        let r = 42_164_000.0;
+        let plan = phasing(Meters(r), -0.2, 1, EARTH.mu).unwrap();
+        assert_relative_eq!(plan.phasing_orbit.periapsis().value(), r);
+        assert!(plan.phasing_orbit.apoapsis().value() > r);
+        assert!(phasing(Meters(7_000_000.0), 5.5, 1, EARTH.mu).is_err());
+        assert!(phasing(Meters(7_000_000.0), 0.1, 0, EARTH.mu).is_err());
+    }
+
+    #[test]
+    fn rendezvous_arrives_with_the_target() {
+        for (r_int, r_tgt) in [(6_778_000.0, 7_178_000.0), (7_178_000.0, 6_778_000.0)] {
+            let phase = 1.0;
+            let plan = rendezvous(Meters(r_int), Meters(r_tgt), phase, EARTH.mu).unwrap();
+            let n = |r: Real| sqrt(EARTH.mu.value() / (r * r * r));
+            // Both start together from the interceptor's angle of zero
+            let interceptor = n(r_int) * plan.wait_time.value() + PI;
+            let target = phase + n(r_tgt) * plan.total_time().value();
+            assert_relative_eq!(wrap_two_pi(target - interceptor + 1.0), 1.0, epsilon = 1e-9);
+            let synodic = synodic_period(Meters(r_int), Meters(r_tgt), EARTH.mu).unwrap();
+            assert!(plan.wait_time >= Seconds::ZERO && plan.wait_time < synodic);
+        }
+        assert!(rendezvous(Meters(7.0e6), Meters(7.0e6), 0.5, EARTH.mu).is_err());
+    }
+}
@@ -0,0 +1,558 @@
+//! Milankovitch elements and their averaged evolution.
+//!
+//! An orbit's shape and orientation are held as two vectors: the
+//! angular momentum scaled to h = H/√(μa), so |h| = √(1 − e²), and the
+//! eccentricity vector e pointing at periapsis. Unlike Ω, ω and i, they
+//! stay well defined for circular and equatorial orbits, so long runs
+//! through those cases don't stall on singular rates. The fast angle is
+//! averaged out: a [`SecularModel`] gives the orbit-averaged rates of a
+//! perturbation, and [`evolve`] integrates them with steps of days
+//! rather than minutes, which is what makes century-scale studies of
+//! graveyard and disposal orbits affordable.
+//!
+//! The constraints h·e = 0 and |h|² + |e|² = 1 are kept by the
+//! equations themselves; their drift is a measure of integration error.
+
+use libm::{acos, cos, sin, sqrt};
+
+use crate::bodies::{CelestialBody, EARTH};
+use crate::error::AlmagestError;
+use crate::forces::ForceModel;
+use crate::kepler::{KeplerianElements, eccentric_to_true};
+use crate::lunar::MU_MOON;
+use crate::sail::MU_SUN;
+use crate::state::StateVector;
+use crate::sun::ASTRONOMICAL_UNIT;
+use crate::utils::{GravParam, Meters, Real, Seconds, TAU};
+use crate::vectors::Vec3;
+
+/// Earth's zonal harmonic J2 (EGM-96)
+pub const EARTH_J2: Real = EARTH.j2;
+
+/// Obliquity of the ecliptic at J2000, in radians
+const OBLIQUITY: Real = 0.409_092_8;
+
+/// Orbit shape and orientation as vectors, plus size
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MilankovitchElements {
+    pub semi_major_axis: Meters,
+    /// Angular momentum over √(μa), along the orbit normal
+    pub angular_momentum: Vec3,
+    /// Eccentricity vector, toward periapsis
+    pub eccentricity: Vec3,
+}
+
+/// Time derivatives of [`MilankovitchElements`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MilankovitchRates {
+    /// In m/s
+    pub semi_major_axis: Real,
+    /// Per second
+    pub angular_momentum: Vec3,
+    /// Per second
+    pub eccentricity: Vec3,
+}
+
+impl MilankovitchRates {
+    pub const ZERO: Self = MilankovitchRates {
+        semi_major_axis: 0.0,
+        angular_momentum: Vec3::ZERO,
+        eccentricity: Vec3::ZERO,
+    };
+}
+
+impl MilankovitchElements {
+    /// Elements of a bound Cartesian state
+    pub fn from_state(state: &StateVector, mu: GravParam) -> Result<Self, AlmagestError> {
+        let mu = mu.value();
+        let (r, v) = (state.position, state.velocity);
+        let r_norm = r.norm();
+        let energy = v.norm_squared() / 2.0 - mu / r_norm;
+        if energy >= 0.0 {
+            return Err(AlmagestError::InvalidEccentricity(
+                "Milankovitch elements need a bound orbit",
+            ));
+        }
+        let a = -mu / (2.0 * energy);
+        Ok(MilankovitchElements {
+            semi_major_axis: Meters(a),
+            angular_momentum: r.cross(v) / sqrt(mu * a),
+            eccentricity: (r * (v.norm_squared() - mu / r_norm) - v * r.dot(v)) / mu,
+        })
+    }
+
+    pub fn from_keplerian(elements: &KeplerianElements) -> Self {
+        let e = elements.eccentricity.value();
+        let (periapsis, _) = elements.perifocal_axes();
+        MilankovitchElements {
+            semi_major_axis: elements.semi_major_axis,
+            angular_momentum: elements.orbit_normal() * sqrt(1.0 - e * e),
+            eccentricity: periapsis * e,
+        }
+    }
+
+    /// Cartesian state at `true_anomaly`, measured from periapsis or,
+    /// for a circular orbit, from the ascending node
+    pub fn to_state(&self, true_anomaly: Real, mu: GravParam) -> StateVector {
+        let e = self.eccentricity.norm();
+        let normal = self.angular_momentum.unit();
+        let p_hat = if e > 1e-12 {
+            self.eccentricity / e
+        } else {
+            let node = Vec3::Z.cross(normal);
+            if node.norm() > 1e-12 {
+                node.unit()
+            } else {
+                Vec3::X
+            }
+        };
+        let q_hat = normal.cross(p_hat);
+        let p = self.semi_latus_rectum().value();
+        let r = p / (1.0 + e * cos(true_anomaly));
+        let speed = sqrt(mu.value() / p);
+        StateVector::new(
+            p_hat * (r * cos(true_anomaly)) + q_hat * (r * sin(true_anomaly)),
+            p_hat * (-speed * sin(true_anomaly)) + q_hat * (speed * (e + cos(true_anomaly))),
+        )
+    }
+
+    /// Classical elements with the body at `true_anomaly`
+    pub fn to_keplerian(
+        &self,
+        true_anomaly: Real,
+        mu: GravParam,
+    ) -> Result<KeplerianElements, AlmagestError> {
+        KeplerianElements::from_state(&self.to_state(true_anomaly, mu), mu)
+    }
+
+    pub fn inclination(&self) -> Real {
+        acos(self.angular_momentum.unit().z.clamp(-1.0, 1.0))
+    }
+
+    /// p = a|h|²
+    pub fn semi_latus_rectum(&self) -> Meters {
+        self.semi_major_axis * self.angular_momentum.norm_squared()
+    }
+
+    pub fn periapsis_radius(&self) -> Meters {
+        self.semi_major_axis * (1.0 - self.eccentricity.norm())
+    }
+
+    pub fn apoapsis_radius(&self) -> Meters {
+        self.semi_major_axis * (1.0 + self.eccentricity.norm())
+    }
+
+    /// Mean motion n = √(μ/a³), in rad/s
+    pub fn mean_motion(&self, mu: GravParam) -> Real {
+        let a = self.semi_major_axis.value();
+        sqrt(mu.value() / (a * a * a))
+    }
+
+    /// How far h·e = 0 and |h|² + |e|² = 1 are from holding, the
+    /// larger of the two
+    pub fn constraint_error(&self) -> Real {
+        let (h, e) = (self.angular_momentum, self.eccentricity);
+        let orthogonality = h.dot(e).abs();
+        let magnitude = (h.norm_squared() + e.norm_squared() - 1.0).abs();
+        orthogonality.max(magnitude)
+    }
+
+    fn advance(&self, rates: &MilankovitchRates, dt: Real) -> Self {
+        MilankovitchElements {
+            semi_major_axis: self.semi_major_axis + Meters(rates.semi_major_axis * dt),
+            angular_momentum: self.angular_momentum + rates.angular_momentum * dt,
+            eccentricity: self.eccentricity + rates.eccentricity * dt,
+        }
+    }
+}
+
+/// A perturbation averaged over one orbit
+pub trait SecularModel {
+    /// Rates of the elements at `time`
+    fn rates(
+        &self,
+        time: Seconds,
+        elements: &MilankovitchElements,
+        mu: GravParam,
+    ) -> MilankovitchRates;
+}
+
+impl<A: SecularModel, B: SecularModel> SecularModel for (A, B) {
+    fn rates(
+        &self,
+        time: Seconds,
+        elements: &MilankovitchElements,
+        mu: GravParam,
+    ) -> MilankovitchRates {
+        let (a, b) = (
+            self.0.rates(time, elements, mu),
+            self.1.rates(time, elements, mu),
+        );
+        MilankovitchRates {
+            semi_major_axis: a.semi_major_axis + b.semi_major_axis,
+            angular_momentum: a.angular_momentum + b.angular_momentum,
+            eccentricity: a.eccentricity + b.eccentricity,
+        }
+    }
+}
+
+impl<M: SecularModel> SecularModel for &M {
+    fn rates(
+        &self,
+        time: Seconds,
+        elements: &MilankovitchElements,
+        mu: GravParam,
+    ) -> MilankovitchRates {
+        (*self).rates(time, elements, mu)
+    }
+}
+
+/// The central body's oblateness, to first order in J2
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZonalJ2 {
+    pub j2: Real,
+    pub radius: Meters,
+    /// Unit vector along the body's spin axis
+    pub pole: Vec3,
+}
+
+impl ZonalJ2 {
+    /// The Earth, with its pole along z
+    pub const EARTH: Self = ZonalJ2::of(&EARTH, Vec3::Z);
+
+    /// A body's oblateness about its spin axis `pole`
+    pub const fn of(body: &CelestialBody, pole: Vec3) -> Self {
+        ZonalJ2 {
+            j2: body.j2,
+            radius: body.equatorial_radius,
+            pole,
+        }
+    }
+}
+
+impl SecularModel for ZonalJ2 {
+    /// ḣ = −(3nJ2R²/2a²|h|⁵)(p̂·h) p̂×h and
+    /// ė = −(3nJ2R²/4a²|h|⁵)[(1 − 5(p̂·ĥ)²) h×e + 2(p̂·h) p̂×e]
+    fn rates(
+        &self,
+        _time: Seconds,
+        elements: &MilankovitchElements,
+        mu: GravParam,
+    ) -> MilankovitchRates {
+        let (h, e, p) = (elements.angular_momentum, elements.eccentricity, self.pole);
+        let ratio = self.radius / elements.semi_major_axis;
+        let h_norm = h.norm();
+        let scale = 3.0 * elements.mean_motion(mu) * self.j2 * ratio * ratio
+            / (4.0 * h_norm * h_norm * h_norm * h_norm * h_norm);
+        let cos_i = p.dot(h) / h_norm;
+        MilankovitchRates {
+            semi_major_axis: 0.0,
+            angular_momentum: p.cross(h) * (-2.0 * scale * p.dot(h)),
+            eccentricity: (h.cross(e) * (1.0 - 5.0 * cos_i * cos_i)
+                + p.cross(e) * (2.0 * p.dot(h)))
+                * -scale,
+        }
+    }
+}
+
+/// A distant body on a circular orbit, to quadrupole order
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThirdBody {
+    pub mu: GravParam,
+    /// Radius of its orbit about the central body
+    pub distance: Meters,
+    /// Unit normal of its orbit plane
+    pub pole: Vec3,
+}
+
+impl ThirdBody {
+    /// The Sun, seen from the Earth, in equatorial axes
+    pub fn sun() -> Self {
+        ThirdBody {
+            mu: MU_SUN,
+            distance: ASTRONOMICAL_UNIT,
+            pole: Vec3::new(0.0, -sin(OBLIQUITY), cos(OBLIQUITY)),
+        }
+    }
+
+    /// The Moon, in equatorial axes. Its 5° tilt to the ecliptic
+    /// precesses every 18.6 years and averages out over long runs, so
+    /// its orbit is taken to lie in the ecliptic.
+    pub fn moon() -> Self {
+        ThirdBody {
+            mu: MU_MOON,
+            distance: Meters(384_400_000.0),
+            pole: Vec3::new(0.0, -sin(OBLIQUITY), cos(OBLIQUITY)),
+        }
+    }
+}
+
+impl SecularModel for ThirdBody {
+    /// ḣ = ε[(n̂·h) h×n̂ − 5(n̂·e) e×n̂] and
+    /// ė = ε[(n̂·h) e×n̂ + 2 h×e − 5(n̂·e) h×n̂], with
+    /// ε = 3μ₃/(4na₃³)
+    fn rates(
+        &self,
+        _time: Seconds,
+        elements: &MilankovitchElements,
+        mu: GravParam,
+    ) -> MilankovitchRates {
+        let (h, e, pole) = (elements.angular_momentum, elements.eccentricity, self.pole);
+        let d = self.distance.value();
+        let scale = 3.0 * self.mu.value() / (4.0 * elements.mean_motion(mu) * d * d * d);
+        let (hn, en) = (h.dot(pole), e.dot(pole));
+        MilankovitchRates {
+            semi_major_axis: 0.0,
+            angular_momentum: (h.cross(pole) * hn - e.cross(pole) * (5.0 * en)) * scale,
+            eccentricity: (e.cross(pole) * hn + h.cross(e) * 2.0 - h.cross(pole) * (5.0 * en))
+                * scale,
+        }
+    }
+}
+
+/// Any [`ForceModel`] averaged over the orbit by quadrature, for
+/// perturbations such as drag that have no closed-form averages. The
+/// orbit is sampled evenly in eccentric anomaly and weighted by time
+/// spent, so a well-sampled periapsis needs more samples the more
+/// eccentric the orbit.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Averaged<F> {
+    pub model: F,
+    pub samples: usize,
+}
+
+impl<F: ForceModel> Averaged<F> {
+    pub const fn new(model: F) -> Self {
+        Averaged { model, samples: 72 }
+    }
+}
+
+impl<F: ForceModel> SecularModel for Averaged<F> {
+    /// Gauss's equations in vector form, ȧ = 2a²(v·f)/μ,
+    /// Ḣ = r×f and μė = 2(v·f)r − (r·f)v − (r·v)f
+    fn rates(
+        &self,
+        time: Seconds,
+        elements: &MilankovitchElements,
+        mu: GravParam,
+    ) -> MilankovitchRates {
+        let a = elements.semi_major_axis.value();
+        let e = elements.eccentricity.norm();
+        let scale = sqrt(mu.value() * a);
+        let mut sum = MilankovitchRates::ZERO;
+        for k in 0..self.samples {
+            let ecc_anomaly = TAU * (k as Real + 0.5) / self.samples as Real;
+            let state = elements.to_state(eccentric_to_true(ecc_anomaly, e), mu);
+            let (r, v) = (state.position, state.velocity);
+            let f = self.model.acceleration(time, &state);
+            let weight = (1.0 - e * cos(ecc_anomaly)) / self.samples as Real;
+            let a_rate = 2.0 * a * a * v.dot(f) / mu.value();
+            sum.semi_major_axis += weight * a_rate;
+            sum.angular_momentum +=
+                (r.cross(f) / scale - elements.angular_momentum * (a_rate / (2.0 * a))) * weight;
+            sum.eccentricity +=
+                (r * (2.0 * v.dot(f)) - v * r.dot(f) - f * r.dot(v)) * (weight / mu.value());
+        }
+        sum
+    }
+}
+
+/// One Runge-Kutta step of `step` of the averaged equations
+pub fn rk4_step<M: SecularModel>(
+    model: &M,
+    time: Seconds,
+    elements: &MilankovitchElements,
+    mu: GravParam,
+    step: Seconds,
+) -> MilankovitchElements {
+    let half = step / 2.0;
+    let k1 = model.rates(time, elements, mu);
+    let k2 = model.rates(time + half, &elements.advance(&k1, half.value()), mu);
+    let k3 = model.rates(time + half, &elements.advance(&k2, half.value()), mu);
+    let k4 = model.rates(time + step, &elements.advance(&k3, step.value()), mu);
+    let sum = MilankovitchRates {
+        semi_major_axis: k1.semi_major_axis
+            + 2.0 * (k2.semi_major_axis + k3.semi_major_axis)
+            + k4.semi_major_axis,
+        angular_momentum: k1.angular_momentum
+            + (k2.angular_momentum + k3.angular_momentum) * 2.0
+            + k4.angular_momentum,
+        eccentricity: k1.eccentricity + (k2.eccentricity + k3.eccentricity) * 2.0 + k4.eccentricity,
+    };
+    elements.advance(&sum, step.value() / 6.0)
+}
+
+/// Evolve `elements` from `start` to `end` in steps of at most `step`,
+/// shortening the last one to land on `end`
+pub fn evolve<M: SecularModel>(
+    model: &M,
+    elements: &MilankovitchElements,
+    mu: GravParam,
+    start: Seconds,
+    end: Seconds,
+    step: Seconds,
+) -> Result<MilankovitchElements, AlmagestError> {
+    let (start, end, step) = (start.value(), end.value(), step.value());
+    if step <= 0.0 {
+        return Err(AlmagestError::InvalidInput("Step must be positive"));
+    }
+    let direction = if end >= start { 1.0 } else { -1.0 };
+    let mut time = start;
+    let mut current = *elements;
+    while (end - time) * direction > 0.0 {
+        let h = ((end - time) * direction).min(step) * direction;
+        current = rk4_step(model, Seconds(time), &current, mu, Seconds(h));
+        time += h;
+    }
+    Ok(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{Eccentricity, Radians};
+    use approx::assert_relative_eq;
+
+    const YEAR: Real = 365.25 * 86_400.0;
+
+    fn orbit(a: Real, e: Real, i: Real, raan: Real, argp: Real) -> KeplerianElements {
+        KeplerianElements {
+            semi_major_axis: Meters(a),
+            eccentricity: Eccentricity::new(e).unwrap(),
+            inclination: Radians(i),
+            raan: Radians(raan),
+            argument_of_periapsis: Radians(argp),
+            true_anomaly: Radians(0.0),
+        }
+    }
+
+    #[test]
+    fn state_round_trip() {
+        let elements = orbit(2.4e7, 0.7, 0.5, 1.2, 2.0);
+        let state = elements.to_state(EARTH.mu);
+        let vectors = MilankovitchElements::from_state(&state, EARTH.mu).unwrap();
+        let direct = MilankovitchElements::from_keplerian(&elements);
+        assert_relative_eq!(vectors.semi_major_axis.value(), 2.4e7, max_relative = 1e-12);
+        assert_relative_eq!(
+            (vectors.angular_momentum - direct.angular_momentum).norm(),
+            0.0,
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            (vectors.eccentricity - direct.eccentricity).norm(),
+            0.0,
+            epsilon = 1e-12
+        );
+        assert!(vectors.constraint_error() < 1e-12);
+        assert_relative_eq!(vectors.inclination(), 0.5, epsilon = 1e-12);
+        let back = vectors.to_keplerian(0.0, EARTH.mu).unwrap();
+        assert_relative_eq!(back.raan.value(), 1.2, epsilon = 1e-9);
+        assert_relative_eq!(back.argument_of_periapsis.value(), 2.0, epsilon = 1e-9);
+
+        let escaping = StateVector::new(state.position, state.velocity * 2.0);
+        assert!(MilankovitchElements::from_state(&escaping, EARTH.mu).is_err());
+    }
+
+    #[test]
+    fn j2_matches_classical_rates() {
+        let (a, e, i) = (1.2e7, 0.3, 0.9);
+        let elements = orbit(a, e, i, 0.4, 1.1);
+        let vectors = MilankovitchElements::from_keplerian(&elements);
+        let rates = ZonalJ2::EARTH.rates(Seconds::ZERO, &vectors, EARTH.mu);
+
+        // Ω̇ = −(3/2)nJ2(R/p)² cos i, ω̇ = (3/4)nJ2(R/p)²(5cos²i − 1)
+        let n = elements.mean_motion(EARTH.mu).value();
+        let p = a * (1.0 - e * e);
+        let k = n * EARTH_J2 * (6_378_137.0 / p) * (6_378_137.0 / p);
+        let raan_rate = -1.5 * k * cos(i);
+        let argp_rate = 0.75 * k * (5.0 * cos(i) * cos(i) - 1.0);
+        let (h, ev) = (vectors.angular_momentum, vectors.eccentricity);
+        let expected_h = Vec3::Z.cross(h) * raan_rate;
+        let expected_e = Vec3::Z.cross(ev) * raan_rate + h.unit().cross(ev) * argp_rate;
+        assert_relative_eq!(
+            (rates.angular_momentum - expected_h).norm(),
+            0.0,
+            epsilon = 1e-9 * expected_h.norm()
+        );
+        assert_relative_eq!(
+            (rates.eccentricity - expected_e).norm(),
+            0.0,
+            epsilon = 1e-9 * expected_e.norm()
+        );
+    }
+
+    #[test]
+    fn lunisolar_nodal_regression_of_a_circular_orbit() {
+        // A circular orbit stays circular and its node regresses about
+        // the perturber's pole at −(3/4)(μ₃/a₃³n) cos i
+        let elements = orbit(4.2164e7, 0.0, 0.3, 0.0, 0.0);
+        let vectors = MilankovitchElements::from_keplerian(&elements);
+        let moon = ThirdBody {
+            pole: Vec3::Z,
+            ..ThirdBody::moon()
+        };
+        let rates = moon.rates(Seconds::ZERO, &vectors, EARTH.mu);
+        assert_relative_eq!(rates.eccentricity.norm(), 0.0, epsilon = 1e-24);
+        let d = moon.distance.value();
+        let n = vectors.mean_motion(EARTH.mu);
+        let raan_rate = -0.75 * moon.mu.value() / (d * d * d * n) * cos(0.3);
+        let expected = Vec3::Z.cross(vectors.angular_momentum) * raan_rate;
+        assert_relative_eq!(
+            (rates.angular_momentum - expected).norm(),
+            0.0,
+            epsilon = 1e-9 * expected.norm()
+        );
+    }
+
+    #[test]
+    fn averaged_along_track_thrust() {
+        use crate::forces::ConstantAcceleration;
+        // Gauss: a circular orbit's semi-major axis grows at 2f/n
+        let circular = MilankovitchElements::from_keplerian(&orbit(7.0e6, 0.0, 0.4, 1.0, 0.0));
+        let push = Averaged::new(ConstantAcceleration::Rsw(Vec3::new(0.0, 1e-4, 0.0)));
+        let rates = push.rates(Seconds::ZERO, &circular, EARTH.mu);
+        let n = circular.mean_motion(EARTH.mu);
+        assert_relative_eq!(rates.semi_major_axis, 2e-4 / n, max_relative = 1e-9);
+        assert_relative_eq!(rates.eccentricity.norm(), 0.0, epsilon = 1e-18);
+        assert_relative_eq!(rates.angular_momentum.norm(), 0.0, epsilon = 1e-18);
+    }
+
+    #[test]
+    fn century_of_a_graveyard_orbit() {
+        let graveyard = orbit(4.2164e7 + 3.0e5, 0.005, 0.05, 0.5, 0.3);
+        let start = MilankovitchElements::from_keplerian(&graveyard);
+        let model = (ZonalJ2::EARTH, (ThirdBody::sun(), ThirdBody::moon()));
+        let (century, step) = (Seconds(100.0 * YEAR), Seconds(5.0 * 86_400.0));
+        let end = evolve(&model, &start, EARTH.mu, Seconds::ZERO, century, step).unwrap();
+        assert!(end.constraint_error() < 1e-10);
+        // The plane precesses about the Laplace plane, 7.5° off the
+        // equator, so the inclination cycles up to 15° every 53 years
+        let inclination = end.inclination().to_degrees();
+        assert!((0.5..16.0).contains(&inclination));
+        // Eccentricity stays small, so perigee stays clear of GEO
+        assert!(end.eccentricity.norm() < 0.01);
+        assert!(end.periapsis_radius().value() > 4.2164e7 + 5.0e4);
+        // Backward returns to the start
+        let back = evolve(&model, &end, EARTH.mu, century, Seconds::ZERO, step).unwrap();
+        assert_relative_eq!(
+            (back.angular_momentum - start.angular_momentum).norm(),
+            0.0,
+            epsilon = 1e-8
+        );
+        assert!(
+            evolve(
+                &model,
+                &start,
+                EARTH.mu,
+                Seconds::ZERO,
+                century,
+                Seconds::ZERO
+            )
+            .is_err()
+        );
+    }
+}
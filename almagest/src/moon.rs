@@ -0,0 +1,83 @@
+//! Low-precision position of the Moon.
+//!
+//! Vallado's Algorithm 31, a truncated series good to about 0.3° in
+//! longitude and 0.2° in latitude. As with [`crate::sun`], times are
+//! Julian dates and results are geocentric, in the mean equator and
+//! equinox of date. For anything beyond mission design use an SPK
+//! kernel instead.
+
+use libm::{cos, sin};
+
+use crate::groundtrack::EARTH_RADIUS;
+use crate::state::StateVector;
+use crate::sun::J2000;
+use crate::utils::Real;
+use crate::vectors::Vec3;
+
+/// Geocentric position of the Moon at `julian_date`, in meters
+pub fn moon_position(julian_date: Real) -> Vec3 {
+    let t = (julian_date - J2000) / 36_525.0;
+    let s = |a: Real, b: Real| sin((a + b * t).to_radians());
+    let c = |a: Real, b: Real| cos((a + b * t).to_radians());
+    let longitude = (218.32 + 481_267.881_3 * t + 6.29 * s(134.9, 477_198.85)
+        - 1.27 * s(259.2, -413_335.38)
+        + 0.66 * s(235.7, 890_534.23)
+        + 0.21 * s(269.9, 954_397.70)
+        - 0.19 * s(357.5, 35_999.05)
+        - 0.11 * s(186.6, 966_404.05))
+    .to_radians();
+    let latitude = (5.13 * s(93.3, 483_202.03) + 0.28 * s(228.2, 960_400.87)
+        - 0.28 * s(318.3, 6_003.18)
+        - 0.17 * s(217.6, -407_332.20))
+    .to_radians();
+    let parallax = (0.9508
+        + 0.0518 * c(134.9, 477_198.85)
+        + 0.0095 * c(259.2, -413_335.38)
+        + 0.0078 * c(235.7, 890_534.23)
+        + 0.0028 * c(269.9, 954_397.70))
+    .to_radians();
+    let obliquity = (23.439_291 - 0.013_004_2 * t).to_radians();
+    let distance = EARTH_RADIUS.value() / sin(parallax);
+    let ecliptic = Vec3::new(
+        cos(latitude) * cos(longitude),
+        cos(latitude) * sin(longitude),
+        sin(latitude),
+    );
+    Vec3::new(
+        ecliptic.x,
+        cos(obliquity) * ecliptic.y - sin(obliquity) * ecliptic.z,
+        sin(obliquity) * ecliptic.y + cos(obliquity) * ecliptic.z,
+    ) * distance
+}
+
+/// Geocentric state of the Moon at `julian_date`, with the velocity
+/// taken by central differences over a minute
+pub fn moon_state(julian_date: Real) -> StateVector {
+    let h = 30.0 / 86_400.0;
+    let velocity = (moon_position(julian_date + h) - moon_position(julian_date - h)) / 60.0;
+    StateVector::new(moon_position(julian_date), velocity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    // Vallado Example 5-3
+    #[test]
+    fn moon_on_april_28_1994() {
+        let r = moon_position(2_449_470.5) / 1_000.0;
+        assert_relative_eq!(r.x, -134_240.626, epsilon = 1.0);
+        assert_relative_eq!(r.y, -311_571.590, epsilon = 1.0);
+        assert_relative_eq!(r.z, -126_693.785, epsilon = 1.0);
+    }
+
+    #[test]
+    fn moon_moves_about_a_kilometer_per_second() {
+        let state = moon_state(2_460_000.5);
+        let speed = state.velocity.norm();
+        assert!(speed > 950.0 && speed < 1_100.0);
+        // Prograde, like the Earth's rotation
+        assert!(state.position.cross(state.velocity).z > 0.0);
+    }
+}
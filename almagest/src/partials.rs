@@ -0,0 +1,274 @@
+//! Analytic partial derivatives of observations with respect to
+//! the Cartesian state.
+//!
+//! Each function takes the state of the observed object relative to
+//! the observer, ρ = r − r_site and ρ̇ = v − v_site, and returns the
+//! observation together with its gradient with respect to ρ and ρ̇.
+//! For an observer whose own state does not depend on the estimated
+//! one, these are also the partials with respect to the object's
+//! state in that frame.
+//!
+//! Right ascension and declination expect ρ in an inertial,
+//! equatorial frame. Azimuth and elevation expect it in the
+//! topocentric south-east-zenith (SEZ) frame, with azimuth measured
+//! clockwise from north. To get partials with respect to a state in
+//! another frame, rotate both gradient vectors by the transpose of
+//! the rotation that took the state into the observation frame.
+
+use libm::{asin, atan2, sqrt};
+
+use crate::state::StateVector;
+use crate::utils::{Real, TAU};
+use crate::vectors::Vec3;
+
+/// A scalar observation and its gradient with respect to the
+/// relative position and velocity
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Partials {
+    /// The observation itself
+    pub value: Real,
+    /// ∂value/∂ρ
+    pub d_position: Vec3,
+    /// ∂value/∂ρ̇
+    pub d_velocity: Vec3,
+}
+
+/// Range ρ = |ρ|, in meters
+pub fn range(relative: &StateVector) -> Partials {
+    let rho = relative.position;
+    let r = rho.norm();
+    Partials {
+        value: r,
+        d_position: rho / r,
+        d_velocity: Vec3::ZERO,
+    }
+}
+
+/// Range rate ρ̇ = ρ·ρ̇ / |ρ|, in meters per second
+pub fn range_rate(relative: &StateVector) -> Partials {
+    let rho = relative.position;
+    let r = rho.norm();
+    let unit = rho / r;
+    let rate = unit.dot(relative.velocity);
+    Partials {
+        value: rate,
+        d_position: (relative.velocity - unit * rate) / r,
+        d_velocity: unit,
+    }
+}
+
+/// Topocentric right ascension α, in radians on [0, 2π)
+pub fn right_ascension(relative: &StateVector) -> Partials {
+    let (x, y) = (relative.position.x, relative.position.y);
+    let h2 = x * x + y * y;
+    Partials {
+        value: wrap(atan2(y, x)),
+        d_position: Vec3::new(-y / h2, x / h2, 0.0),
+        d_velocity: Vec3::ZERO,
+    }
+}
+
+/// Topocentric declination δ, in radians
+pub fn declination(relative: &StateVector) -> Partials {
+    let rho = relative.position;
+    let (x, y, z) = (rho.x, rho.y, rho.z);
+    let r2 = rho.norm_squared();
+    let h = sqrt(x * x + y * y);
+    Partials {
+        value: asin(z / sqrt(r2)),
+        d_position: Vec3::new(-x * z / (r2 * h), -y * z / (r2 * h), h / r2),
+        d_velocity: Vec3::ZERO,
+    }
+}
+
+/// Rate of change of right ascension α̇, in radians per second
+pub fn right_ascension_rate(relative: &StateVector) -> Partials {
+    let (p, v) = (relative.position, relative.velocity);
+    let h2 = p.x * p.x + p.y * p.y;
+    let cross = p.x * v.y - p.y * v.x;
+    let rate = cross / h2;
+    Partials {
+        value: rate,
+        d_position: Vec3::new(
+            v.y / h2 - 2.0 * p.x * rate / h2,
+            -v.x / h2 - 2.0 * p.y * rate / h2,
+            0.0,
+        ),
+        d_velocity: Vec3::new(-p.y / h2, p.x / h2, 0.0),
+    }
+}
+
+/// Rate of change of declination δ̇, in radians per second
+pub fn declination_rate(relative: &StateVector) -> Partials {
+    let (p, v) = (relative.position, relative.velocity);
+    let h2 = p.x * p.x + p.y * p.y;
+    let h = sqrt(h2);
+    let r2 = h2 + p.z * p.z;
+    let radial = p.x * v.x + p.y * v.y;
+    // δ̇ = n / d with n = ż h² − z (x ẋ + y ẏ) and d = ρ² h
+    let n = v.z * h2 - p.z * radial;
+    let d = r2 * h;
+    let dn = Vec3::new(
+        2.0 * p.x * v.z - p.z * v.x,
+        2.0 * p.y * v.z - p.z * v.y,
+        -radial,
+    );
+    let dd = Vec3::new(
+        2.0 * p.x * h + r2 * p.x / h,
+        2.0 * p.y * h + r2 * p.y / h,
+        2.0 * p.z * h,
+    );
+    Partials {
+        value: n / d,
+        d_position: (dn * d - dd * n) / (d * d),
+        d_velocity: Vec3::new(-p.z * p.x, -p.z * p.y, h2) / d,
+    }
+}
+
+/// Azimuth β, clockwise from north, in radians on [0, 2π);
+/// the relative state must be in the SEZ frame
+pub fn azimuth(relative_sez: &StateVector) -> Partials {
+    from_north_east_zenith(right_ascension(&to_north_east_zenith(relative_sez)))
+}
+
+/// Elevation above the local horizon, in radians;
+/// the relative state must be in the SEZ frame
+pub fn elevation(relative_sez: &StateVector) -> Partials {
+    from_north_east_zenith(declination(&to_north_east_zenith(relative_sez)))
+}
+
+/// Rate of change of azimuth, in radians per second;
+/// the relative state must be in the SEZ frame
+pub fn azimuth_rate(relative_sez: &StateVector) -> Partials {
+    from_north_east_zenith(right_ascension_rate(&to_north_east_zenith(relative_sez)))
+}
+
+/// Rate of change of elevation, in radians per second;
+/// the relative state must be in the SEZ frame
+pub fn elevation_rate(relative_sez: &StateVector) -> Partials {
+    from_north_east_zenith(declination_rate(&to_north_east_zenith(relative_sez)))
+}
+
+// Azimuth and elevation are right ascension and declination measured
+// in (north, east, zenith) = (−south, east, zenith) coordinates
+fn to_north_east_zenith(sez: &StateVector) -> StateVector {
+    let flip = |v: Vec3| Vec3::new(-v.x, v.y, v.z);
+    StateVector::new(flip(sez.position), flip(sez.velocity))
+}
+
+// Map gradients taken in north-east-zenith back onto SEZ components
+fn from_north_east_zenith(partials: Partials) -> Partials {
+    let flip = |v: Vec3| Vec3::new(-v.x, v.y, v.z);
+    Partials {
+        value: partials.value,
+        d_position: flip(partials.d_position),
+        d_velocity: flip(partials.d_velocity),
+    }
+}
+
+fn wrap(angle: Real) -> Real {
+    if angle < 0.0 { angle + TAU } else { angle }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::PI;
+    use approx::assert_relative_eq;
+
+    fn sample() -> StateVector {
+        StateVector::new(
+            Vec3::new(-1_200_000.0, 900_000.0, 650_000.0),
+            Vec3::new(3_500.0, 5_200.0, -1_300.0),
+        )
+    }
+
+    // Check an analytic gradient against central differences
+    fn check(f: fn(&StateVector) -> Partials, state: StateVector) {
+        let analytic = f(&state);
+        let component = |v: Vec3, i: usize| [v.x, v.y, v.z][i];
+        let nudge = |v: Vec3, i: usize, h: Real| {
+            let mut c = [v.x, v.y, v.z];
+            c[i] += h;
+            Vec3::new(c[0], c[1], c[2])
+        };
+        for i in 0..3 {
+            let h = 1.0;
+            let plus = f(&StateVector::new(
+                nudge(state.position, i, h),
+                state.velocity,
+            ));
+            let minus = f(&StateVector::new(
+                nudge(state.position, i, -h),
+                state.velocity,
+            ));
+            let numeric = (plus.value - minus.value) / (2.0 * h);
+            assert_relative_eq!(
+                component(analytic.d_position, i),
+                numeric,
+                epsilon = 1e-12,
+                max_relative = 1e-5
+            );
+
+            let h = 1e-3;
+            let plus = f(&StateVector::new(
+                state.position,
+                nudge(state.velocity, i, h),
+            ));
+            let minus = f(&StateVector::new(
+                state.position,
+                nudge(state.velocity, i, -h),
+            ));
+            let numeric = (plus.value - minus.value) / (2.0 * h);
+            assert_relative_eq!(
+                component(analytic.d_velocity, i),
+                numeric,
+                epsilon = 1e-12,
+                max_relative = 1e-5
+            );
+        }
+    }
+
+    #[test]
+    fn range_partials() {
+        let state = sample();
+        assert_relative_eq!(range(&state).value, state.position.norm());
+        check(range, state);
+        check(range_rate, state);
+    }
+
+    #[test]
+    fn equatorial_angle_partials() {
+        check(right_ascension, sample());
+        check(declination, sample());
+        check(right_ascension_rate, sample());
+        check(declination_rate, sample());
+    }
+
+    #[test]
+    fn horizon_angle_partials() {
+        check(azimuth, sample());
+        check(elevation, sample());
+        check(azimuth_rate, sample());
+        check(elevation_rate, sample());
+    }
+
+    #[test]
+    fn azimuth_conventions() {
+        let at = |s: Real, e: Real| StateVector::new(Vec3::new(s, e, 1_000.0), Vec3::ZERO);
+        // Due north is a negative south component
+        assert_relative_eq!(azimuth(&at(-1_000.0, 0.0)).value, 0.0);
+        assert_relative_eq!(azimuth(&at(0.0, 1_000.0)).value, PI / 2.0);
+        assert_relative_eq!(azimuth(&at(1_000.0, 0.0)).value, PI);
+        assert_relative_eq!(azimuth(&at(0.0, -1_000.0)).value, 3.0 * PI / 2.0);
+        assert_relative_eq!(elevation(&at(-1_000.0, 0.0)).value, PI / 4.0);
+    }
+
+    #[test]
+    fn range_rate_of_receding_object() {
+        let state = StateVector::new(Vec3::new(1_000.0, 0.0, 0.0), Vec3::new(7.0, 3.0, 0.0));
+        assert_eq!(range_rate(&state).value, 7.0);
+        assert_eq!(range_rate(&state).d_velocity, Vec3::X);
+    }
+}
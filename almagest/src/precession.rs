@@ -0,0 +1,233 @@
+//! Secular drift of the node and periapsis under a body's zonal
+//! harmonics, in closed form.
+//!
+//! These are the mean rates, first order in J2 and in J4, for studies
+//! that want Ω̇ and ω̇ without flying an orbit: choosing a
+//! sun-synchronous inclination, spacing the planes of a constellation,
+//! or checking a frozen orbit's periapsis. J3 and the odd harmonics
+//! only add long-period terms and are left to the propagators.
+//!
+//! ```
+//! use almagest::bodies::EARTH;
+//! use almagest::kepler::KeplerianElements;
+//! use almagest::precession::{ZonalHarmonics, nodal_precession};
+//! use almagest::utils::{Eccentricity, Meters, Radians};
+//!
+//! // 800 km sun-synchronous
+//! let orbit = KeplerianElements {
+//!     semi_major_axis: Meters(7_178_137.0),
+//!     eccentricity: Eccentricity::new(0.0).unwrap(),
+//!     inclination: Radians(98.6_f64.to_radians()),
+//!     raan: Radians(0.0),
+//!     argument_of_periapsis: Radians(0.0),
+//!     true_anomaly: Radians(0.0),
+//! };
+//! let rate = nodal_precession(&orbit, &ZonalHarmonics::of(&EARTH)).unwrap();
+//! assert!((rate.to_degrees_per_day() - 0.9856).abs() < 0.01);
+//! ```
+
+use libm::sqrt;
+
+use crate::bodies::{CelestialBody, EARTH};
+use crate::error::AlmagestError;
+use crate::kepler::KeplerianElements;
+use crate::utils::{GravParam, Meters, RadiansPerSecond, Real};
+
+/// EGM2008 J4 of the Earth, unnormalized
+pub const EARTH_J4: Real = -1.619_621_591_37e-6;
+
+/// The even zonal harmonics of a body's gravity field that drive
+/// secular precession
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZonalHarmonics {
+    pub mu: GravParam,
+    pub radius: Meters,
+    pub j2: Real,
+    pub j4: Real,
+}
+
+impl ZonalHarmonics {
+    /// The Earth, through J4
+    pub const EARTH: Self = ZonalHarmonics {
+        j4: EARTH_J4,
+        ..ZonalHarmonics::of(&EARTH)
+    };
+
+    /// A body's J2 alone, the only zonal term [`CelestialBody`] carries
+    pub const fn of(body: &CelestialBody) -> Self {
+        ZonalHarmonics {
+            mu: body.mu,
+            radius: body.equatorial_radius,
+            j2: body.j2,
+            j4: 0.0,
+        }
+    }
+}
+
+// Ω̇ and ω̇ together, since ω̇ reuses the node's inclination term
+fn secular_rates(
+    elements: &KeplerianElements,
+    field: &ZonalHarmonics,
+) -> Result<(Real, Real), AlmagestError> {
+    let e = elements.eccentricity.value();
+    if e >= 1.0 {
+        return Err(AlmagestError::InvalidEccentricity(
+            "Secular rates need a closed orbit",
+        ));
+    }
+    let n = elements.mean_motion(field.mu).value();
+    let ratio = field.radius.value() / elements.semi_latus_rectum().value();
+    let (s, c) = elements.inclination.sin_cos();
+    let (s2, e2) = (s * s, e * e);
+
+    let k2 = n * field.j2 * ratio * ratio;
+    let k4 = n * field.j4 * ratio * ratio * ratio * ratio;
+
+    // Ω̇ = −(3/2)nJ2(R/p)² cos i + (15/16)nJ4(R/p)⁴(1 + 3e²/2) cos i (4 − 7 sin²i)
+    let node = -1.5 * k2 * c + 15.0 / 16.0 * k4 * (1.0 + 1.5 * e2) * c * (4.0 - 7.0 * s2);
+    // ω̇ = (3/4)nJ2(R/p)²(4 − 5 sin²i)
+    //     − (1/8)nJ4(R/p)⁴(10 + 15e²/2)(105 sin⁴i/8 − 15 sin²i + 3) − cos i Ω̇_J4
+    let periapsis = 0.75 * k2 * (4.0 - 5.0 * s2)
+        - k4 / 8.0 * (10.0 + 7.5 * e2) * (105.0 / 8.0 * s2 * s2 - 15.0 * s2 + 3.0)
+        - 15.0 / 16.0 * k4 * (1.0 + 1.5 * e2) * c * c * (4.0 - 7.0 * s2);
+    Ok((node, periapsis))
+}
+
+/// Mean rate of change of the right ascension of the ascending node,
+/// dΩ/dt; negative, a regression, for prograde orbits
+pub fn nodal_precession(
+    elements: &KeplerianElements,
+    field: &ZonalHarmonics,
+) -> Result<RadiansPerSecond, AlmagestError> {
+    secular_rates(elements, field).map(|(node, _)| RadiansPerSecond(node))
+}
+
+/// Mean rate of change of the argument of periapsis, dω/dt; to first
+/// order in J2 it vanishes at the critical inclination of 63.4°
+pub fn apsidal_rotation(
+    elements: &KeplerianElements,
+    field: &ZonalHarmonics,
+) -> Result<RadiansPerSecond, AlmagestError> {
+    secular_rates(elements, field).map(|(_, periapsis)| RadiansPerSecond(periapsis))
+}
+
+/// Inclination at which the node precesses at `rate` — for a
+/// sun-synchronous orbit, once around in a tropical year
+pub fn inclination_for_precession(
+    semi_major_axis: Meters,
+    eccentricity: Real,
+    rate: RadiansPerSecond,
+    field: &ZonalHarmonics,
+) -> Result<Real, AlmagestError> {
+    let a = semi_major_axis.value();
+    let p = a * (1.0 - eccentricity * eccentricity);
+    let n = sqrt(field.mu.value() / (a * a * a));
+    let ratio = field.radius.value() / p;
+    let cos_i = -rate.value() / (1.5 * n * field.j2 * ratio * ratio);
+    if !(-1.0..=1.0).contains(&cos_i) {
+        return Err(AlmagestError::InvalidInput(
+            "J2 cannot drive the node that fast at this orbit",
+        ));
+    }
+    Ok(libm::acos(cos_i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{Eccentricity, Radians};
+    use approx::assert_relative_eq;
+    use core::f64::consts::TAU;
+    use libm::{cos, sin};
+
+    fn orbit(a: Real, e: Real, i: Real) -> KeplerianElements {
+        KeplerianElements {
+            semi_major_axis: Meters(a),
+            eccentricity: Eccentricity::new(e).unwrap(),
+            inclination: Radians(i),
+            raan: Radians(0.3),
+            argument_of_periapsis: Radians(1.0),
+            true_anomaly: Radians(0.0),
+        }
+    }
+
+    #[test]
+    fn sun_synchronous_and_critical_inclination() {
+        let earth = ZonalHarmonics::of(&EARTH);
+        let year = RadiansPerSecond(TAU / (365.242_2 * 86_400.0));
+        let i = inclination_for_precession(Meters(7_178_137.0), 0.0, year, &earth).unwrap();
+        assert_relative_eq!(i.to_degrees(), 98.6, epsilon = 0.05);
+        let rate = nodal_precession(&orbit(7_178_137.0, 0.0, i), &earth).unwrap();
+        assert_relative_eq!(rate.value(), year.value(), max_relative = 1e-12);
+
+        // Molniya orbits sit at the critical inclination
+        let molniya = orbit(26_600_000.0, 0.74, libm::acos(sqrt(0.2)));
+        assert_relative_eq!(
+            apsidal_rotation(&molniya, &earth).unwrap().value(),
+            0.0,
+            epsilon = 1e-20
+        );
+
+        assert!(nodal_precession(&orbit(-7e6, 1.5, 0.5), &earth).is_err());
+        assert!(inclination_for_precession(Meters(4.2e7), 0.0, year, &earth).is_err());
+    }
+
+    #[test]
+    fn j4_matches_averaged_potential() {
+        let field = ZonalHarmonics {
+            j2: 0.0,
+            ..ZonalHarmonics::EARTH
+        };
+        let (mu, radius) = (field.mu.value(), field.radius.value());
+        // The J4 disturbing function averaged over the orbit and over
+        // the argument of periapsis, which leaves its secular part
+        let averaged = |a: Real, e: Real, i: Real| {
+            let (steps, turns) = (64, 16);
+            let mut sum = 0.0;
+            for k in 0..turns {
+                for j in 0..steps {
+                    let ecc_anomaly = TAU * j as Real / steps as Real;
+                    let half = ecc_anomaly / 2.0;
+                    let nu =
+                        2.0 * libm::atan2(sqrt(1.0 + e) * sin(half), sqrt(1.0 - e) * cos(half));
+                    let mut elements = orbit(a, e, i);
+                    elements.argument_of_periapsis = Radians(TAU * k as Real / turns as Real);
+                    elements.true_anomaly = Radians(nu);
+                    let r = elements.to_state(field.mu).position;
+                    let d = r.norm();
+                    let x = r.z / d;
+                    let p4 = (35.0 * x * x * x * x - 30.0 * x * x + 3.0) / 8.0;
+                    let ratio = radius / d;
+                    let potential = -mu / d * field.j4 * ratio * ratio * ratio * ratio * p4;
+                    // dM = (1 − e cos E) dE
+                    sum += potential * (1.0 - e * cos(ecc_anomaly));
+                }
+            }
+            sum / (steps * turns) as Real
+        };
+
+        for (e, i) in [(0.1, 0.5), (0.3, 1.9)] {
+            let elements = orbit(8_000_000.0, e, i);
+            let a = elements.semi_major_axis.value();
+            let n = elements.mean_motion(field.mu).value();
+            let eta = sqrt(1.0 - e * e);
+            let h = 1e-5;
+            let d_di = (averaged(a, e, i + h) - averaged(a, e, i - h)) / (2.0 * h);
+            let d_de = (averaged(a, e + h, i) - averaged(a, e - h, i)) / (2.0 * h);
+            // Lagrange's planetary equations
+            let node = d_di / (n * a * a * eta * sin(i));
+            let periapsis = eta / (n * a * a * e) * d_de - cos(i) * node;
+            assert_relative_eq!(
+                nodal_precession(&elements, &field).unwrap().value(),
+                node,
+                max_relative = 1e-6
+            );
+            assert_relative_eq!(
+                apsidal_rotation(&elements, &field).unwrap().value(),
+                periapsis,
+                max_relative = 1e-6
+            );
+        }
+    }
+}
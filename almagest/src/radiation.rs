@@ -0,0 +1,282 @@
+//! Passes through the South Atlantic Anomaly and the radiation belts.
+//!
+//! The anomaly can be bounded two ways: by a region on the ground with
+//! a ceiling, which is quick and easy to compare with published maps,
+//! or by a threshold on the strength of an eccentric dipole field,
+//! which follows the weak-field region up and down in altitude.
+//! Belt exposure is usually read from the dipole L-shell.
+//!
+//! Trajectories are supplied as an inertial ephemeris, a function of
+//! time, as in [`crate::scan`].
+
+use crate::bodies::RotatingBody;
+use crate::groundtrack::{
+    EARTH_FLATTENING, EARTH_RADIUS, EARTH_ROTATION_RATE, GroundPoint, Region, body_fixed,
+    geodetic_subsatellite_point,
+};
+use crate::magnetic::DipoleField;
+use crate::search::{Visits, visits};
+use crate::state::StateVector;
+use crate::utils::{Meters, Real, Seconds};
+use crate::vectors::Vec3;
+use crate::windows::Windows;
+
+/// A rough outline of the South Atlantic Anomaly near 500 km,
+/// for quick duty-cycle studies
+pub const SAA_OUTLINE: [GroundPoint; 8] = [
+    degrees(-50.0, -75.0),
+    degrees(-30.0, -90.0),
+    degrees(-5.0, -80.0),
+    degrees(0.0, -40.0),
+    degrees(-5.0, 10.0),
+    degrees(-20.0, 30.0),
+    degrees(-40.0, 20.0),
+    degrees(-50.0, -20.0),
+];
+
+const fn degrees(latitude: Real, longitude: Real) -> GroundPoint {
+    GroundPoint::new(latitude.to_radians(), longitude.to_radians())
+}
+
+/// How to decide whether a position is inside the anomaly
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SaaModel<'a> {
+    /// Over a ground region and below an altitude above the
+    /// equatorial radius
+    Outline { region: Region<'a>, ceiling: Meters },
+    /// Where the dipole field is weaker than `threshold` nT
+    FieldStrength { field: DipoleField, threshold: Real },
+}
+
+impl SaaModel<'static> {
+    /// [`SAA_OUTLINE`] up to 1000 km
+    pub fn outline() -> Self {
+        SaaModel::Outline {
+            region: Region::Polygon(&SAA_OUTLINE),
+            ceiling: Meters(1_000_000.0),
+        }
+    }
+
+    /// Field below 23,000 nT in the eccentric IGRF 2020 dipole, which
+    /// covers the South Atlantic at low-Earth-orbit altitudes
+    pub fn field_strength() -> Self {
+        SaaModel::FieldStrength {
+            field: DipoleField::igrf_2020_eccentric(),
+            threshold: 23_000.0,
+        }
+    }
+}
+
+impl SaaModel<'_> {
+    /// Whether a body-fixed (ECEF) position is inside the anomaly
+    pub fn contains(&self, position: Vec3) -> bool {
+        match *self {
+            SaaModel::Outline { region, ceiling } => {
+                let altitude = Meters(position.norm()) - EARTH_RADIUS;
+                altitude <= ceiling
+                    && region.contains(&geodetic_subsatellite_point(
+                        position,
+                        0.0,
+                        EARTH_RADIUS,
+                        EARTH_FLATTENING,
+                    ))
+            }
+            SaaModel::FieldStrength { field, threshold } => {
+                field.field_strength(position) < threshold
+            }
+        }
+    }
+}
+
+/// Summary of the time spent inside the anomaly
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DutyCycle {
+    pub passes: usize,
+    /// Total time inside
+    pub total_time: Seconds,
+    /// Longest single pass
+    pub longest: Seconds,
+    /// Share of the searched span spent inside
+    pub fraction: Real,
+}
+
+/// A search for anomaly passes along an inertial ephemeris
+#[derive(Copy, Clone, Debug)]
+pub struct SaaSearch<'a, E> {
+    pub model: SaaModel<'a>,
+    pub ephemeris: E,
+    /// The Earth, with time zero at the epoch of the ephemeris
+    pub body: RotatingBody,
+    /// Sampling interval; passes shorter than this can be missed
+    pub step: Seconds,
+}
+
+impl<'a, E: Fn(Seconds) -> StateVector> SaaSearch<'a, E> {
+    /// A search sampled every 10 s
+    pub fn new(model: SaaModel<'a>, ephemeris: E, greenwich_angle: Real) -> Self {
+        SaaSearch {
+            model,
+            ephemeris,
+            body: RotatingBody::earth(greenwich_angle),
+            step: Seconds(10.0),
+        }
+    }
+
+    /// Whether the ephemeris is inside the anomaly at time `t`
+    pub fn inside(&self, t: Seconds) -> bool {
+        let position = (self.ephemeris)(t).position;
+        self.model
+            .contains(body_fixed(position, self.body.angle(t)))
+    }
+
+    /// Passes between `start` and `end`, in time order; passes under
+    /// way at either end are cut off there
+    pub fn passes(&self, start: Seconds, end: Seconds) -> Visits<impl Fn(Seconds) -> bool + '_> {
+        visits(move |t| self.inside(t), start, end, self.step)
+    }
+
+    /// Pass count and time inside between `start` and `end`
+    pub fn duty_cycle(&self, start: Seconds, end: Seconds) -> DutyCycle {
+        let statistics = self.passes(start, end).statistics();
+        DutyCycle {
+            passes: statistics.count,
+            total_time: statistics.total,
+            longest: statistics.longest,
+            fraction: if end > start {
+                statistics.total / (end - start)
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// L-shell along an inertial ephemeris
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LShellSample {
+    /// Time from the epoch of the ephemeris
+    pub time: Seconds,
+    pub l_shell: Real,
+    /// Magnetic latitude, in radians
+    pub magnetic_latitude: Real,
+    /// Field strength, in nT
+    pub field_strength: Real,
+}
+
+/// Sample the dipole L-shell every `step` from `start` through `end`
+pub fn l_shells<E: Fn(Seconds) -> StateVector>(
+    field: DipoleField,
+    ephemeris: E,
+    greenwich_angle: Real,
+    start: Seconds,
+    end: Seconds,
+    step: Seconds,
+) -> impl Iterator<Item = LShellSample> {
+    let count = if step > Seconds::ZERO && end >= start {
+        ((end - start) / step) as usize + 1
+    } else {
+        0
+    };
+    (0..count).map(move |i| {
+        let time = start + step * i as Real;
+        let angle = greenwich_angle + EARTH_ROTATION_RATE * time.value();
+        let position = body_fixed(ephemeris(time).position, angle);
+        LShellSample {
+            time,
+            l_shell: field.l_shell(position),
+            magnetic_latitude: field.magnetic_latitude(position),
+            field_strength: field.field_strength(position),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::magnetic::IGRF_REFERENCE_RADIUS;
+    use crate::utils::PI;
+    use approx::assert_relative_eq;
+    use libm::{cos, sin, sqrt};
+
+    const RADIUS: Real = 6_878_137.0;
+
+    // Circular orbit inclined at 51.6°
+    fn ephemeris(t: Seconds) -> StateVector {
+        let t = t.value();
+        let v = sqrt(EARTH.mu.value() / RADIUS);
+        let n = v / RADIUS;
+        let i = 51.6_f64.to_radians();
+        let (s, c) = (sin(n * t), cos(n * t));
+        StateVector::new(
+            Vec3::new(c, s * cos(i), s * sin(i)) * RADIUS,
+            Vec3::new(-s, c * cos(i), c * sin(i)) * v,
+        )
+    }
+
+    fn over(latitude: Real, longitude: Real, altitude: Real) -> Vec3 {
+        GroundPoint::new(latitude.to_radians(), longitude.to_radians()).unit()
+            * (EARTH_RADIUS.value() + altitude)
+    }
+
+    #[test]
+    fn models_agree_on_the_core() {
+        for model in [SaaModel::outline(), SaaModel::field_strength()] {
+            assert!(model.contains(over(-26.0, -50.0, 500_000.0)));
+            assert!(!model.contains(over(-26.0, 130.0, 500_000.0)));
+            assert!(!model.contains(over(45.0, -50.0, 500_000.0)));
+        }
+        // The outline has a ceiling
+        assert!(!SaaModel::outline().contains(over(-26.0, -50.0, 2_000_000.0)));
+    }
+
+    #[test]
+    fn duty_cycle_matches_sampling() {
+        let search = SaaSearch::new(SaaModel::outline(), ephemeris, 1.0);
+        let end = 86_400.0;
+        let cycle = search.duty_cycle(Seconds::ZERO, Seconds(end));
+        assert!(cycle.passes > 0);
+        assert!(cycle.longest <= cycle.total_time);
+
+        let mut inside = 0.0;
+        let mut t = 0.5;
+        while t < end {
+            if search.inside(Seconds(t)) {
+                inside += 1.0;
+            }
+            t += 1.0;
+        }
+        let total = cycle.total_time.value();
+        assert_relative_eq!(total, inside, epsilon = cycle.passes as Real);
+        assert_relative_eq!(cycle.fraction, total / end);
+        for pass in search.passes(Seconds::ZERO, Seconds(end)) {
+            assert!(pass.end > pass.start);
+        }
+    }
+
+    #[test]
+    fn l_shell_along_an_orbit() {
+        let field = DipoleField::igrf_2020();
+        let samples = l_shells(
+            field,
+            ephemeris,
+            0.0,
+            Seconds::ZERO,
+            Seconds(5_400.0),
+            Seconds(60.0),
+        );
+        let mut count = 0;
+        let floor = RADIUS / IGRF_REFERENCE_RADIUS.value();
+        for sample in samples {
+            // Never below the orbit radius, highest at high magnetic latitude
+            assert!(sample.l_shell >= floor * (1.0 - 1e-12));
+            let c = cos(sample.magnetic_latitude);
+            assert_relative_eq!(sample.l_shell, floor / (c * c), max_relative = 1e-12);
+            assert!(sample.magnetic_latitude.abs() < PI / 2.0);
+            count += 1;
+        }
+        assert_eq!(count, 91);
+    }
+}
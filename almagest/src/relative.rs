@@ -0,0 +1,255 @@
+//! Relative motion about a circular orbit: the Clohessy-Wiltshire
+//! (Hill) equations.
+//!
+//! Relative states are in the target's Hill frame: x radial (away from
+//! the central body), y along track and z along the orbit normal, with
+//! velocities as seen in that rotating frame. The linearized motion
+//! holds while the separation is small compared with the orbit radius
+//! (Vallado, §6.8).
+
+use libm::{cos, sin, sqrt};
+
+use crate::error::AlmagestError;
+use crate::state::StateVector;
+use crate::utils::{GravParam, Meters, Real, Seconds};
+use crate::vectors::{Matrix3, Vec3};
+
+/// Mean motion of a circular orbit of `radius`
+pub fn circular_mean_motion(radius: Meters, mu: GravParam) -> Real {
+    let r = radius.value();
+    sqrt(mu.value() / (r * r * r))
+}
+
+/// Rotation from the target's Hill axes to inertial axes
+pub fn hill_axes(target: &StateVector) -> Matrix3 {
+    let x = target.position.unit();
+    let z = target.position.cross(target.velocity).unit();
+    Matrix3::from_columns(x, z.cross(x), z)
+}
+
+/// The chaser's state relative to the target, in the target's Hill
+/// frame, from inertial states of both
+pub fn to_hill(target: &StateVector, chaser: &StateVector) -> StateVector {
+    let to_hill = hill_axes(target).transpose();
+    let r = target.position.norm();
+    let omega = target.position.cross(target.velocity) / (r * r);
+    let relative = *chaser - *target;
+    StateVector::new(
+        to_hill * relative.position,
+        to_hill * (relative.velocity - omega.cross(relative.position)),
+    )
+}
+
+/// The chaser's inertial state from its state in the target's Hill
+/// frame
+pub fn from_hill(target: &StateVector, relative: &StateVector) -> StateVector {
+    let axes = hill_axes(target);
+    let r = target.position.norm();
+    let omega = target.position.cross(target.velocity) / (r * r);
+    let position = axes * relative.position;
+    StateVector::new(
+        target.position + position,
+        target.velocity + axes * relative.velocity + omega.cross(position),
+    )
+}
+
+/// The Clohessy-Wiltshire state transition matrix over one interval,
+/// in 3×3 blocks
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CwTransition {
+    pub rr: Matrix3,
+    pub rv: Matrix3,
+    pub vr: Matrix3,
+    pub vv: Matrix3,
+}
+
+impl CwTransition {
+    /// Transition over `time` about an orbit of mean motion `n`
+    pub fn new(n: Real, time: Seconds) -> Self {
+        let nt = n * time.value();
+        let (s, c) = (sin(nt), cos(nt));
+        CwTransition {
+            rr: Matrix3::from_rows(
+                Vec3::new(4.0 - 3.0 * c, 0.0, 0.0),
+                Vec3::new(6.0 * (s - nt), 1.0, 0.0),
+                Vec3::new(0.0, 0.0, c),
+            ),
+            rv: Matrix3::from_rows(
+                Vec3::new(s / n, 2.0 * (1.0 - c) / n, 0.0),
+                Vec3::new(-2.0 * (1.0 - c) / n, (4.0 * s - 3.0 * nt) / n, 0.0),
+                Vec3::new(0.0, 0.0, s / n),
+            ),
+            vr: Matrix3::from_rows(
+                Vec3::new(3.0 * n * s, 0.0, 0.0),
+                Vec3::new(-6.0 * n * (1.0 - c), 0.0, 0.0),
+                Vec3::new(0.0, 0.0, -n * s),
+            ),
+            vv: Matrix3::from_rows(
+                Vec3::new(c, 2.0 * s, 0.0),
+                Vec3::new(-2.0 * s, 4.0 * c - 3.0, 0.0),
+                Vec3::new(0.0, 0.0, c),
+            ),
+        }
+    }
+
+    pub fn apply(&self, relative: &StateVector) -> StateVector {
+        let (r, v) = (relative.position, relative.velocity);
+        StateVector::new(self.rr * r + self.rv * v, self.vr * r + self.vv * v)
+    }
+}
+
+/// Relative state `time` on, about an orbit of mean motion `n`
+pub fn cw_propagate(relative: &StateVector, n: Real, time: Seconds) -> StateVector {
+    CwTransition::new(n, time).apply(relative)
+}
+
+/// A two-burn rendezvous in the Hill frame
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CwRendezvous {
+    /// Burn at the start
+    pub departure: Vec3,
+    /// Burn on arrival at the target, to stop there
+    pub arrival: Vec3,
+}
+
+impl CwRendezvous {
+    pub fn total_delta_v(&self) -> Real {
+        self.departure.norm() + self.arrival.norm()
+    }
+}
+
+/// Burns to bring the chaser at `relative` to the target in `time`,
+/// about an orbit of mean motion `n`. Transfer times of a
+/// whole number of orbits, and in the plane of half ones, have no
+/// solution.
+pub fn cw_rendezvous(
+    relative: &StateVector,
+    n: Real,
+    time: Seconds,
+) -> Result<CwRendezvous, AlmagestError> {
+    let phi = CwTransition::new(n, time);
+    // n Φrv is dimensionless, so its determinant says how close to
+    // singular the transfer is
+    let unreachable = AlmagestError::InvalidInput("No CW transfer reaches the target in that time");
+    if (phi.rv.determinant() * n * n * n).abs() < 1e-9 {
+        return Err(unreachable);
+    }
+    let inverse = phi.rv.inverse().ok_or(unreachable)?;
+    let start = inverse * -(phi.rr * relative.position);
+    let end = phi.vr * relative.position + phi.vv * start;
+    Ok(CwRendezvous {
+        departure: start - relative.velocity,
+        arrival: -end,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::kepler::KeplerianElements;
+    use crate::utils::TAU;
+    use approx::assert_relative_eq;
+
+    const RADIUS: Meters = Meters(6_778_137.0);
+
+    fn target() -> StateVector {
+        let v = sqrt(EARTH.mu.value() / RADIUS.value());
+        let i = 0.9;
+        StateVector::new(
+            Vec3::new(RADIUS.value(), 0.0, 0.0),
+            Vec3::new(0.0, v * cos(i), v * sin(i)),
+        )
+    }
+
+    fn kepler(state: &StateVector, time: Seconds) -> StateVector {
+        KeplerianElements::from_state(state, EARTH.mu)
+            .unwrap()
+            .propagate(time, EARTH.mu)
+            .unwrap()
+            .to_state(EARTH.mu)
+    }
+
+    #[test]
+    fn hill_frame_round_trip() {
+        let relative =
+            StateVector::new(Vec3::new(100.0, -2_000.0, 50.0), Vec3::new(0.1, -0.2, 0.05));
+        let chaser = from_hill(&target(), &relative);
+        let back = to_hill(&target(), &chaser);
+        assert_relative_eq!(
+            (back.position - relative.position).norm(),
+            0.0,
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            (back.velocity - relative.velocity).norm(),
+            0.0,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn matches_two_body_motion_nearby() {
+        let n = circular_mean_motion(RADIUS, EARTH.mu);
+        let relative = StateVector::new(
+            Vec3::new(200.0, -1_000.0, 100.0),
+            Vec3::new(0.05, 0.1, -0.1),
+        );
+        let time = Seconds(1_500.0);
+        let predicted = cw_propagate(&relative, n, time);
+        let target = target();
+        let truth = to_hill(
+            &kepler(&target, time),
+            &kepler(&from_hill(&target, &relative), time),
+        );
+        // Linearization error grows with the square of the separation
+        assert_relative_eq!(
+            (predicted.position - truth.position).norm(),
+            0.0,
+            epsilon = 1.0
+        );
+        assert_relative_eq!(
+            (predicted.velocity - truth.velocity).norm(),
+            0.0,
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn drift_below_the_target() {
+        // A chaser 1 km below in a circular orbit moves ahead 3π km an orbit
+        let n = circular_mean_motion(RADIUS, EARTH.mu);
+        let relative = StateVector::new(
+            Vec3::new(-1_000.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.5 * n * 1_000.0, 0.0),
+        );
+        let later = cw_propagate(&relative, n, Seconds(TAU / n));
+        assert_relative_eq!(later.position.x, -1_000.0, epsilon = 1e-6);
+        assert_relative_eq!(
+            later.position.y,
+            3.0 * core::f64::consts::PI * 1_000.0,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn rendezvous_burns_reach_the_target() {
+        let n = circular_mean_motion(RADIUS, EARTH.mu);
+        let relative =
+            StateVector::new(Vec3::new(500.0, -3_000.0, 200.0), Vec3::new(0.0, 0.0, 0.0));
+        let time = Seconds(0.4 * TAU / n);
+        let burns = cw_rendezvous(&relative, n, time).unwrap();
+        let after = StateVector::new(relative.position, relative.velocity + burns.departure);
+        let arrival = cw_propagate(&after, n, time);
+        assert_relative_eq!(arrival.position.norm(), 0.0, epsilon = 1e-6);
+        assert_relative_eq!(
+            (arrival.velocity + burns.arrival).norm(),
+            0.0,
+            epsilon = 1e-9
+        );
+        assert!(burns.total_delta_v() > 0.0);
+        assert!(cw_rendezvous(&relative, n, Seconds(TAU / n)).is_err());
+    }
+}
@@ -0,0 +1,416 @@
+//! Mission reports.
+//!
+//! A [`MissionReport`] collects the summaries a mission analysis hands
+//! over: passes over a target, contact, eclipse and coverage windows
+//! and the maneuver budget. Every section is optional, so a tool fills
+//! in what it computed and leaves the rest out. The report renders as
+//! plain text through `Display` and serializes with the `serde`
+//! feature, either as stored, in SI, or through
+//! [`MissionReport::display_in`] with lengths, times and speeds in any
+//! [`UnitSystem`].
+//!
+//! ```
+//! use almagest::report::MissionReport;
+//! use almagest::search::visits;
+//! use almagest::utils::Seconds;
+//! use almagest::windows::{Interval, Windows};
+//!
+//! let span = Interval::new(Seconds(0.0), Seconds(6_000.0));
+//! let eclipsed = |t: Seconds| t.value() % 5_400.0 > 3_300.0;
+//! let eclipses = visits(eclipsed, span.start, span.end, Seconds(10.0));
+//! let report = MissionReport {
+//!     eclipses: Some(eclipses.statistics()),
+//!     ..MissionReport::new("Demo", span)
+//! };
+//! assert_eq!(report.eclipses.unwrap().count, 1);
+//! ```
+
+use core::fmt::{Display, Formatter, Result};
+
+use crate::budget::ManeuverPlan;
+use crate::format::{InUnits, Quantity, UnitSystem};
+use crate::groundtrack::Overflight;
+use crate::utils::{MetersPerSecond, PI, Real, Seconds};
+use crate::windows::{Interval, WindowStatistics};
+
+/// Passes over a ground target, as found by
+/// [`crate::groundtrack::OverflightSearch`]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PassSummary {
+    pub count: usize,
+    /// Time of the first pass
+    pub first: Option<Seconds>,
+    /// Time of the last pass
+    pub last: Option<Seconds>,
+    /// The pass nearest the target
+    pub closest: Option<Overflight>,
+}
+
+impl PassSummary {
+    pub fn new(passes: impl IntoIterator<Item = Overflight>) -> Self {
+        passes
+            .into_iter()
+            .fold(PassSummary::default(), |mut summary, pass| {
+                summary.count += 1;
+                summary.first.get_or_insert(pass.time);
+                summary.last = Some(pass.time);
+                if summary
+                    .closest
+                    .is_none_or(|closest| pass.ground_distance < closest.ground_distance)
+                {
+                    summary.closest = Some(pass);
+                }
+                summary
+            })
+    }
+}
+
+/// Totals of a [`ManeuverPlan`]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManeuverSummary {
+    pub burns: usize,
+    /// In m/s
+    pub total_delta_v: Real,
+    /// In kg
+    pub total_propellant: Real,
+    pub initial_mass: Real,
+    pub final_mass: Real,
+}
+
+impl<const N: usize> From<&ManeuverPlan<N>> for ManeuverSummary {
+    fn from(plan: &ManeuverPlan<N>) -> Self {
+        ManeuverSummary {
+            burns: plan.burns().len(),
+            total_delta_v: plan.total_delta_v().value(),
+            total_propellant: plan.total_propellant().value(),
+            initial_mass: plan.initial_mass().value(),
+            final_mass: plan.final_mass().value(),
+        }
+    }
+}
+
+/// The deliverable of a mission analysis over a span of time
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MissionReport<'a> {
+    pub mission: &'a str,
+    /// Analysis span
+    pub span: Interval,
+    pub passes: Option<PassSummary>,
+    /// Ground station contact windows
+    pub contacts: Option<WindowStatistics>,
+    pub eclipses: Option<WindowStatistics>,
+    /// Windows in which the target or region is covered
+    pub coverage: Option<WindowStatistics>,
+    pub maneuvers: Option<ManeuverSummary>,
+}
+
+impl<'a> MissionReport<'a> {
+    /// A report with no sections filled in
+    pub fn new(mission: &'a str, span: Interval) -> Self {
+        MissionReport {
+            mission,
+            span,
+            passes: None,
+            contacts: None,
+            eclipses: None,
+            coverage: None,
+            maneuvers: None,
+        }
+    }
+
+    /// Fraction of the span that is covered, if coverage was computed
+    pub fn coverage_fraction(&self) -> Option<Real> {
+        self.coverage
+            .map(|coverage| coverage.total / self.span.duration())
+    }
+
+    /// Wrap the report so that `Display` and serialization give
+    /// lengths, times and speeds in `system`. Masses are always in
+    /// kilograms and angles in degrees.
+    pub fn display_in(&self, system: UnitSystem) -> ReportInUnits<'_, 'a> {
+        ReportInUnits {
+            report: self,
+            system,
+        }
+    }
+}
+
+/// Renders in kilometers
+impl Display for MissionReport<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.display_in(UnitSystem::Kilometer).fmt(f)
+    }
+}
+
+/// A report paired with the unit system it is displayed in
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ReportInUnits<'r, 'a> {
+    report: &'r MissionReport<'a>,
+    system: UnitSystem,
+}
+
+impl ReportInUnits<'_, '_> {
+    fn time(&self, seconds: Seconds) -> InUnits<Seconds> {
+        seconds.display_in(self.system)
+    }
+
+    fn write_windows(
+        &self,
+        f: &mut Formatter<'_>,
+        name: &str,
+        windows: &Option<WindowStatistics>,
+    ) -> Result {
+        if let Some(windows) = windows {
+            write!(
+                f,
+                "\n{name}: {} windows, {:.1} total (mean {:.1}, longest {:.1})",
+                windows.count,
+                self.time(windows.total),
+                self.time(windows.mean()),
+                self.time(windows.longest)
+            )?;
+        }
+        Ok(())
+    }
+
+    // The report with its times, lengths and speeds in the system,
+    // for serializing only
+    #[cfg(feature = "serde")]
+    fn converted(&self) -> MissionReport<'_> {
+        let report = *self.report;
+        let time = |seconds: Seconds| Seconds(self.system.convert(seconds));
+        let span = |interval: Interval| Interval::new(time(interval.start), time(interval.end));
+        let windows = |windows: WindowStatistics| WindowStatistics {
+            total: time(windows.total),
+            shortest: time(windows.shortest),
+            longest: time(windows.longest),
+            ..windows
+        };
+        MissionReport {
+            span: span(report.span),
+            passes: report.passes.map(|passes| PassSummary {
+                first: passes.first.map(time),
+                last: passes.last.map(time),
+                closest: passes.closest.map(|pass| Overflight {
+                    time: time(pass.time),
+                    ground_distance: Quantity::from_si(self.system.convert(pass.ground_distance)),
+                    ..pass
+                }),
+                ..passes
+            }),
+            contacts: report.contacts.map(windows),
+            eclipses: report.eclipses.map(windows),
+            coverage: report.coverage.map(windows),
+            maneuvers: report.maneuvers.map(|maneuvers| ManeuverSummary {
+                total_delta_v: self
+                    .system
+                    .convert(MetersPerSecond(maneuvers.total_delta_v)),
+                ..maneuvers
+            }),
+            ..report
+        }
+    }
+}
+
+impl Display for ReportInUnits<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let report = self.report;
+        write!(
+            f,
+            "Mission report: {}\nSpan: {:.1} to {:.1} ({:.1})",
+            report.mission,
+            self.time(report.span.start),
+            self.time(report.span.end),
+            self.time(report.span.duration())
+        )?;
+        if let Some(passes) = &report.passes {
+            write!(f, "\nPasses: {}", passes.count)?;
+            if let Some(closest) = &passes.closest {
+                write!(
+                    f,
+                    ", closest {:.1} at {:.1} ({:.1}° off nadir)",
+                    closest.ground_distance.display_in(self.system),
+                    self.time(closest.time),
+                    closest.off_nadir * 180.0 / PI
+                )?;
+            }
+        }
+        self.write_windows(f, "Contacts", &report.contacts)?;
+        self.write_windows(f, "Eclipses", &report.eclipses)?;
+        self.write_windows(f, "Coverage", &report.coverage)?;
+        if let Some(fraction) = report.coverage_fraction() {
+            write!(f, ", {:.1}% of span", fraction * 100.0)?;
+        }
+        if let Some(maneuvers) = &report.maneuvers {
+            let delta_v = MetersPerSecond(maneuvers.total_delta_v);
+            write!(
+                f,
+                "\nManeuvers: {} burns, {:.3}, {:.1} kg propellant, {:.1} kg final mass",
+                maneuvers.burns,
+                delta_v.display_in(self.system),
+                maneuvers.total_propellant,
+                maneuvers.final_mass
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Serializes as the system under `units` and the report, with its
+/// numbers in the system, under `report`
+#[cfg(feature = "serde")]
+impl serde::Serialize for ReportInUnits<'_, '_> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        #[derive(serde::Serialize)]
+        struct Converted<'a> {
+            units: UnitSystem,
+            report: MissionReport<'a>,
+        }
+        Converted {
+            units: self.system,
+            report: self.converted(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{Kilograms, Meters, MetersPerSecond};
+    use crate::windows::Windows;
+    use approx::assert_relative_eq;
+    use core::fmt::Write;
+
+    struct Buffer {
+        bytes: [u8; 512],
+        len: usize,
+    }
+
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> Result {
+            let end = self.len + s.len();
+            self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    fn pass(time: Real, distance: Real) -> Overflight {
+        Overflight {
+            time: Seconds(time),
+            ground_distance: Meters(distance),
+            off_nadir: 0.1,
+        }
+    }
+
+    #[test]
+    fn aggregates_and_renders() {
+        let passes = PassSummary::new([
+            pass(600.0, 150_000.0),
+            pass(6_400.0, 12_000.0),
+            pass(12_200.0, 80_000.0),
+        ]);
+        assert_eq!(passes.count, 3);
+        assert_eq!(passes.first, Some(Seconds(600.0)));
+        assert_eq!(passes.last, Some(Seconds(12_200.0)));
+        assert_eq!(passes.closest, Some(pass(6_400.0, 12_000.0)));
+        assert_eq!(PassSummary::new([]), PassSummary::default());
+
+        let mut plan = ManeuverPlan::<4>::new(Kilograms(1_000.0));
+        plan.burn("raise", MetersPerSecond(100.0), 300.0)
+            .unwrap()
+            .burn("circularize", MetersPerSecond(50.0), 300.0)
+            .unwrap();
+        let maneuvers = ManeuverSummary::from(&plan);
+        assert_eq!(maneuvers.burns, 2);
+        assert_eq!(maneuvers.total_delta_v, 150.0);
+        assert_relative_eq!(
+            maneuvers.final_mass + maneuvers.total_propellant,
+            1_000.0,
+            max_relative = 1e-12
+        );
+
+        let interval = |start, end| Interval::new(Seconds(start), Seconds(end));
+        let span = interval(0.0, 1_000.0);
+        let coverage = [interval(100.0, 200.0), interval(500.0, 650.0)];
+        let report = MissionReport {
+            passes: Some(passes),
+            coverage: Some(coverage.into_iter().statistics()),
+            maneuvers: Some(maneuvers),
+            ..MissionReport::new("Demo", span)
+        };
+        assert_eq!(report.coverage_fraction(), Some(0.25));
+        assert_eq!(MissionReport::new("Empty", span).coverage_fraction(), None);
+
+        let mut buf = Buffer {
+            bytes: [0; 512],
+            len: 0,
+        };
+        write!(buf, "{}", report).unwrap();
+        let text = core::str::from_utf8(&buf.bytes[..buf.len]).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(lines.next(), Some("Mission report: Demo"));
+        assert_eq!(lines.next(), Some("Span: 0.0 s to 1000.0 s (1000.0 s)"));
+        assert_eq!(
+            lines.next(),
+            Some("Passes: 3, closest 12.0 km at 6400.0 s (5.7° off nadir)")
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                "Coverage: 2 windows, 250.0 s total (mean 125.0 s, longest 150.0 s), 25.0% of span"
+            )
+        );
+        assert!(
+            lines
+                .next()
+                .unwrap()
+                .starts_with("Maneuvers: 2 burns, 0.150 km/s")
+        );
+        assert_eq!(lines.next(), None);
+
+        // Canonical units carry through to the times
+        let canonical = UnitSystem::Canonical {
+            distance_unit: Meters(1_000.0),
+            time_unit: Seconds(100.0),
+        };
+        buf.len = 0;
+        write!(buf, "{}", report.display_in(canonical)).unwrap();
+        let text = core::str::from_utf8(&buf.bytes[..buf.len]).unwrap();
+        assert_eq!(
+            text.lines().nth(1),
+            Some("Span: 0.0 TU to 10.0 TU (10.0 TU)")
+        );
+        assert!(text.contains("closest 12.0 DU at 64.0 TU"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_in_the_chosen_units() {
+        let report = MissionReport {
+            passes: Some(PassSummary::new([pass(600.0, 150_000.0)])),
+            ..MissionReport::new("Demo", Interval::new(Seconds(0.0), Seconds(3_600.0)))
+        };
+        let si = serde_json::to_value(report).unwrap();
+        let km = serde_json::to_value(report.display_in(UnitSystem::Kilometer)).unwrap();
+        assert_eq!(km["units"], "Kilometer");
+        assert_eq!(km["report"]["span"], si["span"]);
+        let closest = &km["report"]["passes"]["closest"];
+        assert_eq!(closest["ground_distance"], 150.0);
+        assert_eq!(closest["time"], 600.0);
+
+        let hours = UnitSystem::Canonical {
+            distance_unit: Meters(1_000.0),
+            time_unit: Seconds(3_600.0),
+        };
+        let canonical = serde_json::to_value(report.display_in(hours)).unwrap();
+        assert_eq!(canonical["report"]["span"]["end"], 1.0);
+    }
+}
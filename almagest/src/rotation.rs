@@ -0,0 +1,433 @@
+//! Rotations: elementary frame rotations, unit quaternions and Euler
+//! angle sequences, all convertible to and from a direction cosine
+//! [`Matrix3`].
+//!
+//! A rotation here is whatever maps a vector's components in one frame
+//! to its components in another, so a quaternion and its matrix agree
+//! on every vector: `q.rotate(v) == Matrix3::from(q) * v`. The
+//! elementary [`rot1`], [`rot2`] and [`rot3`] rotate the frame, not the
+//! vector, as in Vallado: `rot3(θ)` takes inertial components to those
+//! of a frame turned by `θ` about z.
+//!
+//! ```
+//! use almagest::rotation::{EulerSequence, Quaternion};
+//! use almagest::vectors::{Matrix3, Vec3};
+//!
+//! // Yaw, pitch and roll of a body frame
+//! let dcm = EulerSequence::ThreeTwoOne.to_matrix([0.3_f64, -0.2, 1.1]);
+//! let q = Quaternion::from(dcm);
+//! let v = Vec3::new(1.0, 2.0, 3.0);
+//! assert!((q.rotate(v) - dcm * v).norm() < 1e-12);
+//! # let angles = EulerSequence::ThreeTwoOne.angles(&Matrix3::from(q));
+//! # assert!((angles[2] - 1.1).abs() < 1e-12);
+//! ```
+
+use core::ops::Mul;
+
+use crate::error::AlmagestError;
+use crate::float::Float;
+use crate::utils::Real;
+use crate::vectors::{Matrix3, Vec3};
+
+/// Rotate the frame by `angle` about its x axis
+pub fn rot1<T: Float>(angle: T) -> Matrix3<T> {
+    let (s, c) = (angle.sin(), angle.cos());
+    Matrix3::from_rows(Vec3::X, Vec3::new(T::ZERO, c, s), Vec3::new(T::ZERO, -s, c))
+}
+
+/// Rotate the frame by `angle` about its y axis
+pub fn rot2<T: Float>(angle: T) -> Matrix3<T> {
+    let (s, c) = (angle.sin(), angle.cos());
+    Matrix3::from_rows(Vec3::new(c, T::ZERO, -s), Vec3::Y, Vec3::new(s, T::ZERO, c))
+}
+
+/// Rotate the frame by `angle` about its z axis
+pub fn rot3<T: Float>(angle: T) -> Matrix3<T> {
+    let (s, c) = (angle.sin(), angle.cos());
+    Matrix3::from_rows(Vec3::new(c, s, T::ZERO), Vec3::new(-s, c, T::ZERO), Vec3::Z)
+}
+
+/// A rotation as a unit quaternion, scalar part first
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Quaternion<T = Real> {
+    w: T,
+    x: T,
+    y: T,
+    z: T,
+}
+
+impl<T: Float> Quaternion<T> {
+    pub const IDENTITY: Self = Quaternion {
+        w: T::ONE,
+        x: T::ZERO,
+        y: T::ZERO,
+        z: T::ZERO,
+    };
+
+    /// The rotation `w + xi + yj + zk` stands for, scaled to unit
+    /// length; zero has no direction to scale
+    pub fn new(w: T, x: T, y: T, z: T) -> Result<Self, AlmagestError> {
+        let norm = (w * w + x * x + y * y + z * z).sqrt();
+        if norm == T::ZERO {
+            return Err(AlmagestError::InvalidInput(
+                "A rotation quaternion cannot be zero",
+            ));
+        }
+        Ok(Quaternion {
+            w: w / norm,
+            x: x / norm,
+            y: y / norm,
+            z: z / norm,
+        })
+    }
+
+    /// Turn vectors by `angle` about `axis`, right-handed
+    pub fn from_axis_angle(axis: Vec3<T>, angle: T) -> Result<Self, AlmagestError> {
+        if axis == Vec3::ZERO {
+            return Err(AlmagestError::InvalidInput("The rotation axis is zero"));
+        }
+        let half = angle / T::from_real(2.0);
+        let v = axis.unit() * half.sin();
+        Ok(Quaternion {
+            w: half.cos(),
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        })
+    }
+
+    pub fn scalar(&self) -> T {
+        self.w
+    }
+
+    pub fn vector(&self) -> Vec3<T> {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    /// The inverse rotation
+    pub fn conjugate(&self) -> Self {
+        Quaternion {
+            w: self.w,
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+
+    /// The angle turned through, in [0, π]
+    pub fn angle(&self) -> T {
+        let two = T::from_real(2.0);
+        two * self.vector().norm().atan2(self.w.abs())
+    }
+
+    pub fn rotate(&self, v: Vec3<T>) -> Vec3<T> {
+        let two = T::from_real(2.0);
+        let u = self.vector();
+        let t = u.cross(v) * two;
+        v + t * self.w + u.cross(t)
+    }
+}
+
+/// `a * b` rotates by `b`, then by `a`, as the matrix product does
+impl<T: Float> Mul for Quaternion<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (u, v) = (self.vector(), rhs.vector());
+        let vector = v * self.w + u * rhs.w + u.cross(v);
+        Quaternion {
+            w: self.w * rhs.w - u.dot(v),
+            x: vector.x,
+            y: vector.y,
+            z: vector.z,
+        }
+    }
+}
+
+impl<T: Float> From<Quaternion<T>> for Matrix3<T> {
+    fn from(q: Quaternion<T>) -> Self {
+        let two = T::from_real(2.0);
+        let Quaternion { w, x, y, z } = q;
+        Matrix3::from_rows(
+            Vec3::new(
+                T::ONE - two * (y * y + z * z),
+                two * (x * y - w * z),
+                two * (x * z + w * y),
+            ),
+            Vec3::new(
+                two * (x * y + w * z),
+                T::ONE - two * (x * x + z * z),
+                two * (y * z - w * x),
+            ),
+            Vec3::new(
+                two * (x * z - w * y),
+                two * (y * z + w * x),
+                T::ONE - two * (x * x + y * y),
+            ),
+        )
+    }
+}
+
+/// Shepperd's method, taking the root of the largest of the four
+/// squared components. Trusts the matrix to be a proper rotation; the
+/// result is scaled to unit length and has a non-negative scalar part.
+impl<T: Float> From<Matrix3<T>> for Quaternion<T> {
+    fn from(m: Matrix3<T>) -> Self {
+        let [r0, r1, r2] = m.rows;
+        let quarter = T::from_real(0.25);
+        let trace = r0.x + r1.y + r2.z;
+        let (w, x, y, z) = if trace >= r0.x && trace >= r1.y && trace >= r2.z {
+            let s = (T::ONE + trace).sqrt() * T::from_real(2.0);
+            (
+                quarter * s,
+                (r2.y - r1.z) / s,
+                (r0.z - r2.x) / s,
+                (r1.x - r0.y) / s,
+            )
+        } else if r0.x >= r1.y && r0.x >= r2.z {
+            let s = (T::ONE + r0.x - r1.y - r2.z).sqrt() * T::from_real(2.0);
+            (
+                (r2.y - r1.z) / s,
+                quarter * s,
+                (r0.y + r1.x) / s,
+                (r0.z + r2.x) / s,
+            )
+        } else if r1.y >= r2.z {
+            let s = (T::ONE - r0.x + r1.y - r2.z).sqrt() * T::from_real(2.0);
+            (
+                (r0.z - r2.x) / s,
+                (r0.y + r1.x) / s,
+                quarter * s,
+                (r1.z + r2.y) / s,
+            )
+        } else {
+            let s = (T::ONE - r0.x - r1.y + r2.z).sqrt() * T::from_real(2.0);
+            (
+                (r1.x - r0.y) / s,
+                (r0.z + r2.x) / s,
+                (r1.z + r2.y) / s,
+                quarter * s,
+            )
+        };
+        let sign = T::ONE.copysign(w);
+        // Only a zero matrix, which is no rotation, can fail to scale
+        Quaternion::new(sign * w, sign * x, sign * y, sign * z).unwrap_or(Quaternion::IDENTITY)
+    }
+}
+
+/// Goes through [`Quaternion::new`], so stored components are scaled
+/// back to unit length and a zero quaternion is an error
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for Quaternion<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Components<T> {
+            w: T,
+            x: T,
+            y: T,
+            z: T,
+        }
+        let Components { w, x, y, z } = Components::deserialize(deserializer)?;
+        Quaternion::new(w, x, y, z).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The order of the three frame rotations making up a set of Euler
+/// angles. Angles are listed in the order they are applied.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EulerSequence {
+    /// z, then the new x, then the new z: right ascension of the
+    /// ascending node, inclination and argument of latitude for an
+    /// orbit
+    ThreeOneThree,
+    /// z, then the new y, then the new x: yaw, pitch and roll
+    ThreeTwoOne,
+}
+
+impl EulerSequence {
+    /// The direction cosine matrix from the reference frame to the
+    /// frame the three rotations end in
+    pub fn to_matrix<T: Float>(self, angles: [T; 3]) -> Matrix3<T> {
+        let [first, second, third] = angles;
+        match self {
+            EulerSequence::ThreeOneThree => rot3(third) * rot1(second) * rot3(first),
+            EulerSequence::ThreeTwoOne => rot1(third) * rot2(second) * rot3(first),
+        }
+    }
+
+    /// The angles of a direction cosine matrix. The first and third
+    /// are in (−π, π]; the second is in [0, π] for 3-1-3 and
+    /// [−π/2, π/2] for 3-2-1. At gimbal lock, where only the first
+    /// and third combined are fixed, the third is taken as zero.
+    pub fn angles<T: Float>(self, m: &Matrix3<T>) -> [T; 3] {
+        let [r0, r1, r2] = m.rows;
+        let lock = T::EPSILON * T::from_real(16.0);
+        match self {
+            EulerSequence::ThreeOneThree => {
+                let second = r2.z.max(-T::ONE).min(T::ONE).acos();
+                if (r2.x * r2.x + r2.y * r2.y).sqrt() <= lock {
+                    return [r0.y.atan2(r0.x), second, T::ZERO];
+                }
+                [r2.x.atan2(-r2.y), second, r0.z.atan2(r1.z)]
+            }
+            EulerSequence::ThreeTwoOne => {
+                let second = -(r0.z.max(-T::ONE).min(T::ONE).asin());
+                if (r1.z * r1.z + r2.z * r2.z).sqrt() <= lock {
+                    return [(-r1.x).atan2(r1.y), second, T::ZERO];
+                }
+                [r0.y.atan2(r0.x), second, r1.z.atan2(r2.z)]
+            }
+        }
+    }
+}
+
+/// Conversions to and from `nalgebra`'s unit quaternion, which shares
+/// this module's convention
+#[cfg(feature = "nalgebra")]
+mod nalgebra_interop {
+    use super::Quaternion;
+    use crate::float::Float;
+    use nalgebra as na;
+
+    impl<T: Float + na::RealField> From<Quaternion<T>> for na::UnitQuaternion<T> {
+        fn from(q: Quaternion<T>) -> Self {
+            na::UnitQuaternion::new_unchecked(na::Quaternion::new(q.w, q.x, q.y, q.z))
+        }
+    }
+
+    impl<T: Float + na::RealField> From<na::UnitQuaternion<T>> for Quaternion<T> {
+        fn from(q: na::UnitQuaternion<T>) -> Self {
+            Quaternion {
+                w: q.w,
+                x: q.i,
+                y: q.j,
+                z: q.k,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groundtrack::body_fixed;
+    use approx::assert_relative_eq;
+    use core::f64::consts::{FRAC_PI_2, PI};
+
+    fn assert_matrix_eq(a: &Matrix3, b: &Matrix3) {
+        for (ra, rb) in a.rows.iter().zip(b.rows.iter()) {
+            assert_relative_eq!((*ra - *rb).norm(), 0.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn elementary_rotations_turn_the_frame() {
+        assert_relative_eq!(
+            (rot3(FRAC_PI_2) * Vec3::X + Vec3::Y).norm(),
+            0.0,
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            (rot1(FRAC_PI_2) * Vec3::Y + Vec3::Z).norm(),
+            0.0,
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            (rot2(FRAC_PI_2) * Vec3::Z + Vec3::X).norm(),
+            0.0,
+            epsilon = 1e-15
+        );
+        let p = Vec3::new(7.0e6, -1.0e6, 3.0e5);
+        assert_relative_eq!(
+            (rot3(1.2) * p - body_fixed(p, 1.2)).norm(),
+            0.0,
+            epsilon = 1e-8
+        );
+        // The orbital rotation to perifocal is the 3-1-3 sequence
+        assert_matrix_eq(
+            &EulerSequence::ThreeOneThree.to_matrix([0.4, 0.9, 1.3]),
+            &(rot3(1.3) * rot1(0.9) * rot3(0.4)),
+        );
+    }
+
+    #[test]
+    fn quaternion_matches_its_matrix() {
+        let v = Vec3::new(1.0, -2.0, 0.5);
+        let axes = [Vec3::X, Vec3::new(1.0, 1.0, 0.0), Vec3::new(-0.3, 0.2, 0.9)];
+        for axis in axes {
+            // Small, large and near half-turn angles exercise each
+            // branch of Shepperd's method
+            for angle in [0.1, 2.0, PI - 1e-9, -1.0] {
+                let q = Quaternion::from_axis_angle(axis, angle).unwrap();
+                let m = Matrix3::from(q);
+                assert_relative_eq!((q.rotate(v) - m * v).norm(), 0.0, epsilon = 1e-12);
+                assert_relative_eq!(q.angle(), angle.abs(), epsilon = 1e-9);
+                let back = Quaternion::from(m);
+                assert_relative_eq!(
+                    libm::fabs(q.vector().dot(back.vector()) + q.w * back.w),
+                    1.0
+                );
+                assert_relative_eq!(
+                    (q.conjugate().rotate(q.rotate(v)) - v).norm(),
+                    0.0,
+                    epsilon = 1e-12
+                );
+            }
+        }
+        let a = Quaternion::from_axis_angle(Vec3::Z, 0.7).unwrap();
+        let b = Quaternion::from_axis_angle(Vec3::new(1.0, 2.0, 3.0), -1.9).unwrap();
+        assert_matrix_eq(
+            &Matrix3::from(a * b),
+            &(Matrix3::from(a) * Matrix3::from(b)),
+        );
+        // Quarter turn of vectors about z is a quarter turn of the frame
+        // the other way
+        let quarter = Quaternion::from_axis_angle(Vec3::Z, FRAC_PI_2).unwrap();
+        assert_matrix_eq(&Matrix3::from(quarter), &rot3(-FRAC_PI_2));
+
+        assert!(Quaternion::new(0.0, 0.0, 0.0, 0.0).is_err());
+        assert!(Quaternion::from_axis_angle(Vec3::ZERO, 1.0).is_err());
+        let q = Quaternion::new(2.0, 0.0, 0.0, 0.0).unwrap();
+        assert_eq!(q, Quaternion::IDENTITY);
+    }
+
+    #[test]
+    fn euler_angles_round_trip() {
+        for sequence in [EulerSequence::ThreeOneThree, EulerSequence::ThreeTwoOne] {
+            for angles in [[0.3, 1.2, -2.5], [-3.0, 0.4, 0.1], [2.0, 0.01, 1.0]] {
+                let m = sequence.to_matrix(angles);
+                let back = sequence.angles(&m);
+                for (a, b) in angles.iter().zip(back.iter()) {
+                    assert_relative_eq!(a, b, epsilon = 1e-12);
+                }
+                let q = Quaternion::from(m);
+                assert_matrix_eq(&sequence.to_matrix(sequence.angles(&Matrix3::from(q))), &m);
+            }
+        }
+        // Gimbal lock keeps the matrix, if not the split between the
+        // first and third angles
+        let locked = [
+            (EulerSequence::ThreeOneThree, [0.5, 0.0, 0.7]),
+            (EulerSequence::ThreeOneThree, [0.5, PI, 0.7]),
+            (EulerSequence::ThreeTwoOne, [0.5, FRAC_PI_2, 0.7]),
+            (EulerSequence::ThreeTwoOne, [0.5, -FRAC_PI_2, 0.7]),
+        ];
+        for (sequence, angles) in locked {
+            let m = sequence.to_matrix(angles);
+            let back = sequence.angles(&m);
+            assert_eq!(back[2], 0.0);
+            assert_matrix_eq(&sequence.to_matrix(back), &m);
+        }
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra_quaternions() {
+        use nalgebra as na;
+        let q = Quaternion::from_axis_angle(Vec3::new(0.2, -0.5, 1.0), 2.2).unwrap();
+        let na_q = na::UnitQuaternion::from(q);
+        assert_matrix_eq(&Matrix3::from(na_q), &Matrix3::from(q));
+        assert_eq!(Quaternion::from(na_q), q);
+    }
+}
@@ -0,0 +1,304 @@
+//! Solar sail acceleration.
+//!
+//! A sail is sized by its characteristic acceleration a꜀, the
+//! acceleration of a perfectly reflecting sail facing the Sun at
+//! 1 AU. Its attitude is given by the cone angle α between the sail
+//! normal and the Sun line and the clock angle δ of the normal about
+//! the Sun line, measured from the orbit normal toward the direction
+//! of motion: n̂ = cos α r̂ + sin α sin δ θ̂ + sin α cos δ ĥ, in the
+//! radial, transverse and normal axes of a heliocentric state.
+//!
+//! The optical model follows McInnes (Solar Sailing, §2.6), with
+//! reflectivity, specular fraction, emissivities and non-Lambertian
+//! coefficients for each face.
+
+use libm::{cos, sin};
+
+use crate::bodies::SUN;
+use crate::forces::ForceModel;
+use crate::state::StateVector;
+use crate::sun::ASTRONOMICAL_UNIT;
+use crate::utils::{GravParam, Real, Seconds};
+use crate::vectors::Vec3;
+
+/// Gravitational parameter of the Sun
+pub const MU_SUN: GravParam = SUN.mu;
+
+/// Solar radiation pressure on a perfect absorber at 1 AU, in N/m²
+pub const SOLAR_PRESSURE: Real = 4.56e-6;
+
+/// Characteristic acceleration of a perfectly reflecting sail of
+/// `area` m² carrying a total `mass` kg, in m/s²
+pub fn characteristic_acceleration(mass: Real, area: Real) -> Real {
+    2.0 * SOLAR_PRESSURE * area / mass
+}
+
+/// Ratio of a sail's characteristic acceleration to the Sun's gravity
+/// at 1 AU
+pub fn lightness_number(characteristic_acceleration: Real) -> Real {
+    let au = ASTRONOMICAL_UNIT.value();
+    characteristic_acceleration * au * au / MU_SUN.value()
+}
+
+/// Orientation of the sail normal
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SailAttitude {
+    pub cone: Real,
+    pub clock: Real,
+}
+
+impl SailAttitude {
+    /// Facing the Sun
+    pub const SUN_FACING: Self = SailAttitude {
+        cone: 0.0,
+        clock: 0.0,
+    };
+
+    /// Sail normal for a heliocentric state
+    pub fn normal(&self, state: &StateVector) -> Vec3 {
+        let radial = state.position.unit();
+        let orbit_normal = state.position.cross(state.velocity).unit();
+        let transverse = orbit_normal.cross(radial);
+        radial * cos(self.cone)
+            + transverse * (sin(self.cone) * sin(self.clock))
+            + orbit_normal * (sin(self.cone) * cos(self.clock))
+    }
+}
+
+/// How the sail film handles light
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SailOptics {
+    /// Perfect specular reflection: force along the normal only,
+    /// scaling with cos² α
+    Ideal,
+    Optical {
+        reflectivity: Real,
+        /// Share of the reflection that is specular
+        specular: Real,
+        front_emissivity: Real,
+        back_emissivity: Real,
+        /// Non-Lambertian coefficients of the front and back
+        front_lambertian: Real,
+        back_lambertian: Real,
+    },
+}
+
+impl SailOptics {
+    /// Aluminized Kapton with a chromium back, McInnes Table 2.3
+    pub const ALUMINIZED: Self = SailOptics::Optical {
+        reflectivity: 0.88,
+        specular: 0.94,
+        front_emissivity: 0.05,
+        back_emissivity: 0.55,
+        front_lambertian: 0.79,
+        back_lambertian: 0.55,
+    };
+
+    // Normal and tangential force at cone angle α, as a fraction of
+    // the ideal sail's 2PA
+    fn coefficients(&self, cone: Real) -> (Real, Real) {
+        let (c, s) = (cos(cone), sin(cone));
+        match *self {
+            SailOptics::Ideal => (c * c, 0.0),
+            SailOptics::Optical {
+                reflectivity: r,
+                specular: spec,
+                front_emissivity: ef,
+                back_emissivity: eb,
+                front_lambertian: bf,
+                back_lambertian: bb,
+            } => {
+                let normal = (1.0 + r * spec) * c * c
+                    + bf * (1.0 - spec) * r * c
+                    + (1.0 - r) * (ef * bf - eb * bb) / (ef + eb) * c;
+                let tangential = (1.0 - r * spec) * c * s;
+                (normal / 2.0, tangential / 2.0)
+            }
+        }
+    }
+}
+
+/// A solar sail with a fixed optical model
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolarSail {
+    /// In m/s²
+    pub characteristic_acceleration: Real,
+    pub optics: SailOptics,
+}
+
+impl SolarSail {
+    /// Acceleration for a heliocentric `state` and sail `attitude`.
+    /// A sail turned edge-on or away from the Sun feels nothing.
+    pub fn acceleration(&self, state: &StateVector, attitude: SailAttitude) -> Vec3 {
+        if cos(attitude.cone) <= 0.0 {
+            return Vec3::ZERO;
+        }
+        let r = state.position.norm();
+        let au = ASTRONOMICAL_UNIT.value();
+        let scale = self.characteristic_acceleration * (au / r) * (au / r);
+        let normal = attitude.normal(state);
+        let (f_n, f_t) = self.optics.coefficients(attitude.cone);
+        let mut acceleration = normal * (scale * f_n);
+        if f_t != 0.0 {
+            // In the plane of the Sun line and the normal, away from
+            // the Sun
+            let sun_line = state.position / r;
+            let along = sun_line - normal * cos(attitude.cone);
+            acceleration += along.unit() * (scale * f_t);
+        }
+        acceleration
+    }
+
+    /// The sail flown under a steering law giving the attitude for a
+    /// time and state, for use with [`crate::forces::propagate`]
+    pub fn steered<C>(self, control: C) -> SteeredSail<C>
+    where
+        C: Fn(Seconds, &StateVector) -> SailAttitude,
+    {
+        SteeredSail {
+            sail: self,
+            control,
+        }
+    }
+}
+
+/// A [`SolarSail`] under a steering law
+#[derive(Copy, Clone, Debug)]
+pub struct SteeredSail<C> {
+    pub sail: SolarSail,
+    pub control: C,
+}
+
+impl<C: Fn(Seconds, &StateVector) -> SailAttitude> ForceModel for SteeredSail<C> {
+    fn acceleration(&self, time: Seconds, state: &StateVector) -> Vec3 {
+        self.sail.acceleration(state, (self.control)(time, state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forces::{TwoBody, propagate};
+    use crate::utils::Seconds;
+    use approx::assert_relative_eq;
+    use libm::sqrt;
+
+    const PI_2: Real = core::f64::consts::FRAC_PI_2;
+
+    fn earth_like() -> StateVector {
+        let au = ASTRONOMICAL_UNIT.value();
+        StateVector::new(
+            Vec3::new(au, 0.0, 0.0),
+            Vec3::new(0.0, sqrt(MU_SUN.value() / au), 0.0),
+        )
+    }
+
+    #[test]
+    fn characteristic_acceleration_and_lightness() {
+        // A 1 mm/s² sail is a lightness number of about 0.17
+        let a_c = characteristic_acceleration(100.0, 10_965.0);
+        assert_relative_eq!(a_c, 1e-3, max_relative = 1e-3);
+        assert_relative_eq!(lightness_number(a_c), 0.1686, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn ideal_sail_force() {
+        let sail = SolarSail {
+            characteristic_acceleration: 1e-3,
+            optics: SailOptics::Ideal,
+        };
+        let state = earth_like();
+        let facing = sail.acceleration(&state, SailAttitude::SUN_FACING);
+        assert_relative_eq!(facing.x, 1e-3);
+        let tilted = SailAttitude {
+            cone: 35.0_f64.to_radians(),
+            clock: PI_2,
+        };
+        let a = sail.acceleration(&state, tilted);
+        let c = cos(tilted.cone);
+        assert_relative_eq!(a.norm(), 1e-3 * c * c, max_relative = 1e-12);
+        // Tilted toward the direction of motion, so it pushes forward
+        assert!(a.y > 0.0);
+        assert_relative_eq!(a.z, 0.0, epsilon = 1e-18);
+
+        // Twice as far out, a quarter of the push
+        let far = StateVector::new(state.position * 2.0, state.velocity);
+        assert_relative_eq!(sail.acceleration(&far, SailAttitude::SUN_FACING).x, 2.5e-4);
+        let away = SailAttitude {
+            cone: 2.0,
+            clock: 0.0,
+        };
+        assert_eq!(sail.acceleration(&state, away), Vec3::ZERO);
+    }
+
+    #[test]
+    fn optical_sail_is_weaker_and_deflected() {
+        let ideal = SolarSail {
+            characteristic_acceleration: 1e-3,
+            optics: SailOptics::Ideal,
+        };
+        let real = SolarSail {
+            optics: SailOptics::ALUMINIZED,
+            ..ideal
+        };
+        let state = earth_like();
+        let attitude = SailAttitude {
+            cone: 0.6,
+            clock: PI_2,
+        };
+        let a_ideal = ideal.acceleration(&state, attitude);
+        let a_real = real.acceleration(&state, attitude);
+        assert!(a_real.norm() < a_ideal.norm());
+        // Absorbed light pushes along the Sun line, so the force leans
+        // outward from the sail normal
+        let normal = attitude.normal(&state);
+        assert!(a_real.unit().dot(normal) < 1.0 - 1e-6);
+        assert!(a_real.unit().x > a_ideal.unit().x);
+
+        // A perfect mirror through the optical model is the ideal sail
+        let mirror = SailOptics::Optical {
+            reflectivity: 1.0,
+            specular: 1.0,
+            front_emissivity: 0.5,
+            back_emissivity: 0.5,
+            front_lambertian: 0.0,
+            back_lambertian: 0.0,
+        };
+        let a_mirror = SolarSail {
+            optics: mirror,
+            ..ideal
+        }
+        .acceleration(&state, attitude);
+        assert_relative_eq!((a_mirror - a_ideal).norm(), 0.0, epsilon = 1e-18);
+    }
+
+    #[test]
+    fn sun_facing_sail_lightens_gravity() {
+        // A circular orbit at the reduced gravity μ(1 − β) stays circular
+        let a_c = 1e-3;
+        let beta = lightness_number(a_c);
+        let au = ASTRONOMICAL_UNIT.value();
+        let state = StateVector::new(
+            Vec3::new(au, 0.0, 0.0),
+            Vec3::new(0.0, sqrt(MU_SUN.value() * (1.0 - beta) / au), 0.0),
+        );
+        let sail = SolarSail {
+            characteristic_acceleration: a_c,
+            optics: SailOptics::Ideal,
+        }
+        .steered(|_, _: &StateVector| SailAttitude::SUN_FACING);
+        let model = (TwoBody { mu: MU_SUN }, sail);
+        let end = propagate(
+            &model,
+            &state,
+            Seconds::ZERO,
+            Seconds(1.0e7),
+            Seconds(3_600.0),
+        )
+        .unwrap();
+        assert_relative_eq!(end.position.norm(), au, max_relative = 1e-9);
+    }
+}
@@ -0,0 +1,339 @@
+//! Ground coverage of scanning sensors.
+//!
+//! A sensor looks out along its own frame's +z axis (the boresight).
+//! With the [`nadir_attitude`] frame, +z points at the center of the
+//! body, +y along track and +x across track toward the orbit normal.
+//! The scan pattern sweeps the look direction about that frame over
+//! time, and each look direction is traced down to the surface of a
+//! spherical, rotating body, giving ground points in the same
+//! geocentric latitude and longitude as [`crate::groundtrack`].
+//!
+//! The ephemeris and attitude are supplied as functions of time, so
+//! any propagator or attitude law can drive a scan.
+
+use libm::{cos, sin, sqrt};
+
+use crate::bodies::RotatingBody;
+use crate::groundtrack::{GroundPoint, subsatellite_point};
+use crate::state::StateVector;
+use crate::utils::{Real, Seconds, TAU};
+use crate::vectors::{Matrix3, Vec3};
+
+/// How a sensor sweeps its look direction
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScanPattern {
+    /// A line of detectors spanning ±`half_width` radians across track,
+    /// imaging the whole line at once
+    Pushbroom { half_width: Real },
+    /// A single detector swept back and forth across track through
+    /// ±`half_width` radians, completing a sweep out and back every
+    /// `period`
+    Whiskbroom { half_width: Real, period: Seconds },
+    /// A single detector held `half_angle` radians off boresight and
+    /// rotated about it once every `period`, starting `phase` radians
+    /// from the +x axis toward +y
+    Conical {
+        half_angle: Real,
+        period: Seconds,
+        phase: Real,
+    },
+}
+
+impl ScanPattern {
+    // Look direction in the sensor frame, `across` radians toward +x
+    fn across_track(across: Real) -> Vec3 {
+        Vec3::new(sin(across), 0.0, cos(across))
+    }
+}
+
+/// The ground seen at one instant. A look direction that misses the
+/// body gives `None`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Footprint {
+    /// The ends of a pushbroom's detector line, on the −x and +x sides
+    Line {
+        left: Option<GroundPoint>,
+        right: Option<GroundPoint>,
+    },
+    /// The spot under a single scanning detector
+    Spot(Option<GroundPoint>),
+}
+
+/// A footprint and the time it was seen
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScanSample {
+    /// Time from the epoch of the ephemeris
+    pub time: Seconds,
+    pub footprint: Footprint,
+}
+
+/// The sensor frame for a nadir-pointing spacecraft, as the rotation
+/// from sensor to inertial axes
+pub fn nadir_attitude(state: &StateVector) -> Matrix3 {
+    let z = -state.position.unit();
+    let x = state.velocity.cross(z).unit();
+    let y = z.cross(x);
+    Matrix3::from_columns(x, y, z)
+}
+
+/// A scanning sensor over a spherical, rotating body
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scanner {
+    pub pattern: ScanPattern,
+    /// The body, with time zero at the epoch of the ephemeris
+    pub body: RotatingBody,
+}
+
+impl Scanner {
+    /// A sensor scanning the Earth
+    pub fn earth(pattern: ScanPattern, greenwich_angle: Real) -> Self {
+        Scanner {
+            pattern,
+            body: RotatingBody::earth(greenwich_angle),
+        }
+    }
+
+    /// What the sensor sees at `time`, from the inertial `state` with
+    /// the sensor frame rotated into inertial axes by `attitude`
+    pub fn footprint(&self, time: Seconds, state: &StateVector, attitude: &Matrix3) -> Footprint {
+        let ground = |look: Vec3| self.ground_point(time, state.position, *attitude * look);
+        match self.pattern {
+            ScanPattern::Pushbroom { half_width } => Footprint::Line {
+                left: ground(ScanPattern::across_track(-half_width)),
+                right: ground(ScanPattern::across_track(half_width)),
+            },
+            ScanPattern::Whiskbroom { half_width, period } => {
+                // Triangle wave: −w → +w over the first half period, back
+                // over the second
+                let phase = fraction(time / period);
+                let across = half_width * (1.0 - 4.0 * (phase - 0.5).abs());
+                Footprint::Spot(ground(ScanPattern::across_track(across)))
+            }
+            ScanPattern::Conical {
+                half_angle,
+                period,
+                phase,
+            } => {
+                let azimuth = phase + TAU * (time / period);
+                let look = Vec3::new(
+                    sin(half_angle) * cos(azimuth),
+                    sin(half_angle) * sin(azimuth),
+                    cos(half_angle),
+                );
+                Footprint::Spot(ground(look))
+            }
+        }
+    }
+
+    /// Footprints every `step` from `start` through `end`,
+    /// following `ephemeris` and an `attitude` law that gives the
+    /// sensor-to-inertial rotation for a time and state, such as
+    /// `|_, state| nadir_attitude(state)`
+    pub fn samples<E, A>(
+        &self,
+        ephemeris: E,
+        attitude: A,
+        start: Seconds,
+        end: Seconds,
+        step: Seconds,
+    ) -> ScanSamples<E, A>
+    where
+        E: Fn(Seconds) -> StateVector,
+        A: Fn(Seconds, &StateVector) -> Matrix3,
+    {
+        ScanSamples {
+            scanner: *self,
+            ephemeris,
+            attitude,
+            next: 0,
+            start,
+            end,
+            step,
+        }
+    }
+
+    // Where the ray from `origin` along `direction` first meets the
+    // surface, if it does
+    fn ground_point(&self, time: Seconds, origin: Vec3, direction: Vec3) -> Option<GroundPoint> {
+        let d = direction.unit();
+        let radius = self.body.equatorial_radius.value();
+        let b = origin.dot(d);
+        let c = origin.norm_squared() - radius * radius;
+        let discriminant = b * b - c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let distance = -b - sqrt(discriminant);
+        if distance <= 0.0 {
+            return None;
+        }
+        Some(subsatellite_point(
+            origin + d * distance,
+            self.body.angle(time),
+        ))
+    }
+}
+
+// Fractional part, on [0, 1)
+fn fraction(x: Real) -> Real {
+    let r = x % 1.0;
+    if r < 0.0 { r + 1.0 } else { r }
+}
+
+/// Iterator over the footprints of a scan, created by
+/// [`Scanner::samples`]
+#[derive(Copy, Clone, Debug)]
+pub struct ScanSamples<E, A> {
+    scanner: Scanner,
+    ephemeris: E,
+    attitude: A,
+    next: usize,
+    start: Seconds,
+    end: Seconds,
+    step: Seconds,
+}
+
+impl<E, A> Iterator for ScanSamples<E, A>
+where
+    E: Fn(Seconds) -> StateVector,
+    A: Fn(Seconds, &StateVector) -> Matrix3,
+{
+    type Item = ScanSample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Count steps rather than accumulate, so times do not drift
+        let time = self.start + self.step * self.next as Real;
+        if self.step <= Seconds::ZERO || time > self.end {
+            return None;
+        }
+        self.next += 1;
+        let state = (self.ephemeris)(time);
+        let attitude = (self.attitude)(time, &state);
+        Some(ScanSample {
+            time,
+            footprint: self.scanner.footprint(time, &state, &attitude),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::groundtrack::{EARTH_RADIUS, EARTH_ROTATION_RATE, Reach, central_angle};
+    use crate::utils::Meters;
+    use approx::assert_relative_eq;
+
+    const RADIUS: Real = 7_078_137.0;
+
+    // Circular polar orbit
+    fn ephemeris(t: Seconds) -> StateVector {
+        let v = sqrt(EARTH.mu.value() / RADIUS);
+        let n = v / RADIUS;
+        let (s, c) = (sin(n * t.value()), cos(n * t.value()));
+        StateVector::new(Vec3::new(c, 0.0, s) * RADIUS, Vec3::new(-s, 0.0, c) * v)
+    }
+
+    fn nadir(t: Seconds) -> GroundPoint {
+        let angle = EARTH_ROTATION_RATE * t.value();
+        subsatellite_point(ephemeris(t).position, angle)
+    }
+
+    #[test]
+    fn nadir_attitude_axes() {
+        let state = ephemeris(Seconds::ZERO);
+        let attitude = nadir_attitude(&state);
+        assert_relative_eq!(attitude.column(2).x, -1.0);
+        assert_relative_eq!(attitude.column(1).z, 1.0);
+        // +x points along the orbit normal
+        let normal = state.position.cross(state.velocity).unit();
+        assert_relative_eq!(attitude.column(0).dot(normal), 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn pushbroom_swath_is_symmetric() {
+        let scanner = Scanner::earth(ScanPattern::Pushbroom { half_width: 0.3 }, 0.0);
+        let time = Seconds(600.0);
+        let state = ephemeris(time);
+        let footprint = scanner.footprint(time, &state, &nadir_attitude(&state));
+        let Footprint::Line { left, right } = footprint else {
+            panic!("pushbroom should see a line");
+        };
+        let (left, right) = (left.unwrap(), right.unwrap());
+        let expected = Reach::OffNadir(0.3).central_angle(Meters(RADIUS), EARTH_RADIUS);
+        let center = nadir(time);
+        assert_relative_eq!(central_angle(&center, &left), expected, epsilon = 1e-9);
+        assert_relative_eq!(central_angle(&center, &right), expected, epsilon = 1e-9);
+        assert_relative_eq!(central_angle(&left, &right), 2.0 * expected, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn whiskbroom_sweeps_back_and_forth() {
+        let pattern = ScanPattern::Whiskbroom {
+            half_width: 0.2,
+            period: Seconds(2.0),
+        };
+        let scanner = Scanner::earth(pattern, 0.0);
+        let spot = |t: Real| {
+            let t = Seconds(t);
+            let state = ephemeris(t);
+            match scanner.footprint(t, &state, &nadir_attitude(&state)) {
+                Footprint::Spot(point) => central_angle(&nadir(t), &point.unwrap()),
+                Footprint::Line { .. } => panic!("whiskbroom should see a spot"),
+            }
+        };
+        let edge = Reach::OffNadir(0.2).central_angle(Meters(RADIUS), EARTH_RADIUS);
+        assert_relative_eq!(spot(0.0), edge, epsilon = 1e-9);
+        assert_relative_eq!(spot(0.5), 0.0, epsilon = 1e-9);
+        assert_relative_eq!(spot(1.0), edge, epsilon = 1e-9);
+        assert_relative_eq!(spot(2.0), edge, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn conical_scan_keeps_its_distance() {
+        let pattern = ScanPattern::Conical {
+            half_angle: 0.4,
+            period: Seconds(60.0),
+            phase: 0.0,
+        };
+        let scanner = Scanner::earth(pattern, 0.3);
+        let expected = Reach::OffNadir(0.4).central_angle(Meters(RADIUS), EARTH_RADIUS);
+        let samples = scanner.samples(
+            ephemeris,
+            |_, state: &StateVector| nadir_attitude(state),
+            Seconds::ZERO,
+            Seconds(300.0),
+            Seconds(7.5),
+        );
+        let mut count = 0;
+        for sample in samples {
+            let Footprint::Spot(Some(point)) = sample.footprint else {
+                panic!("conical scan should stay on the ground");
+            };
+            let center = subsatellite_point(
+                ephemeris(sample.time).position,
+                0.3 + EARTH_ROTATION_RATE * sample.time.value(),
+            );
+            assert_relative_eq!(central_angle(&center, &point), expected, epsilon = 1e-9);
+            count += 1;
+        }
+        assert_eq!(count, 41);
+    }
+
+    #[test]
+    fn looking_past_the_horizon_misses() {
+        let scanner = Scanner::earth(ScanPattern::Pushbroom { half_width: 1.4 }, 0.0);
+        let state = ephemeris(Seconds::ZERO);
+        assert_eq!(
+            scanner.footprint(Seconds::ZERO, &state, &nadir_attitude(&state)),
+            Footprint::Line {
+                left: None,
+                right: None
+            }
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Stretches of time over which a condition holds.
+//!
+//! The condition is sampled at a fixed step and each change found
+//! between samples is refined by bisection, so anything that holds
+//! for less than one step can be missed. Searches with structure to
+//! exploit, such as [`crate::groundtrack::OverflightSearch`], do
+//! better with their own geometry.
+
+use crate::utils::Seconds;
+use crate::windows::Interval;
+
+/// Iterator over the spans where a condition holds, created by
+/// [`visits`]
+#[derive(Copy, Clone, Debug)]
+pub struct Visits<F> {
+    condition: F,
+    step: Seconds,
+    cursor: Seconds,
+    end: Seconds,
+}
+
+/// Spans between `start` and `end` where `condition` holds, in time
+/// order, sampled every `step` and refined to a millisecond; spans
+/// under way at either end are cut off there
+pub fn visits<F: Fn(Seconds) -> bool>(
+    condition: F,
+    start: Seconds,
+    end: Seconds,
+    step: Seconds,
+) -> Visits<F> {
+    Visits {
+        condition,
+        step,
+        cursor: start,
+        end,
+    }
+}
+
+impl<F: Fn(Seconds) -> bool> Visits<F> {
+    // First time after `t` the condition differs from `state`, or
+    // `end` if it never does
+    fn next_change(&self, mut t: Seconds, state: bool) -> Seconds {
+        while t < self.end {
+            let next = (t + self.step).min(self.end);
+            if (self.condition)(next) != state {
+                let (mut a, mut b) = (t, next);
+                while b - a > Seconds(1e-3) {
+                    let mid = a + (b - a) / 2.0;
+                    if (self.condition)(mid) == state {
+                        a = mid;
+                    } else {
+                        b = mid;
+                    }
+                }
+                return b;
+            }
+            t = next;
+        }
+        self.end
+    }
+}
+
+impl<F: Fn(Seconds) -> bool> Iterator for Visits<F> {
+    type Item = Interval;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step <= Seconds::ZERO || self.cursor >= self.end {
+            return None;
+        }
+        let entry = if (self.condition)(self.cursor) {
+            self.cursor
+        } else {
+            self.next_change(self.cursor, false)
+        };
+        if entry >= self.end {
+            self.cursor = self.end;
+            return None;
+        }
+        let exit = self.next_change(entry, true);
+        self.cursor = exit;
+        Some(Interval::new(entry, exit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use libm::sin;
+
+    #[test]
+    fn finds_positive_half_cycles() {
+        let mut spans = visits(
+            |t| sin(t.value()) > 0.0,
+            Seconds(-1.0),
+            Seconds(9.0),
+            Seconds(0.5),
+        );
+        let first = spans.next().unwrap();
+        assert_relative_eq!(first.start.value(), 0.0, epsilon = 1e-3);
+        assert_relative_eq!(first.end.value(), core::f64::consts::PI, epsilon = 1e-3);
+        let second = spans.next().unwrap();
+        assert_relative_eq!(
+            second.start.value(),
+            2.0 * core::f64::consts::PI,
+            epsilon = 1e-3
+        );
+        // Still under way at the end
+        assert_eq!(second.end, Seconds(9.0));
+        assert!(spans.next().is_none());
+    }
+
+    #[test]
+    fn spans_already_under_way_start_at_the_beginning() {
+        let first = visits(
+            |t| t < Seconds(2.0),
+            Seconds(0.0),
+            Seconds(5.0),
+            Seconds(1.0),
+        )
+        .next()
+        .unwrap();
+        assert_eq!(first.start, Seconds(0.0));
+        assert_relative_eq!(first.end.value(), 2.0, epsilon = 1e-3);
+    }
+}
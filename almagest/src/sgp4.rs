@@ -0,0 +1,613 @@
+//! SGP4 propagation of two-line element sets.
+//!
+//! TLEs hold Kozai mean elements fitted to the SGP4 theory, so they
+//! only give good positions through the same theory: the secular and
+//! long-period effects of J2, J3 and J4 and a power-law drag model
+//! scaled by B*. This is the near-Earth branch as revised by Vallado,
+//! Crawford, Hujsak and Kelso (2006), with the WGS-72 constants the
+//! element sets are fitted with. Orbits with periods of 225 minutes
+//! or more need the deep-space (SDP4) terms, which are not
+//! implemented, and are rejected.
+//!
+//! States come out in the TEME frame the theory is built in.
+//!
+//! ```
+//! use almagest::sgp4::{Sgp4, Tle};
+//! use almagest::utils::Seconds;
+//!
+//! let tle = Tle::parse(
+//!     "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753",
+//!     "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667",
+//! )
+//! .unwrap();
+//! let sgp4 = Sgp4::new(&tle).unwrap();
+//! let state = sgp4.propagate(Seconds(3_600.0)).unwrap();
+//! assert!(state.position().norm() > 6_378_135.0);
+//! ```
+
+use libm::{atan2, cos, fabs, floor, pow, sin, sqrt};
+
+use crate::error::AlmagestError;
+use crate::frames::{State, Teme};
+use crate::kepler::wrap_two_pi;
+use crate::state::StateVector;
+use crate::utils::{Eccentricity, Radians, RadiansPerSecond, Real, SECONDS_PER_DAY, Seconds, TAU};
+use crate::vectors::Vec3;
+
+// WGS-72 constants, in the Earth radii and minutes SGP4 works in
+const RADIUS_KM: Real = 6_378.135;
+const MU_KM: Real = 398_600.8;
+const J2: Real = 0.001_082_616;
+const J3: Real = -0.000_002_538_81;
+const J4: Real = -0.000_001_655_97;
+const J3OJ2: Real = J3 / J2;
+
+// √(μ/R³) in 1/min
+fn xke() -> Real {
+    60.0 / sqrt(RADIUS_KM * RADIUS_KM * RADIUS_KM / MU_KM)
+}
+
+/// Mean elements of a two-line element set, with angles in radians
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tle {
+    pub catalog_number: u32,
+    /// Julian date of the elements, UTC
+    pub epoch: Real,
+    /// Drag term B*, in inverse Earth radii
+    pub bstar: Real,
+    pub inclination: Radians,
+    /// Right ascension of the ascending node, Ω
+    pub raan: Radians,
+    pub eccentricity: Eccentricity,
+    /// Argument of perigee, ω
+    pub argument_of_perigee: Radians,
+    pub mean_anomaly: Radians,
+    /// Kozai mean motion
+    pub mean_motion: RadiansPerSecond,
+}
+
+impl Tle {
+    /// Parse the two 69-column lines of an element set, checking the
+    /// line numbers, the checksums and that both lines name the same
+    /// satellite
+    pub fn parse(line1: &str, line2: &str) -> Result<Self, AlmagestError> {
+        let (line1, line2) = (line1.trim_end(), line2.trim_end());
+        if !line1.is_ascii() || !line2.is_ascii() || line1.len() != 69 || line2.len() != 69 {
+            return Err(AlmagestError::Parse("TLE lines must be 69 ASCII columns"));
+        }
+        if !line1.starts_with("1 ") || !line2.starts_with("2 ") {
+            return Err(AlmagestError::Parse("TLE lines must be numbered 1 and 2"));
+        }
+        if !checksum_matches(line1) || !checksum_matches(line2) {
+            return Err(AlmagestError::Parse("TLE checksum does not match"));
+        }
+        let catalog_number = field(line1, 2, 7)?;
+        if field::<u32>(line2, 2, 7)? != catalog_number {
+            return Err(AlmagestError::Parse(
+                "TLE lines are for different satellites",
+            ));
+        }
+
+        let year: i32 = field(line1, 18, 20)?;
+        let year = if year < 57 { 2000 + year } else { 1900 + year };
+        let day: Real = field(line1, 20, 32)?;
+        let degrees = |start, end| field(line2, start, end).map(|d: Real| Radians(d.to_radians()));
+        let eccentricity: Real = field(line2, 26, 33)?;
+        let revolutions_per_day: Real = field(line2, 52, 63)?;
+        if !revolutions_per_day.is_finite() {
+            return Err(AlmagestError::Parse("Malformed TLE field"));
+        }
+        Ok(Tle {
+            catalog_number,
+            epoch: julian_date_of_january_first(year) + day - 1.0,
+            bstar: exponential(&line1[53..61])?,
+            inclination: degrees(8, 16)?,
+            raan: degrees(17, 25)?,
+            eccentricity: Eccentricity::new(eccentricity * 1e-7)?,
+            argument_of_perigee: degrees(34, 42)?,
+            mean_anomaly: degrees(43, 51)?,
+            mean_motion: RadiansPerSecond(revolutions_per_day * TAU / SECONDS_PER_DAY),
+        })
+    }
+}
+
+// Sum of the digits, minus signs counting one, modulo ten
+fn checksum_matches(line: &str) -> bool {
+    let (body, check) = line.as_bytes().split_at(68);
+    let sum: u32 = body
+        .iter()
+        .map(|&c| match c {
+            b'0'..=b'9' => (c - b'0') as u32,
+            b'-' => 1,
+            _ => 0,
+        })
+        .sum();
+    check[0].is_ascii_digit() && sum % 10 == (check[0] - b'0') as u32
+}
+
+fn field<T: core::str::FromStr>(line: &str, start: usize, end: usize) -> Result<T, AlmagestError> {
+    line[start..end]
+        .trim()
+        .parse()
+        .map_err(|_| AlmagestError::Parse("Malformed TLE field"))
+}
+
+// A TLE number with an assumed leading decimal point and a one-digit
+// exponent, " 12345-3" for 0.12345e-3
+fn exponential(text: &str) -> Result<Real, AlmagestError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(0.0);
+    }
+    if text.len() < 2 {
+        return Err(AlmagestError::Parse("Malformed TLE field"));
+    }
+    let (mantissa, exponent) = text.split_at(text.len() - 2);
+    let (sign, digits) = match mantissa.strip_prefix('-') {
+        Some(digits) => (-1.0, digits),
+        None => (1.0, mantissa.trim_start_matches('+')),
+    };
+    let digits: Real = digits
+        .parse()
+        .ok()
+        .filter(|d: &Real| d.is_finite())
+        .ok_or(AlmagestError::Parse("Malformed TLE field"))?;
+    let exponent: i32 = exponent
+        .parse()
+        .map_err(|_| AlmagestError::Parse("Malformed TLE field"))?;
+    let scale = pow(
+        10.0,
+        -(mantissa.trim_start_matches(['-', '+']).len() as Real),
+    );
+    Ok(sign * digits * scale * pow(10.0, exponent as Real))
+}
+
+// Julian date at midnight starting January 1 (Vallado, Algorithm 14)
+fn julian_date_of_january_first(year: i32) -> Real {
+    let y = year as Real;
+    367.0 * y - floor(7.0 * y / 4.0) + floor(275.0 / 9.0) + 1.0 + 1_721_013.5
+}
+
+/// A TLE initialized for SGP4, ready to propagate
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sgp4 {
+    epoch: Real,
+    bstar: Real,
+    inclination: Real,
+    raan: Real,
+    eccentricity: Real,
+    argument_of_perigee: Real,
+    mean_anomaly: Real,
+    // Un-Kozai'd (Brouwer) mean motion, rad/min
+    mean_motion: Real,
+    // Perigee under 220 km: drop the higher-order drag terms
+    simple: bool,
+    aycof: Real,
+    con41: Real,
+    cc1: Real,
+    cc4: Real,
+    cc5: Real,
+    d2: Real,
+    d3: Real,
+    d4: Real,
+    delmo: Real,
+    eta: Real,
+    argpdot: Real,
+    omgcof: Real,
+    sinmao: Real,
+    t2cof: Real,
+    t3cof: Real,
+    t4cof: Real,
+    t5cof: Real,
+    x1mth2: Real,
+    x7thm1: Real,
+    mdot: Real,
+    nodedot: Real,
+    xlcof: Real,
+    xmcof: Real,
+    nodecf: Real,
+}
+
+impl Sgp4 {
+    /// Initialize the theory for `tle`. Deep-space orbits, with
+    /// periods of 225 minutes or more, are rejected.
+    pub fn new(tle: &Tle) -> Result<Self, AlmagestError> {
+        let xke = xke();
+        let ecco = tle.eccentricity.value();
+        let inclo = tle.inclination.value();
+        let argpo = tle.argument_of_perigee.value();
+        let mo = tle.mean_anomaly.value();
+        let bstar = tle.bstar;
+        let no_kozai = tle.mean_motion.value() * 60.0;
+        if !no_kozai.is_finite() || no_kozai <= 0.0 {
+            return Err(AlmagestError::InvalidInput("Mean motion must be positive"));
+        }
+
+        // Recover the Brouwer mean motion and semi-major axis
+        let eccsq = ecco * ecco;
+        let omeosq = 1.0 - eccsq;
+        let rteosq = sqrt(omeosq);
+        let (sinio, cosio) = (sin(inclo), cos(inclo));
+        let cosio2 = cosio * cosio;
+        let ak = pow(xke / no_kozai, 2.0 / 3.0);
+        let d1 = 0.75 * J2 * (3.0 * cosio2 - 1.0) / (rteosq * omeosq);
+        let del = d1 / (ak * ak);
+        let adel = ak * (1.0 - del * del - del * (1.0 / 3.0 + 134.0 * del * del / 81.0));
+        let del = d1 / (adel * adel);
+        let no = no_kozai / (1.0 + del);
+        if TAU / no >= 225.0 {
+            return Err(AlmagestError::InvalidInput(
+                "Deep-space orbits need SDP4, which is not implemented",
+            ));
+        }
+        let ao = pow(xke / no, 2.0 / 3.0);
+        let po = ao * omeosq;
+        let con42 = 1.0 - 5.0 * cosio2;
+        let con41 = -con42 - cosio2 - cosio2;
+        let posq = po * po;
+        let rp = ao * (1.0 - ecco);
+        if rp < 1.0 {
+            return Err(AlmagestError::InvalidInput(
+                "Perigee lies below the surface",
+            ));
+        }
+
+        // Density function parameters, lowered for perigees below 156 km
+        let simple = rp < 220.0 / RADIUS_KM + 1.0;
+        let mut sfour = 78.0 / RADIUS_KM + 1.0;
+        let mut qzms24 = pow((120.0 - 78.0) / RADIUS_KM, 4.0);
+        let perigee = (rp - 1.0) * RADIUS_KM;
+        if perigee < 156.0 {
+            let s = if perigee < 98.0 { 20.0 } else { perigee - 78.0 };
+            qzms24 = pow((120.0 - s) / RADIUS_KM, 4.0);
+            sfour = s / RADIUS_KM + 1.0;
+        }
+
+        let pinvsq = 1.0 / posq;
+        let tsi = 1.0 / (ao - sfour);
+        let eta = ao * ecco * tsi;
+        let etasq = eta * eta;
+        let eeta = ecco * eta;
+        let psisq = fabs(1.0 - etasq);
+        let coef = qzms24 * pow(tsi, 4.0);
+        let coef1 = coef / pow(psisq, 3.5);
+        let cc2 = coef1
+            * no
+            * (ao * (1.0 + 1.5 * etasq + eeta * (4.0 + etasq))
+                + 0.375 * J2 * tsi / psisq * con41 * (8.0 + 3.0 * etasq * (8.0 + etasq)));
+        let cc1 = bstar * cc2;
+        let cc3 = if ecco > 1e-4 {
+            -2.0 * coef * tsi * J3OJ2 * no * sinio / ecco
+        } else {
+            0.0
+        };
+        let x1mth2 = 1.0 - cosio2;
+        let cc4 = 2.0
+            * no
+            * coef1
+            * ao
+            * omeosq
+            * (eta * (2.0 + 0.5 * etasq) + ecco * (0.5 + 2.0 * etasq)
+                - J2 * tsi / (ao * psisq)
+                    * (-3.0 * con41 * (1.0 - 2.0 * eeta + etasq * (1.5 - 0.5 * eeta))
+                        + 0.75 * x1mth2 * (2.0 * etasq - eeta * (1.0 + etasq)) * cos(2.0 * argpo)));
+        let cc5 = 2.0 * coef1 * ao * omeosq * (1.0 + 2.75 * (etasq + eeta) + eeta * etasq);
+
+        // Secular rates from J2 and J4
+        let cosio4 = cosio2 * cosio2;
+        let temp1 = 1.5 * J2 * pinvsq * no;
+        let temp2 = 0.5 * temp1 * J2 * pinvsq;
+        let temp3 = -0.468_75 * J4 * pinvsq * pinvsq * no;
+        let mdot = no
+            + 0.5 * temp1 * rteosq * con41
+            + 0.0625 * temp2 * rteosq * (13.0 - 78.0 * cosio2 + 137.0 * cosio4);
+        let argpdot = -0.5 * temp1 * con42
+            + 0.0625 * temp2 * (7.0 - 114.0 * cosio2 + 395.0 * cosio4)
+            + temp3 * (3.0 - 36.0 * cosio2 + 49.0 * cosio4);
+        let xhdot1 = -temp1 * cosio;
+        let nodedot = xhdot1
+            + (0.5 * temp2 * (4.0 - 19.0 * cosio2) + 2.0 * temp3 * (3.0 - 7.0 * cosio2)) * cosio;
+
+        // Guard the J3 long-period term against division by zero at
+        // i = 180°
+        let xlcof_denominator = if fabs(cosio + 1.0) > 1.5e-12 {
+            1.0 + cosio
+        } else {
+            1.5e-12
+        };
+        let mut sgp4 = Sgp4 {
+            epoch: tle.epoch,
+            bstar,
+            inclination: inclo,
+            raan: tle.raan.value(),
+            eccentricity: ecco,
+            argument_of_perigee: argpo,
+            mean_anomaly: mo,
+            mean_motion: no,
+            simple,
+            aycof: -0.5 * J3OJ2 * sinio,
+            con41,
+            cc1,
+            cc4,
+            cc5,
+            d2: 0.0,
+            d3: 0.0,
+            d4: 0.0,
+            delmo: pow(1.0 + eta * cos(mo), 3.0),
+            eta,
+            argpdot,
+            omgcof: bstar * cc3 * cos(argpo),
+            sinmao: sin(mo),
+            t2cof: 1.5 * cc1,
+            t3cof: 0.0,
+            t4cof: 0.0,
+            t5cof: 0.0,
+            x1mth2,
+            x7thm1: 7.0 * cosio2 - 1.0,
+            mdot,
+            nodedot,
+            xlcof: -0.25 * J3OJ2 * sinio * (3.0 + 5.0 * cosio) / xlcof_denominator,
+            xmcof: if ecco > 1e-4 {
+                -2.0 / 3.0 * coef * bstar / eeta
+            } else {
+                0.0
+            },
+            nodecf: 3.5 * omeosq * xhdot1 * cc1,
+        };
+        if !simple {
+            let cc1sq = cc1 * cc1;
+            let d2 = 4.0 * ao * tsi * cc1sq;
+            let temp = d2 * tsi * cc1 / 3.0;
+            let d3 = (17.0 * ao + sfour) * temp;
+            let d4 = 0.5 * temp * ao * tsi * (221.0 * ao + 31.0 * sfour) * cc1;
+            sgp4.d2 = d2;
+            sgp4.d3 = d3;
+            sgp4.d4 = d4;
+            sgp4.t3cof = d2 + 2.0 * cc1sq;
+            sgp4.t4cof = 0.25 * (3.0 * d3 + cc1 * (12.0 * d2 + 10.0 * cc1sq));
+            sgp4.t5cof = 0.2
+                * (3.0 * d4 + 12.0 * cc1 * d3 + 6.0 * d2 * d2 + 15.0 * cc1sq * (2.0 * d2 + cc1sq));
+        }
+        Ok(sgp4)
+    }
+
+    /// Julian date of the element set
+    pub fn epoch(&self) -> Real {
+        self.epoch
+    }
+
+    /// The TEME state `since_epoch` after the element set. Fails once
+    /// drag has decayed the orbit or driven the eccentricity out of
+    /// range.
+    pub fn propagate(&self, since_epoch: Seconds) -> Result<State<Teme>, AlmagestError> {
+        let xke = xke();
+        let t = since_epoch.value() / 60.0;
+
+        // Secular gravity and drag
+        let xmdf = self.mean_anomaly + self.mdot * t;
+        let argpdf = self.argument_of_perigee + self.argpdot * t;
+        let nodedf = self.raan + self.nodedot * t;
+        let t2 = t * t;
+        let nodem = nodedf + self.nodecf * t2;
+        let mut argpm = argpdf;
+        let mut mm = xmdf;
+        let mut tempa = 1.0 - self.cc1 * t;
+        let mut tempe = self.bstar * self.cc4 * t;
+        let mut templ = self.t2cof * t2;
+        if !self.simple {
+            let delomg = self.omgcof * t;
+            let delm = self.xmcof * (pow(1.0 + self.eta * cos(xmdf), 3.0) - self.delmo);
+            mm = xmdf + delomg + delm;
+            argpm = argpdf - delomg - delm;
+            let t3 = t2 * t;
+            let t4 = t3 * t;
+            tempa -= self.d2 * t2 + self.d3 * t3 + self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (sin(mm) - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+        let am = pow(xke / self.mean_motion, 2.0 / 3.0) * tempa * tempa;
+        let nm = xke / pow(am, 1.5);
+        let em = self.eccentricity - tempe;
+        if !(-0.001..1.0).contains(&em) {
+            return Err(AlmagestError::InvalidEccentricity(
+                "Drag has driven the eccentricity out of range",
+            ));
+        }
+        let em = em.max(1e-6);
+        mm += self.mean_motion * templ;
+        let xlm = mm + argpm + nodem;
+        let nodem = wrap_two_pi(nodem);
+        let argpm = wrap_two_pi(argpm);
+        let mm = wrap_two_pi(xlm - argpm - nodem);
+        let (sinim, cosim) = (sin(self.inclination), cos(self.inclination));
+
+        // Long-period terms from J3
+        let axnl = em * cos(argpm);
+        let temp = 1.0 / (am * (1.0 - em * em));
+        let aynl = em * sin(argpm) + temp * self.aycof;
+        let xl = mm + argpm + nodem + temp * self.xlcof * axnl;
+
+        // Kepler's equation in the equinoctial-like variables
+        let u = wrap_two_pi(xl - nodem);
+        let mut eo1 = u;
+        let (mut sineo1, mut coseo1) = (sin(eo1), cos(eo1));
+        for _ in 0..10 {
+            sineo1 = sin(eo1);
+            coseo1 = cos(eo1);
+            let step =
+                (u - aynl * coseo1 + axnl * sineo1 - eo1) / (1.0 - coseo1 * axnl - sineo1 * aynl);
+            eo1 += step.clamp(-0.95, 0.95);
+            if fabs(step) < 1e-12 {
+                break;
+            }
+        }
+
+        // Short-period terms from J2
+        let ecose = axnl * coseo1 + aynl * sineo1;
+        let esine = axnl * sineo1 - aynl * coseo1;
+        let el2 = axnl * axnl + aynl * aynl;
+        let pl = am * (1.0 - el2);
+        if pl < 0.0 {
+            return Err(AlmagestError::InvalidEccentricity(
+                "Semi-latus rectum has gone negative",
+            ));
+        }
+        let rl = am * (1.0 - ecose);
+        let rdotl = sqrt(am) * esine / rl;
+        let rvdotl = sqrt(pl) / rl;
+        let betal = sqrt(1.0 - el2);
+        let temp = esine / (1.0 + betal);
+        let sinu = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu = am / rl * (coseo1 - axnl + aynl * temp);
+        let su = atan2(sinu, cosu);
+        let sin2u = (cosu + cosu) * sinu;
+        let cos2u = 1.0 - 2.0 * sinu * sinu;
+        let temp = 1.0 / pl;
+        let temp1 = 0.5 * J2 * temp;
+        let temp2 = temp1 * temp;
+
+        let mrt = rl * (1.0 - 1.5 * temp2 * betal * self.con41) + 0.5 * temp1 * self.x1mth2 * cos2u;
+        if mrt < 1.0 {
+            return Err(AlmagestError::InvalidInput("Orbit has decayed"));
+        }
+        let su = su - 0.25 * temp2 * self.x7thm1 * sin2u;
+        let xnode = nodem + 1.5 * temp2 * cosim * sin2u;
+        let xinc = self.inclination + 1.5 * temp2 * cosim * sinim * cos2u;
+        let mvt = rdotl - nm * temp1 * self.x1mth2 * sin2u / xke;
+        let rvdot = rvdotl + nm * temp1 * (self.x1mth2 * cos2u + 1.5 * self.con41) / xke;
+
+        // Orient in TEME and scale to meters
+        let (sinsu, cossu) = (sin(su), cos(su));
+        let (snod, cnod) = (sin(xnode), cos(xnode));
+        let (sini, cosi) = (sin(xinc), cos(xinc));
+        let (xmx, xmy) = (-snod * cosi, cnod * cosi);
+        let u = Vec3::new(
+            xmx * sinsu + cnod * cossu,
+            xmy * sinsu + snod * cossu,
+            sini * sinsu,
+        );
+        let v = Vec3::new(
+            xmx * cossu - cnod * sinsu,
+            xmy * cossu - snod * sinsu,
+            sini * cossu,
+        );
+        let radius = RADIUS_KM * 1_000.0;
+        let speed = radius * xke / 60.0;
+        Ok(State::new(StateVector::new(
+            u * (mrt * radius),
+            (u * mvt + v * rvdot) * speed,
+        )))
+    }
+
+    /// The TEME state at `julian_date`
+    pub fn state_at(&self, julian_date: Real) -> Result<State<Teme>, AlmagestError> {
+        self.propagate(Seconds((julian_date - self.epoch) * SECONDS_PER_DAY))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use approx::assert_relative_eq;
+    use std::format;
+    use std::string::String;
+
+    // Vallado's verification case for satellite 5, from "Revisiting
+    // Spacetrack Report #3"
+    const LINE1: &str = "1 00005U 58002B   00179.78495062  .00000023  00000-0  28098-4 0  4753";
+    const LINE2: &str = "2 00005  34.2682 348.7242 1859667 331.7664  19.3264 10.82419157413667";
+
+    #[test]
+    fn parses_the_element_set() {
+        let tle = Tle::parse(LINE1, LINE2).unwrap();
+        assert_eq!(tle.catalog_number, 5);
+        assert_relative_eq!(tle.epoch, 2_451_723.284_950_62, epsilon = 1e-8);
+        assert_relative_eq!(tle.bstar, 2.8098e-5, max_relative = 1e-12);
+        assert_relative_eq!(tle.inclination.to_degrees().value(), 34.2682);
+        assert_relative_eq!(tle.eccentricity.value(), 0.185_966_7);
+        assert_relative_eq!(
+            tle.mean_motion.value() * SECONDS_PER_DAY / TAU,
+            10.824_191_57
+        );
+
+        let corrupt = LINE2.replace("34.2682", "34.2683");
+        assert!(Tle::parse(LINE1, &corrupt).is_err());
+        assert!(Tle::parse(LINE2, LINE1).is_err());
+        assert!(Tle::parse(LINE1, &LINE2[..60]).is_err());
+    }
+
+    // `line` with its last column replaced by the right checksum
+    fn checksummed(line: &str) -> String {
+        let sum: u32 = line[..68]
+            .bytes()
+            .map(|c| match c {
+                b'0'..=b'9' => (c - b'0') as u32,
+                b'-' => 1,
+                _ => 0,
+            })
+            .sum();
+        format!("{}{}", &line[..68], sum % 10)
+    }
+
+    #[test]
+    fn rejects_malformed_fields() {
+        assert_eq!(checksummed(LINE1), LINE1);
+        for bstar in ["       5", "     nan", "  12345x"] {
+            let line1 = checksummed(&format!("{}{}{}", &LINE1[..53], bstar, &LINE1[61..]));
+            assert!(matches!(
+                Tle::parse(&line1, LINE2),
+                Err(AlmagestError::Parse(_))
+            ));
+        }
+        let line2 = checksummed(&format!("{}{:>11}{}", &LINE2[..52], "nan", &LINE2[63..]));
+        assert!(matches!(
+            Tle::parse(LINE1, &line2),
+            Err(AlmagestError::Parse(_))
+        ));
+
+        let tle = Tle {
+            mean_motion: RadiansPerSecond(Real::NAN),
+            ..Tle::parse(LINE1, LINE2).unwrap()
+        };
+        assert!(Sgp4::new(&tle).is_err());
+    }
+
+    #[test]
+    fn matches_the_verification_states() {
+        let sgp4 = Sgp4::new(&Tle::parse(LINE1, LINE2).unwrap()).unwrap();
+        // km and km/s from the reference implementation
+        for (minutes, r, v) in [
+            (
+                0.0,
+                [7_022.465_292_66, -1_400.082_967_55, 0.039_951_55],
+                [1.893_841_015, 6.405_893_759, 4.534_807_250],
+            ),
+            (
+                360.0,
+                [-7_154.031_202_02, -3_783.176_825_04, -3_536.194_122_94],
+                [4.741_887_409, -4.151_817_765, -2.093_935_425],
+            ),
+        ] {
+            let state = sgp4.propagate(Seconds(minutes * 60.0)).unwrap();
+            let expected = Vec3::new(r[0], r[1], r[2]) * 1_000.0;
+            assert_relative_eq!((state.position() - expected).norm(), 0.0, epsilon = 1e-3);
+            let expected = Vec3::new(v[0], v[1], v[2]) * 1_000.0;
+            assert_relative_eq!((state.velocity() - expected).norm(), 0.0, epsilon = 1e-5);
+        }
+        let at_epoch = sgp4.state_at(sgp4.epoch()).unwrap();
+        assert_eq!(at_epoch, sgp4.propagate(Seconds::ZERO).unwrap());
+    }
+
+    #[test]
+    fn rejects_deep_space_orbits() {
+        // A geostationary element set
+        let tle = Tle {
+            mean_motion: RadiansPerSecond(TAU / 86_164.0),
+            ..Tle::parse(LINE1, LINE2).unwrap()
+        };
+        assert!(Sgp4::new(&tle).is_err());
+    }
+}
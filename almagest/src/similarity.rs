@@ -0,0 +1,175 @@
+//! The Tisserand parameter and distances between orbits in element
+//! space.
+//!
+//! The Tisserand parameter with respect to a perturbing planet is
+//! nearly conserved through encounters with it, so a comet keeps its
+//! value across a close approach to Jupiter that scrambles its
+//! elements. The D-criteria measure how alike two orbits are, for
+//! linking meteor streams to parent bodies or telling whether an
+//! uncorrelated track is a catalog object after a maneuver: values
+//! below a few tenths suggest a common origin.
+//!
+//! Inclinations and nodes are taken in whatever plane the elements
+//! use: the perturber's orbit plane for the Tisserand parameter, any
+//! common plane for the D-criteria.
+
+use libm::{acos, asin, atan2, cos, sin, sqrt};
+
+use crate::kepler::KeplerianElements;
+use crate::utils::{Meters, PI, Real};
+use crate::vectors::Vec3;
+
+/// T = a_P/a + 2 cos i √(a(1 − e²)/a_P) with respect to a perturber
+/// on a circular orbit of radius `perturber`; 3 for a body sharing
+/// its orbit, and below 2 for orbits Jupiter cannot have captured
+/// from the outer solar system
+pub fn tisserand_parameter(elements: &KeplerianElements, perturber: Meters) -> Real {
+    let a_p = perturber.value();
+    a_p / elements.semi_major_axis.value()
+        + 2.0 * elements.inclination.cos() * sqrt(elements.semi_latus_rectum().value() / a_p)
+}
+
+fn periapsis_radius(elements: &KeplerianElements) -> Real {
+    elements.semi_major_axis.value() * (1.0 - elements.eccentricity.value())
+}
+
+/// Southworth and Hawkins' D_SH, with periapsis distances in AU as the
+/// criterion was defined. For geocentric orbits, where the AU scale
+/// leaves periapsis all but unweighted, prefer [`drummond`].
+pub fn southworth_hawkins(a: &KeplerianElements, b: &KeplerianElements) -> Real {
+    let (ea, eb) = (a.eccentricity.value(), b.eccentricity.value());
+    let qa = Meters(periapsis_radius(a)).to_au().value();
+    let qb = Meters(periapsis_radius(b)).to_au().value();
+    let (ia, ib) = (a.inclination.value(), b.inclination.value());
+    // Taking ΔΩ on [−π, π) flips the mutual node's side past 180°
+    let node = (b.raan - a.raan).signed().value();
+
+    // sin²(I/2) for the angle I between the orbit planes
+    let half_mutual = sin((ib - ia) / 2.0) * sin((ib - ia) / 2.0)
+        + sin(ia) * sin(ib) * sin(node / 2.0) * sin(node / 2.0);
+    let cos_half_mutual = sqrt(1.0 - half_mutual);
+    // Difference of the periapsis longitudes from the mutual node
+    let along = if cos_half_mutual > 0.0 {
+        let ratio = cos((ib + ia) / 2.0) * sin(node / 2.0) / cos_half_mutual;
+        asin(ratio.clamp(-1.0, 1.0))
+    } else {
+        0.0
+    };
+    let periapsis = (b.argument_of_periapsis - a.argument_of_periapsis).value() + 2.0 * along;
+    let chord = 2.0 * sin(periapsis / 2.0);
+    let mean_e = (ea + eb) / 2.0;
+
+    sqrt(
+        (eb - ea) * (eb - ea)
+            + (qb - qa) * (qb - qa)
+            + 4.0 * half_mutual
+            + mean_e * mean_e * chord * chord,
+    )
+}
+
+/// Drummond's D_D, which weights eccentricity and periapsis distance
+/// by their sums, so it is the same at any scale, and uses the angles
+/// between the orbit planes and between the lines of apsides
+pub fn drummond(a: &KeplerianElements, b: &KeplerianElements) -> Real {
+    let (ea, eb) = (a.eccentricity.value(), b.eccentricity.value());
+    let (qa, qb) = (periapsis_radius(a), periapsis_radius(b));
+    let ratio = |x: Real, y: Real| if x + y == 0.0 { 0.0 } else { (y - x) / (y + x) };
+    let angle = |u: Vec3, v: Vec3| atan2(u.cross(v).norm(), u.dot(v));
+
+    let mutual = angle(a.orbit_normal(), b.orbit_normal()) / PI;
+    let apsides = angle(a.perifocal_axes().0, b.perifocal_axes().0) / PI;
+    let mean_e = (ea + eb) / 2.0;
+    let de = ratio(ea, eb);
+    let dq = ratio(qa, qb);
+
+    sqrt(de * de + dq * dq + mutual * mutual + mean_e * mean_e * apsides * apsides)
+}
+
+/// Angle between the orbit planes of `a` and `b`, in [0, π]
+pub fn mutual_inclination(a: &KeplerianElements, b: &KeplerianElements) -> Real {
+    acos(a.orbit_normal().dot(b.orbit_normal()).clamp(-1.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::SUN;
+    use crate::state::StateVector;
+    use crate::utils::{Eccentricity, METERS_PER_AU, Radians};
+    use approx::assert_relative_eq;
+
+    fn orbit(a: Real, e: Real, i: Real, raan: Real, argp: Real) -> KeplerianElements {
+        KeplerianElements {
+            semi_major_axis: Meters(a),
+            eccentricity: Eccentricity::new(e).unwrap(),
+            inclination: Radians(i),
+            raan: Radians(raan),
+            argument_of_periapsis: Radians(argp),
+            true_anomaly: Radians(0.0),
+        }
+    }
+
+    #[test]
+    fn tisserand_of_a_jupiter_family_comet() {
+        let jupiter = Meters(5.2026 * METERS_PER_AU);
+        // 67P/Churyumov-Gerasimenko
+        let comet = orbit(
+            3.463 * METERS_PER_AU,
+            0.641,
+            7.04_f64.to_radians(),
+            0.0,
+            0.0,
+        );
+        assert_relative_eq!(tisserand_parameter(&comet, jupiter), 2.745, epsilon = 1e-3);
+        let trojan = orbit(jupiter.value(), 0.0, 0.0, 0.0, 0.0);
+        assert_relative_eq!(tisserand_parameter(&trojan, jupiter), 3.0);
+
+        // At the planet's distance, T = 3 − (v∞/v_P)², whatever the
+        // direction of the encounter
+        let r = jupiter.value();
+        let v_planet = Vec3::new(0.0, sqrt(SUN.mu.value() / r), 0.0);
+        let position = Vec3::new(r, 0.0, 0.0);
+        for v_infinity in [
+            Vec3::new(1_000.0, 2_000.0, 500.0),
+            Vec3::new(-3_000.0, -800.0, 0.0),
+        ] {
+            let state = StateVector::new(position, v_planet + v_infinity);
+            let elements = KeplerianElements::from_state(&state, SUN.mu).unwrap();
+            let ratio = v_infinity.norm() / v_planet.norm();
+            assert_relative_eq!(
+                tisserand_parameter(&elements, jupiter),
+                3.0 - ratio * ratio,
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn d_criteria() {
+        let a = orbit(1.2 * METERS_PER_AU, 0.6, 0.4, 1.0, 2.0);
+        assert_relative_eq!(southworth_hawkins(&a, &a), 0.0);
+        assert_relative_eq!(drummond(&a, &a), 0.0);
+
+        // Only the eccentricity differs, at a fixed periapsis
+        let q = 1.2 * 0.4 * METERS_PER_AU;
+        let b = orbit(q / 0.3, 0.7, 0.4, 1.0, 2.0);
+        assert_relative_eq!(southworth_hawkins(&a, &b), 0.1, epsilon = 1e-12);
+        assert_relative_eq!(drummond(&a, &b), 0.1 / 1.3, epsilon = 1e-12);
+
+        // In a shared plane only the line of apsides counts, however
+        // it is split between node and periapsis
+        let c = orbit(1.2 * METERS_PER_AU, 0.6, 0.0, 0.0, 3.0);
+        let d = orbit(1.2 * METERS_PER_AU, 0.6, 0.0, 2.5, 0.5);
+        assert_relative_eq!(southworth_hawkins(&c, &d), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(drummond(&c, &d), 0.0, epsilon = 1e-7);
+
+        // D_D is symmetric, and a small burn barely moves it
+        let leo = orbit(7_000_000.0, 0.001, 0.9, 0.5, 1.0);
+        let burned = orbit(7_010_000.0, 0.001, 0.9, 0.5, 1.0);
+        let polar = orbit(7_000_000.0, 0.001, 1.6, 0.5, 1.0);
+        assert_relative_eq!(drummond(&leo, &burned), drummond(&burned, &leo));
+        assert!(drummond(&leo, &burned) < 1e-3);
+        assert_relative_eq!(mutual_inclination(&leo, &polar), 0.7, epsilon = 1e-12);
+        assert_relative_eq!(drummond(&leo, &polar), 0.7 / PI, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,55 @@
+//! Stopping criteria shared by the iterative solvers, and the report
+//! of how a solve went.
+//!
+//! Each solver has a plain entry point using its own defaults and a
+//! `_with` variant taking a [`SolverConfig`] and returning a
+//! [`Convergence`] alongside the result, for batch work that wants to
+//! trade accuracy for speed:
+//!
+//! ```
+//! use almagest::kepler::solve_kepler_with;
+//! use almagest::solver::SolverConfig;
+//!
+//! let coarse = SolverConfig { tolerance: 1e-6, max_iterations: 10 };
+//! let (ecc_anomaly, report) = solve_kepler_with(1.0, 0.3, &coarse).unwrap();
+//! assert!(report.iterations <= 10);
+//! assert!(report.residual < 1e-6);
+//! # assert!((ecc_anomaly - 0.3 * libm::sin(ecc_anomaly) - 1.0).abs() < 1e-6);
+//! ```
+
+use crate::float::Float;
+use crate::utils::Real;
+
+/// When an iterative solver stops
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverConfig<T = Real> {
+    /// Converged once a step changes the unknown by less than this,
+    /// in the unknown's own units
+    pub tolerance: T,
+    /// Give up, with [`crate::error::AlmagestError::NoConvergence`],
+    /// after this many steps
+    pub max_iterations: u32,
+}
+
+/// A few dozen ulps, reached in a handful of Newton steps from any
+/// reasonable start, within 50 iterations
+impl<T: Float> Default for SolverConfig<T> {
+    fn default() -> Self {
+        SolverConfig {
+            tolerance: T::EPSILON * T::from_real(64.0),
+            max_iterations: 50,
+        }
+    }
+}
+
+/// How a converged solve went
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Convergence<T = Real> {
+    /// Steps taken
+    pub iterations: u32,
+    /// How far the solution is from satisfying its equation; each
+    /// solver documents the measure
+    pub residual: T,
+}
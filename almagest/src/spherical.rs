@@ -0,0 +1,312 @@
+//! Spherical and ADBARV state representations.
+//!
+//! Both describe a state by the position's radius and direction, and
+//! the velocity's speed, flight-path angle and azimuth in the local
+//! horizon. Azimuth is measured clockwise from north. Spherical
+//! elements are body-fixed, with geocentric latitude and longitude,
+//! and a flight-path angle above the local horizontal. ADBARV
+//! elements (right ascension, declination, azimuth, radius, speed and
+//! flight-path angle) are inertial, and by launch convention their
+//! flight-path angle is measured from the local vertical (Vallado,
+//! §2.5).
+//!
+//! [`GeodeticVelocity`] splits a body-fixed velocity along the
+//! ellipsoid's north, east and down axes instead, for ground speed,
+//! heading and climb rate as an aircraft would report them.
+
+use libm::{asin, atan2, cos, sin, sqrt};
+
+use crate::groundtrack::{body_fixed, geodetic_subsatellite_point};
+use crate::state::StateVector;
+use crate::utils::{Meters, PI, Real};
+use crate::vectors::Vec3;
+
+// Up, east and north unit vectors at a latitude and longitude
+fn local_axes(latitude: Real, longitude: Real) -> (Vec3, Vec3, Vec3) {
+    let (sl, cl) = (sin(latitude), cos(latitude));
+    let (so, co) = (sin(longitude), cos(longitude));
+    (
+        Vec3::new(cl * co, cl * so, sl),
+        Vec3::new(-so, co, 0.0),
+        Vec3::new(-sl * co, -sl * so, cl),
+    )
+}
+
+// Radius, latitude, longitude, speed, flight-path angle from the
+// horizontal and azimuth of a state
+fn decompose(state: &StateVector) -> (Real, Real, Real, Real, Real, Real) {
+    let (r, v) = (state.position, state.velocity);
+    let radius = r.norm();
+    let latitude = asin(r.z / radius);
+    let longitude = atan2(r.y, r.x);
+    let speed = v.norm();
+    let (up, east, north) = local_axes(latitude, longitude);
+    let flight_path_angle = if speed > 0.0 {
+        asin((v.dot(up) / speed).clamp(-1.0, 1.0))
+    } else {
+        0.0
+    };
+    let azimuth = atan2(v.dot(east), v.dot(north));
+    (
+        radius,
+        latitude,
+        longitude,
+        speed,
+        flight_path_angle,
+        azimuth,
+    )
+}
+
+fn compose(
+    radius: Real,
+    latitude: Real,
+    longitude: Real,
+    speed: Real,
+    flight_path_angle: Real,
+    azimuth: Real,
+) -> StateVector {
+    let (up, east, north) = local_axes(latitude, longitude);
+    let horizontal = north * cos(azimuth) + east * sin(azimuth);
+    StateVector::new(
+        up * radius,
+        (up * sin(flight_path_angle) + horizontal * cos(flight_path_angle)) * speed,
+    )
+}
+
+/// A body-fixed state in spherical elements
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spherical {
+    pub radius: Meters,
+    /// In m/s, relative to the rotating body
+    pub speed: Real,
+    /// Geocentric
+    pub latitude: Real,
+    pub longitude: Real,
+    /// Above the local horizontal
+    pub flight_path_angle: Real,
+    pub azimuth: Real,
+}
+
+impl Spherical {
+    /// From a body-fixed state
+    pub fn from_state(state: &StateVector) -> Self {
+        let (radius, latitude, longitude, speed, flight_path_angle, azimuth) = decompose(state);
+        Spherical {
+            radius: Meters(radius),
+            speed,
+            latitude,
+            longitude,
+            flight_path_angle,
+            azimuth,
+        }
+    }
+
+    /// The body-fixed state
+    pub fn to_state(&self) -> StateVector {
+        compose(
+            self.radius.value(),
+            self.latitude,
+            self.longitude,
+            self.speed,
+            self.flight_path_angle,
+            self.azimuth,
+        )
+    }
+
+    /// From an inertial state, given the angle of the prime meridian
+    /// from the inertial x axis and the body's rotation rate in rad/s
+    pub fn from_inertial(state: &StateVector, greenwich_angle: Real, rotation_rate: Real) -> Self {
+        let rotation = Vec3::Z * rotation_rate;
+        let velocity = state.velocity - rotation.cross(state.position);
+        Spherical::from_state(&StateVector::new(
+            body_fixed(state.position, greenwich_angle),
+            body_fixed(velocity, greenwich_angle),
+        ))
+    }
+
+    /// The inertial state, given the angle of the prime meridian from
+    /// the inertial x axis and the body's rotation rate in rad/s
+    pub fn to_inertial(&self, greenwich_angle: Real, rotation_rate: Real) -> StateVector {
+        let fixed = self.to_state();
+        let position = body_fixed(fixed.position, -greenwich_angle);
+        let rotation = Vec3::Z * rotation_rate;
+        StateVector::new(
+            position,
+            body_fixed(fixed.velocity, -greenwich_angle) + rotation.cross(position),
+        )
+    }
+}
+
+/// An inertial state in ADBARV elements
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Adbarv {
+    pub right_ascension: Real,
+    pub declination: Real,
+    pub azimuth: Real,
+    pub radius: Meters,
+    /// Inertial, in m/s
+    pub speed: Real,
+    /// From the local vertical
+    pub flight_path_angle: Real,
+}
+
+impl Adbarv {
+    pub fn from_state(state: &StateVector) -> Self {
+        let (radius, declination, right_ascension, speed, flight_path_angle, azimuth) =
+            decompose(state);
+        Adbarv {
+            right_ascension,
+            declination,
+            azimuth,
+            radius: Meters(radius),
+            speed,
+            flight_path_angle: PI / 2.0 - flight_path_angle,
+        }
+    }
+
+    pub fn to_state(&self) -> StateVector {
+        compose(
+            self.radius.value(),
+            self.declination,
+            self.right_ascension,
+            self.speed,
+            PI / 2.0 - self.flight_path_angle,
+            self.azimuth,
+        )
+    }
+}
+
+/// Body-fixed velocity in the geodetic north-east-down frame at the
+/// point beneath the vehicle, in m/s
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeodeticVelocity {
+    pub north: Real,
+    pub east: Real,
+    pub down: Real,
+}
+
+impl GeodeticVelocity {
+    /// From a body-fixed state over an ellipsoid with the given
+    /// equatorial radius and flattening
+    pub fn from_state(state: &StateVector, equatorial_radius: Meters, flattening: Real) -> Self {
+        let point = geodetic_subsatellite_point(state.position, 0.0, equatorial_radius, flattening);
+        let (up, east, north) = local_axes(point.latitude, point.longitude);
+        GeodeticVelocity {
+            north: state.velocity.dot(north),
+            east: state.velocity.dot(east),
+            down: -state.velocity.dot(up),
+        }
+    }
+
+    /// Horizontal speed over the ground
+    pub fn ground_speed(&self) -> Real {
+        sqrt(self.north * self.north + self.east * self.east)
+    }
+
+    /// Direction of travel clockwise from north, on [-π, π]
+    pub fn heading(&self) -> Real {
+        atan2(self.east, self.north)
+    }
+
+    /// Rate of climb along the ellipsoid normal
+    pub fn climb_rate(&self) -> Real {
+        -self.down
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::groundtrack::{EARTH_FLATTENING, EARTH_RADIUS, EARTH_ROTATION_RATE};
+    use approx::assert_relative_eq;
+
+    fn state() -> StateVector {
+        StateVector::new(
+            Vec3::new(6_524_834.0, 6_862_875.0, 6_448_296.0),
+            Vec3::new(4_901.327, 5_533.756, -1_976.341),
+        )
+    }
+
+    fn assert_same(a: &StateVector, b: &StateVector) {
+        assert_relative_eq!((a.position - b.position).norm(), 0.0, epsilon = 1e-6);
+        assert_relative_eq!((a.velocity - b.velocity).norm(), 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn round_trips() {
+        let state = state();
+        assert_same(&Adbarv::from_state(&state).to_state(), &state);
+        assert_same(&Spherical::from_state(&state).to_state(), &state);
+        let spherical = Spherical::from_inertial(&state, 1.2, EARTH_ROTATION_RATE);
+        assert_same(&spherical.to_inertial(1.2, EARTH_ROTATION_RATE), &state);
+    }
+
+    #[test]
+    fn circular_equatorial_orbit() {
+        let (r, v) = (7_000_000.0, 7_546.0);
+        let state = StateVector::new(Vec3::new(0.0, r, 0.0), Vec3::new(-v, 0.0, 0.0));
+        let adbarv = Adbarv::from_state(&state);
+        assert_relative_eq!(adbarv.right_ascension.to_degrees(), 90.0);
+        assert_relative_eq!(adbarv.declination, 0.0);
+        assert_relative_eq!(adbarv.azimuth.to_degrees(), 90.0);
+        assert_relative_eq!(adbarv.flight_path_angle.to_degrees(), 90.0);
+        assert_relative_eq!(adbarv.speed, v);
+
+        // Relative to the ground it moves slower; with the prime
+        // meridian along the inertial y axis, it is over longitude 0
+        let spherical = Spherical::from_inertial(&state, PI / 2.0, EARTH_ROTATION_RATE);
+        assert_relative_eq!(spherical.longitude, 0.0, epsilon = 1e-15);
+        assert_relative_eq!(spherical.flight_path_angle, 0.0, epsilon = 1e-15);
+        assert_relative_eq!(spherical.speed, v - EARTH_ROTATION_RATE * r, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn climbing_northward() {
+        let state = StateVector::new(
+            Vec3::new(6_400_000.0, 0.0, 0.0),
+            Vec3::new(1_000.0, 0.0, 1_000.0),
+        );
+        let spherical = Spherical::from_state(&state);
+        assert_relative_eq!(spherical.flight_path_angle.to_degrees(), 45.0);
+        assert_relative_eq!(spherical.azimuth, 0.0);
+        assert_relative_eq!(
+            Adbarv::from_state(&state).flight_path_angle.to_degrees(),
+            45.0
+        );
+    }
+
+    #[test]
+    fn geodetic_velocity_components() {
+        // Climbing north-east at 45° latitude, where geodetic and
+        // geocentric verticals differ by about 0.19°
+        let latitude = 45.0_f64.to_radians();
+        let longitude = 0.3;
+        let e2 = EARTH_FLATTENING * (2.0 - EARTH_FLATTENING);
+        let n = EARTH_RADIUS.value() / sqrt(1.0 - e2 * sin(latitude) * sin(latitude));
+        let position = Vec3::new(
+            n * cos(latitude) * cos(longitude),
+            n * cos(latitude) * sin(longitude),
+            n * (1.0 - e2) * sin(latitude),
+        );
+        let (up, east, north) = local_axes(latitude, longitude);
+        let velocity = north * 200.0 + east * 200.0 + up * 10.0;
+        let ned = GeodeticVelocity::from_state(
+            &StateVector::new(position, velocity),
+            EARTH_RADIUS,
+            EARTH_FLATTENING,
+        );
+        assert_relative_eq!(ned.north, 200.0, epsilon = 1e-6);
+        assert_relative_eq!(ned.east, 200.0, epsilon = 1e-6);
+        assert_relative_eq!(ned.climb_rate(), 10.0, epsilon = 1e-6);
+        assert_relative_eq!(ned.ground_speed(), 200.0 * sqrt(2.0), epsilon = 1e-6);
+        assert_relative_eq!(ned.heading().to_degrees(), 45.0, epsilon = 1e-6);
+
+        // The geocentric split of the same velocity leans differently
+        let geocentric = Spherical::from_state(&StateVector::new(position, velocity));
+        let vertical = geocentric.speed * sin(geocentric.flight_path_angle);
+        assert!((vertical - 10.0).abs() > 0.1);
+    }
+}
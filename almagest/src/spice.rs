@@ -0,0 +1,662 @@
+//! Reader for binary SPICE SPK kernels.
+//!
+//! Kernels are parsed straight out of a byte slice, so the reader
+//! works without `std`: load the file however the platform allows
+//! (memory map, flash, `include_bytes!`) and hand the bytes over.
+//! Chebyshev segments of type 2 (position only) and type 3
+//! (position and velocity) are supported, which covers the JPL
+//! planetary ephemerides such as DE440.
+//!
+//! Epochs are TDB seconds past J2000. States are returned in meters
+//! and meters per second, in whatever frame the segment was written
+//! in (J2000/ICRF for the JPL kernels).
+
+use crate::error::AlmagestError;
+use crate::state::StateVector;
+use crate::utils::{Real, Seconds};
+use crate::vectors::Vec3;
+
+const RECORD_LEN: usize = 1024;
+const WORD_LEN: usize = 8;
+const KM_TO_M: f64 = 1_000.0;
+// Longest center-of-motion chain followed when resolving a state,
+// e.g. Moon -> Earth-Moon barycenter -> solar system barycenter
+const MAX_CHAIN: usize = 8;
+
+type Chain = [(i32, StateVector); MAX_CHAIN];
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Endian {
+    Little,
+    Big,
+}
+
+/// A binary SPK kernel borrowed from a byte buffer
+#[derive(Copy, Clone, Debug)]
+pub struct SpkFile<'a> {
+    data: &'a [u8],
+    endian: Endian,
+    nd: usize,
+    ni: usize,
+    fward: usize,
+}
+
+/// The descriptor of one segment of an SPK kernel
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    /// NAIF ID of the body whose state the segment describes
+    pub target: i32,
+    /// NAIF ID of the body the state is measured from
+    pub center: i32,
+    /// NAIF ID of the reference frame
+    pub frame: i32,
+    /// SPK data type
+    pub data_type: i32,
+    /// First epoch covered, in TDB past J2000
+    pub start_epoch: Seconds,
+    /// Last epoch covered, in TDB past J2000
+    pub end_epoch: Seconds,
+    start_address: usize,
+    end_address: usize,
+}
+
+impl Segment {
+    /// Whether the segment covers the given epoch
+    pub fn covers(&self, et: Seconds) -> bool {
+        et >= self.start_epoch && et <= self.end_epoch
+    }
+}
+
+impl<'a> SpkFile<'a> {
+    /// Validate the file record of a DAF/SPK kernel
+    pub fn parse(data: &'a [u8]) -> Result<Self, AlmagestError> {
+        if data.len() < RECORD_LEN {
+            return Err(AlmagestError::Parse("SPK file is shorter than one record"));
+        }
+        let id = &data[0..8];
+        if id != b"DAF/SPK " && id != b"NAIF/DAF" {
+            return Err(AlmagestError::Parse("Not a DAF/SPK file"));
+        }
+        let endian = match &data[88..96] {
+            b"LTL-IEEE" => Endian::Little,
+            b"BIG-IEEE" => Endian::Big,
+            // Pre-N0050 files carry no format marker; ND is always
+            // small, so its byte order gives the file's away
+            _ => {
+                let nd = i32::from_le_bytes([data[8], data[9], data[10], data[11]]);
+                if (1..=124).contains(&nd) {
+                    Endian::Little
+                } else {
+                    Endian::Big
+                }
+            }
+        };
+        let mut file = SpkFile {
+            data,
+            endian,
+            nd: 0,
+            ni: 0,
+            fward: 0,
+        };
+        let nd = file.read_i32(8);
+        let ni = file.read_i32(12);
+        let fward = file.read_i32(76);
+        if nd != 2 || ni != 6 {
+            return Err(AlmagestError::Parse(
+                "DAF summary format does not match SPK",
+            ));
+        }
+        if fward < 1 {
+            return Err(AlmagestError::Parse("SPK file has no summary records"));
+        }
+        file.nd = nd as usize;
+        file.ni = ni as usize;
+        file.fward = fward as usize;
+        Ok(file)
+    }
+
+    /// Iterate over every segment descriptor, in file order
+    pub fn segments(&self) -> Segments<'a> {
+        Segments {
+            file: *self,
+            record: self.fward,
+            index: 0,
+            count: 0,
+            next: 0,
+        }
+    }
+
+    /// The segment providing `target` relative to `center` at `et`.
+    /// As in SPICE, later segments take precedence over earlier ones.
+    pub fn find_segment(
+        &self,
+        target: i32,
+        center: i32,
+        et: Seconds,
+    ) -> Result<Option<Segment>, AlmagestError> {
+        self.last_segment(|s| s.target == target && s.center == center && s.covers(et))
+    }
+
+    // The last segment `wanted` picks out, failing on the first
+    // descriptor that cannot be read
+    fn last_segment(
+        &self,
+        wanted: impl Fn(&Segment) -> bool,
+    ) -> Result<Option<Segment>, AlmagestError> {
+        let mut found = None;
+        for segment in self.segments() {
+            let segment = segment?;
+            if wanted(&segment) {
+                found = Some(segment);
+            }
+        }
+        Ok(found)
+    }
+
+    /// Evaluate a segment at `et`
+    pub fn evaluate(&self, segment: &Segment, et: Seconds) -> Result<StateVector, AlmagestError> {
+        if !segment.covers(et) {
+            return Err(AlmagestError::EpochOutOfRange(
+                "Epoch lies outside the segment",
+            ));
+        }
+        let et = et.value();
+        let components = match segment.data_type {
+            2 => 3,
+            3 => 6,
+            _ => return Err(AlmagestError::Parse("Unsupported SPK segment type")),
+        };
+        // Segment directory: INIT, INTLEN, RSIZE, N
+        let end = segment.end_address;
+        if end < 4 || end < segment.start_address {
+            return Err(AlmagestError::Parse("Corrupt SPK segment addresses"));
+        }
+        let init = self.read_word(end - 3)?;
+        let intlen = self.read_word(end - 2)?;
+        let rsize = self.read_word(end - 1)? as usize;
+        let n = self.read_word(end)? as usize;
+        if n == 0
+            || intlen <= 0.0
+            || rsize < 2 + components
+            || !(rsize - 2).is_multiple_of(components)
+        {
+            return Err(AlmagestError::Parse("Corrupt SPK segment directory"));
+        }
+        let ncoef = (rsize - 2) / components;
+
+        let index = libm::floor((et - init) / intlen);
+        let index = if index < 0.0 {
+            0
+        } else {
+            (index as usize).min(n - 1)
+        };
+        let record = segment.start_address + index * rsize;
+        if record + rsize - 1 > end - 4 {
+            return Err(AlmagestError::Parse("Corrupt SPK segment record"));
+        }
+        let mid = self.read_word(record)?;
+        let radius = self.read_word(record + 1)?;
+        let s = (et - mid) / radius;
+
+        let mut position = [0.0; 3];
+        let mut velocity = [0.0; 3];
+        for axis in 0..3 {
+            let coefs = record + 2 + axis * ncoef;
+            let (value, derivative) = self.chebyshev(coefs, ncoef, s)?;
+            position[axis] = value;
+            velocity[axis] = derivative / radius;
+        }
+        if components == 6 {
+            for (axis, v) in velocity.iter_mut().enumerate() {
+                let coefs = record + 2 + (3 + axis) * ncoef;
+                *v = self.chebyshev(coefs, ncoef, s)?.0;
+            }
+        }
+
+        Ok(StateVector::new(
+            Vec3::new(position[0], position[1], position[2]) * KM_TO_M,
+            Vec3::new(velocity[0], velocity[1], velocity[2]) * KM_TO_M,
+        ))
+    }
+
+    /// The state of `target` relative to `observer` at `et`,
+    /// following center-of-motion chains through the kernel
+    /// (e.g. Moon relative to Earth via the Earth-Moon barycenter)
+    pub fn state(
+        &self,
+        target: i32,
+        observer: i32,
+        et: Seconds,
+    ) -> Result<StateVector, AlmagestError> {
+        let (target_chain, target_len) = self.chain(target, et)?;
+        let (observer_chain, observer_len) = self.chain(observer, et)?;
+        for (body, observer_state) in &observer_chain[..observer_len] {
+            if let Some((_, target_state)) =
+                target_chain[..target_len].iter().find(|(b, _)| b == body)
+            {
+                return Ok(*target_state - *observer_state);
+            }
+        }
+        Err(AlmagestError::InvalidInput(
+            "No common center links target and observer",
+        ))
+    }
+
+    // The bodies reachable from `body` by following segment centers,
+    // paired with the state of `body` relative to each of them
+    fn chain(&self, body: i32, et: Seconds) -> Result<(Chain, usize), AlmagestError> {
+        let mut chain = [(body, StateVector::ZERO); MAX_CHAIN];
+        let mut len = 1;
+        let mut current = body;
+        let mut accumulated = StateVector::ZERO;
+        while len < MAX_CHAIN {
+            let segment = self.last_segment(|s| s.target == current && s.covers(et))?;
+            let Some(segment) = segment else { break };
+            accumulated = accumulated + self.evaluate(&segment, et)?;
+            current = segment.center;
+            chain[len] = (current, accumulated);
+            len += 1;
+        }
+        Ok((chain, len))
+    }
+
+    // Sum a Chebyshev series and its derivative with respect to `s`
+    fn chebyshev(&self, address: usize, n: usize, s: Real) -> Result<(Real, Real), AlmagestError> {
+        let (mut t_prev, mut t) = (1.0, s);
+        let (mut dt_prev, mut dt) = (0.0, 1.0);
+        let mut value = self.read_word(address)?;
+        let mut derivative = 0.0;
+        for k in 1..n {
+            let c = self.read_word(address + k)?;
+            value += c * t;
+            derivative += c * dt;
+            let t_next = 2.0 * s * t - t_prev;
+            let dt_next = 2.0 * t + 2.0 * s * dt - dt_prev;
+            (t_prev, t) = (t, t_next);
+            (dt_prev, dt) = (dt, dt_next);
+        }
+        Ok((value, derivative))
+    }
+
+    // Read the double precision word at a 1-based DAF address
+    fn read_word(&self, address: usize) -> Result<Real, AlmagestError> {
+        let offset = address
+            .checked_sub(1)
+            .ok_or(AlmagestError::Parse("Invalid DAF address"))?
+            * WORD_LEN;
+        self.read_f64(offset)
+            .ok_or(AlmagestError::Parse("DAF address beyond end of file"))
+    }
+
+    fn read_f64(&self, offset: usize) -> Option<Real> {
+        let bytes: [u8; 8] = self.data.get(offset..offset + 8)?.try_into().ok()?;
+        let value = match self.endian {
+            Endian::Little => f64::from_le_bytes(bytes),
+            Endian::Big => f64::from_be_bytes(bytes),
+        };
+        Some(value as Real)
+    }
+
+    fn read_i32(&self, offset: usize) -> i32 {
+        let bytes = [
+            self.data[offset],
+            self.data[offset + 1],
+            self.data[offset + 2],
+            self.data[offset + 3],
+        ];
+        match self.endian {
+            Endian::Little => i32::from_le_bytes(bytes),
+            Endian::Big => i32::from_be_bytes(bytes),
+        }
+    }
+}
+
+/// Iterator over the segment descriptors of an [`SpkFile`]
+pub struct Segments<'a> {
+    file: SpkFile<'a>,
+    // Current summary record (0 once the list is exhausted)
+    record: usize,
+    index: usize,
+    // Summaries in the current record
+    count: usize,
+    // The record after the current one
+    next: usize,
+}
+
+impl Iterator for Segments<'_> {
+    type Item = Result<Segment, AlmagestError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.record != 0 {
+            if self.index == 0
+                && let Err(error) = self.read_header()
+            {
+                self.record = 0;
+                return Some(Err(error));
+            }
+            if self.index < self.count {
+                let base = (self.record - 1) * RECORD_LEN;
+                let offset = base + (3 + self.index * self.summary_words()) * WORD_LEN;
+                self.index += 1;
+                return Some(self.read_summary(offset));
+            }
+            self.record = self.next;
+            self.index = 0;
+        }
+        None
+    }
+}
+
+impl Segments<'_> {
+    fn summary_words(&self) -> usize {
+        self.file.nd + self.file.ni.div_ceil(2)
+    }
+
+    // Read the next-record link and the summary count of the current
+    // record. Summary records are only ever appended, so each link
+    // must point past the record holding it; that keeps a corrupt
+    // kernel from looping back over records already read.
+    fn read_header(&mut self) -> Result<(), AlmagestError> {
+        let records = self.file.data.len() / RECORD_LEN;
+        if self.record > records {
+            return Err(AlmagestError::Parse("Summary record beyond end of file"));
+        }
+        let base = (self.record - 1) * RECORD_LEN;
+        let (Some(next), Some(count)) = (
+            self.file.read_f64(base),
+            self.file.read_f64(base + 2 * WORD_LEN),
+        ) else {
+            return Err(AlmagestError::Parse("Summary record beyond end of file"));
+        };
+        let capacity = (RECORD_LEN / WORD_LEN - 3) / self.summary_words();
+        if !(0.0..=capacity as Real).contains(&count) || count.fract() != 0.0 {
+            return Err(AlmagestError::Parse("Corrupt DAF summary count"));
+        }
+        let forward = next > self.record as Real && next <= records as Real;
+        if next.fract() != 0.0 || (next != 0.0 && !forward) {
+            return Err(AlmagestError::Parse("Corrupt DAF summary record link"));
+        }
+        self.count = count as usize;
+        self.next = next as usize;
+        Ok(())
+    }
+
+    fn read_summary(&self, offset: usize) -> Result<Segment, AlmagestError> {
+        let ints = offset + self.file.nd * WORD_LEN;
+        if self.file.data.len() < ints + self.file.ni * 4 {
+            return Err(AlmagestError::Parse("Segment summary beyond end of file"));
+        }
+        let int = |i: usize| self.file.read_i32(ints + i * 4);
+        let (Some(start_epoch), Some(end_epoch)) = (
+            self.file.read_f64(offset),
+            self.file.read_f64(offset + WORD_LEN),
+        ) else {
+            return Err(AlmagestError::Parse("Segment summary beyond end of file"));
+        };
+        if int(4) < 1 || int(5) < int(4) {
+            return Err(AlmagestError::Parse("Corrupt SPK segment addresses"));
+        }
+        Ok(Segment {
+            target: int(0),
+            center: int(1),
+            frame: int(2),
+            data_type: int(3),
+            start_epoch: Seconds(start_epoch),
+            end_epoch: Seconds(end_epoch),
+            start_address: int(4) as usize,
+            end_address: int(5) as usize,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use approx::assert_relative_eq;
+    use std::vec::Vec;
+
+    struct TestSegment {
+        target: i32,
+        center: i32,
+        data_type: i32,
+        init: f64,
+        intlen: f64,
+        // One entry per record: Chebyshev coefficients, component-major
+        records: Vec<Vec<f64>>,
+    }
+
+    // Assemble a minimal DAF/SPK kernel: file record, one summary
+    // record, one (empty) name record, then the segment data
+    fn build_spk(segments: &[TestSegment], big_endian: bool) -> Vec<u8> {
+        let f64_bytes = |v: f64| {
+            if big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+        let i32_bytes = |v: i32| {
+            if big_endian {
+                v.to_be_bytes()
+            } else {
+                v.to_le_bytes()
+            }
+        };
+
+        let mut words: Vec<f64> = Vec::new();
+        let mut summaries = Vec::new();
+        let first_address = 3 * 128 + 1;
+        for seg in segments {
+            let start = first_address + words.len();
+            let rsize = 2 + seg.records[0].len();
+            for (i, coefs) in seg.records.iter().enumerate() {
+                words.push(seg.init + (i as f64 + 0.5) * seg.intlen);
+                words.push(seg.intlen / 2.0);
+                words.extend_from_slice(coefs);
+            }
+            words.extend_from_slice(&[
+                seg.init,
+                seg.intlen,
+                rsize as f64,
+                seg.records.len() as f64,
+            ]);
+            let end = first_address + words.len() - 1;
+            let stop = seg.init + seg.intlen * seg.records.len() as f64;
+            summaries.push((seg, seg.init, stop, start as i32, end as i32));
+        }
+
+        let mut out = std::vec![0u8; 3 * RECORD_LEN];
+        out[0..8].copy_from_slice(b"DAF/SPK ");
+        out[8..12].copy_from_slice(&i32_bytes(2));
+        out[12..16].copy_from_slice(&i32_bytes(6));
+        out[76..80].copy_from_slice(&i32_bytes(2));
+        out[80..84].copy_from_slice(&i32_bytes(2));
+        out[88..96].copy_from_slice(if big_endian { b"BIG-IEEE" } else { b"LTL-IEEE" });
+
+        let base = RECORD_LEN;
+        out[base + 16..base + 24].copy_from_slice(&f64_bytes(summaries.len() as f64));
+        for (i, (seg, start, stop, begin, end)) in summaries.iter().enumerate() {
+            let off = base + 24 + i * 40;
+            out[off..off + 8].copy_from_slice(&f64_bytes(*start));
+            out[off + 8..off + 16].copy_from_slice(&f64_bytes(*stop));
+            let ints = [seg.target, seg.center, 1, seg.data_type, *begin, *end];
+            for (j, v) in ints.iter().enumerate() {
+                out[off + 16 + j * 4..off + 20 + j * 4].copy_from_slice(&i32_bytes(*v));
+            }
+        }
+        for w in words {
+            out.extend_from_slice(&f64_bytes(w));
+        }
+        out
+    }
+
+    fn linear_segment(target: i32, center: i32, offset: f64) -> TestSegment {
+        // x(s) = offset + 10 s, y(s) = 2 + T2(s), z(s) = -3
+        TestSegment {
+            target,
+            center,
+            data_type: 2,
+            init: 0.0,
+            intlen: 100.0,
+            records: std::vec![
+                std::vec![offset, 10.0, 0.0, 2.0, 0.0, 1.0, -3.0, 0.0, 0.0],
+                std::vec![offset + 20.0, 10.0, 0.0, 2.0, 0.0, 1.0, -3.0, 0.0, 0.0],
+            ],
+        }
+    }
+
+    #[test]
+    fn rejects_non_spk_data() {
+        assert!(SpkFile::parse(&[0u8; 10]).is_err());
+        assert!(SpkFile::parse(&[0u8; RECORD_LEN]).is_err());
+    }
+
+    #[test]
+    fn lists_segments() {
+        let bytes = build_spk(
+            &[linear_segment(3, 0, 0.0), linear_segment(301, 3, 5.0)],
+            false,
+        );
+        let spk = SpkFile::parse(&bytes).unwrap();
+        let mut segments = spk.segments();
+        let first = segments.next().unwrap().unwrap();
+        assert_eq!((first.target, first.center, first.data_type), (3, 0, 2));
+        assert_eq!(
+            (first.start_epoch, first.end_epoch),
+            (Seconds(0.0), Seconds(200.0))
+        );
+        let second = segments.next().unwrap().unwrap();
+        assert_eq!((second.target, second.center), (301, 3));
+        assert!(segments.next().is_none());
+    }
+
+    #[test]
+    fn evaluates_type_2_chebyshev() {
+        let bytes = build_spk(&[linear_segment(3, 0, 0.0)], false);
+        let spk = SpkFile::parse(&bytes).unwrap();
+        let segment = spk.find_segment(3, 0, Seconds(75.0)).unwrap().unwrap();
+
+        // et = 75 falls in the first record: mid 50, radius 50, s = 0.5
+        let state = spk.evaluate(&segment, Seconds(75.0)).unwrap();
+        let s: f64 = 0.5;
+        assert_relative_eq!(state.position.x, 10.0 * s * KM_TO_M, epsilon = 1e-9);
+        assert_relative_eq!(
+            state.position.y,
+            (2.0 + 2.0 * s * s - 1.0) * KM_TO_M,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(state.position.z, -3.0 * KM_TO_M, epsilon = 1e-9);
+        // d/dt = (d/ds) / radius
+        assert_relative_eq!(state.velocity.x, 10.0 / 50.0 * KM_TO_M, epsilon = 1e-9);
+        assert_relative_eq!(state.velocity.y, 4.0 * s / 50.0 * KM_TO_M, epsilon = 1e-9);
+        assert_relative_eq!(state.velocity.z, 0.0, epsilon = 1e-9);
+
+        // et = 150 falls in the second record
+        let state = spk.evaluate(&segment, Seconds(150.0)).unwrap();
+        assert_relative_eq!(state.position.x, 20.0 * KM_TO_M, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn evaluates_type_3_velocity_coefficients() {
+        let segment = TestSegment {
+            target: 10,
+            center: 0,
+            data_type: 3,
+            init: -50.0,
+            intlen: 100.0,
+            records: std::vec![std::vec![
+                1.0, 0.0, 2.0, 0.0, 3.0, 0.0, 0.1, 0.0, 0.2, 0.0, 0.3, 0.5
+            ]],
+        };
+        let bytes = build_spk(&[segment], false);
+        let spk = SpkFile::parse(&bytes).unwrap();
+        let state = spk.state(10, 0, Seconds(25.0)).unwrap();
+        assert_eq!(state.position, Vec3::new(1.0, 2.0, 3.0) * KM_TO_M);
+        assert_relative_eq!(state.velocity.x, 0.1 * KM_TO_M, epsilon = 1e-9);
+        assert_relative_eq!(
+            state.velocity.z,
+            (0.3 + 0.5 * 0.5) * KM_TO_M,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn reads_big_endian_files() {
+        let little = build_spk(&[linear_segment(3, 0, 0.0)], false);
+        let big = build_spk(&[linear_segment(3, 0, 0.0)], true);
+        let a = SpkFile::parse(&little)
+            .unwrap()
+            .state(3, 0, Seconds(42.0))
+            .unwrap();
+        let b = SpkFile::parse(&big)
+            .unwrap()
+            .state(3, 0, Seconds(42.0))
+            .unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn chains_through_common_center() {
+        // Earth-Moon barycenter, Moon and Earth relative to it
+        let bytes = build_spk(
+            &[
+                linear_segment(3, 0, 100.0),
+                linear_segment(301, 3, 5.0),
+                linear_segment(399, 3, -1.0),
+            ],
+            false,
+        );
+        let spk = SpkFile::parse(&bytes).unwrap();
+        let moon = spk.state(301, 399, Seconds(60.0)).unwrap();
+        assert_relative_eq!(moon.position.x, 6.0 * KM_TO_M, epsilon = 1e-9);
+        assert_relative_eq!(moon.velocity.x, 0.0, epsilon = 1e-9);
+
+        let moon_ssb = spk.state(301, 0, Seconds(60.0)).unwrap();
+        let emb = spk.state(3, 0, Seconds(60.0)).unwrap();
+        let moon_emb = spk.state(301, 3, Seconds(60.0)).unwrap();
+        assert_eq!(moon_ssb, emb + moon_emb);
+    }
+
+    #[test]
+    fn rejects_uncovered_epochs() {
+        let bytes = build_spk(&[linear_segment(3, 0, 0.0)], false);
+        let spk = SpkFile::parse(&bytes).unwrap();
+        assert!(spk.find_segment(3, 0, Seconds(250.0)).unwrap().is_none());
+        assert!(spk.state(3, 0, Seconds(250.0)).is_err());
+        assert!(spk.state(499, 0, Seconds(50.0)).is_err());
+    }
+    #[test]
+    fn rejects_corrupt_summary_records() {
+        let bytes = build_spk(&[linear_segment(3, 0, 0.0)], false);
+        // Overwrite the next-record link or the summary count
+        let corrupt = |word: usize, value: f64| {
+            let mut bytes = bytes.clone();
+            let offset = RECORD_LEN + word * WORD_LEN;
+            bytes[offset..offset + WORD_LEN].copy_from_slice(&value.to_le_bytes());
+            bytes
+        };
+        for (word, value) in [
+            (2, f64::NAN),
+            (2, 1e300),
+            (2, 26.0),
+            (2, -1.0),
+            (2, 1.5),
+            (0, 2.0),
+            (0, 1.0),
+            (0, 1e300),
+            (0, f64::INFINITY),
+            (0, 4.0),
+        ] {
+            let bytes = corrupt(word, value);
+            let spk = SpkFile::parse(&bytes).unwrap();
+            let read: Vec<_> = spk.segments().take(100).collect();
+            assert!(read.len() <= 2, "{word} {value}");
+            assert!(read.last().unwrap().is_err());
+            assert!(spk.find_segment(3, 0, Seconds(75.0)).is_err());
+            assert!(spk.state(3, 0, Seconds(75.0)).is_err());
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use core::ops::{Add, Sub};
+
+use crate::float::Float;
+use crate::utils::{Meters, MetersPerSecond, Real};
+use crate::vectors::Vec3;
+
+/// A Cartesian state: position in meters and
+/// velocity in meters per second
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateVector<T = Real> {
+    pub position: Vec3<T>,
+    pub velocity: Vec3<T>,
+}
+
+impl<T: Float> StateVector<T> {
+    pub const ZERO: Self = StateVector::new(Vec3::ZERO, Vec3::ZERO);
+
+    pub const fn new(position: Vec3<T>, velocity: Vec3<T>) -> Self {
+        StateVector { position, velocity }
+    }
+
+    /// Distance from the origin
+    pub fn radius(&self) -> Meters<T> {
+        Meters(self.position.norm())
+    }
+
+    pub fn speed(&self) -> MetersPerSecond<T> {
+        MetersPerSecond(self.velocity.norm())
+    }
+}
+
+impl<T: Float> Add for StateVector<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        StateVector::new(self.position + rhs.position, self.velocity + rhs.velocity)
+    }
+}
+
+impl<T: Float> Sub for StateVector<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        StateVector::new(self.position - rhs.position, self.velocity - rhs.velocity)
+    }
+}
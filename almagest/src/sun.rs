@@ -0,0 +1,59 @@
+//! Low-precision position of the Sun.
+//!
+//! Vallado's Algorithm 29, good to about 0.01° between 1950 and 2050.
+//! Times are Julian dates; the result is in the mean equator and
+//! equinox of date, which is close enough to any Earth-centered
+//! inertial frame for lighting and local-time geometry.
+
+use libm::{cos, sin};
+
+use crate::utils::{METERS_PER_AU, Meters, Real};
+use crate::vectors::Vec3;
+
+/// The astronomical unit (IAU 2012)
+pub const ASTRONOMICAL_UNIT: Meters = Meters(METERS_PER_AU);
+
+/// Julian date of the J2000.0 epoch
+pub const J2000: Real = 2_451_545.0;
+
+/// Geocentric position of the Sun at `julian_date`, in meters
+pub fn sun_position(julian_date: Real) -> Vec3 {
+    let t = (julian_date - J2000) / 36_525.0;
+    let mean_longitude = 280.460 + 36_000.771 * t;
+    let mean_anomaly = (357.529_109_2 + 35_999.050_34 * t).to_radians();
+    let ecliptic_longitude = (mean_longitude
+        + 1.914_666_471 * sin(mean_anomaly)
+        + 0.019_994_643 * sin(2.0 * mean_anomaly))
+    .to_radians();
+    let distance =
+        1.000_140_612 - 0.016_708_617 * cos(mean_anomaly) - 0.000_139_589 * cos(2.0 * mean_anomaly);
+    let obliquity = (23.439_291 - 0.013_004_2 * t).to_radians();
+    Vec3::new(
+        cos(ecliptic_longitude),
+        cos(obliquity) * sin(ecliptic_longitude),
+        sin(obliquity) * sin(ecliptic_longitude),
+    ) * (distance * ASTRONOMICAL_UNIT.value())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    // Vallado Example 5-1
+    #[test]
+    fn sun_on_april_2_2006() {
+        let r = sun_position(2_453_827.5) / ASTRONOMICAL_UNIT.value();
+        assert_relative_eq!(r.x, 0.977_194_5, epsilon = 1e-6);
+        assert_relative_eq!(r.y, 0.192_442_4, epsilon = 2e-6);
+        assert_relative_eq!(r.z, 0.083_430_8, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn sun_near_the_equinox() {
+        // 2020 March 20 03:50 UT: the Sun crosses the equator
+        let r = sun_position(2_458_928.659_7);
+        assert_relative_eq!(r.z / r.norm(), 0.0, epsilon = 1e-3);
+        assert!(r.x > 0.0);
+    }
+}
@@ -0,0 +1,253 @@
+//! Synodic (rotating) frames built from the actual states of two
+//! primaries, such as the Earth and Moon or the Sun and Earth.
+//!
+//! At each epoch the frame is centered on the barycenter of the pair,
+//! with +x from the larger primary toward the smaller one, +z along
+//! their orbital angular momentum and +y completing the triad. Because
+//! the axes follow the real ephemerides rather than an idealized
+//! circular orbit, eccentricity and perturbations of the primaries'
+//! motion show up as they should when a trajectory is viewed in the
+//! rotating frame.
+//!
+//! The frame's angular velocity is taken as h/r² of the primaries'
+//! relative motion; the slow precession of their orbit plane is
+//! neglected.
+
+use crate::error::AlmagestError;
+use crate::state::StateVector;
+use crate::utils::Real;
+use crate::vectors::{Matrix3, Vec3};
+
+/// Mass ratio m₂ / (m₁ + m₂) of the Earth-Moon system
+pub const EARTH_MOON_MASS_RATIO: Real = 0.012_150_58;
+
+/// Mass ratio m₂ / (m₁ + m₂) of the Sun and the Earth-Moon barycenter
+pub const SUN_EARTH_MASS_RATIO: Real = 3.040_423e-6;
+
+/// The synodic frame of two primaries at one epoch
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SynodicFrame {
+    // Barycenter state in the inertial frame
+    barycenter: StateVector,
+    // Columns are the rotating axes expressed in the inertial frame
+    axes: Matrix3,
+    // Instantaneous rotation rate, rad/s
+    rate: Real,
+    // Distance between the primaries and its rate of change
+    distance: Real,
+    distance_rate: Real,
+    mass_ratio: Real,
+}
+
+impl SynodicFrame {
+    /// Build the frame from the inertial states of the larger
+    /// (`primary`) and smaller (`secondary`) body, both in the same
+    /// inertial frame, and the mass ratio m₂ / (m₁ + m₂)
+    pub fn new(
+        primary: &StateVector,
+        secondary: &StateVector,
+        mass_ratio: Real,
+    ) -> Result<Self, AlmagestError> {
+        let relative = *secondary - *primary;
+        let r = relative.position;
+        let h = r.cross(relative.velocity);
+        let distance = r.norm();
+        if distance == 0.0 || h.norm() == 0.0 {
+            return Err(AlmagestError::Coplanarity(
+                "Primaries must be separated and in relative rotation",
+            ));
+        }
+        let x = r.unit();
+        let z = h.unit();
+        let y = z.cross(x);
+        let barycenter = StateVector::new(
+            primary.position * (1.0 - mass_ratio) + secondary.position * mass_ratio,
+            primary.velocity * (1.0 - mass_ratio) + secondary.velocity * mass_ratio,
+        );
+        Ok(SynodicFrame {
+            barycenter,
+            axes: Matrix3::from_columns(x, y, z),
+            rate: h.norm() / (distance * distance),
+            distance,
+            distance_rate: r.dot(relative.velocity) / distance,
+            mass_ratio,
+        })
+    }
+
+    /// Build the frame from the primaries' states in an SPK kernel,
+    /// e.g. NAIF IDs 399 and 301 for the Earth-Moon frame
+    #[cfg(feature = "spice")]
+    pub fn from_spk(
+        spk: &crate::spice::SpkFile<'_>,
+        primary: i32,
+        secondary: i32,
+        mass_ratio: Real,
+        et: crate::utils::Seconds,
+    ) -> Result<Self, AlmagestError> {
+        let primary_state = spk.state(primary, 0, et)?;
+        let secondary_state = spk.state(secondary, 0, et)?;
+        SynodicFrame::new(&primary_state, &secondary_state, mass_ratio)
+    }
+
+    /// Instantaneous rotation rate of the frame, in rad/s
+    pub fn rate(&self) -> Real {
+        self.rate
+    }
+
+    /// Instantaneous distance between the primaries, in meters
+    pub fn distance(&self) -> Real {
+        self.distance
+    }
+
+    /// Express an inertial state in the rotating frame, in meters
+    /// and meters per second relative to the barycenter
+    pub fn to_rotating(&self, inertial: &StateVector) -> StateVector {
+        let to_rotating = self.axes.transpose();
+        let position = to_rotating * (inertial.position - self.barycenter.position);
+        let velocity = to_rotating * (inertial.velocity - self.barycenter.velocity)
+            - self.omega().cross(position);
+        StateVector::new(position, velocity)
+    }
+
+    /// Express a rotating-frame state, in meters and meters per
+    /// second relative to the barycenter, in the inertial frame
+    pub fn to_inertial(&self, rotating: &StateVector) -> StateVector {
+        let position = self.axes * rotating.position + self.barycenter.position;
+        let velocity = self.axes * (rotating.velocity + self.omega().cross(rotating.position))
+            + self.barycenter.velocity;
+        StateVector::new(position, velocity)
+    }
+
+    /// Express an inertial state in the normalized (pulsating) rotating
+    /// frame used by the circular restricted three-body problem: unit
+    /// distance between the primaries and unit rotation rate, with the
+    /// primaries at (−μ, 0, 0) and (1 − μ, 0, 0)
+    pub fn to_normalized(&self, inertial: &StateVector) -> StateVector {
+        let rotating = self.to_rotating(inertial);
+        let l = self.distance;
+        let position = rotating.position / l;
+        let velocity =
+            (rotating.velocity - rotating.position * (self.distance_rate / l)) / (l * self.rate);
+        StateVector::new(position, velocity)
+    }
+
+    /// Inverse of [`SynodicFrame::to_normalized`]
+    pub fn from_normalized(&self, normalized: &StateVector) -> StateVector {
+        let l = self.distance;
+        let position = normalized.position * l;
+        let velocity = normalized.velocity * (l * self.rate) + position * (self.distance_rate / l);
+        self.to_inertial(&StateVector::new(position, velocity))
+    }
+
+    /// Mass ratio m₂ / (m₁ + m₂) the frame was built with
+    pub fn mass_ratio(&self) -> Real {
+        self.mass_ratio
+    }
+
+    // Angular velocity in rotating coordinates
+    fn omega(&self) -> Vec3 {
+        Vec3::Z * self.rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::invariants::jacobi_constant;
+    use approx::assert_relative_eq;
+    use libm::{cos, sin, sqrt};
+
+    const MU_EARTH_MOON: Real = 4.035_032e14;
+    const LUNAR_DISTANCE: Real = 384_400_000.0;
+
+    // Earth and Moon on circular orbits about their barycenter,
+    // in an inclined inertial frame
+    fn primaries(t: Real) -> (StateVector, StateVector) {
+        let mu = EARTH_MOON_MASS_RATIO;
+        let n = sqrt(MU_EARTH_MOON / (LUNAR_DISTANCE * LUNAR_DISTANCE * LUNAR_DISTANCE));
+        let tilt = Matrix3::from_rows(
+            Vec3::X,
+            Vec3::new(0.0, cos(0.4), -sin(0.4)),
+            Vec3::new(0.0, sin(0.4), cos(0.4)),
+        );
+        let (s, c) = (sin(n * t), cos(n * t));
+        let r = tilt * Vec3::new(c, s, 0.0) * LUNAR_DISTANCE;
+        let v = tilt * Vec3::new(-s, c, 0.0) * LUNAR_DISTANCE * n;
+        let barycenter = Vec3::new(1.0e11, -2.0e10, 3.0e9);
+        let drift = Vec3::new(29_000.0, 1_000.0, -50.0);
+        let origin = barycenter + drift * t;
+        (
+            StateVector::new(origin - r * mu, drift - v * mu),
+            StateVector::new(origin + r * (1.0 - mu), drift + v * (1.0 - mu)),
+        )
+    }
+
+    #[test]
+    fn primaries_sit_on_the_x_axis() {
+        let (earth, moon) = primaries(3.0e5);
+        let frame = SynodicFrame::new(&earth, &moon, EARTH_MOON_MASS_RATIO).unwrap();
+        let mu = EARTH_MOON_MASS_RATIO;
+
+        let moon_rot = frame.to_normalized(&moon);
+        assert_relative_eq!(moon_rot.position.x, 1.0 - mu, epsilon = 1e-9);
+        assert_relative_eq!(moon_rot.position.y, 0.0, epsilon = 1e-9);
+        assert_relative_eq!(moon_rot.velocity.norm(), 0.0, epsilon = 1e-9);
+
+        let earth_rot = frame.to_rotating(&earth);
+        assert_relative_eq!(earth_rot.position.x, -mu * LUNAR_DISTANCE, epsilon = 1e-3);
+        assert_relative_eq!(earth_rot.velocity.norm(), 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn round_trips() {
+        let (earth, moon) = primaries(1.0e5);
+        let frame = SynodicFrame::new(&earth, &moon, EARTH_MOON_MASS_RATIO).unwrap();
+        let craft = StateVector::new(
+            earth.position + Vec3::new(2.0e8, 1.0e8, -3.0e7),
+            earth.velocity + Vec3::new(-300.0, 800.0, 100.0),
+        );
+        let back = frame.to_inertial(&frame.to_rotating(&craft));
+        assert_relative_eq!((back.position - craft.position).norm(), 0.0, epsilon = 1e-4);
+        assert_relative_eq!((back.velocity - craft.velocity).norm(), 0.0, epsilon = 1e-9);
+        let back = frame.from_normalized(&frame.to_normalized(&craft));
+        assert_relative_eq!((back.position - craft.position).norm(), 0.0, epsilon = 1e-4);
+        assert_relative_eq!((back.velocity - craft.velocity).norm(), 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn l4_co_rotates_with_the_primaries() {
+        let mu = EARTH_MOON_MASS_RATIO;
+        let l4 = StateVector::new(Vec3::new(0.5 - mu, sqrt(3.0) / 2.0, 0.0), Vec3::ZERO);
+        let (earth, moon) = primaries(2.0e5);
+        let frame = SynodicFrame::new(&earth, &moon, mu).unwrap();
+        let inertial = frame.from_normalized(&l4);
+
+        // Equidistant from both primaries...
+        let to_earth = (inertial.position - earth.position).norm();
+        let to_moon = (inertial.position - moon.position).norm();
+        assert_relative_eq!(to_earth, LUNAR_DISTANCE, max_relative = 1e-12);
+        assert_relative_eq!(to_moon, LUNAR_DISTANCE, max_relative = 1e-12);
+
+        // ...and circling the barycenter at the frame's rate
+        let barycenter = earth.position * (1.0 - mu) + moon.position * mu;
+        let barycenter_velocity = earth.velocity * (1.0 - mu) + moon.velocity * mu;
+        let rho = inertial.position - barycenter;
+        let rho_dot = inertial.velocity - barycenter_velocity;
+        assert_relative_eq!(rho.dot(rho_dot) / rho.norm(), 0.0, epsilon = 1e-9);
+        assert_relative_eq!(
+            rho_dot.norm(),
+            frame.rate() * rho.norm(),
+            max_relative = 1e-12
+        );
+
+        // Its Jacobi constant is 3 − μ + μ²
+        assert_relative_eq!(jacobi_constant(&l4, mu), 3.0 - mu + mu * mu, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn rejects_coincident_primaries() {
+        let (earth, _) = primaries(0.0);
+        assert!(SynodicFrame::new(&earth, &earth, EARTH_MOON_MASS_RATIO).is_err());
+    }
+}
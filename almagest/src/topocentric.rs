@@ -0,0 +1,170 @@
+//! Range, range rate and Doppler shift of a satellite seen from a
+//! ground station.
+//!
+//! A [`Site`] turns with its body, so its inertial velocity ω × r
+//! enters the range rate: a satellite hanging inertially still over
+//! the equator still drifts in range as the station turns under it.
+//! Light time is neglected; over a LEO pass it moves the satellite by
+//! a few tens of meters along its track.
+//!
+//! ```
+//! use almagest::groundtrack::GroundPoint;
+//! use almagest::state::StateVector;
+//! use almagest::topocentric::Site;
+//! use almagest::utils::{Meters, Seconds};
+//! use almagest::vectors::Vec3;
+//!
+//! let station = Site::earth(GroundPoint::new(0.0, 0.0), Meters(0.0), 0.0);
+//! // 500 km up, heading for the station's zenith
+//! let satellite = StateVector::new(
+//!     Vec3::new(6_878_137.0, -1_000_000.0, 0.0),
+//!     Vec3::new(0.0, 7_600.0, 0.0),
+//! );
+//! // An approaching satellite's 437 MHz beacon arrives high
+//! let shift = station.doppler_shift(Seconds::ZERO, &satellite, 437e6);
+//! assert!(shift > 0.0 && shift < 11e3);
+//! ```
+
+use crate::bodies::RotatingBody;
+use crate::groundtrack::{GroundPoint, body_fixed};
+use crate::state::StateVector;
+use crate::utils::{Meters, MetersPerSecond, Real, Seconds};
+use crate::vectors::Vec3;
+
+/// Speed of light in vacuum
+pub const SPEED_OF_LIGHT: MetersPerSecond = MetersPerSecond(299_792_458.0);
+
+/// A ground station on a spherical rotating body
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Site {
+    pub location: GroundPoint,
+    /// Height of the antenna above the reference sphere
+    pub altitude: Meters,
+    pub body: RotatingBody,
+}
+
+impl Site {
+    /// A station on the Earth
+    pub fn earth(location: GroundPoint, altitude: Meters, greenwich_angle: Real) -> Self {
+        Site {
+            location,
+            altitude,
+            body: RotatingBody::earth(greenwich_angle),
+        }
+    }
+
+    /// Inertial position and velocity of the station at `time`
+    pub fn state(&self, time: Seconds) -> StateVector {
+        let radius = (self.body.equatorial_radius + self.altitude).value();
+        let position = body_fixed(self.location.unit(), -self.body.angle(time)) * radius;
+        StateVector::new(
+            position,
+            (Vec3::Z * self.body.rotation_rate).cross(position),
+        )
+    }
+
+    /// The satellite's inertial state relative to the station, ρ and ρ̇
+    pub fn relative(&self, time: Seconds, state: &StateVector) -> StateVector {
+        let site = self.state(time);
+        StateVector::new(
+            state.position - site.position,
+            state.velocity - site.velocity,
+        )
+    }
+
+    pub fn range(&self, time: Seconds, state: &StateVector) -> Meters {
+        Meters(self.relative(time, state).position.norm())
+    }
+
+    /// Rate of change of the range, ρ·ρ̇/|ρ|; positive as the
+    /// satellite recedes
+    pub fn range_rate(&self, time: Seconds, state: &StateVector) -> MetersPerSecond {
+        let relative = self.relative(time, state);
+        let rho = relative.position.norm();
+        MetersPerSecond(relative.position.dot(relative.velocity) / rho)
+    }
+
+    /// Shift of a downlink at `frequency` Hz as received here, in Hz,
+    /// as [`doppler_shift`]
+    pub fn doppler_shift(&self, time: Seconds, state: &StateVector, frequency: Real) -> Real {
+        doppler_shift(self.range_rate(time, state), frequency)
+    }
+}
+
+/// Received minus transmitted frequency, in Hz, for a one-way link at
+/// `frequency` Hz closing at −`range_rate`. First order in ρ̇/c; the
+/// next term is under a part per billion of the carrier for Earth
+/// orbits.
+pub fn doppler_shift(range_rate: MetersPerSecond, frequency: Real) -> Real {
+    -frequency * (range_rate / SPEED_OF_LIGHT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use crate::geo::synchronous_radius;
+    use crate::groundtrack::{EARTH_RADIUS, EARTH_ROTATION_RATE};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn station_turns_with_the_earth() {
+        let station = Site::earth(GroundPoint::new(0.0, 0.5), Meters(0.0), 0.3);
+        let time = Seconds(1_000.0);
+        let site = station.state(time);
+        assert_relative_eq!(site.velocity.norm(), 465.1, epsilon = 0.1);
+
+        // A geostationary satellite over the station neither closes
+        // nor opens
+        let radius = synchronous_radius(EARTH.mu, EARTH_ROTATION_RATE).value();
+        let over = site.position.unit() * radius;
+        let geostationary = StateVector::new(over, (Vec3::Z * EARTH_ROTATION_RATE).cross(over));
+        assert_relative_eq!(
+            station.range_rate(time, &geostationary).value(),
+            0.0,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            station.range(time, &geostationary).value(),
+            radius - EARTH_RADIUS.value(),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn range_rate_is_the_derivative_of_range() {
+        let station = Site::earth(GroundPoint::new(0.7, -1.3), Meters(120.0), 2.0);
+        let satellite = |t: Seconds| {
+            StateVector::new(
+                Vec3::new(-2_000_000.0, -5_500_000.0, 4_000_000.0)
+                    + Vec3::new(5_000.0, -3_000.0, 4_500.0) * t.value(),
+                Vec3::new(5_000.0, -3_000.0, 4_500.0),
+            )
+        };
+        let (now, h) = (Seconds::ZERO, Seconds(0.01));
+        let expected = (station.range(h, &satellite(h)).value()
+            - station.range(-h, &satellite(-h)).value())
+            / (2.0 * h.value());
+        let rate = station.range_rate(now, &satellite(now));
+        assert_relative_eq!(rate.value(), expected, epsilon = 1e-5);
+
+        // Ignoring the station's motion gets it wrong by up to ωR
+        let fixed = Site {
+            body: RotatingBody {
+                rotation_rate: 0.0,
+                ..station.body
+            },
+            ..station
+        };
+        assert!((fixed.range_rate(now, &satellite(now)).value() - rate.value()).abs() > 1.0);
+
+        // Receding at 7 km/s lowers a 2.2 GHz S-band downlink by ~51 kHz
+        let shift = doppler_shift(MetersPerSecond(7_000.0), 2.2e9);
+        assert_relative_eq!(shift, -51_368.9, epsilon = 0.1);
+        assert_relative_eq!(
+            station.doppler_shift(now, &satellite(now), 2.2e9),
+            -2.2e9 * rate.value() / SPEED_OF_LIGHT.value()
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Error budgets: how input uncertainties carry into derived
+//! quantities.
+//!
+//! Uncertainties are 1-σ and the inputs are taken as independent.
+//! Each output is linearized about the nominal inputs with central
+//! differences, so its σ is the root-sum-square of one contribution
+//! per input, ∂f/∂xᵢ σᵢ. Keeping the contributions apart shows which
+//! measurement dominates the budget. The linearization holds while
+//! the σs are small enough that the outputs stay close to linear over
+//! them.
+
+use libm::sqrt;
+
+use crate::kepler::{Ellipse, Point};
+use crate::utils::{Eccentricity, GravParam, Meters, Real, TAU};
+
+/// A value with its 1-σ uncertainty
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Uncertain {
+    pub value: Real,
+    pub sigma: Real,
+}
+
+impl Uncertain {
+    pub const fn new(value: Real, sigma: Real) -> Self {
+        Uncertain { value, sigma }
+    }
+
+    /// A value known exactly
+    pub const fn exact(value: Real) -> Self {
+        Uncertain { value, sigma: 0.0 }
+    }
+}
+
+/// A derived quantity and the share of its uncertainty due to each of
+/// `N` inputs
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ErrorBudget<const N: usize> {
+    pub value: Real,
+    /// ∂f/∂xᵢ σᵢ, signed
+    pub contributions: [Real; N],
+}
+
+impl<const N: usize> ErrorBudget<N> {
+    /// Total 1-σ uncertainty, the root-sum-square of the contributions
+    pub fn sigma(&self) -> Real {
+        sqrt(self.contributions.iter().map(|c| c * c).sum())
+    }
+
+    /// Index of the input contributing most
+    pub fn dominant(&self) -> usize {
+        (0..N)
+            .max_by(|&a, &b| {
+                self.contributions[a]
+                    .abs()
+                    .total_cmp(&self.contributions[b].abs())
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn to_uncertain(&self) -> Uncertain {
+        Uncertain::new(self.value, self.sigma())
+    }
+}
+
+/// Budgets for the `M` outputs of `f` given `N` uncertain inputs
+pub fn error_budget<const N: usize, const M: usize, F>(
+    f: F,
+    inputs: [Uncertain; N],
+) -> [ErrorBudget<N>; M]
+where
+    F: Fn([Real; N]) -> [Real; M],
+{
+    let nominal = inputs.map(|x| x.value);
+    let values = f(nominal);
+    let mut budgets = values.map(|value| ErrorBudget {
+        value,
+        contributions: [0.0; N],
+    });
+    for (i, input) in inputs.iter().enumerate() {
+        if input.sigma == 0.0 {
+            continue;
+        }
+        let h = 1e-6 * (input.value.abs() + input.sigma);
+        let (mut up, mut down) = (nominal, nominal);
+        up[i] += h;
+        down[i] -= h;
+        let (high, low) = (f(up), f(down));
+        for (budget, (hi, lo)) in budgets.iter_mut().zip(high.iter().zip(low)) {
+            budget.contributions[i] = (hi - lo) / (2.0 * h) * input.sigma;
+        }
+    }
+    budgets
+}
+
+/// Uncertainties in an orbit's shape and timing from uncertain apsides
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ApsidesBudget {
+    pub semi_major_axis: ErrorBudget<2>,
+    pub eccentricity: ErrorBudget<2>,
+    /// In seconds
+    pub period: ErrorBudget<2>,
+    /// In m/s
+    pub periapsis_speed: ErrorBudget<2>,
+    /// In m/s
+    pub apoapsis_speed: ErrorBudget<2>,
+}
+
+impl ApsidesBudget {
+    /// Budgets from periapsis and apoapsis radii in meters, with
+    /// contributions in that order
+    pub fn new(periapsis: Uncertain, apoapsis: Uncertain, mu: GravParam) -> Self {
+        let budgets = error_budget(
+            |[rp, ra]| {
+                let ellipse = Ellipse::new(
+                    Eccentricity::new(((ra - rp) / (ra + rp)).abs()).unwrap(),
+                    Point::ORIGIN,
+                    Meters(rp.min(ra)),
+                );
+                let a = ellipse.semi_major_axis().value();
+                [
+                    a,
+                    ellipse.eccentricity().value(),
+                    TAU * sqrt(a * a * a / mu.value()),
+                    ellipse.periapsis_speed(mu).value(),
+                    ellipse.speed_at(ellipse.apoapsis(), mu).value(),
+                ]
+            },
+            [periapsis, apoapsis],
+        );
+        ApsidesBudget {
+            semi_major_axis: budgets[0],
+            eccentricity: budgets[1],
+            period: budgets[2],
+            periapsis_speed: budgets[3],
+            apoapsis_speed: budgets[4],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bodies::EARTH;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn linear_function_is_exact() {
+        let [sum, product] = error_budget(
+            |[x, y]| [x + 2.0 * y, x * y],
+            [Uncertain::new(3.0, 0.3), Uncertain::new(5.0, 0.4)],
+        );
+        assert_relative_eq!(sum.value, 13.0);
+        assert_relative_eq!(sum.sigma(), sqrt(0.09 + 0.64), max_relative = 1e-9);
+        assert_eq!(sum.dominant(), 1);
+        // d(xy) = y dx + x dy
+        assert_relative_eq!(product.contributions[0], 5.0 * 0.3, max_relative = 1e-9);
+        assert_relative_eq!(product.contributions[1], 3.0 * 0.4, max_relative = 1e-9);
+        let exact = error_budget(|[x]| [x * x], [Uncertain::exact(2.0)]);
+        assert_eq!(exact[0].to_uncertain(), Uncertain::exact(4.0));
+    }
+
+    #[test]
+    fn apsides_budget_matches_analytic_partials() {
+        let (rp, ra) = (6_778_000.0, 42_164_000.0);
+        let (sp, sa) = (100.0, 2_000.0);
+        let budget = ApsidesBudget::new(Uncertain::new(rp, sp), Uncertain::new(ra, sa), EARTH.mu);
+        let a = (rp + ra) / 2.0;
+        assert_relative_eq!(budget.semi_major_axis.value, a, max_relative = 1e-12);
+        assert_relative_eq!(
+            budget.semi_major_axis.sigma(),
+            sqrt(sp * sp + sa * sa) / 2.0,
+            max_relative = 1e-6
+        );
+        let sum2 = (rp + ra) * (rp + ra);
+        assert_relative_eq!(
+            budget.eccentricity.contributions[0],
+            -2.0 * ra / sum2 * sp,
+            max_relative = 1e-6
+        );
+        assert_relative_eq!(
+            budget.eccentricity.contributions[1],
+            2.0 * rp / sum2 * sa,
+            max_relative = 1e-6
+        );
+        // T ∝ a^(3/2)
+        assert_relative_eq!(
+            budget.period.sigma() / budget.period.value,
+            1.5 * budget.semi_major_axis.sigma() / a,
+            max_relative = 1e-6
+        );
+        assert_eq!(budget.semi_major_axis.dominant(), 1);
+        assert!(budget.apoapsis_speed.sigma() > 0.0);
+    }
+}
@@ -1,6 +1,9 @@
 use core::cmp::{PartialEq, PartialOrd};
 use core::fmt::{Debug, Display};
-use core::ops::{Add, Div, Mul, Sub};
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::error::AlmagestError;
+use crate::float::Float;
 
 pub type Real = f64;
 
@@ -11,32 +14,93 @@ pub const PI: Real = 3.14159265358979323846264338327950288;
 pub const TAU: Real = 6.28318530717958647692528676655900577;
 /// Euler's number (e)
 pub const E: Real = 2.71828182845904523536028747135266250;
+/// Seconds in a day of 86 400 SI seconds
+pub const SECONDS_PER_DAY: Real = 86_400.0;
+/// Meters in one astronomical unit (IAU 2012)
+pub const METERS_PER_AU: Real = 149_597_870_700.0;
+
+/// The number inside a quantity of any float type.
+///
+/// Quantities of [`Real`] have an inherent `value` as well, which is
+/// what lets `Meters(10.0).value()` settle on `f64`; generic and `f32`
+/// code reads them through this trait.
+pub trait Quantity {
+    type Value: Float;
+
+    fn value(&self) -> Self::Value;
+}
+
+macro_rules! quantity {
+    ($($unit:ident),*) => {
+        $(
+            impl<T: Float> Quantity for $unit<T> {
+                type Value = T;
+
+                fn value(&self) -> T { self.0 }
+            }
+        )*
+    };
+}
+
+quantity!(
+    Meters,
+    Kilometers,
+    AstronomicalUnits,
+    MetersSquared,
+    MetersCubed,
+    GravParam,
+    Seconds,
+    Days,
+    MetersPerSecond,
+    MetersSquaredPerSecondSquared,
+    MetersPerSecondSquared,
+    Kilograms,
+    Newtons,
+    Eccentricity,
+    Radians,
+    Degrees,
+    RadiansPerSecond
+);
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
-pub struct Meters(pub Real);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Meters<T = Real>(pub T);
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
-pub struct Kilometers(pub Real);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Kilometers<T = Real>(pub T);
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
-pub struct MetersSquared(pub Real);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AstronomicalUnits<T = Real>(pub T);
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
-pub struct MetersCubed(pub Real);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetersSquared<T = Real>(pub T);
+
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetersCubed<T = Real>(pub T);
 
 impl Meters {
-    pub const ZERO: Self = Meters(0.0);
+    pub fn value(&self) -> Real {
+        self.0
+    }
+}
+
+impl<T: Float> Meters<T> {
+    pub const ZERO: Self = Meters(T::ZERO);
 
-    pub fn to_km(&self) -> Kilometers {
-        Kilometers(self.value() / 1_000.0)
+    pub fn to_km(&self) -> Kilometers<T> {
+        Kilometers(self.0 / T::from_real(1_000.0))
     }
 
-    pub fn value(&self) -> Real {
-        self.0
+    pub fn to_au(&self) -> AstronomicalUnits<T> {
+        AstronomicalUnits(self.0 / T::from_real(METERS_PER_AU))
     }
 }
 
-impl Add for Meters {
+impl<T: Float> Add for Meters<T> {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output {
         Meters(self.0 + rhs.0)
@@ -44,51 +108,63 @@ impl Add for Meters {
 }
 
 // Meters / Meters = dimensionless ratio
-impl Div for Meters {
-    type Output = Real;
+impl<T: Float> Div for Meters<T> {
+    type Output = T;
     fn div(self, rhs: Self) -> Self::Output {
         self.0 / rhs.0
     }
 }
 
 // Scalar multiplication
-impl Mul<Real> for Meters {
+impl<T: Float> Mul<T> for Meters<T> {
     type Output = Self;
-    fn mul(self, rhs: Real) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Meters(self.0 * rhs)
     }
 }
 
 // Scalar division
-impl Div<Real> for Meters {
+impl<T: Float> Div<T> for Meters<T> {
     type Output = Self;
-    fn div(self, rhs: Real) -> Self::Output {
+    fn div(self, rhs: T) -> Self::Output {
         Meters(self.0 / rhs)
     }
 }
 
 // Meters * Meters = MetersSquared (area)
-impl Mul for Meters {
-    type Output = MetersSquared;
+impl<T: Float> Mul for Meters<T> {
+    type Output = MetersSquared<T>;
     fn mul(self, rhs: Self) -> Self::Output {
         MetersSquared(self.0 * rhs.0)
     }
 }
 
 // Display implementations
-impl Display for Meters {
+impl<T: Float> Display for Meters<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{} m", self.0)
     }
 }
 
-impl Display for MetersSquared {
+impl<T: Float> Display for Kilometers<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} km", self.0)
+    }
+}
+
+impl<T: Float> Display for AstronomicalUnits<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} AU", self.0)
+    }
+}
+
+impl<T: Float> Display for MetersSquared<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{} m²", self.0)
     }
 }
 
-impl Display for MetersCubed {
+impl<T: Float> Display for MetersCubed<T> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{} m³", self.0)
     }
@@ -104,68 +180,856 @@ impl MetersCubed {
     pub const fn value(self) -> Real { self.0 }
 }
 
-impl Add for MetersSquared {
+impl<T: Float> Add for MetersSquared<T> {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output { MetersSquared(self.0 + rhs.0) }
 }
 
-impl Sub for MetersSquared {
+impl<T: Float> Sub for MetersSquared<T> {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self::Output { MetersSquared(self.0 - rhs.0) }
 }
 
-impl Mul<Real> for MetersSquared {
+impl<T: Float> Mul<T> for MetersSquared<T> {
     type Output = Self;
-    fn mul(self, rhs: Real) -> Self::Output { MetersSquared(self.0 * rhs) }
+    fn mul(self, rhs: T) -> Self::Output { MetersSquared(self.0 * rhs) }
 }
 
-impl Div<Real> for MetersSquared {
+impl<T: Float> Div<T> for MetersSquared<T> {
     type Output = Self;
-    fn div(self, rhs: Real) -> Self::Output { MetersSquared(self.0 / rhs) }
+    fn div(self, rhs: T) -> Self::Output { MetersSquared(self.0 / rhs) }
 }
 
 // MetersSquared / Meters = Meters
-impl Div<Meters> for MetersSquared {
-    type Output = Meters;
-    fn div(self, rhs: Meters) -> Self::Output { Meters(self.0 / rhs.0) }
+impl<T: Float> Div<Meters<T>> for MetersSquared<T> {
+    type Output = Meters<T>;
+    fn div(self, rhs: Meters<T>) -> Self::Output { Meters(self.0 / rhs.0) }
 }
 
 // Meters * MetersSquared = MetersCubed
-impl Mul<MetersSquared> for Meters {
-    type Output = MetersCubed;
-    fn mul(self, rhs: MetersSquared) -> Self::Output { MetersCubed(self.0 * rhs.0) }
+impl<T: Float> Mul<MetersSquared<T>> for Meters<T> {
+    type Output = MetersCubed<T>;
+    fn mul(self, rhs: MetersSquared<T>) -> Self::Output { MetersCubed(self.0 * rhs.0) }
 }
 
-// Real * Meters = Meters (commutative scalar multiplication)
-impl Mul<Meters> for Real {
-    type Output = Meters;
-    fn mul(self, rhs: Meters) -> Self::Output { Meters(self * rhs.0) }
-}
-
-impl Sub for Meters {
+impl<T: Float> Sub for Meters<T> {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self::Output {
         Meters(self.0 - rhs.0)
     }
 }
 
+// Kilometers operations
+impl Kilometers {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl<T: Float> Kilometers<T> {
+    pub const ZERO: Self = Kilometers(T::ZERO);
+
+    pub fn to_meters(self) -> Meters<T> { Meters(self.0 * T::from_real(1_000.0)) }
+
+    pub fn to_au(self) -> AstronomicalUnits<T> { self.to_meters().to_au() }
+}
+
+impl<T: Float> Add for Kilometers<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { Kilometers(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for Kilometers<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { Kilometers(self.0 - rhs.0) }
+}
+
+impl<T: Float> Mul<T> for Kilometers<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { Kilometers(self.0 * rhs) }
+}
+
+impl<T: Float> Div<T> for Kilometers<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output { Kilometers(self.0 / rhs) }
+}
+
+// Kilometers / Kilometers = dimensionless ratio
+impl<T: Float> Div for Kilometers<T> {
+    type Output = T;
+    fn div(self, rhs: Self) -> Self::Output { self.0 / rhs.0 }
+}
+
+// Kilometers * Kilometers = MetersSquared, since areas are kept in SI
+impl<T: Float> Mul for Kilometers<T> {
+    type Output = MetersSquared<T>;
+    fn mul(self, rhs: Self) -> Self::Output { self.to_meters() * rhs.to_meters() }
+}
+
+// AstronomicalUnits operations
+impl AstronomicalUnits {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl<T: Float> AstronomicalUnits<T> {
+    pub fn to_meters(self) -> Meters<T> { Meters(self.0 * T::from_real(METERS_PER_AU)) }
+
+    pub fn to_km(self) -> Kilometers<T> { self.to_meters().to_km() }
+}
+
+impl<T: Float> Add for AstronomicalUnits<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { AstronomicalUnits(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for AstronomicalUnits<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { AstronomicalUnits(self.0 - rhs.0) }
+}
+
+impl<T: Float> Mul<T> for AstronomicalUnits<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { AstronomicalUnits(self.0 * rhs) }
+}
+
+impl<T: Float> Div<T> for AstronomicalUnits<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output { AstronomicalUnits(self.0 / rhs) }
+}
+
+// AstronomicalUnits / AstronomicalUnits = dimensionless ratio
+impl<T: Float> Div for AstronomicalUnits<T> {
+    type Output = T;
+    fn div(self, rhs: Self) -> Self::Output { self.0 / rhs.0 }
+}
+
+// Conversions among the length types. Each is exact up to rounding, so
+// the fallible `TryFrom` forms come from these through the standard
+// blanket impl.
+impl<T: Float> From<Kilometers<T>> for Meters<T> {
+    fn from(length: Kilometers<T>) -> Self { length.to_meters() }
+}
+
+impl<T: Float> From<Meters<T>> for Kilometers<T> {
+    fn from(length: Meters<T>) -> Self { length.to_km() }
+}
+
+impl<T: Float> From<AstronomicalUnits<T>> for Meters<T> {
+    fn from(length: AstronomicalUnits<T>) -> Self { length.to_meters() }
+}
+
+impl<T: Float> From<Meters<T>> for AstronomicalUnits<T> {
+    fn from(length: Meters<T>) -> Self { length.to_au() }
+}
+
+impl<T: Float> From<AstronomicalUnits<T>> for Kilometers<T> {
+    fn from(length: AstronomicalUnits<T>) -> Self { length.to_km() }
+}
+
+impl<T: Float> From<Kilometers<T>> for AstronomicalUnits<T> {
+    fn from(length: Kilometers<T>) -> Self { length.to_au() }
+}
+
+/// Standard gravitational parameter (μ = GM) of a central body, in m³/s²
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GravParam<T = Real>(pub T);
+
+impl GravParam {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl<T: Float> Display for GravParam<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} m³/s²", self.0)
+    }
+}
+
+/// A duration or time offset, in seconds.
+///
+/// Every time in seconds the crate takes or returns is a `Seconds`:
+/// steps, spans, periods, and epochs counted from J2000 or from the
+/// start of a run. The exceptions are Julian dates, which stay `Real`
+/// days, and the C structs of the `ffi` module.
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Seconds<T = Real>(pub T);
+
+/// A duration, in days of 86 400 s
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Days<T = Real>(pub T);
+
+impl Seconds {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl<T: Float> Seconds<T> {
+    pub const ZERO: Self = Seconds(T::ZERO);
+
+    pub fn abs(self) -> Self { Seconds(self.0.abs()) }
+
+    pub fn min(self, other: Self) -> Self { Seconds(self.0.min(other.0)) }
+
+    pub fn max(self, other: Self) -> Self { Seconds(self.0.max(other.0)) }
+
+    pub fn to_days(self) -> Days<T> { Days(self.0 / T::from_real(SECONDS_PER_DAY)) }
+}
+
+impl Days {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl<T: Float> Days<T> {
+    pub fn to_seconds(self) -> Seconds<T> { Seconds(self.0 * T::from_real(SECONDS_PER_DAY)) }
+}
+
+impl<T: Float> From<Days<T>> for Seconds<T> {
+    fn from(duration: Days<T>) -> Self { duration.to_seconds() }
+}
+
+impl<T: Float> From<Seconds<T>> for Days<T> {
+    fn from(duration: Seconds<T>) -> Self { duration.to_days() }
+}
+
+impl<T: Float> Add for Seconds<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { Seconds(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for Seconds<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { Seconds(self.0 - rhs.0) }
+}
+
+impl<T: Float> Mul<T> for Seconds<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { Seconds(self.0 * rhs) }
+}
+
+impl<T: Float> Div<T> for Seconds<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output { Seconds(self.0 / rhs) }
+}
+
+// Seconds / Seconds = dimensionless ratio
+impl<T: Float> Div for Seconds<T> {
+    type Output = T;
+    fn div(self, rhs: Self) -> Self::Output { self.0 / rhs.0 }
+}
+
+impl<T: Float> Neg for Seconds<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output { Seconds(-self.0) }
+}
+
+impl<T: Float> Add for Days<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { Days(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for Days<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { Days(self.0 - rhs.0) }
+}
+
+impl<T: Float> Mul<T> for Days<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { Days(self.0 * rhs) }
+}
+
+impl<T: Float> Div<T> for Days<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output { Days(self.0 / rhs) }
+}
+
+// Days / Days = dimensionless ratio
+impl<T: Float> Div for Days<T> {
+    type Output = T;
+    fn div(self, rhs: Self) -> Self::Output { self.0 / rhs.0 }
+}
+
+impl<T: Float> Display for Seconds<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} s", self.0)
+    }
+}
+
+impl<T: Float> Display for Days<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} d", self.0)
+    }
+}
+
+/// Speed, in m/s
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetersPerSecond<T = Real>(pub T);
+
+/// Specific energy or C3, in m²/s²
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetersSquaredPerSecondSquared<T = Real>(pub T);
+
+impl MetersPerSecond {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl<T: Float> MetersPerSecond<T> {
+    pub const ZERO: Self = MetersPerSecond(T::ZERO);
+
+    pub fn abs(self) -> Self { MetersPerSecond(self.0.abs()) }
+}
+
+impl MetersSquaredPerSecondSquared {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl<T: Float> MetersSquaredPerSecondSquared<T> {
+    /// The speed whose square this is; NaN if negative
+    pub fn sqrt(self) -> MetersPerSecond<T> { MetersPerSecond(self.0.sqrt()) }
+}
+
+impl<T: Float> Add for MetersPerSecond<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { MetersPerSecond(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for MetersPerSecond<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { MetersPerSecond(self.0 - rhs.0) }
+}
+
+impl<T: Float> Mul<T> for MetersPerSecond<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { MetersPerSecond(self.0 * rhs) }
+}
+
+// MetersPerSecond * MetersPerSecond = MetersSquaredPerSecondSquared
+impl<T: Float> Mul for MetersPerSecond<T> {
+    type Output = MetersSquaredPerSecondSquared<T>;
+    fn mul(self, rhs: Self) -> Self::Output { MetersSquaredPerSecondSquared(self.0 * rhs.0) }
+}
+
+impl<T: Float> Div<T> for MetersPerSecond<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output { MetersPerSecond(self.0 / rhs) }
+}
+
+impl<T: Float> Neg for MetersPerSecond<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output { MetersPerSecond(-self.0) }
+}
+
+// MetersPerSecond / MetersPerSecond = dimensionless ratio
+impl<T: Float> Div for MetersPerSecond<T> {
+    type Output = T;
+    fn div(self, rhs: Self) -> Self::Output { self.0 / rhs.0 }
+}
+
+// Meters / Seconds = MetersPerSecond
+impl<T: Float> Div<Seconds<T>> for Meters<T> {
+    type Output = MetersPerSecond<T>;
+    fn div(self, rhs: Seconds<T>) -> Self::Output { MetersPerSecond(self.0 / rhs.0) }
+}
+
+// MetersPerSecond * Seconds = Meters
+impl<T: Float> Mul<Seconds<T>> for MetersPerSecond<T> {
+    type Output = Meters<T>;
+    fn mul(self, rhs: Seconds<T>) -> Self::Output { Meters(self.0 * rhs.0) }
+}
+
+// Seconds * MetersPerSecond = Meters
+impl<T: Float> Mul<MetersPerSecond<T>> for Seconds<T> {
+    type Output = Meters<T>;
+    fn mul(self, rhs: MetersPerSecond<T>) -> Self::Output { Meters(self.0 * rhs.0) }
+}
+
+// Meters / MetersPerSecond = Seconds
+impl<T: Float> Div<MetersPerSecond<T>> for Meters<T> {
+    type Output = Seconds<T>;
+    fn div(self, rhs: MetersPerSecond<T>) -> Self::Output { Seconds(self.0 / rhs.0) }
+}
+
+// MetersPerSecond / Seconds = MetersPerSecondSquared
+impl<T: Float> Div<Seconds<T>> for MetersPerSecond<T> {
+    type Output = MetersPerSecondSquared<T>;
+    fn div(self, rhs: Seconds<T>) -> Self::Output { MetersPerSecondSquared(self.0 / rhs.0) }
+}
+
+impl<T: Float> Add for MetersSquaredPerSecondSquared<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { MetersSquaredPerSecondSquared(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for MetersSquaredPerSecondSquared<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { MetersSquaredPerSecondSquared(self.0 - rhs.0) }
+}
+
+impl<T: Float> Mul<T> for MetersSquaredPerSecondSquared<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { MetersSquaredPerSecondSquared(self.0 * rhs) }
+}
+
+// GravParam / Meters = MetersSquaredPerSecondSquared (potential)
+impl<T: Float> Div<Meters<T>> for GravParam<T> {
+    type Output = MetersSquaredPerSecondSquared<T>;
+    fn div(self, rhs: Meters<T>) -> Self::Output { MetersSquaredPerSecondSquared(self.0 / rhs.0) }
+}
+
+impl<T: Float> Display for MetersPerSecond<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} m/s", self.0)
+    }
+}
+
+impl<T: Float> Display for MetersSquaredPerSecondSquared<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} m²/s²", self.0)
+    }
+}
+
+/// Acceleration, in m/s²
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetersPerSecondSquared<T = Real>(pub T);
+
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Kilograms<T = Real>(pub T);
+
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Newtons<T = Real>(pub T);
+
+impl MetersPerSecondSquared {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl Kilograms {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl Newtons {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl<T: Float> Add for MetersPerSecondSquared<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { MetersPerSecondSquared(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for MetersPerSecondSquared<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { MetersPerSecondSquared(self.0 - rhs.0) }
+}
+
+impl<T: Float> Mul<T> for MetersPerSecondSquared<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { MetersPerSecondSquared(self.0 * rhs) }
+}
+
+impl<T: Float> Div<T> for MetersPerSecondSquared<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output { MetersPerSecondSquared(self.0 / rhs) }
+}
+
+impl<T: Float> Neg for MetersPerSecondSquared<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output { MetersPerSecondSquared(-self.0) }
+}
+
+// MetersPerSecondSquared / MetersPerSecondSquared = dimensionless ratio
+impl<T: Float> Div for MetersPerSecondSquared<T> {
+    type Output = T;
+    fn div(self, rhs: Self) -> Self::Output { self.0 / rhs.0 }
+}
+
+// MetersPerSecondSquared * Seconds = MetersPerSecond
+impl<T: Float> Mul<Seconds<T>> for MetersPerSecondSquared<T> {
+    type Output = MetersPerSecond<T>;
+    fn mul(self, rhs: Seconds<T>) -> Self::Output { MetersPerSecond(self.0 * rhs.0) }
+}
+
+// Seconds * MetersPerSecondSquared = MetersPerSecond
+impl<T: Float> Mul<MetersPerSecondSquared<T>> for Seconds<T> {
+    type Output = MetersPerSecond<T>;
+    fn mul(self, rhs: MetersPerSecondSquared<T>) -> Self::Output { MetersPerSecond(self.0 * rhs.0) }
+}
+
+// GravParam / MetersSquared = MetersPerSecondSquared (point-mass gravity)
+impl<T: Float> Div<MetersSquared<T>> for GravParam<T> {
+    type Output = MetersPerSecondSquared<T>;
+    fn div(self, rhs: MetersSquared<T>) -> Self::Output { MetersPerSecondSquared(self.0 / rhs.0) }
+}
+
+impl<T: Float> Add for Kilograms<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { Kilograms(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for Kilograms<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { Kilograms(self.0 - rhs.0) }
+}
+
+impl<T: Float> Mul<T> for Kilograms<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { Kilograms(self.0 * rhs) }
+}
+
+// Kilograms / Kilograms = dimensionless mass ratio
+impl<T: Float> Div for Kilograms<T> {
+    type Output = T;
+    fn div(self, rhs: Self) -> Self::Output { self.0 / rhs.0 }
+}
+
+impl<T: Float> Add for Newtons<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { Newtons(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for Newtons<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { Newtons(self.0 - rhs.0) }
+}
+
+impl<T: Float> Mul<T> for Newtons<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { Newtons(self.0 * rhs) }
+}
+
+// Newtons / Kilograms = MetersPerSecondSquared (thrust-to-mass)
+impl<T: Float> Div<Kilograms<T>> for Newtons<T> {
+    type Output = MetersPerSecondSquared<T>;
+    fn div(self, rhs: Kilograms<T>) -> Self::Output { MetersPerSecondSquared(self.0 / rhs.0) }
+}
+
+// Newtons / MetersPerSecondSquared = Kilograms
+impl<T: Float> Div<MetersPerSecondSquared<T>> for Newtons<T> {
+    type Output = Kilograms<T>;
+    fn div(self, rhs: MetersPerSecondSquared<T>) -> Self::Output { Kilograms(self.0 / rhs.0) }
+}
+
+// Kilograms * MetersPerSecondSquared = Newtons
+impl<T: Float> Mul<MetersPerSecondSquared<T>> for Kilograms<T> {
+    type Output = Newtons<T>;
+    fn mul(self, rhs: MetersPerSecondSquared<T>) -> Self::Output { Newtons(self.0 * rhs.0) }
+}
+
+impl<T: Float> Display for MetersPerSecondSquared<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} m/s²", self.0)
+    }
+}
+
+impl<T: Float> Display for Kilograms<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} kg", self.0)
+    }
+}
+
+impl<T: Float> Display for Newtons<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} N", self.0)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
-pub struct Eccentricity(Real);
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Eccentricity<T = Real>(T);
 
 impl Eccentricity {
-    pub fn new(value: Real) -> Result<Self, &'static str> {
-        if value < 0.0 {
-            Err("Eccentricity cannot be negative")
+    pub fn value(&self) -> Real {
+        self.0
+    }
+}
+
+impl<T: Float> Eccentricity<T> {
+    pub fn new(value: T) -> Result<Self, AlmagestError> {
+        if value < T::ZERO {
+            Err(AlmagestError::InvalidEccentricity("Eccentricity cannot be negative"))
         } else {
             Ok(Eccentricity(value))
         }
     }
+}
 
-    pub fn value(&self) -> Real {
-        self.0
+/// Goes through [`Eccentricity::new`], so a negative value in a file
+/// is an error rather than an orbit
+#[cfg(feature = "serde")]
+impl<'de, T: Float + serde::Deserialize<'de>> serde::Deserialize<'de> for Eccentricity<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Eccentricity::new(T::deserialize(deserializer)?).map_err(serde::de::Error::custom)
     }
 }
 
+/// A plane angle, in radians
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Radians<T = Real>(pub T);
+
+/// A plane angle, in degrees
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Degrees<T = Real>(pub T);
+
+impl Radians {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl<T: Float> Radians<T> {
+    pub const ZERO: Self = Radians(T::ZERO);
+
+    pub fn to_degrees(self) -> Degrees<T> { Degrees(self.0 * T::from_real(180.0) / T::PI) }
+
+    pub fn sin(self) -> T { self.0.sin() }
+
+    pub fn cos(self) -> T { self.0.cos() }
+
+    pub fn tan(self) -> T { self.0.tan() }
+
+    pub fn sin_cos(self) -> (T, T) { (self.0.sin(), self.0.cos()) }
+
+    /// Angle whose sine is `value`, on [−π/2, π/2]
+    pub fn asin(value: T) -> Self { Radians(value.asin()) }
+
+    /// Angle whose cosine is `value`, on [0, π]
+    pub fn acos(value: T) -> Self { Radians(value.acos()) }
+
+    /// Angle of the point (x, y) from the x axis, on [−π, π]
+    pub fn atan2(y: T, x: T) -> Self { Radians(y.atan2(x)) }
+
+    /// The same direction on [0, 2π)
+    pub fn normalized(self) -> Self {
+        let wrapped = self.0 % T::TAU;
+        // A tiny negative angle wraps to exactly 2π in floating point
+        if wrapped < T::ZERO { Radians((wrapped + T::TAU) % T::TAU) } else { Radians(wrapped) }
+    }
+
+    /// The same direction on [−π, π)
+    pub fn signed(self) -> Self {
+        let wrapped = self.normalized().0;
+        if wrapped >= T::PI { Radians(wrapped - T::TAU) } else { Radians(wrapped) }
+    }
+}
+
+impl Degrees {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl<T: Float> Degrees<T> {
+    pub fn to_radians(self) -> Radians<T> { Radians(self.0 * T::PI / T::from_real(180.0)) }
+
+    /// The same direction on [0°, 360°)
+    pub fn normalized(self) -> Self {
+        let full = T::from_real(360.0);
+        let wrapped = self.0 % full;
+        if wrapped < T::ZERO { Degrees((wrapped + full) % full) } else { Degrees(wrapped) }
+    }
+
+    /// The same direction on [−180°, 180°)
+    pub fn signed(self) -> Self {
+        let wrapped = self.normalized().0;
+        let half = T::from_real(180.0);
+        if wrapped >= half { Degrees(wrapped - half - half) } else { Degrees(wrapped) }
+    }
+}
+
+impl<T: Float> From<Degrees<T>> for Radians<T> {
+    fn from(angle: Degrees<T>) -> Self { angle.to_radians() }
+}
+
+impl<T: Float> From<Radians<T>> for Degrees<T> {
+    fn from(angle: Radians<T>) -> Self { angle.to_degrees() }
+}
+
+impl<T: Float> Add for Radians<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { Radians(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for Radians<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { Radians(self.0 - rhs.0) }
+}
+
+impl<T: Float> Neg for Radians<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output { Radians(-self.0) }
+}
+
+impl<T: Float> Mul<T> for Radians<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { Radians(self.0 * rhs) }
+}
+
+impl<T: Float> Div<T> for Radians<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output { Radians(self.0 / rhs) }
+}
+
+// Radians / Radians = dimensionless ratio
+impl<T: Float> Div for Radians<T> {
+    type Output = T;
+    fn div(self, rhs: Self) -> Self::Output { self.0 / rhs.0 }
+}
+
+impl<T: Float> Add for Degrees<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { Degrees(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for Degrees<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { Degrees(self.0 - rhs.0) }
+}
+
+impl<T: Float> Neg for Degrees<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output { Degrees(-self.0) }
+}
+
+impl<T: Float> Mul<T> for Degrees<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { Degrees(self.0 * rhs) }
+}
+
+impl<T: Float> Div<T> for Degrees<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output { Degrees(self.0 / rhs) }
+}
+
+impl<T: Float> Display for Radians<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} rad", self.0)
+    }
+}
+
+impl<T: Float> Display for Degrees<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}°", self.0)
+    }
+}
+
+/// An angular rate, in rad/s
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadiansPerSecond<T = Real>(pub T);
+
+impl RadiansPerSecond {
+    pub const fn value(self) -> Real { self.0 }
+}
+
+impl<T: Float> RadiansPerSecond<T> {
+    pub const ZERO: Self = RadiansPerSecond(T::ZERO);
+
+    /// The rate in degrees per day, as precession rates are quoted
+    pub fn to_degrees_per_day(self) -> T {
+        self.0 * T::from_real(180.0 * SECONDS_PER_DAY) / T::PI
+    }
+}
+
+impl<T: Float> Add for RadiansPerSecond<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output { RadiansPerSecond(self.0 + rhs.0) }
+}
+
+impl<T: Float> Sub for RadiansPerSecond<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output { RadiansPerSecond(self.0 - rhs.0) }
+}
+
+impl<T: Float> Neg for RadiansPerSecond<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output { RadiansPerSecond(-self.0) }
+}
+
+impl<T: Float> Mul<T> for RadiansPerSecond<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output { RadiansPerSecond(self.0 * rhs) }
+}
+
+impl<T: Float> Div<T> for RadiansPerSecond<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output { RadiansPerSecond(self.0 / rhs) }
+}
+
+// RadiansPerSecond * Seconds = Radians
+impl<T: Float> Mul<Seconds<T>> for RadiansPerSecond<T> {
+    type Output = Radians<T>;
+    fn mul(self, rhs: Seconds<T>) -> Self::Output { Radians(self.0 * rhs.0) }
+}
+
+// Radians / Seconds = RadiansPerSecond
+impl<T: Float> Div<Seconds<T>> for Radians<T> {
+    type Output = RadiansPerSecond<T>;
+    fn div(self, rhs: Seconds<T>) -> Self::Output { RadiansPerSecond(self.0 / rhs.0) }
+}
+
+impl<T: Float> Display for RadiansPerSecond<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} rad/s", self.0)
+    }
+}
+
+// Real * unit (commutative scalar multiplication), for each float
+// type, since an impl over any T would not be coherent
+macro_rules! scalar_times {
+    ($($unit:ident),*) => {$(
+        impl Mul<$unit<f64>> for f64 {
+            type Output = $unit<f64>;
+            fn mul(self, rhs: $unit<f64>) -> Self::Output { $unit(self * rhs.0) }
+        }
+
+        impl Mul<$unit<f32>> for f32 {
+            type Output = $unit<f32>;
+            fn mul(self, rhs: $unit<f32>) -> Self::Output { $unit(self * rhs.0) }
+        }
+    )*};
+}
+
+scalar_times!(
+    Meters, Kilometers, AstronomicalUnits, Seconds, MetersPerSecond, MetersPerSecondSquared,
+    RadiansPerSecond
+);
+
+// Conversions to and from `uom` quantities, so callers on uom keep
+// their types at the boundary: unit, uom quantity, uom unit
+#[cfg(feature = "uom")]
+macro_rules! uom_quantity {
+    ($($unit:ident: $quantity:ident in $uom_unit:path),*) => {$(
+        impl From<$unit<f64>> for uom::si::f64::$quantity {
+            fn from(value: $unit<f64>) -> Self { Self::new::<$uom_unit>(value.0) }
+        }
+
+        impl From<uom::si::f64::$quantity> for $unit<f64> {
+            fn from(quantity: uom::si::f64::$quantity) -> Self {
+                $unit(quantity.get::<$uom_unit>())
+            }
+        }
+
+        impl From<$unit<f32>> for uom::si::f32::$quantity {
+            fn from(value: $unit<f32>) -> Self { Self::new::<$uom_unit>(value.0) }
+        }
+
+        impl From<uom::si::f32::$quantity> for $unit<f32> {
+            fn from(quantity: uom::si::f32::$quantity) -> Self {
+                $unit(quantity.get::<$uom_unit>())
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "uom")]
+uom_quantity!(
+    Meters: Length in uom::si::length::meter,
+    Kilometers: Length in uom::si::length::kilometer,
+    Seconds: Time in uom::si::time::second,
+    MetersPerSecond: Velocity in uom::si::velocity::meter_per_second,
+    MetersPerSecondSquared: Acceleration in uom::si::acceleration::meter_per_second_squared,
+    RadiansPerSecond: AngularVelocity in uom::si::angular_velocity::radian_per_second
+);
+
 #[cfg(test)]
 mod units {
     use super::*;
@@ -258,6 +1122,31 @@ mod units {
         assert_relative_eq!(km.0, 1.234567, epsilon = 1e-10);
     }
 
+    #[test]
+    fn kilometers_arithmetic() {
+        let a = Kilometers(7.0);
+        let b = Kilometers(3.0);
+        assert_eq!(a + b, Kilometers(10.0));
+        assert_eq!(a - b, Kilometers(4.0));
+        assert_eq!(2.0 * a / 7.0, Kilometers(2.0));
+        assert_eq!(Kilometers(9.0) / b, 3.0);
+        assert_eq!(a * b, MetersSquared(21e6));
+        assert_eq!(Meters::from(a), Meters(7_000.0));
+        assert_eq!(Kilometers::from(Meters(2_500.0)), Kilometers(2.5));
+    }
+
+    #[test]
+    fn astronomical_units() {
+        let au = AstronomicalUnits(1.0);
+        assert_eq!(au.to_meters(), Meters(METERS_PER_AU));
+        assert_eq!(Kilometers::from(au), Kilometers(149_597_870.7));
+        assert_relative_eq!(Meters(7.78e11).to_au().value(), 5.2, epsilon = 1e-3);
+        let mars: AstronomicalUnits = Kilometers(227_939_200.0).into();
+        assert_relative_eq!((mars - au).value(), 0.523_7, epsilon = 1e-4);
+        assert_relative_eq!(Meters::from(mars).value(), 2.279_392e11, max_relative = 1e-12);
+        assert_eq!(au * 3.0 / (au / 2.0), 6.0);
+    }
+
     // === Constants and Special Values ===
     
     #[test]
@@ -304,6 +1193,74 @@ mod units {
         assert_eq!(zero / divisor, 0.0);
     }
 
+    #[test]
+    fn speed_squared_is_specific_energy() {
+        let v = MetersPerSecond(3.0);
+        let energy: MetersSquaredPerSecondSquared = v * v;
+        assert_eq!(energy, MetersSquaredPerSecondSquared(9.0));
+        assert_eq!(v + v - MetersPerSecond(1.0), MetersPerSecond(5.0));
+        let potential: MetersSquaredPerSecondSquared = GravParam(8.0) / Meters(2.0);
+        assert_eq!(energy - potential, MetersSquaredPerSecondSquared(5.0));
+    }
+
+    #[test]
+    fn thrust_to_mass_chain() {
+        let thrust = Newtons(0.5);
+        let mass = Kilograms(1_000.0);
+        let acceleration: MetersPerSecondSquared = thrust / mass;
+        assert_eq!(acceleration, MetersPerSecondSquared(5e-4));
+        assert_eq!(mass * acceleration, thrust);
+        assert_eq!(thrust / acceleration, mass);
+        let dry = mass - Kilograms(250.0);
+        assert_relative_eq!(mass / dry, 4.0 / 3.0);
+    }
+
+    #[test]
+    fn kinematics_chain() {
+        let distance = Meters(1_200.0);
+        let time = Seconds(60.0);
+        let speed: MetersPerSecond = distance / time;
+        assert_eq!(speed, MetersPerSecond(20.0));
+        assert_eq!(speed * time, distance);
+        assert_eq!(distance / speed, time);
+        let acceleration: MetersPerSecondSquared = speed / time;
+        assert_relative_eq!(acceleration.value(), 1.0 / 3.0);
+        assert_relative_eq!((acceleration * time).value(), speed.value());
+        assert_eq!((speed * speed).sqrt(), speed);
+        assert_eq!(-speed / 2.0, MetersPerSecond(-10.0));
+        assert_eq!(speed / MetersPerSecond(5.0), 4.0);
+        let radius = Meters(6.378e6);
+        let g: MetersPerSecondSquared = GravParam(3.986_004_418e14) / (radius * radius);
+        assert_relative_eq!(g.value(), 9.799, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn durations() {
+        let week = Days(7.0);
+        assert_eq!(week.to_seconds(), Seconds(604_800.0));
+        let back: Days = Seconds(43_200.0).into();
+        assert_eq!(back, Days(0.5));
+        assert_eq!(Seconds::from(week) / Seconds::from(Days(1.0)), 7.0);
+        assert_eq!(week - Days(2.0) * 3.0, Days(1.0));
+        // Half a day at 7.5 km/s, then a 1 m/s² burn for a minute
+        let distance = Days(0.5).to_seconds() * MetersPerSecond(7_500.0);
+        assert_eq!(distance, Meters(3.24e8));
+        assert_eq!(Seconds(60.0) * MetersPerSecondSquared(1.0), MetersPerSecond(60.0));
+        let three: Seconds = Seconds(3.0);
+        assert_eq!(-(2.0 * three), Seconds(-6.0));
+    }
+
+    #[test]
+    fn single_precision_units() {
+        let r: Meters<f32> = Meters(6_778_000.0) + Meters(2_000.0);
+        assert_eq!(r.to_km(), Kilometers(6_780.0));
+        assert_eq!(2.0_f32 * r / r, 2.0);
+        let v: MetersPerSecond<f32> = r / Seconds(1_000.0);
+        assert_eq!(v * v, MetersSquaredPerSecondSquared(6_780.0 * 6_780.0));
+        assert_relative_eq!(Degrees(90.0_f32).to_radians().sin(), 1.0);
+        assert!(Eccentricity::new(-0.1_f32).is_err());
+    }
+
     // === Eccentricity Validation Tests ===
     
     #[test]
@@ -412,6 +1369,59 @@ mod units {
         assert_relative_eq!(half_au.value(), 74_798_935_350.0, epsilon = 1.0);
     }
 
+    // === Angle Tests ===
+
+    #[test]
+    fn angle_conversions() {
+        assert_relative_eq!(Degrees(180.0).to_radians().value(), PI);
+        assert_relative_eq!(Radians(PI / 2.0).to_degrees().value(), 90.0);
+        let angle: Radians = Degrees(30.0).into();
+        assert_relative_eq!(angle.sin(), 0.5, epsilon = 1e-15);
+        assert_relative_eq!(Radians::atan2(1.0, 1.0).to_degrees().value(), 45.0);
+        assert_relative_eq!(Radians::acos(0.0).value(), PI / 2.0);
+        assert_eq!(-Radians(1.0) + Radians(3.0) * 2.0, Radians(5.0));
+        assert_eq!(Radians(PI) / Radians(TAU), 0.5);
+    }
+
+    #[test]
+    fn angle_wrapping() {
+        assert_relative_eq!(Radians(-PI / 2.0).normalized().value(), 1.5 * PI);
+        assert_relative_eq!(Radians(5.0 * PI).normalized().value(), PI, epsilon = 1e-14);
+        assert_eq!(Radians(TAU).normalized(), Radians::ZERO);
+        assert!(Radians(-1e-18).normalized().value() < TAU);
+        // π is the open end of the signed range
+        assert_relative_eq!(Radians(PI).signed().value(), -PI);
+        assert_relative_eq!(Radians(1.5 * PI).signed().value(), -PI / 2.0);
+        assert_eq!(Degrees(-90.0).normalized(), Degrees(270.0));
+        assert_eq!(Degrees(725.0).normalized(), Degrees(5.0));
+        assert_eq!(Degrees(180.0).signed(), Degrees(-180.0));
+        assert_eq!(Degrees(-181.0).signed(), Degrees(179.0));
+    }
+
+    #[test]
+    fn angular_rates() {
+        let rate = Radians(PI) / Seconds(43_200.0);
+        assert_relative_eq!(rate.to_degrees_per_day(), 360.0);
+        assert_eq!(rate * Seconds(43_200.0), Radians(PI));
+        assert_eq!(2.0 * rate - rate, rate);
+    }
+
+    #[cfg(feature = "uom")]
+    #[test]
+    fn uom_quantities() {
+        use uom::si::f64::{Length, Velocity};
+        use uom::si::length::kilometer;
+        use uom::si::velocity::kilometer_per_second;
+
+        let altitude: Length = Meters(408_000.0).into();
+        assert_relative_eq!(altitude.get::<kilometer>(), 408.0);
+        assert_eq!(Kilometers::from(altitude), Kilometers(408.0));
+        let speed = Velocity::new::<kilometer_per_second>(7.66);
+        assert_relative_eq!(MetersPerSecond::from(speed).value(), 7_660.0);
+        let period: uom::si::f32::Time = Seconds(5_400.0_f32).into();
+        assert_eq!(Seconds::from(period), Seconds(5_400.0));
+    }
+
     // === Display Implementation Tests ===
     // Note: Display tests removed to maintain no_std compatibility
     // Display trait implementations are still available for debugging
@@ -0,0 +1,358 @@
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::float::Float;
+use crate::utils::Real;
+
+/// A three-component Cartesian vector.
+///
+/// The components carry no unit of their own; the function
+/// producing or consuming a `Vec3` documents what it holds
+/// (e.g. a position in meters or a velocity in meters per second).
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vec3<T = Real> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Float> Vec3<T> {
+    pub const ZERO: Self = Vec3::new(T::ZERO, T::ZERO, T::ZERO);
+    pub const X: Self = Vec3::new(T::ONE, T::ZERO, T::ZERO);
+    pub const Y: Self = Vec3::new(T::ZERO, T::ONE, T::ZERO);
+    pub const Z: Self = Vec3::new(T::ZERO, T::ZERO, T::ONE);
+
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    pub fn dot(&self, rhs: Vec3<T>) -> T {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    pub fn cross(&self, rhs: Vec3<T>) -> Vec3<T> {
+        Vec3 {
+            x: self.y * rhs.z - self.z * rhs.y,
+            y: self.z * rhs.x - self.x * rhs.z,
+            z: self.x * rhs.y - self.y * rhs.x,
+        }
+    }
+
+    pub fn norm_squared(&self) -> T {
+        self.dot(*self)
+    }
+
+    /// The Euclidean length of the vector
+    pub fn norm(&self) -> T {
+        self.norm_squared().sqrt()
+    }
+
+    /// The vector scaled to unit length;
+    /// the zero vector is returned unchanged
+    pub fn unit(&self) -> Vec3<T> {
+        let n = self.norm();
+        if n == T::ZERO { *self } else { *self / n }
+    }
+}
+
+impl<T: Float> Add for Vec3<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Vec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Float> AddAssign for Vec3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Float> Sub for Vec3<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Float> SubAssign for Vec3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Float> Neg for Vec3<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+// Scalar multiplication
+impl<T: Float> Mul<T> for Vec3<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Vec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+// Real * Vec3 = Vec3 (commutative scalar multiplication), for each
+// float type
+macro_rules! scalar_times_vec3 {
+    ($($float:ty),*) => {$(
+        impl Mul<Vec3<$float>> for $float {
+            type Output = Vec3<$float>;
+            fn mul(self, rhs: Vec3<$float>) -> Self::Output {
+                rhs * self
+            }
+        }
+    )*};
+}
+
+scalar_times_vec3!(f32, f64);
+
+// Scalar division
+impl<T: Float> Div<T> for Vec3<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output {
+        Vec3::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+
+/// A 3×3 matrix, stored by rows
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Matrix3<T = Real> {
+    pub rows: [Vec3<T>; 3],
+}
+
+impl<T: Float> Matrix3<T> {
+    pub const IDENTITY: Self = Matrix3::from_rows(Vec3::X, Vec3::Y, Vec3::Z);
+
+    pub const fn from_rows(r0: Vec3<T>, r1: Vec3<T>, r2: Vec3<T>) -> Self {
+        Matrix3 { rows: [r0, r1, r2] }
+    }
+
+    pub fn from_columns(c0: Vec3<T>, c1: Vec3<T>, c2: Vec3<T>) -> Self {
+        Matrix3::from_rows(c0, c1, c2).transpose()
+    }
+
+    pub fn column(&self, index: usize) -> Vec3<T> {
+        let pick = |row: &Vec3<T>| [row.x, row.y, row.z][index];
+        Vec3::new(
+            pick(&self.rows[0]),
+            pick(&self.rows[1]),
+            pick(&self.rows[2]),
+        )
+    }
+
+    pub fn transpose(&self) -> Matrix3<T> {
+        Matrix3::from_rows(self.column(0), self.column(1), self.column(2))
+    }
+
+    pub fn determinant(&self) -> T {
+        self.rows[0].dot(self.rows[1].cross(self.rows[2]))
+    }
+
+    /// The inverse, or `None` if the matrix is singular
+    pub fn inverse(&self) -> Option<Matrix3<T>> {
+        let det = self.determinant();
+        if det == T::ZERO {
+            return None;
+        }
+        // Columns of the inverse are the cross products of row pairs
+        let [r0, r1, r2] = self.rows;
+        Some(Matrix3::from_columns(
+            r1.cross(r2) / det,
+            r2.cross(r0) / det,
+            r0.cross(r1) / det,
+        ))
+    }
+}
+
+impl<T: Float> Mul<Vec3<T>> for Matrix3<T> {
+    type Output = Vec3<T>;
+    fn mul(self, rhs: Vec3<T>) -> Self::Output {
+        Vec3::new(
+            self.rows[0].dot(rhs),
+            self.rows[1].dot(rhs),
+            self.rows[2].dot(rhs),
+        )
+    }
+}
+
+impl<T: Float> Mul for Matrix3<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let t = rhs.transpose();
+        let row = |r: Vec3<T>| Vec3::new(r.dot(t.rows[0]), r.dot(t.rows[1]), r.dot(t.rows[2]));
+        Matrix3::from_rows(row(self.rows[0]), row(self.rows[1]), row(self.rows[2]))
+    }
+}
+
+/// Conversions to and from `nalgebra`, for handing states and rotations
+/// to estimation code built on it
+#[cfg(feature = "nalgebra")]
+mod nalgebra_interop {
+    use super::{Matrix3, Vec3};
+    use crate::float::Float;
+    use nalgebra as na;
+
+    impl<T: Float + na::Scalar> From<Vec3<T>> for na::Vector3<T> {
+        fn from(v: Vec3<T>) -> Self {
+            na::Vector3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl<T: Float + na::Scalar> From<na::Vector3<T>> for Vec3<T> {
+        fn from(v: na::Vector3<T>) -> Self {
+            Vec3::new(v.x, v.y, v.z)
+        }
+    }
+
+    impl<T: Float + na::Scalar> From<Matrix3<T>> for na::Matrix3<T> {
+        fn from(m: Matrix3<T>) -> Self {
+            let [r0, r1, r2] = m.rows;
+            na::Matrix3::new(r0.x, r0.y, r0.z, r1.x, r1.y, r1.z, r2.x, r2.y, r2.z)
+        }
+    }
+
+    impl<T: Float + na::Scalar> From<na::Matrix3<T>> for Matrix3<T> {
+        fn from(m: na::Matrix3<T>) -> Self {
+            let row = |i: usize| Vec3::new(m[(i, 0)], m[(i, 1)], m[(i, 2)]);
+            Matrix3::from_rows(row(0), row(1), row(2))
+        }
+    }
+
+    /// The direction cosine matrix of a rotation
+    impl<T: Float + na::RealField> From<na::Rotation3<T>> for Matrix3<T> {
+        fn from(rotation: na::Rotation3<T>) -> Self {
+            rotation.into_inner().into()
+        }
+    }
+
+    impl<T: Float + na::RealField> From<na::UnitQuaternion<T>> for Matrix3<T> {
+        fn from(q: na::UnitQuaternion<T>) -> Self {
+            q.to_rotation_matrix().into()
+        }
+    }
+
+    /// Trusts the matrix to be a proper rotation; nothing is
+    /// re-orthonormalized
+    impl<T: Float + na::RealField> From<Matrix3<T>> for na::Rotation3<T> {
+        fn from(m: Matrix3<T>) -> Self {
+            na::Rotation3::from_matrix_unchecked(m.into())
+        }
+    }
+
+    /// Trusts the matrix to be a proper rotation, as for
+    /// [`na::Rotation3`]
+    impl<T: Float + na::RealField> From<Matrix3<T>> for na::UnitQuaternion<T> {
+        fn from(m: Matrix3<T>) -> Self {
+            na::UnitQuaternion::from_rotation_matrix(&m.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn vec3_arithmetic() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(4.0, 5.0, 6.0);
+        assert_eq!(a + b, Vec3::new(5.0, 7.0, 9.0));
+        assert_eq!(b - a, Vec3::new(3.0, 3.0, 3.0));
+        assert_eq!(-a, Vec3::new(-1.0, -2.0, -3.0));
+        assert_eq!(a * 2.0, 2.0 * a);
+        assert_eq!(b / 2.0, Vec3::new(2.0, 2.5, 3.0));
+    }
+
+    #[test]
+    fn vec3_products() {
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        let b = Vec3::new(4.0, 5.0, 6.0);
+        assert_eq!(a.dot(b), 32.0);
+        assert_eq!(Vec3::<Real>::X.cross(Vec3::Y), Vec3::Z);
+        // The cross product is perpendicular to both inputs
+        let c = a.cross(b);
+        assert_relative_eq!(c.dot(a), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(c.dot(b), 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn vec3_norm_and_unit() {
+        let v = Vec3::new(3.0, 4.0, 12.0);
+        assert_eq!(v.norm(), 13.0);
+        assert_relative_eq!(v.unit().norm(), 1.0, epsilon = 1e-12);
+        assert_eq!(Vec3::<Real>::ZERO.unit(), Vec3::ZERO);
+    }
+
+    #[test]
+    fn matrix3_products() {
+        let m = Matrix3::from_rows(
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(4.0, 5.0, 6.0),
+            Vec3::new(7.0, 8.0, 10.0),
+        );
+        assert_eq!(m * Vec3::X, m.column(0));
+        assert_eq!(m * Matrix3::IDENTITY, m);
+        assert_eq!(m.transpose().rows[0], m.column(0));
+        assert_eq!(
+            Matrix3::from_columns(Vec3::X, Vec3::Z, Vec3::Y) * Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(1.0, 3.0, 2.0)
+        );
+        let mt = m * m.transpose();
+        assert_eq!(mt, mt.transpose());
+        assert_eq!(mt.rows[0].x, 14.0);
+    }
+
+    #[test]
+    fn matrix3_inverse() {
+        let m = Matrix3::from_rows(
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(4.0, 5.0, 6.0),
+            Vec3::new(7.0, 8.0, 10.0),
+        );
+        assert_relative_eq!(m.determinant(), -3.0, epsilon = 1e-12);
+        let product = m * m.inverse().unwrap();
+        for (row, unit) in product.rows.iter().zip([Vec3::X, Vec3::Y, Vec3::Z]) {
+            assert_relative_eq!((*row - unit).norm(), 0.0, epsilon = 1e-12);
+        }
+        let singular: Matrix3 = Matrix3::from_rows(Vec3::X, Vec3::X, Vec3::Z);
+        assert_eq!(singular.inverse(), None);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra_round_trips() {
+        use nalgebra as na;
+        let v = Vec3::new(1.0, -2.0, 3.0);
+        let theirs: na::Vector3<Real> = v.into();
+        assert_eq!(Vec3::from(theirs), v);
+
+        let m = Matrix3::from_rows(
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(4.0, 5.0, 6.0),
+            Vec3::new(7.0, 8.0, 10.0),
+        );
+        let theirs: na::Matrix3<Real> = m.into();
+        assert_eq!(theirs[(1, 2)], 6.0);
+        assert_eq!(Matrix3::from(theirs), m);
+        assert_eq!(na::Vector3::from(m * v), theirs * na::Vector3::from(v));
+
+        // A quarter turn about z takes x to y both ways round
+        let q = na::UnitQuaternion::from_axis_angle(&na::Vector3::z_axis(), crate::utils::PI / 2.0);
+        let dcm = Matrix3::from(q);
+        let turned = dcm * Vec3::X;
+        assert_relative_eq!(turned.x, 0.0, epsilon = 1e-15);
+        assert_relative_eq!(turned.y, 1.0);
+        assert_relative_eq!(
+            na::UnitQuaternion::from(dcm).angle_to(&q),
+            0.0,
+            epsilon = 1e-15
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Time windows and set operations on them.
+//!
+//! Searches such as [`crate::search::visits`], region overflights and
+//! SAA passes yield [`Interval`]s in time order, none overlapping. The
+//! [`Windows`] methods combine such streams lazily, without allocating,
+//! into streams of the same kind, so a question like "visible and
+//! sunlit, outside the SAA" is a chain of set operations:
+//!
+//! ```
+//! use almagest::search::visits;
+//! use almagest::utils::Seconds;
+//! use almagest::windows::Windows;
+//!
+//! let (start, end, step) = (Seconds(0.0), Seconds(300.0), Seconds(1.0));
+//! let visible = visits(|t| t.value() % 100.0 < 60.0, start, end, step);
+//! let sunlit = visits(|t| t < Seconds(150.0), start, end, step);
+//! let in_saa = visits(|t| (20.0..30.0).contains(&t.value()), start, end, step);
+//! let usable = visible
+//!     .intersection(sunlit)
+//!     .intersection(in_saa.complement(start, end));
+//! let statistics = usable.statistics();
+//! assert_eq!(statistics.count, 3);
+//! assert!((statistics.total - Seconds(100.0)).abs() < Seconds(0.01));
+//! ```
+
+use core::iter::Peekable;
+
+use crate::utils::{Real, Seconds};
+
+/// A span of time
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval {
+    pub start: Seconds,
+    pub end: Seconds,
+}
+
+impl Interval {
+    pub const fn new(start: Seconds, end: Seconds) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn duration(&self) -> Seconds {
+        self.end - self.start
+    }
+
+    pub fn contains(&self, time: Seconds) -> bool {
+        (self.start..=self.end).contains(&time)
+    }
+
+    /// The span both cover, if they overlap
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+        (start < end).then_some(Interval::new(start, end))
+    }
+}
+
+/// Count and durations of a set of windows
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowStatistics {
+    pub count: usize,
+    /// Sum of the durations
+    pub total: Seconds,
+    pub shortest: Seconds,
+    pub longest: Seconds,
+}
+
+impl WindowStatistics {
+    /// Mean duration, zero if there are no windows
+    pub fn mean(&self) -> Seconds {
+        if self.count == 0 {
+            Seconds::ZERO
+        } else {
+            self.total / self.count as Real
+        }
+    }
+}
+
+/// Set operations on a time-ordered stream of non-overlapping
+/// intervals
+pub trait Windows: Iterator<Item = Interval> + Sized {
+    /// Times in either stream, with overlapping and touching windows
+    /// merged
+    fn union<B: IntoIterator<Item = Interval>>(self, other: B) -> Union<Self, B::IntoIter> {
+        Union {
+            a: self.peekable(),
+            b: other.into_iter().peekable(),
+        }
+    }
+
+    /// Times in both streams
+    fn intersection<B: IntoIterator<Item = Interval>>(
+        self,
+        other: B,
+    ) -> Intersection<Self, B::IntoIter> {
+        Intersection {
+            a: self.peekable(),
+            b: other.into_iter().peekable(),
+        }
+    }
+
+    /// Times between `start` and `end` in none of the windows
+    fn complement(self, start: Seconds, end: Seconds) -> Complement<Self> {
+        Complement {
+            windows: self,
+            cursor: start,
+            end,
+        }
+    }
+
+    fn statistics(self) -> WindowStatistics {
+        self.fold(WindowStatistics::default(), |mut statistics, window| {
+            let duration = window.duration();
+            statistics.shortest = if statistics.count == 0 {
+                duration
+            } else {
+                statistics.shortest.min(duration)
+            };
+            statistics.longest = statistics.longest.max(duration);
+            statistics.count += 1;
+            statistics.total = statistics.total + duration;
+            statistics
+        })
+    }
+}
+
+impl<I: Iterator<Item = Interval>> Windows for I {}
+
+/// Iterator created by [`Windows::union`]
+#[derive(Clone, Debug)]
+pub struct Union<A: Iterator<Item = Interval>, B: Iterator<Item = Interval>> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<A: Iterator<Item = Interval>, B: Iterator<Item = Interval>> Union<A, B> {
+    // Next window to start across both streams
+    fn peek(&mut self) -> Option<(Interval, bool)> {
+        match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) if b.start < a.start => Some((*b, false)),
+            (Some(a), _) => Some((*a, true)),
+            (None, b) => b.map(|b| (*b, false)),
+        }
+    }
+
+    fn advance(&mut self, from_a: bool) {
+        if from_a {
+            self.a.next();
+        } else {
+            self.b.next();
+        }
+    }
+}
+
+impl<A: Iterator<Item = Interval>, B: Iterator<Item = Interval>> Iterator for Union<A, B> {
+    type Item = Interval;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut merged, from_a) = self.peek()?;
+        self.advance(from_a);
+        while let Some((window, from_a)) = self.peek() {
+            if window.start > merged.end {
+                break;
+            }
+            merged.end = merged.end.max(window.end);
+            self.advance(from_a);
+        }
+        Some(merged)
+    }
+}
+
+/// Iterator created by [`Windows::intersection`]
+#[derive(Clone, Debug)]
+pub struct Intersection<A: Iterator<Item = Interval>, B: Iterator<Item = Interval>> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<A: Iterator<Item = Interval>, B: Iterator<Item = Interval>> Iterator for Intersection<A, B> {
+    type Item = Interval;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (a, b) = (*self.a.peek()?, *self.b.peek()?);
+            // Whichever ends first can't overlap anything further on
+            if a.end <= b.end {
+                self.a.next();
+            } else {
+                self.b.next();
+            }
+            if let Some(overlap) = a.intersection(&b) {
+                return Some(overlap);
+            }
+        }
+    }
+}
+
+/// Iterator created by [`Windows::complement`]
+#[derive(Clone, Debug)]
+pub struct Complement<I> {
+    windows: I,
+    cursor: Seconds,
+    end: Seconds,
+}
+
+impl<I: Iterator<Item = Interval>> Iterator for Complement<I> {
+    type Item = Interval;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cursor < self.end {
+            match self.windows.next() {
+                Some(window) if window.start > self.cursor => {
+                    let gap = Interval::new(self.cursor, window.start.min(self.end));
+                    self.cursor = window.end;
+                    return Some(gap);
+                }
+                Some(window) => self.cursor = self.cursor.max(window.end),
+                None => {
+                    let gap = Interval::new(self.cursor, self.end);
+                    self.cursor = self.end;
+                    return Some(gap);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn windows<const N: usize>(spans: [(Real, Real); N]) -> [Interval; N] {
+        spans.map(|(start, end)| Interval::new(Seconds(start), Seconds(end)))
+    }
+
+    #[test]
+    fn union_merges_overlaps() {
+        let a = windows([(0.0, 2.0), (5.0, 7.0), (10.0, 11.0)]);
+        let b = windows([(1.0, 3.0), (3.0, 4.0), (6.0, 6.5), (12.0, 13.0)]);
+        let expected = windows([(0.0, 4.0), (5.0, 7.0), (10.0, 11.0), (12.0, 13.0)]);
+        assert!(a.into_iter().union(b).eq(expected));
+        // Touching windows merge
+        let touching = windows([(2.0, 3.0)]);
+        assert!(
+            windows([(0.0, 2.0)])
+                .into_iter()
+                .union(touching)
+                .eq(windows([(0.0, 3.0)]))
+        );
+        assert!(a.into_iter().union([]).eq(a));
+    }
+
+    #[test]
+    fn intersection_and_complement() {
+        let a = windows([(0.0, 4.0), (6.0, 10.0)]);
+        let b = windows([(1.0, 2.0), (3.0, 7.0), (9.0, 12.0)]);
+        let expected = windows([(1.0, 2.0), (3.0, 4.0), (6.0, 7.0), (9.0, 10.0)]);
+        assert!(a.into_iter().intersection(b).eq(expected));
+        assert_eq!(a.into_iter().intersection([]).next(), None);
+
+        let gaps = windows([(-1.0, 0.0), (4.0, 6.0), (10.0, 12.0)]);
+        let span = |start, end| (Seconds(start), Seconds(end));
+        let (start, end) = span(-1.0, 12.0);
+        assert!(a.into_iter().complement(start, end).eq(gaps));
+        // Clipped to the span asked for
+        let (start, end) = span(2.0, 8.0);
+        assert!(
+            a.into_iter()
+                .complement(start, end)
+                .eq(windows([(4.0, 6.0)]))
+        );
+        let (start, end) = span(0.0, 1.0);
+        assert!(
+            [].into_iter()
+                .complement(start, end)
+                .eq(windows([(0.0, 1.0)]))
+        );
+        // A and not A is empty; A or not A is everything
+        let (start, end) = span(0.0, 10.0);
+        let everything = a.into_iter().union(a.into_iter().complement(start, end));
+        assert!(everything.eq(windows([(0.0, 10.0)])));
+        let nothing = a
+            .into_iter()
+            .intersection(a.into_iter().complement(start, end));
+        assert_eq!(nothing.count(), 0);
+    }
+
+    #[test]
+    fn statistics() {
+        let statistics = windows([(0.0, 4.0), (6.0, 7.0), (9.0, 12.0)])
+            .into_iter()
+            .statistics();
+        assert_eq!(statistics.count, 3);
+        assert_eq!(statistics.total, Seconds(8.0));
+        assert_eq!(statistics.shortest, Seconds(1.0));
+        assert_eq!(statistics.longest, Seconds(4.0));
+        assert_eq!(statistics.mean(), Seconds(8.0 / 3.0));
+        assert_eq!([].into_iter().statistics(), WindowStatistics::default());
+        let [one, two] = windows([(1.0, 2.0), (2.0, 3.0)]);
+        assert!(one.contains(Seconds(1.5)));
+        assert_eq!(one.intersection(&two), None);
+    }
+}